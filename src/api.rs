@@ -113,33 +113,77 @@
 //! [`C2CMessageParams`]: crate::models::message::C2CMessageParams
 //! [`DirectMessageParams`]: crate::models::message::DirectMessageParams
 
-use crate::error::Result;
-use crate::http::HttpClient;
+use crate::error::{BotError, Result};
+use crate::forum::{
+    ForumChannelSummary, ForumContent, ForumPost, ForumReply, PostPage, ReplyPage, Thread,
+    ThreadPage, ThreadParticipant, ThreadSearchOptions, collect_posts, collect_threads,
+    paginate_replies, search_threads,
+};
+use crate::gateway::GatewaySubscription;
+use crate::http::{HttpClient, UploadProgress};
+use crate::interaction::{Interaction, InteractionPage};
 use crate::models::{
     announce::{Announce, AnnouncesType, RecommendChannel},
-    api::{AudioAction, BotInfo, GatewayResponse, MessageResponse},
-    channel::{Channel, ChannelPermissions, ChannelSubType, ChannelType},
-    emoji::EmojiType,
-    guild::{Guild, GuildRole, GuildRoles, Member},
+    api::{AudioAction, BotInfo, GatewayResponse, MessageQuota, MessageResponse},
+    audit_log::{
+        AuditLogAction, AuditLogEntry, AuditLogStream, ForumAuditEntry, GuildActivityFeed,
+        GuildDailyStats, MemberEvent,
+    },
+    channel::{
+        Channel, ChannelAccessInvite, ChannelCategory, ChannelInvite, ChannelPermissions,
+        ChannelSubType, ChannelThread, ChannelType, InviteOptions, PrivateType, SpeakPermission,
+        VoiceChannelMember,
+    },
+    command::ApplicationCommand,
+    emoji::{EmojiType, EmojiUsageStat, GuildEmoji},
+    guild::{
+        BoostEvent, Guild, GuildAdminLevel, GuildBannedWords, GuildBotInfo, GuildChangeEvent,
+        GuildDiscoveryMetadata, GuildDiscoveryUpdate, GuildPremiumStatus, GuildRole,
+        GuildRoleSyncResult, GuildRoles, GuildStats, GuildTemplate, Member, MemberActivity,
+        MemberField, OnlineMembersPage, RoleMemberCountBreakdown, RoleSpec, RoleSyncResult,
+        VanityUrl, VoiceRegion,
+    },
     message::{
-        Ark, C2CMessageParams, DirectMessageParams, Embed, GroupMessageParams, Keyboard,
-        KeyboardPayload, MarkdownPayload, Media, Message, MessageParams, Reference,
+        Ark, C2CMessageParams, DirectMessageParams, Embed, ExportFormat, GroupMessageParams,
+        GroupThread, InteractiveMessageHandle, Keyboard, KeyboardPayload, MarkdownPayload, Media,
+        Message, MessageAuditStatus, MessageEmbed, MessageParams, MessageRevision,
+        MessageWithContext, MessagesAround, ReactionSummary, Reference, TypingDelay,
     },
-    permission::{APIPermission, APIPermissionDemand, APIPermissionDemandIdentify},
-    schedule::{RemindType, Schedule},
+    permission::{
+        APIPermission, APIPermissionDemand, APIPermissionDemandIdentify, ChannelPermissionBatch,
+        ChannelPermissionEntry, ChannelPermissionSnapshot, PermissionDiff, PermissionEntry,
+        PermissionTarget, Permissions,
+    },
+    poll::{Poll, PollResult},
+    schedule::{
+        DmReminderConfig, EventSubscription, RemindType, Schedule, ScheduleBuildError,
+        ScheduleBuilder, ScheduleBulkReport, ScheduleImage, ScheduleWithDmReminder,
+        UpcomingSchedule, reminder_delay, validate_schedules,
+    },
+    user::UserPresence,
+    webhook::{ChannelWebhook, WebhookDeleteReport, WebhookUpdate},
 };
-use crate::reaction::ReactionUsers;
+use crate::reaction::{ReactionUser, ReactionUsers};
 use crate::token::Token;
 use base64::Engine;
+use futures_util::{Stream, StreamExt, TryStreamExt};
 use serde_json::{Value, json};
 use std::collections::HashMap;
-use tracing::debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Voice regions together with the time they were fetched.
+type CachedRegions = (Vec<VoiceRegion>, Instant);
 
 /// Bot API client for the QQ Guild Bot API.
 #[derive(Clone)]
 pub struct BotApi {
     /// The HTTP client used for making requests
     http: HttpClient,
+    /// Voice regions from the last lookup and when they were fetched
+    voice_regions: Arc<std::sync::Mutex<Option<CachedRegions>>>,
 }
 
 impl BotApi {
@@ -159,7 +203,10 @@ impl BotApi {
     /// let api = BotApi::new(http);
     /// ```
     pub fn new(http: HttpClient) -> Self {
-        Self { http }
+        Self {
+            http,
+            voice_regions: Arc::new(std::sync::Mutex::new(None)),
+        }
     }
 
     /// Gets information about the current bot.
@@ -211,57 +258,64 @@ impl BotApi {
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Gets the current user's guilds.
+    /// Gets a guild's boost count and premium tier.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - Optional starting guild ID
-    /// * `limit` - Maximum number of guilds to return (1-100)
-    /// * `desc` - Whether to return results in descending order
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// List of guilds.
-    pub async fn get_guilds(
+    /// The guild's premium status.
+    pub async fn get_guild_premium_status(
         &self,
         token: &Token,
-        guild_id: Option<&str>,
-        limit: Option<u32>,
-        desc: Option<bool>,
-    ) -> Result<Vec<Guild>> {
-        debug!("Getting guilds");
+        guild_id: &str,
+    ) -> Result<GuildPremiumStatus> {
+        debug!("Getting premium status of guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        let booster_role_id = response
+            .get("premium_subscriber_role_id")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let guild: Guild = serde_json::from_value(response)?;
+        Ok(GuildPremiumStatus::from_guild(&guild, booster_role_id))
+    }
+
+    /// Gets a guild's most recent boost events.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `limit` - Maximum number of events to return
+    ///
+    /// # Returns
+    ///
+    /// Boost events, newest first.
+    pub async fn get_guild_boost_history(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        limit: u32,
+    ) -> Result<Vec<BoostEvent>> {
+        debug!("Getting {} boost events of guild {}", limit, guild_id);
 
         let mut params = HashMap::new();
-        if let Some(limit) = limit {
-            params.insert("limit", limit.to_string());
-        }
-        if let Some(guild_id) = guild_id {
-            if desc.unwrap_or(false) {
-                params.insert("before", guild_id.to_string());
-            } else {
-                params.insert("after", guild_id.to_string());
-            }
-        }
+        params.insert("limit", limit.to_string());
 
-        let response = self
-            .http
-            .get(
-                token,
-                "/users/@me/guilds",
-                if params.is_empty() {
-                    None
-                } else {
-                    Some(&params)
-                },
-            )
-            .await?;
+        let path = format!("/guilds/{guild_id}/boosts");
+        let response = self.http.get(token, &path, Some(&params)).await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    // Guild Role APIs
-
-    /// Gets guild roles.
+    /// Gets the users currently boosting a guild.
+    ///
+    /// Boosters are worked out from the most recent [`BOOST_HISTORY_LIMIT`]
+    /// boost events with [`BoostEvent::current_boosters`]. Use
+    /// [`BotApi::get_guild_boost_history`] directly to look further back.
     ///
     /// # Arguments
     ///
@@ -270,1474 +324,1439 @@ impl BotApi {
     ///
     /// # Returns
     ///
-    /// Guild roles information.
-    pub async fn get_guild_roles(&self, token: &Token, guild_id: &str) -> Result<GuildRoles> {
-        debug!("Getting guild roles for {}", guild_id);
-        let path = format!("/guilds/{guild_id}/roles");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+    /// The IDs of boosting users.
+    pub async fn get_current_boosters(&self, token: &Token, guild_id: &str) -> Result<Vec<String>> {
+        let history = self
+            .get_guild_boost_history(token, guild_id, BOOST_HISTORY_LIMIT)
+            .await?;
+        Ok(BoostEvent::current_boosters(&history))
     }
 
-    /// Creates a new guild role.
+    /// Gets a guild's vanity invite code.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `name` - Role name
-    /// * `color` - Role color (ARGB hex as decimal)
-    /// * `hoist` - Whether to display separately in member list
     ///
     /// # Returns
     ///
-    /// The created role.
-    pub async fn create_guild_role(
-        &self,
-        token: &Token,
-        guild_id: &str,
-        name: Option<&str>,
-        color: Option<u32>,
-        hoist: Option<bool>,
-    ) -> Result<GuildRole> {
-        debug!("Creating guild role in {}", guild_id);
-
-        let mut body = HashMap::new();
-        if let Some(name) = name {
-            body.insert("name", json!(name));
-        }
-        if let Some(color) = color {
-            body.insert("color", json!(color));
-        }
-        if let Some(hoist) = hoist {
-            body.insert("hoist", json!(if hoist { 1 } else { 0 }));
-        }
-
-        let path = format!("/guilds/{guild_id}/roles");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
-            .await?;
+    /// The vanity URL, with no code if the guild hasn't set one.
+    pub async fn get_guild_vanity_url(&self, token: &Token, guild_id: &str) -> Result<VanityUrl> {
+        debug!("Getting vanity URL of guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/vanity-url");
+        let response = self.http.get(token, &path, None::<&()>).await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Updates a guild role.
+    /// Sets a guild's vanity invite code.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `role_id` - The role ID
-    /// * `name` - Role name
-    /// * `color` - Role color (ARGB hex as decimal)
-    /// * `hoist` - Whether to display separately in member list
+    /// * `code` - The new code (2-30 letters, digits or hyphens)
     ///
     /// # Returns
     ///
-    /// The updated role.
-    pub async fn update_guild_role(
+    /// The updated vanity URL.
+    pub async fn update_guild_vanity_url(
         &self,
         token: &Token,
         guild_id: &str,
-        role_id: &str,
-        name: Option<&str>,
-        color: Option<u32>,
-        hoist: Option<bool>,
-    ) -> Result<GuildRole> {
-        debug!("Updating guild role {} in {}", role_id, guild_id);
-
-        let mut body = HashMap::new();
-        if let Some(name) = name {
-            body.insert("name", json!(name));
-        }
-        if let Some(color) = color {
-            body.insert("color", json!(color));
-        }
-        if let Some(hoist) = hoist {
-            body.insert("hoist", json!(if hoist { 1 } else { 0 }));
-        }
+        code: &str,
+    ) -> Result<VanityUrl> {
+        validate_vanity_code(code)?;
+        debug!("Setting vanity URL of guild {} to {}", guild_id, code);
 
-        let path = format!("/guilds/{guild_id}/roles/{role_id}");
+        let body = json!({ "code": code });
+        let path = format!("/guilds/{guild_id}/vanity-url");
         let response = self
             .http
-            .put(token, &path, None::<&()>, Some(&body))
+            .patch(token, &path, None::<&()>, Some(&body))
             .await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Deletes a guild role.
+    /// Removes a guild's vanity invite code.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `role_id` - The role ID
-    ///
-    /// # Returns
-    ///
-    /// Success indication.
-    pub async fn delete_guild_role(
-        &self,
-        token: &Token,
-        guild_id: &str,
-        role_id: &str,
-    ) -> Result<()> {
-        debug!("Deleting guild role {} in {}", role_id, guild_id);
-        let path = format!("/guilds/{guild_id}/roles/{role_id}");
+    pub async fn delete_guild_vanity_url(&self, token: &Token, guild_id: &str) -> Result<()> {
+        debug!("Removing vanity URL of guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/vanity-url");
         self.http.delete(token, &path, None::<&()>).await?;
         Ok(())
     }
 
-    /// Adds a member to a guild role.
+    /// Gets how a guild is presented in public guild discovery.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `role_id` - The role ID
-    /// * `user_id` - The user ID
-    /// * `channel_id` - Optional channel ID (for channel-specific roles)
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn create_guild_role_member(
+    /// The guild's discovery metadata.
+    pub async fn get_guild_discovery_metadata(
         &self,
         token: &Token,
         guild_id: &str,
-        role_id: &str,
-        user_id: &str,
-        channel_id: Option<&str>,
-    ) -> Result<()> {
-        debug!(
-            "Adding user {} to role {} in guild {}",
-            user_id, role_id, guild_id
-        );
-
-        let body = if let Some(channel_id) = channel_id {
-            json!({ "channel": { "id": channel_id } })
-        } else {
-            json!({ "channel": { "id": null } })
-        };
-
-        let path = format!("/guilds/{guild_id}/members/{user_id}/roles/{role_id}");
-        self.http
-            .put(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(())
+    ) -> Result<GuildDiscoveryMetadata> {
+        debug!("Getting discovery metadata of guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/discovery-metadata");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Removes a member from a guild role.
+    /// Updates how a guild is presented in public guild discovery.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `role_id` - The role ID
-    /// * `user_id` - The user ID
-    /// * `channel_id` - Optional channel ID (for channel-specific roles)
+    /// * `keywords` - Optional new search keywords
+    /// * `primary_category` - Optional new main discovery category
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_guild_role_member(
+    /// The updated discovery metadata.
+    pub async fn update_guild_discovery_metadata(
         &self,
         token: &Token,
         guild_id: &str,
-        role_id: &str,
-        user_id: &str,
-        channel_id: Option<&str>,
-    ) -> Result<()> {
-        debug!(
-            "Removing user {} from role {} in guild {}",
-            user_id, role_id, guild_id
-        );
-
-        let body = if let Some(channel_id) = channel_id {
-            json!({ "channel": { "id": channel_id } })
-        } else {
-            json!({ "channel": { "id": null } })
+        keywords: Option<Vec<String>>,
+        primary_category: Option<u32>,
+    ) -> Result<GuildDiscoveryMetadata> {
+        debug!("Updating discovery metadata of guild {}", guild_id);
+        let update = GuildDiscoveryUpdate {
+            keywords,
+            primary_category_id: primary_category,
         };
 
-        let path = format!("/guilds/{guild_id}/members/{user_id}/roles/{role_id}");
-        self.http.delete(token, &path, Some(&body)).await?;
-        Ok(())
+        let path = format!("/guilds/{guild_id}/discovery-metadata");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&update))
+            .await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    // Member APIs
-
-    /// Gets a guild member.
+    /// Gets how many more messages the bot can send to a channel.
+    ///
+    /// The quota comes from the rate limit headers of the last message route
+    /// response for the channel, so no request is made. Message sends are
+    /// rate limited per channel, so there is no guild-wide quota.
     ///
     /// # Arguments
     ///
-    /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID
-    /// * `user_id` - The user ID
+    /// * `channel_id` - The channel ID
     ///
     /// # Returns
     ///
-    /// Member information.
-    pub async fn get_guild_member(
-        &self,
-        token: &Token,
-        guild_id: &str,
-        user_id: &str,
-    ) -> Result<Member> {
-        debug!("Getting guild member {} in {}", user_id, guild_id);
-        let path = format!("/guilds/{guild_id}/members/{user_id}");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+    /// The channel's quota in the current window, or an error if no message
+    /// route response in the current window reported one.
+    pub fn get_message_quota(&self, channel_id: &str) -> Result<MessageQuota> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        self.http
+            .message_rate_limit(channel_id)
+            .filter(|rate_limit| rate_limit.reset > now)
+            .map(|rate_limit| MessageQuota::from(&rate_limit))
+            .ok_or_else(|| {
+                BotError::invalid_data(
+                    "No message has been sent to this channel in the current window",
+                )
+            })
     }
 
-    /// Gets guild members list.
+    /// Gets the current user's guilds.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID
-    /// * `after` - Get members after this user ID
-    /// * `limit` - Maximum number of members to return (1-400)
+    /// * `guild_id` - Optional starting guild ID
+    /// * `limit` - Maximum number of guilds to return (1-100)
+    /// * `desc` - Whether to return results in descending order
     ///
     /// # Returns
     ///
-    /// List of members.
-    pub async fn get_guild_members(
+    /// List of guilds.
+    pub async fn get_guilds(
         &self,
         token: &Token,
-        guild_id: &str,
-        after: Option<&str>,
+        guild_id: Option<&str>,
         limit: Option<u32>,
-    ) -> Result<Vec<Member>> {
-        debug!("Getting guild members for {}", guild_id);
+        desc: Option<bool>,
+    ) -> Result<Vec<Guild>> {
+        debug!("Getting guilds");
 
         let mut params = HashMap::new();
-        params.insert("after", after.unwrap_or("0").to_string());
-        params.insert("limit", limit.unwrap_or(1).to_string());
-
-        let path = format!("/guilds/{guild_id}/members");
-        let response = self.http.get(token, &path, Some(&params)).await?;
-        Ok(serde_json::from_value(response)?)
-    }
+        if let Some(limit) = limit {
+            params.insert("limit", limit.to_string());
+        }
+        if let Some(guild_id) = guild_id {
+            if desc.unwrap_or(false) {
+                params.insert("before", guild_id.to_string());
+            } else {
+                params.insert("after", guild_id.to_string());
+            }
+        }
 
-    /// Removes a member from a guild.
-    ///
+        let response = self
+            .http
+            .get(
+                token,
+                "/users/@me/guilds",
+                if params.is_empty() {
+                    None
+                } else {
+                    Some(&params)
+                },
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Measures the round-trip time to each voice region.
+    ///
+    /// Every region's [`VoiceRegion::ping_url`] is sent a HEAD request at
+    /// the same time. Regions that fail or take longer than 5 seconds are
+    /// logged and left out.
+    ///
     /// # Arguments
     ///
-    /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID
-    /// * `user_id` - The user ID
-    /// * `add_blacklist` - Whether to add to blacklist
-    /// * `delete_history_msg_days` - Days of message history to delete
+    /// * `regions` - The regions to measure
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_member(
+    /// Reachable regions with their latency, fastest first, or a timeout
+    /// error if no region answered.
+    pub async fn measure_region_latency(
         &self,
-        token: &Token,
-        guild_id: &str,
-        user_id: &str,
-        add_blacklist: Option<bool>,
-        delete_history_msg_days: Option<i32>,
-    ) -> Result<()> {
-        debug!("Deleting member {} from guild {}", user_id, guild_id);
-
-        let delete_days = match delete_history_msg_days.unwrap_or(0) {
-            3 | 7 | 15 | 30 | -1 => delete_history_msg_days.unwrap_or(0),
-            _ => 0,
-        };
-
-        let body = json!({
-            "add_blacklist": add_blacklist.unwrap_or(false),
-            "delete_history_msg_days": delete_days
-        });
-
-        let path = format!("/guilds/{guild_id}/members/{user_id}");
-        self.http.delete(token, &path, Some(&body)).await?;
-        Ok(())
+        regions: &[VoiceRegion],
+    ) -> Result<Vec<(VoiceRegion, Duration)>> {
+        debug!("Measuring latency to {} voice regions", regions.len());
+        let results = measure_latencies(regions.to_vec(), REGION_PING_TIMEOUT, |region| {
+            let url = region.ping_url();
+            async move { self.http.head_url(&url).await.map(drop) }
+        })
+        .await;
+        rank_latencies(results)
     }
 
-    // Channel APIs
-
-    /// Gets channel information.
+    /// Fetches link preview data for a URL.
+    ///
+    /// A HEAD request is sent to the URL and the Open Graph values are read
+    /// from the response headers. Header names cannot contain `:`, so
+    /// `og:title` is read from the `og-title` header, and likewise for
+    /// `og-description`, `og-image` and `og-site-name`.
     ///
     /// # Arguments
     ///
-    /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `url` - The URL to preview
     ///
     /// # Returns
     ///
-    /// Channel information.
-    pub async fn get_channel(&self, token: &Token, channel_id: &str) -> Result<Channel> {
-        debug!("Getting channel {}", channel_id);
-        let path = format!("/channels/{channel_id}");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+    /// The preview, with missing values left empty.
+    pub async fn fetch_url_metadata(&self, url: &str) -> Result<MessageEmbed> {
+        debug!("Fetching link preview for {}", url);
+        let headers = self.http.head_url(url).await?;
+        Ok(embed_from_headers(url, &headers))
     }
 
-    /// Gets channels in a guild.
+    /// Gets the available voice regions.
+    ///
+    /// The list rarely changes, so it is cached for 10 minutes.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// List of channels.
-    pub async fn get_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
-        debug!("Getting channels for guild {}", guild_id);
-        let path = format!("/guilds/{guild_id}/channels");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+    /// List of voice regions.
+    pub async fn list_voice_regions(&self, token: &Token) -> Result<Vec<VoiceRegion>> {
+        if let Some((regions, fetched_at)) = self
+            .voice_regions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            && fetched_at.elapsed() < VOICE_REGION_CACHE_TTL
+        {
+            return Ok(regions.clone());
+        }
+
+        debug!("Getting voice regions");
+        let response = self.http.get(token, "/voice/regions", None::<&()>).await?;
+        let regions: Vec<VoiceRegion> = serde_json::from_value(response)?;
+        *self.voice_regions.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((regions.clone(), Instant::now()));
+        Ok(regions)
     }
 
-    /// Creates a new channel.
+    /// Sets the voice region of a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `name` - Channel name
-    /// * `channel_type` - Channel type
-    /// * `sub_type` - Channel sub-type
-    /// * `position` - Optional position
-    /// * `parent_id` - Optional parent category ID
-    /// * `private_type` - Optional private type
-    /// * `private_user_ids` - Optional private user IDs
-    /// * `speak_permission` - Optional speak permission
-    /// * `application_id` - Optional application ID
+    /// * `region_id` - The region ID, which must be one of [`BotApi::list_voice_regions`]
     ///
     /// # Returns
     ///
-    /// The created channel.
-    pub async fn create_channel(
+    /// The updated guild, or `BotError::InvalidParams` for an unknown region.
+    pub async fn update_guild_voice_region(
         &self,
         token: &Token,
         guild_id: &str,
-        name: &str,
-        channel_type: ChannelType,
-        sub_type: ChannelSubType,
-        position: Option<u32>,
-        parent_id: Option<&str>,
-        private_type: Option<u32>,
-        private_user_ids: Option<Vec<String>>,
-        speak_permission: Option<u32>,
-        application_id: Option<&str>,
-    ) -> Result<Channel> {
-        debug!("Creating channel {} in guild {}", name, guild_id);
-
-        let mut body = json!({
-            "name": name,
-            "type": u32::from(channel_type),
-            "subtype": u32::from(sub_type)
-        });
-
-        if let Some(pos) = position {
-            body["position"] = json!(pos);
-        }
-        if let Some(parent) = parent_id {
-            body["parent_id"] = json!(parent);
-        }
-        if let Some(private) = private_type {
-            body["private_type"] = json!(private);
-        }
-        if let Some(users) = private_user_ids {
-            body["private_user_ids"] = json!(users);
-        }
-        if let Some(speak) = speak_permission {
-            body["speak_permission"] = json!(speak);
-        }
-        if let Some(app) = application_id {
-            body["application_id"] = json!(app);
-        }
+        region_id: &str,
+    ) -> Result<Guild> {
+        debug!(
+            "Setting voice region of guild {} to {}",
+            guild_id, region_id
+        );
+        let regions = self.list_voice_regions(token).await?;
+        ensure_known_region(&regions, region_id)?;
 
-        let path = format!("/guilds/{guild_id}/channels");
+        let body = json!({ "region": region_id });
+        let path = format!("/guilds/{guild_id}");
         let response = self
             .http
-            .post(token, &path, None::<&()>, Some(&body))
+            .patch(token, &path, None::<&()>, Some(&body))
             .await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Updates a channel.
+    /// Gets every guild the bot is in, paired with the bot's membership details.
+    ///
+    /// All guild pages are fetched first, then each guild's details and the
+    /// bot's own member record are fetched with up to 10 requests in flight.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `name` - Optional new name
-    /// * `position` - Optional new position
-    /// * `parent_id` - Optional new parent ID
-    /// * `private_type` - Optional new private type
-    /// * `speak_permission` - Optional new speak permission
     ///
     /// # Returns
     ///
-    /// The updated channel.
-    pub async fn update_channel(
+    /// One `(Guild, GuildBotInfo)` pair per guild, in listing order.
+    pub async fn get_bot_guilds_with_info(
         &self,
         token: &Token,
-        channel_id: &str,
-        name: Option<&str>,
-        position: Option<u32>,
-        parent_id: Option<&str>,
-        private_type: Option<u32>,
-        speak_permission: Option<u32>,
-    ) -> Result<Channel> {
-        debug!("Updating channel {}", channel_id);
-
-        let mut body = json!({});
-        if let Some(name) = name {
-            body["name"] = json!(name);
-        }
-        if let Some(pos) = position {
-            body["position"] = json!(pos);
-        }
-        if let Some(parent) = parent_id {
-            body["parent_id"] = json!(parent);
-        }
-        if let Some(private) = private_type {
-            body["private_type"] = json!(private);
-        }
-        if let Some(speak) = speak_permission {
-            body["speak_permission"] = json!(speak);
-        }
-
-        let path = format!("/channels/{channel_id}");
-        let response = self
-            .http
-            .put(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(serde_json::from_value(response)?)
+    ) -> Result<Vec<(Guild, GuildBotInfo)>> {
+        debug!("Getting all guilds with bot info");
+
+        let guilds = collect_guild_pages(GUILD_PAGE_SIZE, |after| async move {
+            self.get_guilds(token, after.as_deref(), Some(GUILD_PAGE_SIZE), None)
+                .await
+        })
+        .await?;
+
+        let ids = guilds.into_iter().filter_map(|g| g.id).collect();
+        map_concurrent(ids, GUILD_INFO_CONCURRENCY, |guild_id: String| async move {
+            let (guild, member) = futures_util::try_join!(
+                self.get_guild(token, &guild_id),
+                self.get_guild_member(token, &guild_id, "@me")
+            )?;
+            Ok((guild, GuildBotInfo::from(member)))
+        })
+        .await
     }
 
-    /// Deletes a channel.
+    /// Gets the guilds shared by the bot and a user.
+    ///
+    /// Membership is checked across all of the bot's guilds, with at most 10
+    /// requests in flight.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID
     ///
     /// # Returns
     ///
-    /// The deleted channel.
-    pub async fn delete_channel(&self, token: &Token, channel_id: &str) -> Result<Channel> {
-        debug!("Deleting channel {}", channel_id);
-        let path = format!("/channels/{channel_id}");
-        let response = self.http.delete(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+    /// The guilds the user is a member of, in listing order.
+    pub async fn get_mutual_guilds(&self, token: &Token, user_id: &str) -> Result<Vec<Guild>> {
+        debug!("Getting mutual guilds with user {}", user_id);
+
+        let guilds = collect_guild_pages(GUILD_PAGE_SIZE, |after| async move {
+            self.get_guilds(token, after.as_deref(), Some(GUILD_PAGE_SIZE), None)
+                .await
+        })
+        .await?;
+
+        filter_concurrent(guilds, MUTUAL_GUILD_CONCURRENCY, |guild: &Guild| {
+            let guild_id = guild.id.clone();
+            async move {
+                match guild_id {
+                    Some(guild_id) => self.is_guild_member(token, &guild_id, user_id).await,
+                    None => Ok(false),
+                }
+            }
+        })
+        .await
     }
 
-    // Message APIs
-
-    /// Gets a specific message.
+    /// Watches a guild's member, channel and role changes.
+    ///
+    /// The API client has no gateway connection of its own, so events are read
+    /// from a subscription obtained with
+    /// [`Gateway::subscribe_events`](crate::gateway::Gateway::subscribe_events).
+    /// The stream ends when the gateway's event stream closes.
     ///
     /// # Arguments
     ///
-    /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
+    /// * `subscription` - A gateway event subscription
+    /// * `guild_id` - The guild to watch
     ///
     /// # Returns
     ///
-    /// The message.
-    pub async fn get_message(
+    /// A stream of changes to the guild.
+    #[deprecated(
+        since = "0.2.6",
+        note = "Use GatewaySubscription::guild_changes or Context::watch_guild_changes instead"
+    )]
+    pub fn watch_guild_changes(
         &self,
-        token: &Token,
-        channel_id: &str,
-        message_id: &str,
-    ) -> Result<Message> {
-        debug!("Getting message {} in channel {}", message_id, channel_id);
-        let path = format!("/channels/{channel_id}/messages/{message_id}");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+        subscription: GatewaySubscription,
+        guild_id: &str,
+    ) -> impl Stream<Item = GuildChangeEvent> + use<> {
+        subscription.guild_changes(guild_id)
     }
 
-    /// Sends a message to a channel using MessageParams.
-    ///
-    /// This is the new, recommended way to send channel messages. It uses a parameter struct
-    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    /// Gets a summary of a guild's recent activity from its audit log.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `params` - Message parameters (see [`MessageParams`])
+    /// * `guild_id` - The guild ID
+    /// * `since` - Only include activity at or after this time
+    /// * `limit` - Maximum number of audit log entries to read
     ///
     /// # Returns
     ///
-    /// The sent message response.
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// # use botrs::*;
-    /// # use botrs::models::message::MessageParams;
-    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
-    /// // Simple text message
-    /// let params = MessageParams::new_text("Hello world!");
-    /// api.post_message_with_params(token, "channel_id", params).await?;
-    ///
-    /// // Message with reply
-    /// let params = MessageParams::new_text("Reply!").with_reply("message_id");
-    /// api.post_message_with_params(token, "channel_id", params).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn post_message_with_params(
+    /// The guild's activity feed.
+    pub async fn get_guild_activity_feed(
         &self,
         token: &Token,
-        channel_id: &str,
-        params: MessageParams,
-    ) -> Result<MessageResponse> {
-        debug!("Sending message to channel {}", channel_id);
-
-        // Handle file_image encoding if raw bytes were provided separately
-        let body = serde_json::to_value(&params)?;
-
-        let path = format!("/channels/{channel_id}/messages");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
+        guild_id: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> Result<GuildActivityFeed> {
+        debug!(
+            "Getting activity feed for guild {} since {}",
+            guild_id, since
+        );
+        let entries = self
+            .get_guild_audit_log(token, guild_id, None, None, Some(limit))
             .await?;
-        Ok(serde_json::from_value(response)?)
+        Ok(GuildActivityFeed::from_audit_log(guild_id, &entries, since))
     }
 
-    /// Sends a message to a channel (legacy API for backward compatibility).
+    /// Gets per-day activity counts for a guild from its audit log.
+    ///
+    /// The audit log is read page by page until it reaches back past the
+    /// first day.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `content` - Message content
-    /// * `embed` - Optional embed
-    /// * `ark` - Optional ark template
-    /// * `message_reference` - Optional message reference
-    /// * `image` - Optional image URL
-    /// * `file_image` - Optional file image data
-    /// * `msg_id` - Optional message ID to reply to
-    /// * `event_id` - Optional event ID
-    /// * `markdown` - Optional markdown
-    /// * `keyboard` - Optional keyboard
+    /// * `guild_id` - The guild ID
+    /// * `days` - Number of days to cover, ending today (UTC)
     ///
     /// # Returns
     ///
-    /// The sent message response.
-    #[deprecated(since = "0.1.0", note = "Use post_message_with_params instead")]
-    pub async fn post_message(
+    /// One item per day, oldest first.
+    pub async fn get_guild_daily_stats(
         &self,
         token: &Token,
-        channel_id: &str,
-        content: Option<&str>,
-        embed: Option<&Embed>,
-        ark: Option<&Ark>,
-        message_reference: Option<&Reference>,
-        image: Option<&str>,
-        file_image: Option<&[u8]>,
-        msg_id: Option<&str>,
-        event_id: Option<&str>,
-        markdown: Option<&MarkdownPayload>,
-        keyboard: Option<&Keyboard>,
-    ) -> Result<MessageResponse> {
-        let params = MessageParams {
-            content: content.map(|s| s.to_string()),
-            embed: embed.cloned(),
-            ark: ark.cloned(),
-            message_reference: message_reference.cloned(),
-            image: image.map(|s| s.to_string()),
-            file_image: file_image
-                .map(|data| base64::engine::general_purpose::STANDARD.encode(data)),
-            msg_id: msg_id.map(|s| s.to_string()),
-            event_id: event_id.map(|s| s.to_string()),
-            markdown: markdown.cloned(),
-            keyboard: keyboard.cloned(),
-        };
-
-        self.post_message_with_params(token, channel_id, params)
-            .await
+        guild_id: &str,
+        days: u32,
+    ) -> Result<Vec<GuildDailyStats>> {
+        debug!(
+            "Getting daily stats for guild {} over {} days",
+            guild_id, days
+        );
+        let today = chrono::Utc::now().date_naive();
+        let start = today - chrono::Days::new(u64::from(days.saturating_sub(1)));
+        let since = start.and_time(chrono::NaiveTime::MIN).and_utc();
+        let entries = collect_audit_log_since(since, AUDIT_LOG_PAGE_SIZE, |before| {
+            self.get_audit_log_before(token, guild_id, None, before)
+        })
+        .await?;
+        Ok(GuildDailyStats::group_by_day(&entries, start, days))
     }
 
-    /// Sends a group message using GroupMessageParams.
+    /// Gets member activity statistics for a guild.
     ///
-    /// This is the new, recommended way to send group messages. It uses a parameter struct
-    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    /// This endpoint is only available to bots that have been granted access to it.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `group_openid` - The group OpenID
-    /// * `params` - Group message parameters (see [`GroupMessageParams`])
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// The sent group message response.
+    /// Guild member statistics.
+    pub async fn get_guild_stats(&self, token: &Token, guild_id: &str) -> Result<GuildStats> {
+        debug!("Getting stats for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/stats");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets entries from a guild's audit log.
     ///
-    /// # Examples
+    /// # Arguments
     ///
-    /// ```rust,no_run
-    /// # use botrs::*;
-    /// # use botrs::models::message::GroupMessageParams;
-    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
-    /// let params = GroupMessageParams::new_text("Hello group!");
-    /// api.post_group_message_with_params(token, "group_openid", params).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn post_group_message_with_params(
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `action_type` - Only return entries of this action type
+    /// * `user_id` - Only return entries performed by this user
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// Audit log entries, newest first.
+    pub async fn get_guild_audit_log(
         &self,
         token: &Token,
-        group_openid: &str,
-        params: GroupMessageParams,
-    ) -> Result<MessageResponse> {
-        debug!("Sending group message to {}", group_openid);
+        guild_id: &str,
+        action_type: Option<AuditLogAction>,
+        user_id: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        debug!("Getting audit log for guild {}", guild_id);
 
-        let body = serde_json::to_value(&params)?;
+        let mut params = HashMap::new();
+        if let Some(action_type) = action_type {
+            params.insert("action_type", u8::from(action_type).to_string());
+        }
+        if let Some(user_id) = user_id {
+            params.insert("user_id", user_id.to_string());
+        }
+        if let Some(limit) = limit {
+            params.insert("limit", limit.to_string());
+        }
 
-        let path = format!("/v2/groups/{group_openid}/messages");
+        let path = format!("/guilds/{guild_id}/audit-logs");
         let response = self
             .http
-            .post(token, &path, None::<&()>, Some(&body))
+            .get(
+                token,
+                &path,
+                if params.is_empty() {
+                    None
+                } else {
+                    Some(&params)
+                },
+            )
             .await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Sends a group message (legacy API for backward compatibility).
+    /// Gets a page of the newest audit log entries in a guild before an entry.
+    async fn get_audit_log_before(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        action_type: Option<AuditLogAction>,
+        before: Option<String>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let mut params = HashMap::new();
+        params.insert("limit", AUDIT_LOG_PAGE_SIZE.to_string());
+        if let Some(action_type) = action_type {
+            params.insert("action_type", u8::from(action_type).to_string());
+        }
+        if let Some(before) = before {
+            params.insert("before", before);
+        }
+
+        let path = format!("/guilds/{guild_id}/audit-logs");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the moderation history of a forum channel.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `group_openid` - The group OpenID
-    /// * `msg_type` - Message type (0=text, 1=rich text, 2=markdown, 3=ark, 4=embed, 7=media)
-    /// * `content` - Message content
-    /// * `embed` - Optional embed
-    /// * `ark` - Optional ark template
-    /// * `message_reference` - Optional message reference
-    /// * `media` - Optional media
-    /// * `msg_id` - Optional message ID to reply to
-    /// * `msg_seq` - Optional message sequence number
-    /// * `event_id` - Optional event ID
-    /// * `markdown` - Optional markdown
-    /// * `keyboard` - Optional keyboard
+    /// * `channel_id` - The forum channel ID
+    /// * `limit` - Maximum number of entries to return
     ///
     /// # Returns
     ///
-    /// The sent group message response.
-    #[deprecated(since = "0.1.0", note = "Use post_group_message_with_params instead")]
-    pub async fn post_group_message(
+    /// Forum audit log entries, newest first.
+    pub async fn get_forum_audit_log(
         &self,
         token: &Token,
-        group_openid: &str,
-        msg_type: Option<u32>,
-        content: Option<&str>,
-        embed: Option<&Embed>,
-        ark: Option<&Ark>,
-        message_reference: Option<&Reference>,
-        media: Option<&Media>,
-        msg_id: Option<&str>,
-        msg_seq: Option<u32>,
-        event_id: Option<&str>,
-        markdown: Option<&MarkdownPayload>,
-        keyboard: Option<&KeyboardPayload>,
-    ) -> Result<MessageResponse> {
-        let params = GroupMessageParams {
-            msg_type: msg_type.unwrap_or(0),
-            content: content.map(|s| s.to_string()),
-            embed: embed.cloned(),
-            ark: ark.cloned(),
-            message_reference: message_reference.cloned(),
-            media: media.cloned(),
-            msg_id: msg_id.map(|s| s.to_string()),
-            msg_seq,
-            event_id: event_id.map(|s| s.to_string()),
-            markdown: markdown.cloned(),
-            keyboard: keyboard.cloned(),
-        };
+        channel_id: &str,
+        limit: u32,
+    ) -> Result<Vec<ForumAuditEntry>> {
+        debug!("Getting forum audit log for channel {}", channel_id);
 
-        self.post_group_message_with_params(token, group_openid, params)
-            .await
+        let mut params = HashMap::new();
+        params.insert("limit", limit.to_string());
+
+        let path = format!("/channels/{channel_id}/audit-logs");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Sends a C2C (client-to-client) message using C2CMessageParams.
+    /// Gets the history of a member in a guild from the audit log.
     ///
-    /// This is the new, recommended way to send C2C messages. It uses a parameter struct
-    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    /// Joins, removals, bans and role or nickname changes are fetched at the
+    /// same time and merged. Each action's log is paged back until it holds
+    /// `limit` events about the member or runs out. The audit log's
+    /// user filter matches the user who performed an action, which for a
+    /// kick or ban is the moderator, so the entries about the member are
+    /// picked out here instead.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `openid` - The user's OpenID
-    /// * `params` - C2C message parameters (see [`C2CMessageParams`])
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The member's user ID
+    /// * `limit` - Maximum number of events to return
     ///
     /// # Returns
     ///
-    /// The sent C2C message response.
-    ///
-    /// # Examples
-    ///
-    /// ```rust,no_run
-    /// # use botrs::*;
-    /// # use botrs::models::message::C2CMessageParams;
-    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
-    /// let params = C2CMessageParams::new_text("Hello user!");
-    /// api.post_c2c_message_with_params(token, "user_openid", params).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn post_c2c_message_with_params(
+    /// The member's most recent events, oldest first.
+    pub async fn get_member_timeline(
         &self,
         token: &Token,
-        openid: &str,
-        params: C2CMessageParams,
-    ) -> Result<MessageResponse> {
-        debug!("Sending C2C message to {}", openid);
-
-        let body = serde_json::to_value(&params)?;
-
-        let path = format!("/v2/users/{openid}/messages");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(serde_json::from_value(response)?)
+        guild_id: &str,
+        user_id: &str,
+        limit: u32,
+    ) -> Result<Vec<MemberEvent>> {
+        debug!("Getting timeline of member {} in {}", user_id, guild_id);
+
+        let pages = futures_util::future::try_join_all(MEMBER_TIMELINE_ACTIONS.map(|action| {
+            collect_member_audit_log(
+                guild_id,
+                user_id,
+                limit,
+                AUDIT_LOG_PAGE_SIZE,
+                move |before| self.get_audit_log_before(token, guild_id, Some(action), before),
+            )
+        }))
+        .await?;
+        let mut seen = std::collections::HashSet::new();
+        let entries: Vec<_> = pages
+            .into_iter()
+            .flatten()
+            .filter(|entry| seen.insert(entry.key()))
+            .collect();
+
+        let mut timeline = MemberEvent::timeline(guild_id, user_id, &entries);
+        let excess = timeline.len().saturating_sub(limit as usize);
+        timeline.drain(..excess);
+        Ok(timeline)
     }
 
-    /// Sends a C2C (client-to-client) message (legacy API for backward compatibility).
+    /// Streams new entries from a guild's audit log as they appear.
+    ///
+    /// The log is polled every `poll_interval` and only entries not seen in
+    /// the previous poll are yielded, oldest first; entries that existed
+    /// before the stream started are skipped. After an error the interval is
+    /// doubled, up to 32 times the original, and it is restored after 3
+    /// polls in a row succeed.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `openid` - The user's OpenID
-    /// * `msg_type` - Message type (0=text, 1=rich text, 2=markdown, 3=ark, 4=embed, 7=media)
-    /// * `content` - Message content
-    /// * `embed` - Optional embed
-    /// * `ark` - Optional ark template
-    /// * `message_reference` - Optional message reference
-    /// * `media` - Optional media
-    /// * `msg_id` - Optional message ID to reply to
-    /// * `msg_seq` - Optional message sequence number
-    /// * `event_id` - Optional event ID
-    /// * `markdown` - Optional markdown
-    /// * `keyboard` - Optional keyboard
+    /// * `guild_id` - The guild ID
+    /// * `poll_interval` - Time between polls
     ///
     /// # Returns
     ///
-    /// The sent C2C message response.
-    #[deprecated(since = "0.1.0", note = "Use post_c2c_message_with_params instead")]
-    pub async fn post_c2c_message(
-        &self,
-        token: &Token,
-        openid: &str,
-        msg_type: Option<u32>,
-        content: Option<&str>,
-        embed: Option<&Embed>,
-        ark: Option<&Ark>,
-        message_reference: Option<&Reference>,
-        media: Option<&Media>,
-        msg_id: Option<&str>,
-        msg_seq: Option<u32>,
-        event_id: Option<&str>,
-        markdown: Option<&MarkdownPayload>,
-        keyboard: Option<&KeyboardPayload>,
-    ) -> Result<MessageResponse> {
-        let params = C2CMessageParams {
-            msg_type: msg_type.unwrap_or(0),
-            content: content.map(|s| s.to_string()),
-            embed: embed.cloned(),
-            ark: ark.cloned(),
-            message_reference: message_reference.cloned(),
-            media: media.cloned(),
-            msg_id: msg_id.map(|s| s.to_string()),
-            msg_seq,
-            event_id: event_id.map(|s| s.to_string()),
-            markdown: markdown.cloned(),
-            keyboard: keyboard.cloned(),
-        };
-
-        self.post_c2c_message_with_params(token, openid, params)
-            .await
+    /// An endless stream of new audit log entries and poll errors.
+    pub fn audit_log_stream<'a>(
+        &'a self,
+        token: &'a Token,
+        guild_id: &'a str,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<AuditLogEntry>> + 'a {
+        self.audit_log_stream_from(
+            token,
+            guild_id,
+            poll_interval,
+            AuditLogStream::since(chrono::Utc::now()),
+        )
     }
 
-    /// Sends a direct message using DirectMessageParams.
+    /// Streams audit log entries like [`BotApi::audit_log_stream`], starting
+    /// from a given tracker.
     ///
-    /// This is the new, recommended way to send direct messages. It uses a parameter struct
-    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    /// Use [`AuditLogStream::since`] to also yield entries created after an
+    /// earlier point in time.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The DM session guild ID
-    /// * `params` - Direct message parameters (see [`DirectMessageParams`])
+    /// * `guild_id` - The guild ID
+    /// * `poll_interval` - Time between polls
+    /// * `tracker` - Which entries count as new
     ///
     /// # Returns
     ///
-    /// The sent direct message response.
+    /// An endless stream of new audit log entries and poll errors.
+    pub fn audit_log_stream_from<'a>(
+        &'a self,
+        token: &'a Token,
+        guild_id: &'a str,
+        poll_interval: Duration,
+        tracker: AuditLogStream,
+    ) -> impl Stream<Item = Result<AuditLogEntry>> + 'a {
+        debug!("Streaming audit log for guild {}", guild_id);
+        poll_audit_log(poll_interval, tracker, move || {
+            self.get_guild_audit_log(token, guild_id, None, None, None)
+        })
+    }
+
+    /// Gets the times a user joined a guild, newest first.
     ///
-    /// # Examples
+    /// Useful for spotting accounts that repeatedly leave and rejoin, for
+    /// example to evade bans.
     ///
-    /// ```rust,no_run
-    /// # use botrs::*;
-    /// # use botrs::models::message::DirectMessageParams;
-    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
-    /// let params = DirectMessageParams::new_text("Hello DM!");
-    /// api.post_dms_with_params(token, "guild_id", params).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn post_dms_with_params(
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// The user's member join audit log entries.
+    pub async fn get_member_join_history(
         &self,
         token: &Token,
         guild_id: &str,
-        params: DirectMessageParams,
-    ) -> Result<MessageResponse> {
-        debug!("Sending direct message to guild session {}", guild_id);
-
-        let body = serde_json::to_value(&params)?;
-
-        let path = format!("/dms/{guild_id}/messages");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
+        user_id: &str,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let entries = self
+            .get_guild_audit_log(
+                token,
+                guild_id,
+                Some(AuditLogAction::MemberAdd),
+                Some(user_id),
+                Some(limit),
+            )
             .await?;
-        Ok(serde_json::from_value(response)?)
+        Ok(filter_join_entries(entries, user_id))
     }
 
-    /// Sends a direct message (legacy API for backward compatibility).
+    // Guild Role APIs
+
+    /// Gets guild roles.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The DM session guild ID
-    /// * `content` - Message content
-    /// * `embed` - Optional embed
-    /// * `ark` - Optional ark template
-    /// * `message_reference` - Optional message reference
-    /// * `image` - Optional image URL
-    /// * `file_image` - Optional file image data
-    /// * `msg_id` - Optional message ID to reply to
-    /// * `event_id` - Optional event ID
-    /// * `markdown` - Optional markdown
-    /// * `keyboard` - Optional keyboard
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// The sent direct message response.
-    #[deprecated(since = "0.1.0", note = "Use post_dms_with_params instead")]
-    pub async fn post_dms(
-        &self,
-        token: &Token,
-        guild_id: &str,
-        content: Option<&str>,
-        embed: Option<&Embed>,
-        ark: Option<&Ark>,
-        message_reference: Option<&Reference>,
-        image: Option<&str>,
-        file_image: Option<&[u8]>,
-        msg_id: Option<&str>,
-        event_id: Option<&str>,
-        markdown: Option<&MarkdownPayload>,
-        keyboard: Option<&Keyboard>,
-    ) -> Result<MessageResponse> {
-        let params = DirectMessageParams {
-            content: content.map(|s| s.to_string()),
-            embed: embed.cloned(),
-            ark: ark.cloned(),
-            message_reference: message_reference.cloned(),
-            image: image.map(|s| s.to_string()),
-            file_image: file_image
-                .map(|data| base64::engine::general_purpose::STANDARD.encode(data)),
-            msg_id: msg_id.map(|s| s.to_string()),
-            event_id: event_id.map(|s| s.to_string()),
-            markdown: markdown.cloned(),
-            keyboard: keyboard.cloned(),
-        };
-
-        self.post_dms_with_params(token, guild_id, params).await
+    /// Guild roles information.
+    pub async fn get_guild_roles(&self, token: &Token, guild_id: &str) -> Result<GuildRoles> {
+        debug!("Getting guild roles for {}", guild_id);
+        let path = format!("/guilds/{guild_id}/roles");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Creates a direct message session.
+    /// Gets the number of members holding each role in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The source guild ID
-    /// * `user_id` - The target user ID
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// DM session information.
-    pub async fn create_dms(&self, token: &Token, guild_id: &str, user_id: &str) -> Result<Value> {
-        debug!(
-            "Creating DM session for user {} from guild {}",
-            user_id, guild_id
-        );
-
-        let body = json!({
-            "recipient_id": user_id,
-            "source_guild_id": guild_id
-        });
-
-        let response = self
-            .http
-            .post(token, "/users/@me/dms", None::<&()>, Some(&body))
-            .await?;
-        Ok(response)
+    /// A map from role ID to member count.
+    pub async fn get_role_member_counts(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<HashMap<String, u32>> {
+        let roles = self.get_guild_roles(token, guild_id).await?;
+        Ok(roles.member_counts())
     }
 
-    /// Recalls (deletes) a message.
+    /// Gets each role's member count and share of the guild, largest first.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
-    /// * `hidetip` - Whether to hide the recall tip
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn recall_message(
+    /// One entry per role, sorted by member count descending.
+    pub async fn get_role_breakdown(
         &self,
         token: &Token,
-        channel_id: &str,
-        message_id: &str,
-        hidetip: Option<bool>,
-    ) -> Result<()> {
-        debug!("Recalling message {} in channel {}", message_id, channel_id);
-
-        let mut params = HashMap::new();
-        params.insert(
-            "hidetip",
-            if hidetip.unwrap_or(false) {
-                "true"
-            } else {
-                "false"
-            }
-            .to_string(),
-        );
-
-        let path = format!("/channels/{channel_id}/messages/{message_id}");
-        self.http.delete(token, &path, Some(&params)).await?;
-        Ok(())
+        guild_id: &str,
+    ) -> Result<Vec<RoleMemberCountBreakdown>> {
+        let (guild, roles) = futures_util::try_join!(
+            self.get_guild(token, guild_id),
+            self.get_guild_roles(token, guild_id)
+        )?;
+        Ok(roles.breakdown(guild.get_member_count()))
     }
 
-    // Audio APIs
-
-    /// Updates audio control.
+    /// Creates a new guild role.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `audio_control` - Audio control data
+    /// * `guild_id` - The guild ID
+    /// * `name` - Role name
+    /// * `color` - Role color (ARGB hex as decimal)
+    /// * `hoist` - Whether to display separately in member list
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn update_audio(
+    /// The created role.
+    pub async fn create_guild_role(
         &self,
         token: &Token,
-        channel_id: &str,
-        audio_control: &AudioAction,
-    ) -> Result<()> {
-        debug!("Updating audio in channel {}", channel_id);
-        let path = format!("/channels/{channel_id}/audio");
-        let _response = self
+        guild_id: &str,
+        name: Option<&str>,
+        color: Option<u32>,
+        hoist: Option<bool>,
+    ) -> Result<GuildRole> {
+        debug!("Creating guild role in {}", guild_id);
+
+        let mut body = HashMap::new();
+        if let Some(name) = name {
+            body.insert("name", json!(name));
+        }
+        if let Some(color) = color {
+            body.insert("color", json!(color));
+        }
+        if let Some(hoist) = hoist {
+            body.insert("hoist", json!(if hoist { 1 } else { 0 }));
+        }
+
+        let path = format!("/guilds/{guild_id}/roles");
+        let response = self
             .http
-            .post(token, &path, None::<&()>, Some(audio_control))
+            .post(token, &path, None::<&()>, Some(&body))
             .await?;
-        Ok(())
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Turn on microphone.
+    /// Updates a guild role.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild ID
+    /// * `role_id` - The role ID
+    /// * `name` - Role name
+    /// * `color` - Role color (ARGB hex as decimal)
+    /// * `hoist` - Whether to display separately in member list
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn on_microphone(&self, token: &Token, channel_id: &str) -> Result<()> {
-        debug!("Turning on microphone in channel {}", channel_id);
-        let path = format!("/channels/{channel_id}/mic");
-        self.http
-            .put(token, &path, None::<&()>, None::<&()>)
+    /// The updated role.
+    pub async fn update_guild_role(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        role_id: &str,
+        name: Option<&str>,
+        color: Option<u32>,
+        hoist: Option<bool>,
+    ) -> Result<GuildRole> {
+        debug!("Updating guild role {} in {}", role_id, guild_id);
+
+        let mut body = HashMap::new();
+        if let Some(name) = name {
+            body.insert("name", json!(name));
+        }
+        if let Some(color) = color {
+            body.insert("color", json!(color));
+        }
+        if let Some(hoist) = hoist {
+            body.insert("hoist", json!(if hoist { 1 } else { 0 }));
+        }
+
+        let path = format!("/guilds/{guild_id}/roles/{role_id}");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
             .await?;
-        Ok(())
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Turn off microphone.
+    /// Makes a guild's custom roles match a list of role specs.
+    ///
+    /// Roles are matched by name: specs without a matching role are created,
+    /// matching roles whose color or hoist differ are updated, and custom
+    /// roles not named in `desired_roles` are deleted. System roles are never
+    /// deleted. Each kind of change runs concurrently; the first failure is
+    /// returned.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild ID
+    /// * `desired_roles` - The roles the guild should have; names must be unique
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn off_microphone(&self, token: &Token, channel_id: &str) -> Result<()> {
-        debug!("Turning off microphone in channel {}", channel_id);
-        let path = format!("/channels/{channel_id}/mic");
-        self.http.delete(token, &path, None::<&()>).await?;
-        Ok(())
-    }
+    /// The roles created, updated and deleted.
+    pub async fn sync_guild_roles(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        desired_roles: Vec<RoleSpec>,
+    ) -> Result<GuildRoleSyncResult> {
+        debug!(
+            "Syncing {} roles in guild {}",
+            desired_roles.len(),
+            guild_id
+        );
+        let current = self.get_guild_roles(token, guild_id).await?;
+        let plan = RoleSyncPlan::new(&current.roles, desired_roles)?;
+
+        let create = map_concurrent(
+            plan.create,
+            ROLE_SYNC_CONCURRENCY,
+            |spec: RoleSpec| async move {
+                self.create_guild_role(token, guild_id, Some(&spec.name), spec.color, spec.hoist)
+                    .await
+            },
+        );
+        let update = map_concurrent(
+            plan.update,
+            ROLE_SYNC_CONCURRENCY,
+            |(role_id, spec): (String, RoleSpec)| async move {
+                self.update_guild_role(token, guild_id, &role_id, None, spec.color, spec.hoist)
+                    .await
+            },
+        );
+        let delete = map_concurrent(
+            plan.delete,
+            ROLE_SYNC_CONCURRENCY,
+            |role_id: String| async move {
+                self.delete_guild_role(token, guild_id, &role_id).await?;
+                Ok(role_id)
+            },
+        );
+        let (created, updated, deleted) = futures_util::try_join!(create, update, delete)?;
 
-    // Muting APIs
+        Ok(GuildRoleSyncResult {
+            created,
+            updated,
+            deleted,
+        })
+    }
 
-    /// Mutes all members in a guild.
+    /// Deletes a guild role.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `mute_end_timestamp` - Optional end timestamp
-    /// * `mute_seconds` - Optional duration in seconds
+    /// * `role_id` - The role ID
     ///
     /// # Returns
     ///
     /// Success indication.
-    pub async fn mute_all(
+    pub async fn delete_guild_role(
         &self,
         token: &Token,
         guild_id: &str,
-        mute_end_timestamp: Option<&str>,
-        mute_seconds: Option<&str>,
+        role_id: &str,
     ) -> Result<()> {
-        debug!("Muting all members in guild {}", guild_id);
-
-        let body = json!({
-            "mute_end_timestamp": mute_end_timestamp,
-            "mute_seconds": mute_seconds
-        });
-
-        let path = format!("/guilds/{guild_id}/mute");
-        self.http
-            .put(token, &path, None::<&()>, Some(&body))
-            .await?;
+        debug!("Deleting guild role {} in {}", role_id, guild_id);
+        let path = format!("/guilds/{guild_id}/roles/{role_id}");
+        self.http.delete(token, &path, None::<&()>).await?;
         Ok(())
     }
 
-    /// Cancels mute for all members.
+    /// Adds a member to a guild role.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
+    /// * `role_id` - The role ID
+    /// * `user_id` - The user ID
+    /// * `channel_id` - Optional channel ID (for channel-specific roles)
     ///
     /// # Returns
     ///
     /// Success indication.
-    pub async fn cancel_mute_all(&self, token: &Token, guild_id: &str) -> Result<()> {
-        debug!("Canceling mute for all members in guild {}", guild_id);
-
-        let body = json!({
-            "mute_end_timestamp": "0",
-            "mute_seconds": "0"
-        });
+    pub async fn create_guild_role_member(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        role_id: &str,
+        user_id: &str,
+        channel_id: Option<&str>,
+    ) -> Result<()> {
+        debug!(
+            "Adding user {} to role {} in guild {}",
+            user_id, role_id, guild_id
+        );
 
-        let path = format!("/guilds/{guild_id}/mute");
+        let body = if let Some(channel_id) = channel_id {
+            json!({ "channel": { "id": channel_id } })
+        } else {
+            json!({ "channel": { "id": null } })
+        };
+
+        let path = format!("/guilds/{guild_id}/members/{user_id}/roles/{role_id}");
         self.http
             .put(token, &path, None::<&()>, Some(&body))
             .await?;
         Ok(())
     }
 
-    /// Mutes a specific member.
+    /// Removes a member from a guild role.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
+    /// * `role_id` - The role ID
     /// * `user_id` - The user ID
-    /// * `mute_end_timestamp` - Optional end timestamp
-    /// * `mute_seconds` - Optional duration in seconds
+    /// * `channel_id` - Optional channel ID (for channel-specific roles)
     ///
     /// # Returns
     ///
     /// Success indication.
-    pub async fn mute_member(
+    pub async fn delete_guild_role_member(
         &self,
         token: &Token,
         guild_id: &str,
+        role_id: &str,
         user_id: &str,
-        mute_end_timestamp: Option<&str>,
-        mute_seconds: Option<&str>,
+        channel_id: Option<&str>,
     ) -> Result<()> {
-        debug!("Muting member {} in guild {}", user_id, guild_id);
+        debug!(
+            "Removing user {} from role {} in guild {}",
+            user_id, role_id, guild_id
+        );
 
-        let body = json!({
-            "mute_end_timestamp": mute_end_timestamp,
-            "mute_seconds": mute_seconds
-        });
+        let body = if let Some(channel_id) = channel_id {
+            json!({ "channel": { "id": channel_id } })
+        } else {
+            json!({ "channel": { "id": null } })
+        };
 
-        let path = format!("/guilds/{guild_id}/members/{user_id}/mute");
-        self.http
-            .put(token, &path, None::<&()>, Some(&body))
-            .await?;
+        let path = format!("/guilds/{guild_id}/members/{user_id}/roles/{role_id}");
+        self.http.delete(token, &path, Some(&body)).await?;
         Ok(())
     }
 
-    /// Gets channel permissions for a user.
+    // Member APIs
+
+    /// Gets a guild member.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild ID
     /// * `user_id` - The user ID
     ///
     /// # Returns
     ///
-    /// Channel permissions.
-    pub async fn get_channel_user_permissions(
+    /// Member information.
+    pub async fn get_guild_member(
         &self,
         token: &Token,
-        channel_id: &str,
+        guild_id: &str,
         user_id: &str,
-    ) -> Result<ChannelPermissions> {
-        debug!(
-            "Getting channel permissions for user {} in channel {}",
-            user_id, channel_id
-        );
-        let path = format!("/channels/{channel_id}/members/{user_id}/permissions");
+    ) -> Result<Member> {
+        debug!("Getting guild member {} in {}", user_id, guild_id);
+        let path = format!("/guilds/{guild_id}/members/{user_id}");
         let response = self.http.get(token, &path, None::<&()>).await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Gets channel permissions for a role.
+    /// Checks whether a user is a member of a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `role_id` - The role ID
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
     ///
     /// # Returns
     ///
-    /// Channel permissions.
-    pub async fn get_channel_role_permissions(
+    /// `true` if the user is a member, `false` if the guild reports them as not found.
+    pub async fn is_guild_member(
         &self,
         token: &Token,
-        channel_id: &str,
-        role_id: &str,
-    ) -> Result<ChannelPermissions> {
-        debug!(
-            "Getting channel permissions for role {} in channel {}",
-            role_id, channel_id
-        );
-        let path = format!("/channels/{channel_id}/roles/{role_id}/permissions");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<bool> {
+        Ok(self
+            .find_guild_member(token, guild_id, user_id)
+            .await?
+            .is_some())
     }
 
-    /// Adds a reaction to a message.
+    /// Gets a guild member, failing with [`BotError::NotFound`] if the user
+    /// isn't in the guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
-    /// * `emoji_type` - The emoji type (1=system, 2=emoji)
-    /// * `emoji_id` - The emoji ID
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn put_reaction(
+    /// The member.
+    pub async fn assert_guild_member(
         &self,
         token: &Token,
-        channel_id: &str,
-        message_id: &str,
-        emoji_type: u32,
-        emoji_id: &str,
-    ) -> Result<()> {
-        debug!(
-            "Adding reaction to message {} in channel {}",
-            message_id, channel_id
-        );
-        let path = format!(
-            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}"
-        );
-        self.http
-            .put(token, &path, None::<&()>, None::<&()>)
-            .await?;
-        Ok(())
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<Member> {
+        self.find_guild_member(token, guild_id, user_id)
+            .await?
+            .ok_or_else(|| {
+                BotError::NotFound(format!(
+                    "user {user_id} is not a member of guild {guild_id}"
+                ))
+            })
     }
 
-    /// Removes a reaction from a message.
+    /// Looks up a guild member, returning `None` if the user isn't in the guild.
+    async fn find_guild_member(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<Option<Member>> {
+        not_found_as_none(self.get_guild_member(token, guild_id, user_id).await)?
+            .map(|member| ensure_member_of(member, user_id))
+            .transpose()
+    }
+
+    /// Gets a member's recent activity in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
-    /// * `emoji_type` - The emoji type (1=system, 2=emoji)
-    /// * `emoji_id` - The emoji ID
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_reaction(
+    /// The member's activity summary.
+    pub async fn get_member_activity(
         &self,
         token: &Token,
-        channel_id: &str,
-        message_id: &str,
-        emoji_type: u32,
-        emoji_id: &str,
-    ) -> Result<()> {
-        debug!(
-            "Removing reaction from message {} in channel {}",
-            message_id, channel_id
-        );
-        let path = format!(
-            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}"
-        );
-        self.http.delete(token, &path, None::<&()>).await?;
-        Ok(())
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<MemberActivity> {
+        debug!("Getting activity of member {} in {}", user_id, guild_id);
+        let path = format!("/guilds/{guild_id}/members/{user_id}/activity");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Pins a message.
+    /// Gets the presences of the members of a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn put_pin(
+    /// The members' presences.
+    pub async fn get_guild_presences(
         &self,
         token: &Token,
-        channel_id: &str,
-        message_id: &str,
-    ) -> Result<Value> {
-        debug!("Pinning message {} in channel {}", message_id, channel_id);
-        let path = format!("/channels/{channel_id}/pins/{message_id}");
-        let response = self
-            .http
-            .put(token, &path, None::<&()>, Some(&json!({})))
-            .await?;
-        Ok(response)
+        guild_id: &str,
+    ) -> Result<Vec<UserPresence>> {
+        debug!("Getting member presences in guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/presences");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Unpins a message.
+    /// Gets guild members list.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID
+    /// * `guild_id` - The guild ID
+    /// * `after` - Get members after this user ID
+    /// * `limit` - Maximum number of members to return (1-400)
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_pin(
+    /// List of members.
+    pub async fn get_guild_members(
         &self,
         token: &Token,
-        channel_id: &str,
-        message_id: &str,
-    ) -> Result<()> {
-        debug!("Unpinning message {} in channel {}", message_id, channel_id);
-        let path = format!("/channels/{channel_id}/pins/{message_id}");
-        self.http.delete(token, &path, None::<&()>).await?;
-        Ok(())
+        guild_id: &str,
+        after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Member>> {
+        debug!("Getting guild members for {}", guild_id);
+
+        let mut params = HashMap::new();
+        params.insert("after", after.unwrap_or("0").to_string());
+        params.insert("limit", limit.unwrap_or(1).to_string());
+
+        let path = format!("/guilds/{guild_id}/members");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    /// Gets pinned messages.
+    /// Streams every member of a guild, fetching pages on demand.
+    ///
+    /// The stream ends once all members have been yielded, or after the first error.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// Pinned messages.
-    pub async fn get_pins(&self, token: &Token, channel_id: &str) -> Result<Value> {
-        debug!("Getting pinned messages in channel {}", channel_id);
-        let path = format!("/channels/{channel_id}/pins");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(response)
+    /// A stream of members in API order.
+    pub fn guild_members_stream<'a>(
+        &'a self,
+        token: &'a Token,
+        guild_id: &'a str,
+    ) -> impl Stream<Item = Result<Member>> + 'a {
+        paginate_members(MEMBER_PAGE_SIZE, move |after| async move {
+            self.get_guild_members(token, guild_id, Some(&after), Some(MEMBER_PAGE_SIZE))
+                .await
+        })
     }
 
-    /// Uploads a group file.
+    /// Gets the members of a guild who joined after a specific time.
+    ///
+    /// Members are fetched page by page until the whole guild has been
+    /// listed or `limit` members have been fetched, then filtered. Members
+    /// without a join time are left out.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `group_openid` - The group OpenID
-    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
-    /// * `url` - File URL
-    /// * `srv_send_msg` - Whether to send directly
+    /// * `guild_id` - The guild ID
+    /// * `since` - Only members who joined after this time are kept
+    /// * `limit` - Maximum number of members to fetch
     ///
     /// # Returns
     ///
-    /// Media response.
-    pub async fn post_group_file(
+    /// The members who joined after `since`, in API order.
+    pub async fn get_members_joined_after(
         &self,
         token: &Token,
-        group_openid: &str,
-        file_type: u32,
-        url: &str,
-        srv_send_msg: Option<bool>,
-    ) -> Result<Value> {
-        debug!("Uploading group file to {}", group_openid);
-
-        let body = json!({
-            "file_type": file_type,
-            "url": url,
-            "srv_send_msg": srv_send_msg.unwrap_or(false)
-        });
+        guild_id: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> Result<Vec<Member>> {
+        debug!(
+            "Getting members of guild {} who joined after {}",
+            guild_id, since
+        );
 
-        let path = format!("/v2/groups/{group_openid}/files");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
+        let members: Vec<Member> = self
+            .guild_members_stream(token, guild_id)
+            .take(limit as usize)
+            .try_collect()
             .await?;
-        Ok(response)
+        Ok(members_joined_after(members, since))
     }
 
-    /// Uploads a C2C file.
+    /// Gets a page of the members currently online in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `openid` - The user's OpenID
-    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
-    /// * `url` - File URL
-    /// * `srv_send_msg` - Whether to send directly
+    /// * `guild_id` - The guild ID
+    /// * `cursor` - Cursor from the previous page, `None` for the first page
+    /// * `limit` - Maximum number of members to return
     ///
     /// # Returns
     ///
-    /// Media response.
-    pub async fn post_c2c_file(
+    /// The page of online members and the cursor for the next page.
+    pub async fn get_online_members(
         &self,
         token: &Token,
-        openid: &str,
-        file_type: u32,
-        url: &str,
-        srv_send_msg: Option<bool>,
-    ) -> Result<Value> {
-        debug!("Uploading C2C file to {}", openid);
+        guild_id: &str,
+        cursor: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<OnlineMembersPage> {
+        debug!("Getting online members for {}", guild_id);
 
-        let body = json!({
-            "file_type": file_type,
-            "url": url,
-            "srv_send_msg": srv_send_msg.unwrap_or(false)
-        });
+        let mut params = HashMap::new();
+        if let Some(cursor) = cursor {
+            params.insert("cursor", cursor.to_string());
+        }
+        if let Some(limit) = limit {
+            params.insert("limit", limit.to_string());
+        }
 
-        let path = format!("/v2/users/{openid}/files");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(response)
+        let path = format!("/guilds/{guild_id}/online/members");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
     }
 
-    // Announcement APIs
+    /// Streams every member currently online in a guild, fetching pages on demand.
+    ///
+    /// The stream ends once a page has no next cursor, or after the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// A stream of online members in API order.
+    pub fn all_online_members_stream<'a>(
+        &'a self,
+        token: &'a Token,
+        guild_id: &'a str,
+    ) -> impl Stream<Item = Result<Member>> + 'a {
+        paginate_online_members(move |cursor| async move {
+            self.get_online_members(
+                token,
+                guild_id,
+                cursor.as_deref(),
+                Some(ONLINE_MEMBER_PAGE_SIZE),
+            )
+            .await
+        })
+    }
 
-    /// Creates a message-type guild announcement.
+    /// Exports every member of a guild as CSV.
+    ///
+    /// The first row holds the field names; each following row is one member
+    /// with `fields` in the given order.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID where the announcement will be created
-    /// * `channel_id` - The channel ID containing the message to announce
-    /// * `message_id` - The message ID to turn into an announcement
+    /// * `guild_id` - The guild ID
+    /// * `fields` - The columns to export
     ///
     /// # Returns
     ///
-    /// The created announcement.
-    pub async fn create_announce(
+    /// The CSV document.
+    pub async fn export_guild_members_csv(
         &self,
         token: &Token,
         guild_id: &str,
-        channel_id: &str,
-        message_id: &str,
-    ) -> Result<Announce> {
-        debug!(
-            "Creating announcement in guild {} for message {}",
-            guild_id, message_id
-        );
-
-        let body = json!({
-            "channel_id": channel_id,
-            "message_id": message_id
-        });
+        fields: &[MemberField],
+    ) -> Result<String> {
+        if fields.is_empty() {
+            return Err(BotError::invalid_params(
+                "at least one member field is required",
+            ));
+        }
+        debug!("Exporting members of guild {} as CSV", guild_id);
 
-        let path = format!("/guilds/{guild_id}/announces");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
+        let members: Vec<Member> = self
+            .guild_members_stream(token, guild_id)
+            .try_collect()
             .await?;
-        Ok(serde_json::from_value(response)?)
+        members_to_csv(&members, fields)
     }
 
-    /// Creates a recommended channel announcement.
+    /// Removes a member from a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID where the announcement will be created
-    /// * `announces_type` - The type of announcement
-    /// * `recommend_channels` - List of channels to recommend
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    /// * `add_blacklist` - Whether to add to blacklist
+    /// * `delete_history_msg_days` - Days of message history to delete
     ///
     /// # Returns
     ///
-    /// The created announcement.
-    pub async fn create_recommend_announce(
+    /// Success indication.
+    pub async fn delete_member(
         &self,
         token: &Token,
         guild_id: &str,
-        announces_type: AnnouncesType,
-        recommend_channels: Vec<RecommendChannel>,
-    ) -> Result<Announce> {
-        debug!("Creating recommend announcement in guild {}", guild_id);
+        user_id: &str,
+        add_blacklist: Option<bool>,
+        delete_history_msg_days: Option<i32>,
+    ) -> Result<()> {
+        debug!("Deleting member {} from guild {}", user_id, guild_id);
+
+        let delete_days = match delete_history_msg_days.unwrap_or(0) {
+            3 | 7 | 15 | 30 | -1 => delete_history_msg_days.unwrap_or(0),
+            _ => 0,
+        };
 
         let body = json!({
-            "announces_type": u8::from(announces_type),
-            "recommend_channels": recommend_channels
+            "add_blacklist": add_blacklist.unwrap_or(false),
+            "delete_history_msg_days": delete_days
         });
 
-        let path = format!("/guilds/{guild_id}/announces");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(serde_json::from_value(response)?)
+        let path = format!("/guilds/{guild_id}/members/{user_id}");
+        self.http.delete(token, &path, Some(&body)).await?;
+        Ok(())
     }
 
-    /// Deletes a guild announcement.
+    /// Sets a member's roles to exactly `desired_roles`.
+    ///
+    /// Only roles the member is missing are added and only roles not in
+    /// `desired_roles` are removed; all changes are applied concurrently.
+    /// Individual failures are collected rather than aborting the sync.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
     /// * `guild_id` - The guild ID
-    /// * `message_id` - The message ID of the announcement to delete, or "all" to delete all
+    /// * `user_id` - The user ID
+    /// * `desired_roles` - The role IDs the member should end up with
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_announce(
+    /// The roles added and removed, and any that failed.
+    pub async fn sync_member_roles(
         &self,
         token: &Token,
         guild_id: &str,
-        message_id: &str,
-    ) -> Result<Value> {
-        debug!("Deleting announcement {} in guild {}", message_id, guild_id);
-
-        let path = format!("/guilds/{guild_id}/announces/{message_id}");
-        let response = self.http.delete(token, &path, None::<&()>).await?;
-        Ok(response)
+        user_id: &str,
+        desired_roles: &[&str],
+    ) -> Result<RoleSyncResult> {
+        debug!("Syncing roles of member {} in {}", user_id, guild_id);
+
+        let member = self.get_guild_member(token, guild_id, user_id).await?;
+        let current_roles = member.roles.unwrap_or_default();
+        Ok(sync_roles(
+            &current_roles,
+            desired_roles,
+            |role_id| self.create_guild_role_member(token, guild_id, role_id, user_id, None),
+            |role_id| self.delete_guild_role_member(token, guild_id, role_id, user_id, None),
+        )
+        .await)
     }
 
-    // Permission APIs
-
-    /// Gets the list of API permissions for a guild.
+    /// Gets the guild's owner, administrators and channel managers.
+    ///
+    /// Admin roles are the guild's system roles listed by
+    /// [`GuildAdminLevel`]. Their members are fetched at the same time and
+    /// deduplicated, so a member holding several admin roles appears once,
+    /// at their highest level.
     ///
     /// # Arguments
     ///
@@ -1746,319 +1765,9193 @@ impl BotApi {
     ///
     /// # Returns
     ///
-    /// List of API permissions.
-    pub async fn get_permissions(
-        &self,
-        token: &Token,
-        guild_id: &str,
-    ) -> Result<Vec<APIPermission>> {
-        debug!("Getting permissions for guild {}", guild_id);
-
-        let path = format!("/guilds/{guild_id}/api_permission");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-
-        // The response has an extra "apis" level
-        if let Some(apis) = response.get("apis") {
-            Ok(serde_json::from_value(apis.clone())?)
-        } else {
-            Ok(vec![])
-        }
+    /// Admin members, highest level first.
+    pub async fn get_guild_admins(&self, token: &Token, guild_id: &str) -> Result<Vec<Member>> {
+        debug!("Getting admins of guild {}", guild_id);
+        let roles = self.get_guild_roles(token, guild_id).await?;
+        let admin_role_ids: Vec<&str> = GuildAdminLevel::ALL
+            .iter()
+            .map(GuildAdminLevel::role_id)
+            .filter(|role_id| {
+                roles
+                    .roles
+                    .iter()
+                    .any(|role| role.id.as_deref() == Some(*role_id))
+            })
+            .collect();
+        let role_members = map_concurrent(admin_role_ids, GuildAdminLevel::ALL.len(), |role_id| {
+            self.get_role_members(token, guild_id, role_id)
+        })
+        .await?;
+        Ok(dedup_members(role_members))
     }
 
-    /// Creates an API permission demand request.
+    /// Gets all members that have a specific role.
+    ///
+    /// Pages are fetched until the API reports no further members.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `guild_id` - The guild ID where permission is requested
-    /// * `channel_id` - The channel ID where the request will be sent
-    /// * `api_identify` - The API identifier for which permission is requested
-    /// * `desc` - Description explaining why the permission is needed
+    /// * `guild_id` - The guild ID
+    /// * `role_id` - The role ID
     ///
     /// # Returns
     ///
-    /// The created permission demand.
-    pub async fn post_permission_demand(
+    /// List of members with the role.
+    pub async fn get_role_members(
         &self,
         token: &Token,
         guild_id: &str,
-        channel_id: &str,
-        api_identify: APIPermissionDemandIdentify,
-        desc: &str,
-    ) -> Result<APIPermissionDemand> {
-        debug!("Creating permission demand in guild {}", guild_id);
-
-        let body = json!({
-            "channel_id": channel_id,
-            "api_identify": api_identify,
-            "desc": desc
-        });
-
-        let path = format!("/guilds/{guild_id}/api_permission/demand");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(serde_json::from_value(response)?)
+        role_id: &str,
+    ) -> Result<Vec<Member>> {
+        debug!("Getting members of role {} in guild {}", role_id, guild_id);
+
+        let path = format!("/guilds/{guild_id}/roles/{role_id}/members");
+        let mut members = Vec::new();
+        let mut start_index = "0".to_string();
+        loop {
+            let mut params = HashMap::new();
+            params.insert("start_index", start_index.clone());
+            params.insert("limit", "400".to_string());
+
+            let response = self.http.get(token, &path, Some(&params)).await?;
+            let page: Vec<Member> = response
+                .get("data")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default();
+            let next = response
+                .get("next")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+
+            let done = page.is_empty() || next.is_empty() || next == start_index;
+            members.extend(page);
+            if done {
+                break;
+            }
+            start_index = next;
+        }
+        Ok(members)
     }
 
-    // Reaction APIs
+    // Channel APIs
 
-    /// Gets the list of users who reacted with a specific emoji.
+    /// Gets channel information.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The channel ID containing the message
-    /// * `message_id` - The message ID
-    /// * `emoji_type` - The type of emoji (1 = system, 2 = custom)
-    /// * `emoji_id` - The emoji ID
-    /// * `cookie` - Optional pagination cookie from previous request
-    /// * `limit` - Maximum number of users to return (1-100, default 20)
+    /// * `channel_id` - The channel ID
     ///
     /// # Returns
     ///
-    /// List of users who reacted and pagination info.
-    pub async fn get_reaction_users(
-        &self,
-        token: &Token,
-        channel_id: &str,
-        message_id: &str,
-        emoji_type: EmojiType,
-        emoji_id: &str,
-        cookie: Option<&str>,
-        limit: Option<u32>,
-    ) -> Result<ReactionUsers> {
-        debug!(
-            "Getting reaction users for message {} with emoji {}",
-            message_id, emoji_id
-        );
-
-        let mut params = HashMap::new();
-        params.insert("limit", limit.unwrap_or(20).to_string());
-        if let Some(cookie) = cookie {
-            params.insert("cookie", cookie.to_string());
-        }
-
-        let path = format!(
-            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}",
-            emoji_type = u8::from(emoji_type)
-        );
-        let response = self.http.get(token, &path, Some(&params)).await?;
+    /// Channel information.
+    pub async fn get_channel(&self, token: &Token, channel_id: &str) -> Result<Channel> {
+        debug!("Getting channel {}", channel_id);
+        let path = format!("/channels/{channel_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    // Schedule APIs
-
-    /// Gets the list of schedules for a channel.
+    /// Gets channels in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The schedule channel ID
-    /// * `since` - Optional timestamp to get schedules after this time
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// List of schedules.
-    pub async fn get_schedules(
-        &self,
-        token: &Token,
-        channel_id: &str,
-        since: Option<&str>,
-    ) -> Result<Vec<Schedule>> {
-        debug!("Getting schedules for channel {}", channel_id);
-
-        let body = if let Some(since) = since {
-            json!({ "since": since })
-        } else {
-            json!({})
-        };
-
-        let path = format!("/channels/{channel_id}/schedules");
-        let response = self
-            .http
-            .get(
-                token,
-                &path,
-                if since.is_some() { Some(&body) } else { None },
-            )
-            .await?;
+    /// List of channels.
+    pub async fn get_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
+        debug!("Getting channels for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/channels");
+        let response = self.http.get(token, &path, None::<&()>).await?;
         Ok(serde_json::from_value(response)?)
     }
 
-    /// Gets a specific schedule by ID.
+    /// Gets the channels of one type in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The schedule channel ID
-    /// * `schedule_id` - The schedule ID
+    /// * `guild_id` - The guild ID
+    /// * `channel_type` - The channel type to keep
     ///
     /// # Returns
     ///
-    /// The schedule details.
-    pub async fn get_schedule(
+    /// List of matching channels.
+    pub async fn get_channels_by_type(
         &self,
         token: &Token,
-        channel_id: &str,
-        schedule_id: &str,
-    ) -> Result<Schedule> {
-        debug!("Getting schedule {} in channel {}", schedule_id, channel_id);
-
-        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
-        let response = self.http.get(token, &path, None::<&()>).await?;
-        Ok(serde_json::from_value(response)?)
+        guild_id: &str,
+        channel_type: ChannelType,
+    ) -> Result<Vec<Channel>> {
+        let channels = self.get_channels(token, guild_id).await?;
+        Ok(channels_of_type(channels, channel_type))
     }
 
-    /// Creates a new schedule in a channel.
+    /// Gets the schedule channels in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The schedule channel ID
-    /// * `name` - Name of the schedule
-    /// * `start_timestamp` - Start time as Unix timestamp string
-    /// * `end_timestamp` - End time as Unix timestamp string
-    /// * `jump_channel_id` - Channel ID to jump to when event starts
-    /// * `remind_type` - Type of reminder to set
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// The created schedule.
-    pub async fn create_schedule(
+    /// List of schedule channels.
+    pub async fn get_schedule_channels(
         &self,
         token: &Token,
-        channel_id: &str,
-        name: &str,
-        start_timestamp: &str,
-        end_timestamp: &str,
-        jump_channel_id: &str,
-        remind_type: RemindType,
-    ) -> Result<Schedule> {
-        debug!("Creating schedule '{}' in channel {}", name, channel_id);
-
-        let body = json!({
-            "schedule": {
-                "name": name,
-                "start_timestamp": start_timestamp,
-                "end_timestamp": end_timestamp,
-                "jump_channel_id": jump_channel_id,
-                "reminder_id": u8::from(remind_type)
-            }
-        });
-
-        let path = format!("/channels/{channel_id}/schedules");
-        let response = self
-            .http
-            .post(token, &path, None::<&()>, Some(&body))
+        guild_id: &str,
+    ) -> Result<Vec<Channel>> {
+        let channels = self
+            .get_channels_by_type(token, guild_id, ChannelType::Application)
             .await?;
-        Ok(serde_json::from_value(response)?)
+        Ok(channels
+            .into_iter()
+            .filter(|channel| channel.is_schedule() && channel.id.is_some())
+            .collect())
     }
 
-    /// Updates an existing schedule.
+    /// Counts the channels of each type in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The schedule channel ID
-    /// * `schedule_id` - The schedule ID to update
-    /// * `name` - New name of the schedule
-    /// * `start_timestamp` - New start time as Unix timestamp string
-    /// * `end_timestamp` - New end time as Unix timestamp string
-    /// * `jump_channel_id` - New channel ID to jump to when event starts
-    /// * `remind_type` - New type of reminder to set
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// The updated schedule.
-    pub async fn update_schedule(
+    /// The number of channels per type.
+    pub async fn count_channels_by_type(
         &self,
         token: &Token,
-        channel_id: &str,
-        schedule_id: &str,
-        name: &str,
-        start_timestamp: &str,
-        end_timestamp: &str,
-        jump_channel_id: &str,
-        remind_type: RemindType,
-    ) -> Result<Schedule> {
-        debug!(
-            "Updating schedule {} in channel {}",
-            schedule_id, channel_id
-        );
+        guild_id: &str,
+    ) -> Result<HashMap<ChannelType, usize>> {
+        let channels = self.get_channels(token, guild_id).await?;
+        Ok(channel_type_counts(&channels))
+    }
 
-        let body = json!({
-            "schedule": {
-                "name": name,
-                "start_timestamp": start_timestamp,
-                "end_timestamp": end_timestamp,
-                "jump_channel_id": jump_channel_id,
-                "reminder_id": u8::from(remind_type)
-            }
-        });
+    /// Gets the forum channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of forum channels.
+    pub async fn get_forum_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
+        self.get_channels_by_type(token, guild_id, ChannelType::Forum)
+            .await
+    }
 
-        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
-        let response = self
-            .http
-            .patch(token, &path, None::<&()>, Some(&body))
-            .await?;
-        Ok(serde_json::from_value(response)?)
+    /// Gets the text channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of text channels.
+    pub async fn get_text_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
+        self.get_channels_by_type(token, guild_id, ChannelType::Text)
+            .await
     }
 
-    /// Deletes a schedule.
+    /// Gets the voice channels in a guild.
     ///
     /// # Arguments
     ///
     /// * `token` - Authentication token
-    /// * `channel_id` - The schedule channel ID
-    /// * `schedule_id` - The schedule ID to delete
+    /// * `guild_id` - The guild ID
     ///
     /// # Returns
     ///
-    /// Success indication.
-    pub async fn delete_schedule(
+    /// List of voice channels.
+    pub async fn get_voice_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
+        self.get_channels_by_type(token, guild_id, ChannelType::Voice)
+            .await
+    }
+
+    /// Gets the age-gated channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of channels marked NSFW.
+    pub async fn get_nsfw_channels(&self, token: &Token, guild_id: &str) -> Result<Vec<Channel>> {
+        let channels = self.get_channels(token, guild_id).await?;
+        Ok(nsfw_channels(channels))
+    }
+
+    /// Gets the category channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of category channels.
+    pub async fn get_category_channels(
         &self,
         token: &Token,
-        channel_id: &str,
-        schedule_id: &str,
-    ) -> Result<Value> {
-        debug!(
-            "Deleting schedule {} in channel {}",
-            schedule_id, channel_id
-        );
+        guild_id: &str,
+    ) -> Result<Vec<Channel>> {
+        self.get_channels_by_type(token, guild_id, ChannelType::Group)
+            .await
+    }
 
-        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
-        let response = self.http.delete(token, &path, None::<&()>).await?;
-        Ok(response)
+    /// Gets the announcement channels in a guild.
+    ///
+    /// Announcement channels are text channels with the
+    /// [`ChannelSubType::Post`] sub type.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of announcement channels.
+    pub async fn get_announcement_channels(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<Channel>> {
+        let channels = self.get_text_channels(token, guild_id).await?;
+        Ok(channels
+            .into_iter()
+            .filter(Channel::is_announcement)
+            .collect())
     }
 
-    /// Gets the HTTP client reference.
-    pub fn http(&self) -> &HttpClient {
-        &self.http
+    /// Gets the forum channels in a guild that are open for discussion.
+    ///
+    /// See [`Channel::is_open_discussion`].
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of open forum channels.
+    pub async fn get_open_forum_channels(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<Channel>> {
+        let channels = self.get_forum_channels(token, guild_id).await?;
+        Ok(channels
+            .into_iter()
+            .filter(Channel::is_open_discussion)
+            .collect())
     }
 
-    /// Closes the API client and cleans up resources.
-    pub async fn close(&self) {
-        self.http.close().await;
+    /// Gets each forum channel in a guild with a summary of its threads.
+    ///
+    /// Every page of threads is read for each channel, with a few channels
+    /// fetched at once. Channels without an ID are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// One summary per forum channel, in listing order.
+    pub async fn get_forum_channel_summaries(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<ForumChannelSummary>> {
+        debug!("Summarizing forum channels in guild {}", guild_id);
+        let channels = self.get_forum_channels(token, guild_id).await?;
+        let channels = channels.into_iter().filter(|c| c.id.is_some()).collect();
+        map_concurrent(
+            channels,
+            FORUM_SUMMARY_CONCURRENCY,
+            |channel: Channel| async move {
+                let channel_id = channel.id.clone().unwrap_or_default();
+                let fetch = |page| self.get_threads(token, &channel_id, page);
+                let threads = collect_threads(fetch, |_| true, usize::MAX).await?;
+                Ok(ForumChannelSummary::new(channel, &threads))
+            },
+        )
+        .await
     }
-}
 
-impl std::fmt::Debug for BotApi {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BotApi").field("http", &self.http).finish()
+    /// Gets the category channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of category channels.
+    pub async fn get_channel_categories(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<Channel>> {
+        let channels = self.get_channels(token, guild_id).await?;
+        Ok(channels.into_iter().filter(Channel::is_group).collect())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::http::HttpClient;
+    /// Gets a guild's channels grouped under their categories.
+    ///
+    /// Channels without a category are returned in a trailing `"root"`
+    /// category; see [`ChannelCategory::build_tree`].
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// The guild's categories with their child channels.
+    pub async fn get_channel_tree(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<ChannelCategory>> {
+        let channels = self.get_channels(token, guild_id).await?;
+        Ok(ChannelCategory::build_tree(channels))
+    }
 
-    #[test]
-    fn test_api_creation() {
-        let http = HttpClient::new(30, false).unwrap();
-        let api = BotApi::new(http);
-        assert!(!api.http().is_sandbox());
+    /// Creates a new channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `name` - Channel name
+    /// * `channel_type` - Channel type
+    /// * `sub_type` - Channel sub-type
+    /// * `position` - Optional position
+    /// * `parent_id` - Optional parent category ID
+    /// * `private_type` - Optional private type
+    /// * `private_user_ids` - Optional private user IDs
+    /// * `speak_permission` - Optional speak permission
+    /// * `application_id` - Optional application ID
+    ///
+    /// # Returns
+    ///
+    /// The created channel.
+    pub async fn create_channel(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        name: &str,
+        channel_type: ChannelType,
+        sub_type: ChannelSubType,
+        position: Option<u32>,
+        parent_id: Option<&str>,
+        private_type: Option<u32>,
+        private_user_ids: Option<Vec<String>>,
+        speak_permission: Option<u32>,
+        application_id: Option<&str>,
+    ) -> Result<Channel> {
+        debug!("Creating channel {} in guild {}", name, guild_id);
+
+        let mut body = json!({
+            "name": name,
+            "type": u32::from(channel_type),
+            "subtype": u32::from(sub_type)
+        });
+
+        if let Some(pos) = position {
+            body["position"] = json!(pos);
+        }
+        if let Some(parent) = parent_id {
+            body["parent_id"] = json!(parent);
+        }
+        if let Some(private) = private_type {
+            body["private_type"] = json!(private);
+        }
+        if let Some(users) = private_user_ids {
+            body["private_user_ids"] = json!(users);
+        }
+        if let Some(speak) = speak_permission {
+            body["speak_permission"] = json!(speak);
+        }
+        if let Some(app) = application_id {
+            body["application_id"] = json!(app);
+        }
+
+        let path = format!("/guilds/{guild_id}/channels");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a private channel visible to the given users.
+    ///
+    /// The channel is created as admin-and-specified-members only, then each
+    /// user is granted view and send permissions. If granting fails, the
+    /// channel is deleted on a best-effort basis and the original error is
+    /// returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `name` - Channel name
+    /// * `channel_type` - Channel type
+    /// * `user_ids` - Users who can access the channel
+    ///
+    /// # Returns
+    ///
+    /// The created channel.
+    pub async fn create_private_channel_with_users(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        name: &str,
+        channel_type: ChannelType,
+        user_ids: &[&str],
+    ) -> Result<Channel> {
+        debug!(
+            "Creating private channel {} for {} users in guild {}",
+            name,
+            user_ids.len(),
+            guild_id
+        );
+
+        let create = self.create_channel(
+            token,
+            guild_id,
+            name,
+            channel_type,
+            ChannelSubType::Talk,
+            None,
+            None,
+            Some(u32::from(PrivateType::AdminAndSpecifiedMembers)),
+            Some(user_ids.iter().map(|id| id.to_string()).collect()),
+            None,
+            None,
+        );
+        let grant = |channel: Channel| async move {
+            let channel_id = created_channel_id(&channel)?;
+            futures_util::future::try_join_all(user_ids.iter().map(|user_id| {
+                self.update_channel_user_permissions(
+                    token,
+                    &channel_id,
+                    user_id,
+                    Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+                    Permissions::NONE,
+                )
+            }))
+            .await
+            .map(drop)
+        };
+        let cleanup = |channel: Channel| async move {
+            self.delete_channel(token, &created_channel_id(&channel)?)
+                .await
+        };
+        let (channel, ()) = create_with_cleanup(create, grant, cleanup).await?;
+        Ok(channel)
+    }
+
+    /// Creates a moderation log channel locked down to admins.
+    ///
+    /// The text channel is created admin-only with speaking restricted, then
+    /// a header message explaining its purpose is posted and pinned. If the
+    /// header can't be posted or pinned, the channel is deleted on a
+    /// best-effort basis and the original error is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `name` - Channel name (defaults to `"mod-log"`)
+    ///
+    /// # Returns
+    ///
+    /// The created channel.
+    pub async fn create_mod_log_channel(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        name: Option<&str>,
+    ) -> Result<Channel> {
+        let name = name.unwrap_or(MOD_LOG_CHANNEL_NAME);
+        debug!("Creating mod log channel {} in guild {}", name, guild_id);
+
+        let create = self.create_channel(
+            token,
+            guild_id,
+            name,
+            ChannelType::Text,
+            ChannelSubType::Talk,
+            None,
+            None,
+            Some(u32::from(PrivateType::AdminOnly)),
+            None,
+            Some(u32::from(SpeakPermission::AdminAndSpecifiedMembers)),
+            None,
+        );
+        let pin_header = |channel: Channel| async move {
+            let channel_id = created_channel_id(&channel)?;
+            let header = self
+                .post_message_with_params(
+                    token,
+                    &channel_id,
+                    MessageParams::new_text(MOD_LOG_HEADER),
+                )
+                .await?;
+            let message_id = header.id.ok_or_else(|| {
+                BotError::invalid_data("mod log header message has no ID".to_string())
+            })?;
+            self.put_pin(token, &channel_id, &message_id)
+                .await
+                .map(drop)
+        };
+        let cleanup = |channel: Channel| async move {
+            self.delete_channel(token, &created_channel_id(&channel)?)
+                .await
+        };
+        let (channel, ()) = create_with_cleanup(create, pin_header, cleanup).await?;
+        Ok(channel)
+    }
+
+    /// Creates a copy of an existing channel.
+    ///
+    /// The copy keeps the source channel's type, sub-type, position, privacy
+    /// and speak settings. Its parent category is kept only when cloning
+    /// within the same guild, since categories don't exist across guilds.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `source_channel_id` - The channel to copy
+    /// * `target_guild_id` - Guild to create the copy in (defaults to the source's guild)
+    /// * `new_name` - Name for the copy (defaults to the source name + `" (copy)"`)
+    ///
+    /// # Returns
+    ///
+    /// The created channel.
+    pub async fn clone_channel(
+        &self,
+        token: &Token,
+        source_channel_id: &str,
+        target_guild_id: Option<&str>,
+        new_name: Option<&str>,
+    ) -> Result<Channel> {
+        debug!("Cloning channel {}", source_channel_id);
+
+        let source = self.get_channel(token, source_channel_id).await?;
+        let source_guild_id = source.guild_id.as_deref().ok_or_else(|| {
+            BotError::invalid_data(format!("channel {source_channel_id} has no guild"))
+        })?;
+        let target_guild_id = target_guild_id.unwrap_or(source_guild_id);
+        let same_guild = target_guild_id == source_guild_id;
+
+        if !same_guild {
+            futures_util::try_join!(
+                self.ensure_bot_in_guild(token, source_guild_id),
+                self.ensure_bot_in_guild(token, target_guild_id)
+            )?;
+        }
+
+        let clone = ChannelClone::from_source(&source, new_name, same_guild)?;
+        self.create_channel(
+            token,
+            target_guild_id,
+            &clone.name,
+            clone.channel_type,
+            clone.sub_type,
+            clone.position,
+            clone.parent_id,
+            clone.private_type,
+            None,
+            clone.speak_permission,
+            None,
+        )
+        .await
+    }
+
+    /// Returns an error unless the bot is a member of the guild.
+    async fn ensure_bot_in_guild(&self, token: &Token, guild_id: &str) -> Result<()> {
+        match self.get_guild_member(token, guild_id, "@me").await {
+            Ok(_) => Ok(()),
+            Err(BotError::NotFound(_) | BotError::Forbidden(_)) => Err(BotError::invalid_params(
+                format!("bot is not a member of guild {guild_id}"),
+            )),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Updates a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `name` - Optional new name
+    /// * `position` - Optional new position
+    /// * `parent_id` - Optional new parent ID
+    /// * `private_type` - Optional new private type
+    /// * `speak_permission` - Optional new speak permission
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn update_channel(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        name: Option<&str>,
+        position: Option<u32>,
+        parent_id: Option<&str>,
+        private_type: Option<u32>,
+        speak_permission: Option<u32>,
+    ) -> Result<Channel> {
+        debug!("Updating channel {}", channel_id);
+
+        let mut body = json!({});
+        if let Some(name) = name {
+            body["name"] = json!(name);
+        }
+        if let Some(pos) = position {
+            body["position"] = json!(pos);
+        }
+        if let Some(parent) = parent_id {
+            body["parent_id"] = json!(parent);
+        }
+        if let Some(private) = private_type {
+            body["private_type"] = json!(private);
+        }
+        if let Some(speak) = speak_permission {
+            body["speak_permission"] = json!(speak);
+        }
+
+        let path = format!("/channels/{channel_id}");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Moves a channel into a category, or out of its current one.
+    ///
+    /// The channel is fetched first so that its other settings are sent back
+    /// unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `parent_id` - The category to move into, or `None` to remove the parent
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn set_channel_parent(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        parent_id: Option<&str>,
+    ) -> Result<Channel> {
+        debug!(
+            "Setting parent of channel {} to {:?}",
+            channel_id, parent_id
+        );
+
+        let current = self.get_channel(token, channel_id).await?;
+        let body = channel_parent_body(&current, parent_id);
+
+        let path = format!("/channels/{channel_id}");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Archives a channel instead of deleting it.
+    ///
+    /// The channel is renamed with an `[ARCHIVED] ` prefix, moved into the
+    /// guild's "Archive" category (created if the guild has none) and made
+    /// visible to admins only. Use [`BotApi::restore_archived_channel`] to
+    /// undo this.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// The archived channel.
+    pub async fn archive_channel(&self, token: &Token, channel_id: &str) -> Result<Channel> {
+        debug!("Archiving channel {}", channel_id);
+
+        let mut channel = self.get_channel(token, channel_id).await?;
+        let guild_id = channel
+            .guild_id
+            .clone()
+            .ok_or_else(|| BotError::invalid_data("Channel has no guild ID"))?;
+        let mut category_id = self
+            .get_category_channels(token, &guild_id)
+            .await?
+            .into_iter()
+            .find(|category| category.name.as_deref() == Some(ARCHIVE_CATEGORY_NAME))
+            .and_then(|category| category.id);
+
+        for step in archive_steps(&channel, category_id.is_some()) {
+            match step {
+                ArchiveStep::Rename(name) => {
+                    channel = self
+                        .update_channel(token, channel_id, Some(&name), None, None, None, None)
+                        .await?;
+                }
+                ArchiveStep::CreateCategory => {
+                    let category = self
+                        .create_channel(
+                            token,
+                            &guild_id,
+                            ARCHIVE_CATEGORY_NAME,
+                            ChannelType::Group,
+                            ChannelSubType::Talk,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                        )
+                        .await?;
+                    category_id = category.id;
+                }
+                ArchiveStep::MoveToCategory => {
+                    channel = self
+                        .set_channel_parent(token, channel_id, category_id.as_deref())
+                        .await?;
+                }
+                ArchiveStep::MakeAdminOnly => {
+                    let admin_only = u32::from(u8::from(PrivateType::AdminOnly));
+                    channel = self
+                        .update_channel(token, channel_id, None, None, None, Some(admin_only), None)
+                        .await?;
+                }
+            }
+        }
+        Ok(channel)
+    }
+
+    /// Restores a channel archived with [`BotApi::archive_channel`].
+    ///
+    /// The channel gets its name back, is moved back to its category and is
+    /// made public again.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `original_name` - The name to restore
+    /// * `original_parent_id` - The category to move back to, or `None` for none
+    ///
+    /// # Returns
+    ///
+    /// The restored channel.
+    pub async fn restore_archived_channel(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        original_name: &str,
+        original_parent_id: Option<&str>,
+    ) -> Result<Channel> {
+        debug!("Restoring archived channel {}", channel_id);
+
+        let public = u32::from(u8::from(PrivateType::Public));
+        self.update_channel(token, channel_id, None, None, None, Some(public), None)
+            .await?;
+        self.set_channel_parent(token, channel_id, original_parent_id)
+            .await?;
+        self.update_channel(
+            token,
+            channel_id,
+            Some(original_name),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Gets the topic (description) of a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// The channel topic, or `None` if the channel has none.
+    pub async fn get_channel_topic(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<Option<String>> {
+        Ok(self.get_channel(token, channel_id).await?.topic)
+    }
+
+    /// Sets the topic (description) of a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `topic` - The new topic, at most 200 characters
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn set_channel_topic(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        topic: &str,
+    ) -> Result<Channel> {
+        debug!("Setting topic of channel {}", channel_id);
+
+        let body = channel_topic_body(topic)?;
+        let path = format!("/channels/{channel_id}");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Marks a channel as age-gated or removes the age gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `nsfw` - Whether the channel should be age-gated
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn set_channel_nsfw(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        nsfw: bool,
+    ) -> Result<Channel> {
+        debug!("Setting NSFW of channel {} to {}", channel_id, nsfw);
+
+        let path = format!("/channels/{channel_id}");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&channel_nsfw_body(nsfw)))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates an invite granting a user temporary access to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `target_user_id` - The user to grant access to
+    /// * `duration_seconds` - How long access lasts, between 300 and 86400 seconds
+    /// * `reason` - Optional reason shown in the audit trail
+    ///
+    /// # Returns
+    ///
+    /// The created invite.
+    pub async fn create_channel_access_invite(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        target_user_id: &str,
+        duration_seconds: u32,
+        reason: Option<&str>,
+    ) -> Result<ChannelAccessInvite> {
+        debug!(
+            "Creating access invite to channel {} for user {}",
+            channel_id, target_user_id
+        );
+
+        let body = channel_access_invite_body(target_user_id, duration_seconds, reason)?;
+        let path = format!("/channels/{channel_id}/access_invites");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Revokes a channel access invite.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `invite_code` - The invite code to revoke
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn revoke_channel_access_invite(
+        &self,
+        token: &Token,
+        invite_code: &str,
+    ) -> Result<()> {
+        debug!("Revoking channel access invite {}", invite_code);
+        let path = format!("/access_invites/{invite_code}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Creates an invite link to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `options` - Expiry and usage limits for the invite
+    ///
+    /// # Returns
+    ///
+    /// The created invite.
+    pub async fn create_channel_invite(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        options: InviteOptions,
+    ) -> Result<ChannelInvite> {
+        debug!("Creating invite to channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/invites");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&options))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the invites to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// List of invites.
+    pub async fn list_channel_invites(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<Vec<ChannelInvite>> {
+        debug!("Getting invites to channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/invites");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a channel invite.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `code` - The invite code
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn delete_channel_invite(&self, token: &Token, code: &str) -> Result<()> {
+        debug!("Deleting channel invite {}", code);
+        let path = format!("/invites/{code}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Starts a thread from a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message to start the thread from
+    /// * `name` - Name of the thread
+    /// * `auto_archive` - Minutes without activity before the thread is archived
+    ///
+    /// # Returns
+    ///
+    /// The created thread.
+    pub async fn create_channel_thread(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        name: &str,
+        auto_archive: u32,
+    ) -> Result<ChannelThread> {
+        debug!(
+            "Creating thread {} from message {} in channel {}",
+            name, message_id, channel_id
+        );
+
+        let body = json!({
+            "name": name,
+            "auto_archive_duration": auto_archive
+        });
+        let path = format!("/channels/{channel_id}/messages/{message_id}/threads");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// The deleted channel.
+    pub async fn delete_channel(&self, token: &Token, channel_id: &str) -> Result<Channel> {
+        debug!("Deleting channel {}", channel_id);
+        let path = format!("/channels/{channel_id}");
+        let response = self.http.delete(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    // Message APIs
+
+    /// Gets a specific message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    ///
+    /// The message.
+    pub async fn get_message(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<Message> {
+        debug!("Getting message {} in channel {}", message_id, channel_id);
+        let path = format!("/channels/{channel_id}/messages/{message_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the revision history of an edited message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    ///
+    /// Every revision of the message, oldest first.
+    pub async fn get_message_edit_history(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<Vec<MessageRevision>> {
+        debug!(
+            "Getting edit history of message {} in channel {}",
+            message_id, channel_id
+        );
+        let path = format!("/channels/{channel_id}/messages/{message_id}/revisions");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        parse_revisions(response)
+    }
+
+    /// Gets the emoji reactions on a message with their counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    ///
+    /// One summary per emoji used, empty if the message has no reactions.
+    pub async fn get_message_reaction_summary(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<Vec<ReactionSummary>> {
+        let message = self.get_message(token, channel_id, message_id).await?;
+        Ok(message.reactions.unwrap_or_default())
+    }
+
+    /// Gets a message together with the messages sent around it.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    /// * `context_size` - Messages to include on each side (at most 49)
+    ///
+    /// # Returns
+    ///
+    /// The message with up to `context_size` messages before and after it,
+    /// or a not found error if the message was not returned.
+    pub async fn get_message_with_context(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        context_size: u32,
+    ) -> Result<MessageWithContext> {
+        let limit = context_size.saturating_mul(2).saturating_add(1).max(2);
+        let around = self
+            .get_messages_around(token, channel_id, message_id, limit)
+            .await?;
+        MessageWithContext::from_around(around, context_size as usize).ok_or_else(|| {
+            BotError::NotFound(format!(
+                "message {message_id} not found in channel {channel_id}"
+            ))
+        })
+    }
+
+    /// Gets the messages surrounding a specific message in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The anchor message ID
+    /// * `limit` - Total number of messages to fetch (2-100)
+    ///
+    /// # Returns
+    ///
+    /// The messages split into those before and after the anchor.
+    pub async fn get_messages_around(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        limit: u32,
+    ) -> Result<MessagesAround> {
+        if !(2..=100).contains(&limit) {
+            return Err(BotError::invalid_params(format!(
+                "limit must be between 2 and 100, got {limit}"
+            )));
+        }
+        debug!(
+            "Getting {} messages around {} in channel {}",
+            limit, message_id, channel_id
+        );
+
+        let mut params = HashMap::new();
+        params.insert("around", message_id.to_string());
+        params.insert("limit", limit.to_string());
+
+        let path = format!("/channels/{channel_id}/messages");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        let messages: Vec<Message> = serde_json::from_value(response)?;
+        Ok(MessagesAround::split(messages, message_id))
+    }
+
+    /// Forwards an existing message to another channel.
+    ///
+    /// The source message is fetched and re-sent with its content, embed, ark and
+    /// attachments. Attachments are referenced by URL rather than re-uploaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `from_channel_id` - The channel containing the source message
+    /// * `message_id` - The source message ID
+    /// * `to_channel_id` - The destination channel ID
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn forward_message(
+        &self,
+        token: &Token,
+        from_channel_id: &str,
+        message_id: &str,
+        to_channel_id: &str,
+    ) -> Result<MessageResponse> {
+        debug!(
+            "Forwarding message {} from channel {} to channel {}",
+            message_id, from_channel_id, to_channel_id
+        );
+
+        let message = self.get_message(token, from_channel_id, message_id).await?;
+        let params = MessageParams::forward_from(&message)?;
+        self.post_message_with_params(token, to_channel_id, params)
+            .await
+    }
+
+    /// Copies messages from one channel to another.
+    ///
+    /// Each message is fetched and re-sent like [`forward_message`](Self::forward_message),
+    /// with a `> [Original by {author}]` line before its content. Messages are
+    /// copied one at a time so they arrive in the order given. The source
+    /// messages are never deleted; recall them with
+    /// [`recall_message`](Self::recall_message) to move them.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `from_channel_id` - The channel containing the source messages
+    /// * `to_channel_id` - The destination channel ID
+    /// * `message_ids` - The source message IDs
+    ///
+    /// # Returns
+    ///
+    /// One result per message, in the order given; see
+    /// [`CopyReport::new`](crate::models::message::CopyReport::new).
+    pub async fn copy_messages_to_channel(
+        &self,
+        token: &Token,
+        from_channel_id: &str,
+        to_channel_id: &str,
+        message_ids: &[&str],
+    ) -> Result<Vec<Result<MessageResponse>>> {
+        debug!(
+            "Copying {} messages from channel {} to channel {}",
+            message_ids.len(),
+            from_channel_id,
+            to_channel_id
+        );
+
+        Ok(copy_each(
+            message_ids,
+            |message_id| self.get_message(token, from_channel_id, message_id),
+            |params| self.post_message_with_params(token, to_channel_id, params),
+        )
+        .await)
+    }
+
+    /// Sends a message to a channel using MessageParams.
+    ///
+    /// This is the new, recommended way to send channel messages. It uses a parameter struct
+    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `params` - Message parameters (see [`MessageParams`])
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use botrs::*;
+    /// # use botrs::models::message::MessageParams;
+    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
+    /// // Simple text message
+    /// let params = MessageParams::new_text("Hello world!");
+    /// api.post_message_with_params(token, "channel_id", params).await?;
+    ///
+    /// // Message with reply
+    /// let params = MessageParams::new_text("Reply!").with_reply("message_id");
+    /// api.post_message_with_params(token, "channel_id", params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_message_with_params(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        params: MessageParams,
+    ) -> Result<MessageResponse> {
+        debug!("Sending message to channel {}", channel_id);
+
+        // Handle file_image encoding if raw bytes were provided separately
+        let body = serde_json::to_value(&params)?;
+
+        let path = format!("/channels/{channel_id}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends a message containing only an embed to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `embed` - The embed to send
+    /// * `reply_to` - Optional ID of the message being replied to
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn post_embed(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        embed: Embed,
+        reply_to: Option<&str>,
+    ) -> Result<MessageResponse> {
+        self.post_message_with_params(token, channel_id, embed_params(embed, reply_to))
+            .await
+    }
+
+    /// Sends a message to a channel after a natural typing delay.
+    ///
+    /// Shows the typing indicator, waits as long as a person typing at
+    /// `words_per_minute` would take to write the content (see
+    /// [`TypingDelay::compute`]), then sends the message. Failing to show the
+    /// indicator doesn't stop the message from being sent, and empty content
+    /// is sent straight away.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `params` - Message parameters
+    /// * `words_per_minute` - Simulated typing speed
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn send_after_typing_delay(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        params: MessageParams,
+        words_per_minute: u32,
+    ) -> Result<MessageResponse> {
+        let delay = TypingDelay::compute(
+            params.content.as_deref().unwrap_or_default(),
+            words_per_minute,
+        );
+        debug!(
+            "Waiting {:?} before sending message to channel {}",
+            delay, channel_id
+        );
+        self.send_with_typing(token, channel_id, params, delay)
+            .await
+    }
+
+    /// Shows the bot's typing indicator in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn trigger_typing(&self, token: &Token, channel_id: &str) -> Result<()> {
+        debug!("Triggering typing in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/typing");
+        self.http
+            .post(token, &path, None::<&()>, Some(&json!({})))
+            .await?;
+        Ok(())
+    }
+
+    /// Shows the typing indicator for a while, then sends a message.
+    ///
+    /// The wait is capped at 10 seconds. A zero `typing_duration` sends the
+    /// message straight away without showing the indicator. Failing to show
+    /// the indicator doesn't stop the message from being sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `params` - Message parameters
+    /// * `typing_duration` - How long to show the indicator before sending
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn send_with_typing(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        params: MessageParams,
+        typing_duration: Duration,
+    ) -> Result<MessageResponse> {
+        send_after_typing(
+            typing_duration,
+            || self.trigger_typing(token, channel_id),
+            || self.post_message_with_params(token, channel_id, params),
+        )
+        .await
+    }
+
+    /// Sends a message with a keyboard of buttons to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `content` - Optional message text
+    /// * `keyboard` - The buttons, e.g. from [`KeyboardPayload::button_row`]
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn post_message_with_components(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        content: Option<&str>,
+        keyboard: KeyboardPayload,
+    ) -> Result<MessageResponse> {
+        let params = MessageParams {
+            content: content.map(String::from),
+            keyboard: Some(Keyboard {
+                content: Some(serde_json::from_value(keyboard.content)?),
+            }),
+            ..Default::default()
+        };
+        self.post_message_with_params(token, channel_id, params)
+            .await
+    }
+
+    /// Sends a poll to a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `poll` - The poll to send
+    ///
+    /// # Returns
+    ///
+    /// The message carrying the poll.
+    pub async fn create_poll(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        poll: Poll,
+    ) -> Result<Message> {
+        poll.validate()?;
+        debug!(
+            "Creating poll with {} options in channel {}",
+            poll.options.len(),
+            channel_id
+        );
+
+        let body = json!({ "poll": poll });
+        let path = format!("/channels/{channel_id}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the current vote counts of a poll.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message carrying the poll
+    ///
+    /// # Returns
+    ///
+    /// The poll's results.
+    pub async fn get_poll_results(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<PollResult> {
+        debug!(
+            "Getting poll results for message {} in channel {}",
+            message_id, channel_id
+        );
+        let path = format!("/channels/{channel_id}/messages/{message_id}/poll");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Closes a poll before its duration runs out.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message carrying the poll
+    ///
+    /// # Returns
+    ///
+    /// The poll's final results.
+    pub async fn end_poll(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<PollResult> {
+        debug!(
+            "Ending poll on message {} in channel {}",
+            message_id, channel_id
+        );
+        let path = format!("/channels/{channel_id}/messages/{message_id}/poll/expire");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, None::<&()>)
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends the same message to several channels.
+    ///
+    /// A failure in one channel does not stop the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_ids` - The channels to send to, at most 50
+    /// * `params` - Message parameters
+    /// * `max_concurrent` - Maximum number of messages sent at once
+    ///
+    /// # Returns
+    ///
+    /// Each channel ID with its send result, in the order given.
+    pub async fn broadcast_message(
+        &self,
+        token: &Token,
+        channel_ids: &[&str],
+        params: MessageParams,
+        max_concurrent: usize,
+    ) -> Result<Vec<(String, Result<MessageResponse>)>> {
+        validate_broadcast_targets(channel_ids)?;
+        debug!("Broadcasting message to {} channels", channel_ids.len());
+        Ok(broadcast_to(channel_ids, max_concurrent, |channel_id| {
+            self.post_message_with_params(token, channel_id, params.clone())
+        })
+        .await)
+    }
+
+    /// Sends a message to a channel and returns a handle for editing or recalling it.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `params` - Message parameters
+    ///
+    /// # Returns
+    ///
+    /// A handle to the sent message.
+    pub async fn post_interactive_message(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        params: MessageParams,
+    ) -> Result<InteractiveMessageHandle> {
+        let response = self
+            .post_message_with_params(token, channel_id, params)
+            .await?;
+        InteractiveMessageHandle::from_response(
+            Arc::new(self.clone()),
+            token.clone(),
+            channel_id,
+            &response,
+        )
+    }
+
+    /// Edits a message previously sent by the bot.
+    ///
+    /// Only reachable through [`InteractiveMessageHandle::edit`].
+    pub(crate) async fn edit_message(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        params: MessageParams,
+    ) -> Result<()> {
+        debug!("Editing message {} in channel {}", message_id, channel_id);
+
+        let path = format!("/channels/{channel_id}/messages/{message_id}");
+        self.http
+            .patch(token, &path, None::<&()>, Some(&params))
+            .await?;
+        Ok(())
+    }
+
+    /// Sends a message to a channel (legacy API for backward compatibility).
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `content` - Message content
+    /// * `embed` - Optional embed
+    /// * `ark` - Optional ark template
+    /// * `message_reference` - Optional message reference
+    /// * `image` - Optional image URL
+    /// * `file_image` - Optional file image data
+    /// * `msg_id` - Optional message ID to reply to
+    /// * `event_id` - Optional event ID
+    /// * `markdown` - Optional markdown
+    /// * `keyboard` - Optional keyboard
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    #[deprecated(since = "0.1.0", note = "Use post_message_with_params instead")]
+    pub async fn post_message(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        content: Option<&str>,
+        embed: Option<&Embed>,
+        ark: Option<&Ark>,
+        message_reference: Option<&Reference>,
+        image: Option<&str>,
+        file_image: Option<&[u8]>,
+        msg_id: Option<&str>,
+        event_id: Option<&str>,
+        markdown: Option<&MarkdownPayload>,
+        keyboard: Option<&Keyboard>,
+    ) -> Result<MessageResponse> {
+        let params = MessageParams {
+            content: content.map(|s| s.to_string()),
+            embed: embed.cloned(),
+            ark: ark.cloned(),
+            message_reference: message_reference.cloned(),
+            image: image.map(|s| s.to_string()),
+            file_image: file_image
+                .map(|data| base64::engine::general_purpose::STANDARD.encode(data)),
+            msg_id: msg_id.map(|s| s.to_string()),
+            event_id: event_id.map(|s| s.to_string()),
+            markdown: markdown.cloned(),
+            keyboard: keyboard.cloned(),
+        };
+
+        self.post_message_with_params(token, channel_id, params)
+            .await
+    }
+
+    /// Sends a group message using GroupMessageParams.
+    ///
+    /// This is the new, recommended way to send group messages. It uses a parameter struct
+    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `params` - Group message parameters (see [`GroupMessageParams`])
+    ///
+    /// # Returns
+    ///
+    /// The sent group message response.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use botrs::*;
+    /// # use botrs::models::message::GroupMessageParams;
+    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
+    /// let params = GroupMessageParams::new_text("Hello group!");
+    /// api.post_group_message_with_params(token, "group_openid", params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_group_message_with_params(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        params: GroupMessageParams,
+    ) -> Result<MessageResponse> {
+        debug!("Sending group message to {}", group_openid);
+
+        let body = serde_json::to_value(&params)?;
+
+        let path = format!("/v2/groups/{group_openid}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Starts a discussion thread from a group message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `name` - The thread name
+    /// * `source_msg_id` - The message the thread starts from
+    ///
+    /// # Returns
+    ///
+    /// The created thread.
+    pub async fn create_group_thread(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        name: &str,
+        source_msg_id: &str,
+    ) -> Result<GroupThread> {
+        debug!(
+            "Creating thread {} from message {} in group {}",
+            name, source_msg_id, group_openid
+        );
+
+        let body = group_thread_body(name, source_msg_id)?;
+        let path = format!("/v2/groups/{group_openid}/threads");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends a message to a group thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `thread_id` - The thread ID
+    /// * `params` - Message parameters
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn post_group_thread_message(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        thread_id: &str,
+        params: GroupMessageParams,
+    ) -> Result<MessageResponse> {
+        debug!(
+            "Sending message to thread {} in group {}",
+            thread_id, group_openid
+        );
+
+        let body = serde_json::to_value(&params)?;
+        let path = format!("/v2/groups/{group_openid}/threads/{thread_id}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends a group message (legacy API for backward compatibility).
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `msg_type` - Message type (0=text, 1=rich text, 2=markdown, 3=ark, 4=embed, 7=media)
+    /// * `content` - Message content
+    /// * `embed` - Optional embed
+    /// * `ark` - Optional ark template
+    /// * `message_reference` - Optional message reference
+    /// * `media` - Optional media
+    /// * `msg_id` - Optional message ID to reply to
+    /// * `msg_seq` - Optional message sequence number
+    /// * `event_id` - Optional event ID
+    /// * `markdown` - Optional markdown
+    /// * `keyboard` - Optional keyboard
+    ///
+    /// # Returns
+    ///
+    /// The sent group message response.
+    #[deprecated(since = "0.1.0", note = "Use post_group_message_with_params instead")]
+    pub async fn post_group_message(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        msg_type: Option<u32>,
+        content: Option<&str>,
+        embed: Option<&Embed>,
+        ark: Option<&Ark>,
+        message_reference: Option<&Reference>,
+        media: Option<&Media>,
+        msg_id: Option<&str>,
+        msg_seq: Option<u32>,
+        event_id: Option<&str>,
+        markdown: Option<&MarkdownPayload>,
+        keyboard: Option<&KeyboardPayload>,
+    ) -> Result<MessageResponse> {
+        let params = GroupMessageParams {
+            msg_type: msg_type.unwrap_or(0),
+            content: content.map(|s| s.to_string()),
+            embed: embed.cloned(),
+            ark: ark.cloned(),
+            message_reference: message_reference.cloned(),
+            media: media.cloned(),
+            msg_id: msg_id.map(|s| s.to_string()),
+            msg_seq,
+            event_id: event_id.map(|s| s.to_string()),
+            markdown: markdown.cloned(),
+            keyboard: keyboard.cloned(),
+        };
+
+        self.post_group_message_with_params(token, group_openid, params)
+            .await
+    }
+
+    /// Sends a C2C (client-to-client) message using C2CMessageParams.
+    ///
+    /// This is the new, recommended way to send C2C messages. It uses a parameter struct
+    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `openid` - The user's OpenID
+    /// * `params` - C2C message parameters (see [`C2CMessageParams`])
+    ///
+    /// # Returns
+    ///
+    /// The sent C2C message response.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use botrs::*;
+    /// # use botrs::models::message::C2CMessageParams;
+    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
+    /// let params = C2CMessageParams::new_text("Hello user!");
+    /// api.post_c2c_message_with_params(token, "user_openid", params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_c2c_message_with_params(
+        &self,
+        token: &Token,
+        openid: &str,
+        params: C2CMessageParams,
+    ) -> Result<MessageResponse> {
+        debug!("Sending C2C message to {}", openid);
+
+        let body = serde_json::to_value(&params)?;
+
+        let path = format!("/v2/users/{openid}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends a C2C (client-to-client) message (legacy API for backward compatibility).
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `openid` - The user's OpenID
+    /// * `msg_type` - Message type (0=text, 1=rich text, 2=markdown, 3=ark, 4=embed, 7=media)
+    /// * `content` - Message content
+    /// * `embed` - Optional embed
+    /// * `ark` - Optional ark template
+    /// * `message_reference` - Optional message reference
+    /// * `media` - Optional media
+    /// * `msg_id` - Optional message ID to reply to
+    /// * `msg_seq` - Optional message sequence number
+    /// * `event_id` - Optional event ID
+    /// * `markdown` - Optional markdown
+    /// * `keyboard` - Optional keyboard
+    ///
+    /// # Returns
+    ///
+    /// The sent C2C message response.
+    #[deprecated(since = "0.1.0", note = "Use post_c2c_message_with_params instead")]
+    pub async fn post_c2c_message(
+        &self,
+        token: &Token,
+        openid: &str,
+        msg_type: Option<u32>,
+        content: Option<&str>,
+        embed: Option<&Embed>,
+        ark: Option<&Ark>,
+        message_reference: Option<&Reference>,
+        media: Option<&Media>,
+        msg_id: Option<&str>,
+        msg_seq: Option<u32>,
+        event_id: Option<&str>,
+        markdown: Option<&MarkdownPayload>,
+        keyboard: Option<&KeyboardPayload>,
+    ) -> Result<MessageResponse> {
+        let params = C2CMessageParams {
+            msg_type: msg_type.unwrap_or(0),
+            content: content.map(|s| s.to_string()),
+            embed: embed.cloned(),
+            ark: ark.cloned(),
+            message_reference: message_reference.cloned(),
+            media: media.cloned(),
+            msg_id: msg_id.map(|s| s.to_string()),
+            msg_seq,
+            event_id: event_id.map(|s| s.to_string()),
+            markdown: markdown.cloned(),
+            keyboard: keyboard.cloned(),
+        };
+
+        self.post_c2c_message_with_params(token, openid, params)
+            .await
+    }
+
+    /// Sends a direct message using DirectMessageParams.
+    ///
+    /// This is the new, recommended way to send direct messages. It uses a parameter struct
+    /// instead of many optional arguments, making the code cleaner and more maintainable.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The DM session guild ID
+    /// * `params` - Direct message parameters (see [`DirectMessageParams`])
+    ///
+    /// # Returns
+    ///
+    /// The sent direct message response.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use botrs::*;
+    /// # use botrs::models::message::DirectMessageParams;
+    /// # async fn example(api: &BotApi, token: &Token) -> Result<()> {
+    /// let params = DirectMessageParams::new_text("Hello DM!");
+    /// api.post_dms_with_params(token, "guild_id", params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_dms_with_params(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        params: DirectMessageParams,
+    ) -> Result<MessageResponse> {
+        debug!("Sending direct message to guild session {}", guild_id);
+
+        let body = serde_json::to_value(&params)?;
+
+        let path = format!("/dms/{guild_id}/messages");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Sends a direct message (legacy API for backward compatibility).
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The DM session guild ID
+    /// * `content` - Message content
+    /// * `embed` - Optional embed
+    /// * `ark` - Optional ark template
+    /// * `message_reference` - Optional message reference
+    /// * `image` - Optional image URL
+    /// * `file_image` - Optional file image data
+    /// * `msg_id` - Optional message ID to reply to
+    /// * `event_id` - Optional event ID
+    /// * `markdown` - Optional markdown
+    /// * `keyboard` - Optional keyboard
+    ///
+    /// # Returns
+    ///
+    /// The sent direct message response.
+    #[deprecated(since = "0.1.0", note = "Use post_dms_with_params instead")]
+    pub async fn post_dms(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        content: Option<&str>,
+        embed: Option<&Embed>,
+        ark: Option<&Ark>,
+        message_reference: Option<&Reference>,
+        image: Option<&str>,
+        file_image: Option<&[u8]>,
+        msg_id: Option<&str>,
+        event_id: Option<&str>,
+        markdown: Option<&MarkdownPayload>,
+        keyboard: Option<&Keyboard>,
+    ) -> Result<MessageResponse> {
+        let params = DirectMessageParams {
+            content: content.map(|s| s.to_string()),
+            embed: embed.cloned(),
+            ark: ark.cloned(),
+            message_reference: message_reference.cloned(),
+            image: image.map(|s| s.to_string()),
+            file_image: file_image
+                .map(|data| base64::engine::general_purpose::STANDARD.encode(data)),
+            msg_id: msg_id.map(|s| s.to_string()),
+            event_id: event_id.map(|s| s.to_string()),
+            markdown: markdown.cloned(),
+            keyboard: keyboard.cloned(),
+        };
+
+        self.post_dms_with_params(token, guild_id, params).await
+    }
+
+    /// Creates a direct message session.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The source guild ID
+    /// * `user_id` - The target user ID
+    ///
+    /// # Returns
+    ///
+    /// DM session information.
+    pub async fn create_dms(&self, token: &Token, guild_id: &str, user_id: &str) -> Result<Value> {
+        debug!(
+            "Creating DM session for user {} from guild {}",
+            user_id, guild_id
+        );
+
+        let body = json!({
+            "recipient_id": user_id,
+            "source_guild_id": guild_id
+        });
+
+        let response = self
+            .http
+            .post(token, "/users/@me/dms", None::<&()>, Some(&body))
+            .await?;
+        Ok(response)
+    }
+
+    /// Sends the same direct message to several users.
+    ///
+    /// A DM session is opened with each user, then the message is sent in
+    /// it. A failure for one user does not stop the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild the users are members of
+    /// * `user_ids` - The users to message, at most 20
+    /// * `params` - Message parameters
+    /// * `max_concurrent` - Maximum number of users messaged at once
+    ///
+    /// # Returns
+    ///
+    /// Each user ID with its send result, in the order given.
+    pub async fn send_dm_to_many(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        user_ids: &[&str],
+        params: DirectMessageParams,
+        max_concurrent: usize,
+    ) -> Result<Vec<(String, Result<MessageResponse>)>> {
+        validate_dm_targets(user_ids)?;
+        debug!(
+            "Sending direct message to {} users from guild {}",
+            user_ids.len(),
+            guild_id
+        );
+        Ok(dm_each(
+            user_ids,
+            max_concurrent,
+            |user_id| self.open_dm_session(token, guild_id, user_id),
+            |dms_guild_id| {
+                let params = params.clone();
+                async move {
+                    self.post_dms_with_params(token, &dms_guild_id, params)
+                        .await
+                }
+            },
+        )
+        .await)
+    }
+
+    /// Opens a DM session with a user and returns its guild ID.
+    async fn open_dm_session(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<String> {
+        let session = self.create_dms(token, guild_id, user_id).await?;
+        session["guild_id"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| BotError::invalid_data("DM session response has no guild_id"))
+    }
+
+    /// Recalls (deletes) a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    /// * `hidetip` - Whether to hide the recall tip
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn recall_message(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        hidetip: Option<bool>,
+    ) -> Result<()> {
+        debug!("Recalling message {} in channel {}", message_id, channel_id);
+
+        let mut params = HashMap::new();
+        params.insert(
+            "hidetip",
+            if hidetip.unwrap_or(false) {
+                "true"
+            } else {
+                "false"
+            }
+            .to_string(),
+        );
+
+        let path = format!("/channels/{channel_id}/messages/{message_id}");
+        self.http.delete(token, &path, Some(&params)).await?;
+        Ok(())
+    }
+
+    // Audio APIs
+
+    /// Gets the members currently in a voice channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The voice channel ID
+    ///
+    /// # Returns
+    ///
+    /// The members in the channel, or `BotError::InvalidChannel` if the
+    /// channel is not a voice channel.
+    pub async fn get_voice_channel_members(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<Vec<VoiceChannelMember>> {
+        debug!("Getting voice members of channel {}", channel_id);
+        let channel = self.get_channel(token, channel_id).await?;
+        ensure_voice_channel(&channel)?;
+
+        let path = format!("/channels/{channel_id}/voice/members");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        parse_voice_members(response)
+    }
+
+    /// Updates audio control.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `audio_control` - Audio control data
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn update_audio(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        audio_control: &AudioAction,
+    ) -> Result<()> {
+        debug!("Updating audio in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/audio");
+        let _response = self
+            .http
+            .post(token, &path, None::<&()>, Some(audio_control))
+            .await?;
+        Ok(())
+    }
+
+    /// Turn on microphone.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn on_microphone(&self, token: &Token, channel_id: &str) -> Result<()> {
+        debug!("Turning on microphone in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/mic");
+        self.http
+            .put(token, &path, None::<&()>, None::<&()>)
+            .await?;
+        Ok(())
+    }
+
+    /// Turn off microphone.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn off_microphone(&self, token: &Token, channel_id: &str) -> Result<()> {
+        debug!("Turning off microphone in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/mic");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    // Muting APIs
+
+    /// Mutes all members in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `mute_end_timestamp` - Optional end timestamp
+    /// * `mute_seconds` - Optional duration in seconds
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn mute_all(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        mute_end_timestamp: Option<&str>,
+        mute_seconds: Option<&str>,
+    ) -> Result<()> {
+        debug!("Muting all members in guild {}", guild_id);
+
+        let body = json!({
+            "mute_end_timestamp": mute_end_timestamp,
+            "mute_seconds": mute_seconds
+        });
+
+        let path = format!("/guilds/{guild_id}/mute");
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Cancels mute for all members.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn cancel_mute_all(&self, token: &Token, guild_id: &str) -> Result<()> {
+        debug!("Canceling mute for all members in guild {}", guild_id);
+
+        let body = json!({
+            "mute_end_timestamp": "0",
+            "mute_seconds": "0"
+        });
+
+        let path = format!("/guilds/{guild_id}/mute");
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Mutes a specific member.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    /// * `mute_end_timestamp` - Optional end timestamp
+    /// * `mute_seconds` - Optional duration in seconds
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn mute_member(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        user_id: &str,
+        mute_end_timestamp: Option<&str>,
+        mute_seconds: Option<&str>,
+    ) -> Result<()> {
+        debug!("Muting member {} in guild {}", user_id, guild_id);
+
+        let body = json!({
+            "mute_end_timestamp": mute_end_timestamp,
+            "mute_seconds": mute_seconds
+        });
+
+        let path = format!("/guilds/{guild_id}/members/{user_id}/mute");
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Mutes every member that has a specific role.
+    ///
+    /// Members are muted five at a time, pausing one second between batches to
+    /// stay within the mute endpoint's rate limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `role_id` - The role whose members should be muted
+    /// * `mute_seconds` - Mute duration in seconds
+    ///
+    /// # Returns
+    ///
+    /// One result per member, in the order the members were returned.
+    pub async fn mute_role_members(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        role_id: &str,
+        mute_seconds: u32,
+    ) -> Result<Vec<Result<()>>> {
+        let user_ids: Vec<String> = self
+            .get_role_members(token, guild_id, role_id)
+            .await?
+            .iter()
+            .filter_map(|m| m.user_id().cloned())
+            .collect();
+        debug!(
+            "Muting {} members of role {} in guild {}",
+            user_ids.len(),
+            role_id,
+            guild_id
+        );
+
+        let mute_seconds = mute_seconds.to_string();
+        Ok(run_in_batches(
+            &user_ids,
+            MUTE_BATCH_SIZE,
+            std::time::Duration::from_secs(1),
+            |user_id| self.mute_member(token, guild_id, user_id, None, Some(&mute_seconds)),
+        )
+        .await)
+    }
+
+    /// Gets channel permissions for a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    ///
+    /// Channel permissions.
+    pub async fn get_channel_user_permissions(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<ChannelPermissions> {
+        debug!(
+            "Getting channel permissions for user {} in channel {}",
+            user_id, channel_id
+        );
+        let path = format!("/channels/{channel_id}/members/{user_id}/permissions");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Updates a user's permissions in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID
+    /// * `add` - Permissions to grant
+    /// * `remove` - Permissions to revoke
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn update_channel_user_permissions(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        user_id: &str,
+        add: Permissions,
+        remove: Permissions,
+    ) -> Result<()> {
+        debug!(
+            "Updating channel permissions for user {} in channel {}",
+            user_id, channel_id
+        );
+        let body = json!({ "add": add, "remove": remove });
+        let path = format!("/channels/{channel_id}/members/{user_id}/permissions");
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Gets channel permissions for a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `role_id` - The role ID
+    ///
+    /// # Returns
+    ///
+    /// Channel permissions.
+    pub async fn get_channel_role_permissions(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        role_id: &str,
+    ) -> Result<ChannelPermissions> {
+        debug!(
+            "Getting channel permissions for role {} in channel {}",
+            role_id, channel_id
+        );
+        let path = channel_role_permissions_path(channel_id, role_id);
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets a member's effective permissions in a channel.
+    ///
+    /// Combines the channel permissions of every role the member holds with
+    /// the member's own channel permissions. Roles carry no administrator
+    /// bit, so a member holding the owner or administrator system role (see
+    /// [`GuildAdminLevel`]) gets [`Permissions::ALL`] without further requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `channel_id` - The channel ID
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    ///
+    /// The member's effective permissions.
+    pub async fn get_member_effective_permissions(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<Permissions> {
+        debug!(
+            "Computing effective permissions for user {} in channel {}",
+            user_id, channel_id
+        );
+        let member = self.get_guild_member(token, guild_id, user_id).await?;
+        if grants_all_permissions(member.role_ids()) {
+            return Ok(Permissions::ALL);
+        }
+        let role_ids = member.roles.unwrap_or_default();
+
+        let (role_permissions, user_permissions) = futures_util::try_join!(
+            futures_util::future::try_join_all(
+                role_ids
+                    .iter()
+                    .map(|role_id| self.get_channel_role_permissions(token, channel_id, role_id)),
+            ),
+            self.get_channel_user_permissions(token, channel_id, user_id),
+        )?;
+
+        let role_permissions: Vec<Permissions> = role_permissions
+            .iter()
+            .map(ChannelPermissions::permission_set)
+            .collect();
+        Ok(effective_permissions(
+            &role_permissions,
+            user_permissions.permission_set(),
+        ))
+    }
+
+    /// Captures every role's permissions in every channel of a guild.
+    ///
+    /// User-specific permissions can't be listed through the API, so
+    /// `user_permissions` is left empty in each entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// A snapshot of the guild's channel permissions.
+    pub async fn snapshot_channel_permissions(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<ChannelPermissionSnapshot> {
+        debug!("Snapshotting channel permissions for guild {}", guild_id);
+
+        let (channels, roles) = futures_util::try_join!(
+            self.get_channels(token, guild_id),
+            self.get_guild_roles(token, guild_id)
+        )?;
+        let role_ids: Vec<String> = roles.roles.into_iter().filter_map(|r| r.id).collect();
+        let channel_ids = channels.into_iter().filter_map(|c| c.id).collect();
+
+        let role_ids = &role_ids;
+        let channels = map_concurrent(
+            channel_ids,
+            PERMISSION_SNAPSHOT_CONCURRENCY,
+            |channel_id: String| async move {
+                self.role_permission_entry(token, channel_id, role_ids)
+                    .await
+            },
+        )
+        .await?;
+
+        Ok(ChannelPermissionSnapshot {
+            guild_id: guild_id.to_string(),
+            channels,
+            captured_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Compares the role and member permissions of two channels.
+    ///
+    /// Both channels are fetched at the same time, along with the roles and
+    /// members of their guilds. Member permissions are fetched one member at
+    /// a time, so this is slow in large guilds. The diff goes from
+    /// `channel_id_a` to `channel_id_b` and attributes every change to
+    /// `channel_id_b`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id_a` - The channel to compare from
+    /// * `channel_id_b` - The channel to compare to
+    ///
+    /// # Returns
+    ///
+    /// The roles and users whose permissions differ between the channels.
+    pub async fn compare_channel_permissions(
+        &self,
+        token: &Token,
+        channel_id_a: &str,
+        channel_id_b: &str,
+    ) -> Result<PermissionDiff> {
+        debug!(
+            "Comparing permissions of channels {} and {}",
+            channel_id_a, channel_id_b
+        );
+
+        let (a, b) = futures_util::try_join!(
+            self.channel_permission_entry(token, channel_id_a),
+            self.channel_permission_entry(token, channel_id_b)
+        )?;
+        Ok(PermissionDiff::between(channel_id_b, &a, &b))
+    }
+
+    /// Gets the permissions of every role and member of a channel's guild in
+    /// the channel.
+    async fn channel_permission_entry(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<ChannelPermissionEntry> {
+        let channel = self.get_channel(token, channel_id).await?;
+        let guild_id = channel
+            .guild_id
+            .ok_or_else(|| BotError::invalid_data("Channel has no guild ID"))?;
+        let (roles, members) = futures_util::try_join!(
+            self.get_guild_roles(token, &guild_id),
+            self.guild_members_stream(token, &guild_id)
+                .try_collect::<Vec<_>>()
+        )?;
+        let role_ids: Vec<String> = roles.roles.into_iter().filter_map(|r| r.id).collect();
+        let user_ids: Vec<String> = members
+            .iter()
+            .filter_map(|m| m.user_id().cloned())
+            .collect();
+
+        let mut entry = self
+            .role_permission_entry(token, channel_id.to_string(), &role_ids)
+            .await?;
+        entry.user_permissions = map_concurrent(
+            user_ids,
+            PERMISSION_SNAPSHOT_CONCURRENCY,
+            |user_id: String| async move {
+                let permissions = self
+                    .get_channel_user_permissions(token, channel_id, &user_id)
+                    .await?;
+                Ok((user_id, permissions.permission_set()))
+            },
+        )
+        .await?;
+        Ok(entry)
+    }
+
+    /// Gets the permissions of the given roles in a channel.
+    async fn role_permission_entry(
+        &self,
+        token: &Token,
+        channel_id: String,
+        role_ids: &[String],
+    ) -> Result<ChannelPermissionEntry> {
+        let role_permissions = futures_util::future::try_join_all(role_ids.iter().map(|role_id| {
+            let channel_id = &channel_id;
+            async move {
+                let permissions = self
+                    .get_channel_role_permissions(token, channel_id, role_id)
+                    .await?;
+                Ok::<_, BotError>((role_id.clone(), permissions.permission_set()))
+            }
+        }))
+        .await?;
+        Ok(ChannelPermissionEntry {
+            channel_id,
+            user_permissions: Vec::new(),
+            role_permissions,
+        })
+    }
+
+    /// Updates a role's permissions in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `role_id` - The role ID
+    /// * `add` - Permissions to grant
+    /// * `remove` - Permissions to revoke
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn update_channel_role_permissions(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        role_id: &str,
+        add: Permissions,
+        remove: Permissions,
+    ) -> Result<()> {
+        debug!(
+            "Updating channel permissions for role {} in channel {}",
+            role_id, channel_id
+        );
+        let body = json!({ "add": add, "remove": remove });
+        let path = channel_role_permissions_path(channel_id, role_id);
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Applies permission changes to many channels.
+    ///
+    /// Channels are updated one after another with a short pause between
+    /// them to stay under the rate limit; the entries of a single channel are
+    /// sent concurrently. A failed entry does not stop the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `batches` - The changes to apply, grouped by channel
+    ///
+    /// # Returns
+    ///
+    /// Each channel ID with one result per entry, in batch order.
+    /// Wrap it in [`PermissionBatchReport`](crate::models::PermissionBatchReport)
+    /// to summarize.
+    pub async fn bulk_update_channel_permissions(
+        &self,
+        token: &Token,
+        batches: Vec<ChannelPermissionBatch>,
+    ) -> Result<Vec<(String, Vec<Result<()>>)>> {
+        debug!("Updating permissions in {} channels", batches.len());
+        let results = apply_permission_batches(
+            batches,
+            PERMISSION_BATCH_DELAY,
+            |channel_id, entry| async move {
+                match &entry.target {
+                    PermissionTarget::User(user_id) => {
+                        self.update_channel_user_permissions(
+                            token,
+                            &channel_id,
+                            user_id,
+                            entry.add,
+                            entry.remove,
+                        )
+                        .await
+                    }
+                    PermissionTarget::Role(role_id) => {
+                        self.update_channel_role_permissions(
+                            token,
+                            &channel_id,
+                            role_id,
+                            entry.add,
+                            entry.remove,
+                        )
+                        .await
+                    }
+                }
+            },
+        )
+        .await;
+        Ok(results)
+    }
+
+    /// Updates the `@everyone` role's permissions in a channel.
+    ///
+    /// Every guild has an implicit `@everyone` role that all members hold.
+    /// Its role ID is the guild ID, so this is `update_channel_role_permissions`
+    /// with `role_id = guild_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild the channel belongs to
+    /// * `add` - Permissions to grant
+    /// * `deny` - Permissions to revoke
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn update_everyone_channel_permissions(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        guild_id: &str,
+        add: Permissions,
+        deny: Permissions,
+    ) -> Result<()> {
+        self.update_channel_role_permissions(token, channel_id, guild_id, add, deny)
+            .await
+    }
+
+    /// Adds a reaction to a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    /// * `emoji_type` - The emoji type (1=system, 2=emoji)
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn put_reaction(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        emoji_type: u32,
+        emoji_id: &str,
+    ) -> Result<()> {
+        debug!(
+            "Adding reaction to message {} in channel {}",
+            message_id, channel_id
+        );
+        let path = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}"
+        );
+        self.http
+            .put(token, &path, None::<&()>, None::<&()>)
+            .await?;
+        Ok(())
+    }
+
+    /// Removes a reaction from a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    /// * `emoji_type` - The emoji type (1=system, 2=emoji)
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn delete_reaction(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        emoji_type: u32,
+        emoji_id: &str,
+    ) -> Result<()> {
+        debug!(
+            "Removing reaction from message {} in channel {}",
+            message_id, channel_id
+        );
+        let path = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}"
+        );
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Pins a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn put_pin(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<Value> {
+        debug!("Pinning message {} in channel {}", message_id, channel_id);
+        let path = format!("/channels/{channel_id}/pins/{message_id}");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&json!({})))
+            .await?;
+        Ok(response)
+    }
+
+    /// Unpins a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn delete_pin(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<()> {
+        debug!("Unpinning message {} in channel {}", message_id, channel_id);
+        let path = format!("/channels/{channel_id}/pins/{message_id}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Pins several messages in a channel at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `message_ids` - The messages to pin, at most 50
+    ///
+    /// # Returns
+    ///
+    /// One result per message, in the order given.
+    pub async fn pin_messages(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_ids: &[&str],
+    ) -> Result<Vec<Result<()>>> {
+        validate_pin_targets(message_ids.len())?;
+        debug!(
+            "Pinning {} messages in channel {}",
+            message_ids.len(),
+            channel_id
+        );
+
+        Ok(
+            create_each(message_ids, PIN_CONCURRENCY, |message_id| async move {
+                self.put_pin(token, channel_id, message_id).await?;
+                Ok(())
+            })
+            .await,
+        )
+    }
+
+    /// Pins the most recent messages in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `count` - How many of the latest messages to pin, at most 50
+    ///
+    /// # Returns
+    ///
+    /// The number of messages that were pinned.
+    pub async fn pin_latest_messages(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        count: u32,
+    ) -> Result<u32> {
+        validate_pin_targets(count as usize)?;
+        debug!(
+            "Pinning the latest {} messages in channel {}",
+            count, channel_id
+        );
+
+        let messages = self.get_messages_before(token, channel_id, None).await?;
+        let message_ids: Vec<&str> = messages
+            .iter()
+            .filter_map(|message| message.id.as_deref())
+            .take(count as usize)
+            .collect();
+        let results = self.pin_messages(token, channel_id, &message_ids).await?;
+        Ok(count_succeeded(&results))
+    }
+
+    /// Gets the audit status of a message held for review.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `audit_id` - The audit ID returned when the message was sent
+    ///
+    /// # Returns
+    ///
+    /// The message's audit status.
+    pub async fn get_message_audit_status(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        audit_id: &str,
+    ) -> Result<MessageAuditStatus> {
+        debug!("Getting audit {} in guild {}", audit_id, guild_id);
+        let path = format!("/guilds/{guild_id}/message/audit/{audit_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Waits until a held message has been reviewed.
+    ///
+    /// The status is polled every two seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `audit_id` - The audit ID returned when the message was sent
+    /// * `timeout` - How long to wait before giving up
+    ///
+    /// # Returns
+    ///
+    /// The first non-pending status, or [`BotError::Timeout`].
+    pub async fn wait_for_audit_result(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        audit_id: &str,
+        timeout: Duration,
+    ) -> Result<MessageAuditStatus> {
+        debug!("Waiting for audit {} in guild {}", audit_id, guild_id);
+        poll_until(
+            || self.get_message_audit_status(token, guild_id, audit_id),
+            |status: &MessageAuditStatus| !status.is_pending(),
+            AUDIT_POLL_INTERVAL,
+            timeout,
+        )
+        .await
+    }
+
+    /// Gets pinned messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// Pinned messages.
+    pub async fn get_pins(&self, token: &Token, channel_id: &str) -> Result<Value> {
+        debug!("Getting pinned messages in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/pins");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(response)
+    }
+
+    /// Uploads a group file.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
+    /// * `url` - File URL
+    /// * `srv_send_msg` - Whether to send directly
+    ///
+    /// # Returns
+    ///
+    /// Media response.
+    pub async fn post_group_file(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        file_type: u32,
+        url: &str,
+        srv_send_msg: Option<bool>,
+    ) -> Result<Value> {
+        debug!("Uploading group file to {}", group_openid);
+
+        let body = json!({
+            "file_type": file_type,
+            "url": url,
+            "srv_send_msg": srv_send_msg.unwrap_or(false)
+        });
+
+        let path = format!("/v2/groups/{group_openid}/files");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(response)
+    }
+
+    /// Uploads file bytes to a group, reporting upload progress.
+    ///
+    /// The file is sent base64-encoded inside a JSON body, so `total_bytes`
+    /// passed to `on_progress` is the size of that body rather than of `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
+    /// * `data` - The file contents
+    /// * `filename` - The file name
+    /// * `on_progress` - Called with `(bytes_sent, total_bytes)` as the body is sent
+    ///
+    /// # Returns
+    ///
+    /// The uploaded media.
+    pub async fn post_group_file_bytes_with_progress<F>(
+        &self,
+        token: &Token,
+        group_openid: &str,
+        file_type: u32,
+        data: &[u8],
+        filename: &str,
+        on_progress: F,
+    ) -> Result<Media>
+    where
+        F: Fn(u64, u64) + Send + 'static,
+    {
+        debug!(
+            "Uploading group file {} ({} bytes) to {}",
+            filename,
+            data.len(),
+            group_openid
+        );
+
+        let body = serde_json::to_vec(&file_bytes_body(file_type, data, filename))?;
+        let path = format!("/v2/groups/{group_openid}/files");
+        let response = self
+            .http
+            .post_with_progress(token, &path, body, on_progress)
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Uploads file bytes to a group, reporting progress over a channel.
+    ///
+    /// Progress updates are only sent while the returned future is polled.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
+    /// * `data` - The file contents
+    /// * `filename` - The file name
+    ///
+    /// # Returns
+    ///
+    /// A receiver of progress updates and the upload future.
+    pub fn post_group_file_bytes_with_progress_channel<'a>(
+        &'a self,
+        token: &'a Token,
+        group_openid: &'a str,
+        file_type: u32,
+        data: &'a [u8],
+        filename: &'a str,
+    ) -> (
+        mpsc::UnboundedReceiver<UploadProgress>,
+        impl Future<Output = Result<Media>> + 'a,
+    ) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let upload = self.post_group_file_bytes_with_progress(
+            token,
+            group_openid,
+            file_type,
+            data,
+            filename,
+            move |bytes_sent, total_bytes| {
+                // The receiver may have been dropped; the upload carries on regardless
+                let _ = tx.send(UploadProgress {
+                    bytes_sent,
+                    total_bytes,
+                });
+            },
+        );
+        (rx, upload)
+    }
+
+    /// Uploads a C2C file.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `openid` - The user's OpenID
+    /// * `file_type` - File type (1=image, 2=video, 3=audio, 4=file)
+    /// * `url` - File URL
+    /// * `srv_send_msg` - Whether to send directly
+    ///
+    /// # Returns
+    ///
+    /// Media response.
+    pub async fn post_c2c_file(
+        &self,
+        token: &Token,
+        openid: &str,
+        file_type: u32,
+        url: &str,
+        srv_send_msg: Option<bool>,
+    ) -> Result<Value> {
+        debug!("Uploading C2C file to {}", openid);
+
+        let body = json!({
+            "file_type": file_type,
+            "url": url,
+            "srv_send_msg": srv_send_msg.unwrap_or(false)
+        });
+
+        let path = format!("/v2/users/{openid}/files");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(response)
+    }
+
+    // Announcement APIs
+
+    /// Creates a message-type guild announcement.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID where the announcement will be created
+    /// * `channel_id` - The channel ID containing the message to announce
+    /// * `message_id` - The message ID to turn into an announcement
+    ///
+    /// # Returns
+    ///
+    /// The created announcement.
+    pub async fn create_announce(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_id: &str,
+        message_id: &str,
+    ) -> Result<Announce> {
+        debug!(
+            "Creating announcement in guild {} for message {}",
+            guild_id, message_id
+        );
+
+        let body = json!({
+            "channel_id": channel_id,
+            "message_id": message_id
+        });
+
+        let path = format!("/guilds/{guild_id}/announces");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a recommended channel announcement.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID where the announcement will be created
+    /// * `announces_type` - The type of announcement
+    /// * `recommend_channels` - List of channels to recommend
+    ///
+    /// # Returns
+    ///
+    /// The created announcement.
+    pub async fn create_recommend_announce(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        announces_type: AnnouncesType,
+        recommend_channels: Vec<RecommendChannel>,
+    ) -> Result<Announce> {
+        debug!("Creating recommend announcement in guild {}", guild_id);
+
+        let body = json!({
+            "announces_type": u8::from(announces_type),
+            "recommend_channels": recommend_channels
+        });
+
+        let path = format!("/guilds/{guild_id}/announces");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a recommended channel announcement from channel names.
+    ///
+    /// The guild's channels are fetched once and each name is resolved to its
+    /// channel ID before creating the announcement.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID where the announcement will be created
+    /// * `channel_names` - Names of the channels to recommend
+    /// * `announce_type` - The type of announcement
+    ///
+    /// # Returns
+    ///
+    /// The created announcement, or `BotError::NotFound` naming the first
+    /// channel that does not exist in the guild.
+    pub async fn create_recommend_announce_by_names(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_names: &[&str],
+        announce_type: AnnouncesType,
+    ) -> Result<Announce> {
+        debug!(
+            "Resolving {} channel names for recommend announcement in guild {}",
+            channel_names.len(),
+            guild_id
+        );
+
+        let channels = self.get_channels(token, guild_id).await?;
+        let recommend_channels = resolve_channel_ids(&channels, channel_names)?
+            .into_iter()
+            .map(|id| RecommendChannel::new(id, None))
+            .collect();
+        self.create_recommend_announce(token, guild_id, announce_type, recommend_channels)
+            .await
+    }
+
+    /// Deletes a guild announcement.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `message_id` - The message ID of the announcement to delete, or "all" to delete all
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn delete_announce(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        message_id: &str,
+    ) -> Result<Value> {
+        debug!("Deleting announcement {} in guild {}", message_id, guild_id);
+
+        let path = format!("/guilds/{guild_id}/announces/{message_id}");
+        let response = self.http.delete(token, &path, None::<&()>).await?;
+        Ok(response)
+    }
+
+    /// Announces a message and starts a discussion thread from it.
+    ///
+    /// The channel is fetched to find its guild. If the thread cannot be
+    /// created, the announcement is deleted again so no announcement is left
+    /// without its thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel containing the message
+    /// * `announcement_message_id` - The message to announce
+    /// * `thread_name` - Name of the thread
+    /// * `auto_archive` - Minutes without activity before the thread is archived
+    ///
+    /// # Returns
+    ///
+    /// The announcement and its thread.
+    pub async fn create_announcement_thread(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        announcement_message_id: &str,
+        thread_name: &str,
+        auto_archive: u32,
+    ) -> Result<(Announce, ChannelThread)> {
+        let channel = self.get_channel(token, channel_id).await?;
+        let guild_id = channel
+            .guild_id
+            .ok_or_else(|| BotError::invalid_data("Channel has no guild ID"))?;
+
+        create_with_cleanup(
+            self.create_announce(token, &guild_id, channel_id, announcement_message_id),
+            |_| {
+                self.create_channel_thread(
+                    token,
+                    channel_id,
+                    announcement_message_id,
+                    thread_name,
+                    auto_archive,
+                )
+            },
+            |_| self.delete_announce(token, &guild_id, announcement_message_id),
+        )
+        .await
+    }
+
+    // Permission APIs
+
+    /// Gets the list of API permissions for a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of API permissions.
+    pub async fn get_permissions(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<APIPermission>> {
+        debug!("Getting permissions for guild {}", guild_id);
+
+        let path = format!("/guilds/{guild_id}/api_permission");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+
+        // The response has an extra "apis" level
+        if let Some(apis) = response.get("apis") {
+            Ok(serde_json::from_value(apis.clone())?)
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Creates an API permission demand request.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID where permission is requested
+    /// * `channel_id` - The channel ID where the request will be sent
+    /// * `api_identify` - The API identifier for which permission is requested
+    /// * `desc` - Description explaining why the permission is needed
+    ///
+    /// # Returns
+    ///
+    /// The created permission demand.
+    pub async fn post_permission_demand(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_id: &str,
+        api_identify: APIPermissionDemandIdentify,
+        desc: &str,
+    ) -> Result<APIPermissionDemand> {
+        debug!("Creating permission demand in guild {}", guild_id);
+
+        let body = json!({
+            "channel_id": channel_id,
+            "api_identify": api_identify,
+            "desc": desc
+        });
+
+        let path = format!("/guilds/{guild_id}/api_permission/demand");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    // Application Command APIs
+
+    /// Gets the application commands registered in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of commands.
+    pub async fn list_guild_commands(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<ApplicationCommand>> {
+        debug!("Getting commands for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/commands");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Registers an application command in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `cmd` - The command to register
+    ///
+    /// # Returns
+    ///
+    /// The created command.
+    pub async fn create_guild_command(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        cmd: ApplicationCommand,
+    ) -> Result<ApplicationCommand> {
+        debug!("Creating command {} in guild {}", cmd.name, guild_id);
+        let path = format!("/guilds/{guild_id}/commands");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&cmd))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Updates an application command in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `cmd_id` - The command ID
+    /// * `cmd` - The new command definition
+    ///
+    /// # Returns
+    ///
+    /// The updated command.
+    pub async fn update_guild_command(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        cmd_id: &str,
+        cmd: ApplicationCommand,
+    ) -> Result<ApplicationCommand> {
+        debug!("Updating command {} in guild {}", cmd_id, guild_id);
+        let path = format!("/guilds/{guild_id}/commands/{cmd_id}");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&cmd))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes an application command from a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `cmd_id` - The command ID
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn delete_guild_command(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        cmd_id: &str,
+    ) -> Result<()> {
+        debug!("Deleting command {} from guild {}", cmd_id, guild_id);
+        let path = format!("/guilds/{guild_id}/commands/{cmd_id}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Gets the bot's global application commands.
+    ///
+    /// The application ID is taken from the token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    ///
+    /// # Returns
+    ///
+    /// List of global commands.
+    pub async fn get_bot_global_commands(&self, token: &Token) -> Result<Vec<ApplicationCommand>> {
+        debug!("Getting global commands for application {}", token.app_id());
+        let path = global_commands_path(token);
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Replaces all of the bot's global application commands.
+    ///
+    /// Commands missing from `commands` are removed; the replacement happens
+    /// in a single request.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `commands` - The complete set of global commands
+    ///
+    /// # Returns
+    ///
+    /// The registered commands.
+    pub async fn bulk_overwrite_global_commands(
+        &self,
+        token: &Token,
+        commands: Vec<ApplicationCommand>,
+    ) -> Result<Vec<ApplicationCommand>> {
+        debug!(
+            "Overwriting {} global commands for application {}",
+            commands.len(),
+            token.app_id()
+        );
+        let path = global_commands_path(token);
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&commands))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    // Guild Template APIs
+
+    /// Gets a guild template by its code.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `template_code` - The template code
+    ///
+    /// # Returns
+    ///
+    /// The template.
+    pub async fn get_guild_template(
+        &self,
+        token: &Token,
+        template_code: &str,
+    ) -> Result<GuildTemplate> {
+        debug!("Getting guild template {}", template_code);
+        let path = format!("/guilds/templates/{template_code}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the templates made from a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of templates.
+    pub async fn get_guild_templates(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<GuildTemplate>> {
+        debug!("Getting templates for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/templates");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a template from a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `name` - Template name, 1-100 characters
+    /// * `description` - Optional template description
+    ///
+    /// # Returns
+    ///
+    /// The created template.
+    pub async fn create_guild_template(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<GuildTemplate> {
+        debug!("Creating template {} for guild {}", name, guild_id);
+        let body = guild_template_body(name, description)?;
+        let path = format!("/guilds/{guild_id}/templates");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Updates a guild template's name or description.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `template_code` - The template code
+    /// * `name` - New template name, 1-100 characters
+    /// * `description` - New template description
+    ///
+    /// # Returns
+    ///
+    /// The updated template.
+    pub async fn update_guild_template(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        template_code: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<GuildTemplate> {
+        debug!("Updating template {} of guild {}", template_code, guild_id);
+        let body = guild_template_update_body(name, description)?;
+        let path = guild_template_path(guild_id, template_code);
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Syncs a guild template with its guild's current channels and roles.
+    ///
+    /// The guild, its channels and its roles are fetched at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `template_code` - The template code
+    ///
+    /// # Returns
+    ///
+    /// The synced template.
+    pub async fn sync_guild_template(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        template_code: &str,
+    ) -> Result<GuildTemplate> {
+        debug!("Syncing template {} of guild {}", template_code, guild_id);
+        let (guild, channels, roles) = futures_util::try_join!(
+            self.get_guild(token, guild_id),
+            self.get_channels(token, guild_id),
+            self.get_guild_roles(token, guild_id)
+        )?;
+        let body = guild_template_sync_body(&guild, &channels, &roles.roles)?;
+        let path = guild_template_path(guild_id, template_code);
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a new guild from a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `template_code` - The template code
+    /// * `guild_name` - Name of the new guild
+    /// * `icon` - Optional icon image bytes
+    ///
+    /// # Returns
+    ///
+    /// The created guild.
+    pub async fn create_guild_from_template(
+        &self,
+        token: &Token,
+        template_code: &str,
+        guild_name: &str,
+        icon: Option<&[u8]>,
+    ) -> Result<Guild> {
+        debug!(
+            "Creating guild {} from template {}",
+            guild_name, template_code
+        );
+        let body = guild_from_template_body(template_code, guild_name, icon);
+        let response = self
+            .http
+            .post(token, "/guilds", None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a guild template.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `template_code` - The template code
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn delete_guild_template(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        template_code: &str,
+    ) -> Result<()> {
+        debug!("Deleting template {} of guild {}", template_code, guild_id);
+        let path = guild_template_path(guild_id, template_code);
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    // Banned Word APIs
+
+    /// Gets the words and patterns a guild filters out of messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// The guild's banned words.
+    pub async fn get_guild_banned_words(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<GuildBannedWords> {
+        debug!("Getting banned words for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/banned_words");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Replaces the words and patterns a guild filters out of messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `banned_words` - The new banned words
+    ///
+    /// # Returns
+    ///
+    /// The guild's banned words after the update.
+    pub async fn update_guild_banned_words(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        banned_words: &GuildBannedWords,
+    ) -> Result<GuildBannedWords> {
+        debug!("Updating banned words for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/banned_words");
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(banned_words))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Adds words to a guild's banned words.
+    ///
+    /// The current list is fetched and written back with the new words, so
+    /// concurrent edits may be lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `words` - Words to ban
+    ///
+    /// # Returns
+    ///
+    /// The guild's banned words after the update.
+    pub async fn add_banned_words(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        words: &[&str],
+    ) -> Result<GuildBannedWords> {
+        let current = self.get_guild_banned_words(token, guild_id).await?;
+        self.update_guild_banned_words(token, guild_id, &current.with_words_added(words)?)
+            .await
+    }
+
+    /// Removes words from a guild's banned words.
+    ///
+    /// The current list is fetched and written back without the words, so
+    /// concurrent edits may be lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `words` - Words to unban
+    ///
+    /// # Returns
+    ///
+    /// The guild's banned words after the update.
+    pub async fn remove_banned_words(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        words: &[&str],
+    ) -> Result<GuildBannedWords> {
+        let current = self.get_guild_banned_words(token, guild_id).await?;
+        self.update_guild_banned_words(token, guild_id, &current.with_words_removed(words)?)
+            .await
+    }
+
+    // Guild Emoji APIs
+
+    /// Gets the custom emojis of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of custom emojis.
+    pub async fn list_guild_emojis(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<GuildEmoji>> {
+        debug!("Getting emojis for guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/emojis");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Uploads a custom emoji to a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `name` - The emoji name (2-32 letters, digits or underscores)
+    /// * `image_data` - The raw image bytes
+    ///
+    /// # Returns
+    ///
+    /// The created emoji.
+    pub async fn create_guild_emoji(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        name: &str,
+        image_data: &[u8],
+    ) -> Result<GuildEmoji> {
+        debug!("Creating emoji {} in guild {}", name, guild_id);
+        let body = guild_emoji_body(name, image_data)?;
+
+        let path = format!("/guilds/{guild_id}/emojis");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Renames a custom emoji.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `emoji_id` - The emoji ID
+    /// * `name` - The new emoji name
+    ///
+    /// # Returns
+    ///
+    /// The updated emoji.
+    pub async fn update_guild_emoji(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        emoji_id: &str,
+        name: &str,
+    ) -> Result<GuildEmoji> {
+        debug!(
+            "Renaming emoji {} in guild {} to {}",
+            emoji_id, guild_id, name
+        );
+        validate_emoji_name(name)?;
+
+        let body = json!({ "name": name });
+        let path = format!("/guilds/{guild_id}/emojis/{emoji_id}");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a custom emoji.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn delete_guild_emoji(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        emoji_id: &str,
+    ) -> Result<()> {
+        debug!("Deleting emoji {} in guild {}", emoji_id, guild_id);
+        let path = format!("/guilds/{guild_id}/emojis/{emoji_id}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    // Webhook APIs
+
+    /// Gets the webhooks in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// List of webhooks.
+    pub async fn list_channel_webhooks(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<Vec<ChannelWebhook>> {
+        debug!("Listing webhooks in channel {}", channel_id);
+        let path = format!("/channels/{channel_id}/webhooks");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the webhooks in every channel of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of webhooks.
+    pub async fn list_guild_webhooks(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<ChannelWebhook>> {
+        debug!("Listing webhooks in guild {}", guild_id);
+        let path = format!("/guilds/{guild_id}/webhooks");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets a channel webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `webhook_id` - The webhook ID
+    ///
+    /// # Returns
+    ///
+    /// The webhook.
+    pub async fn get_channel_webhook(
+        &self,
+        token: &Token,
+        webhook_id: &str,
+    ) -> Result<ChannelWebhook> {
+        debug!("Getting webhook {}", webhook_id);
+        let path = format!("/webhooks/{webhook_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Updates a channel webhook's name or avatar.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `webhook_id` - The webhook ID
+    /// * `name` - Optional new name, at most 80 characters
+    /// * `avatar_url` - Optional new avatar URL
+    ///
+    /// # Returns
+    ///
+    /// The updated webhook.
+    pub async fn update_channel_webhook(
+        &self,
+        token: &Token,
+        webhook_id: &str,
+        name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> Result<ChannelWebhook> {
+        debug!("Updating webhook {}", webhook_id);
+        let update = WebhookUpdate { name, avatar_url };
+        update.validate()?;
+
+        let path = format!("/webhooks/{webhook_id}");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&update))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a channel webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `webhook_id` - The webhook ID
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn delete_channel_webhook(&self, token: &Token, webhook_id: &str) -> Result<()> {
+        debug!("Deleting webhook {}", webhook_id);
+        let path = format!("/webhooks/{webhook_id}");
+        self.http.delete(token, &path, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Deletes every webhook in a channel.
+    ///
+    /// Up to 5 webhooks are deleted at a time. A failed delete doesn't stop
+    /// the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// How many webhooks were deleted, and the ones that couldn't be.
+    pub async fn delete_all_channel_webhooks(
+        &self,
+        token: &Token,
+        channel_id: &str,
+    ) -> Result<WebhookDeleteReport> {
+        let webhooks = self.list_channel_webhooks(token, channel_id).await?;
+        debug!(
+            "Deleting {} webhooks in channel {}",
+            webhooks.len(),
+            channel_id
+        );
+        Ok(delete_webhooks(webhooks, |webhook_id| async move {
+            self.delete_channel_webhook(token, &webhook_id).await
+        })
+        .await)
+    }
+
+    /// Deletes the webhooks in a guild that are older than a given age.
+    ///
+    /// Webhooks without a creation time are kept. Up to 5 webhooks are
+    /// deleted at a time, and a failed delete doesn't stop the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `older_than` - Minimum age of the webhooks to delete
+    ///
+    /// # Returns
+    ///
+    /// How many webhooks were deleted, and the ones that couldn't be.
+    pub async fn prune_old_webhooks(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        older_than: Duration,
+    ) -> Result<WebhookDeleteReport> {
+        let webhooks = self.list_guild_webhooks(token, guild_id).await?;
+        let old = webhooks_older_than(webhooks, older_than, chrono::Utc::now());
+        debug!(
+            "Pruning {} webhooks older than {:?} in guild {}",
+            old.len(),
+            older_than,
+            guild_id
+        );
+        Ok(delete_webhooks(old, |webhook_id| async move {
+            self.delete_channel_webhook(token, &webhook_id).await
+        })
+        .await)
+    }
+
+    // Reaction APIs
+
+    /// Gets the list of users who reacted with a specific emoji.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID containing the message
+    /// * `message_id` - The message ID
+    /// * `emoji_type` - The type of emoji (1 = system, 2 = custom)
+    /// * `emoji_id` - The emoji ID
+    /// * `cookie` - Optional pagination cookie from previous request
+    /// * `limit` - Maximum number of users to return (1-100, default 20)
+    ///
+    /// # Returns
+    ///
+    /// List of users who reacted and pagination info.
+    pub async fn get_reaction_users(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        emoji_type: EmojiType,
+        emoji_id: &str,
+        cookie: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<ReactionUsers> {
+        debug!(
+            "Getting reaction users for message {} with emoji {}",
+            message_id, emoji_id
+        );
+
+        let mut params = HashMap::new();
+        params.insert("limit", limit.unwrap_or(20).to_string());
+        if let Some(cookie) = cookie {
+            params.insert("cookie", cookie.to_string());
+        }
+
+        let path = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji_type}/{emoji_id}",
+            emoji_type = u8::from(emoji_type)
+        );
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets every user who reacted with a specific emoji.
+    ///
+    /// Follows the pagination cookie until the last page.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID containing the message
+    /// * `message_id` - The message ID
+    /// * `emoji_type` - The type of emoji (1 = system, 2 = custom)
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// All users who reacted.
+    pub async fn get_reaction_users_all(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        message_id: &str,
+        emoji_type: EmojiType,
+        emoji_id: &str,
+    ) -> Result<Vec<ReactionUser>> {
+        collect_reaction_users(|cookie| async move {
+            self.get_reaction_users(
+                token,
+                channel_id,
+                message_id,
+                emoji_type,
+                emoji_id,
+                cookie.as_deref(),
+                Some(REACTION_USERS_PAGE_SIZE),
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Gets the guild members who reacted with a specific emoji.
+    ///
+    /// Each distinct user is looked up once, with a few member requests in
+    /// flight at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `channel_id` - The channel ID containing the message
+    /// * `message_id` - The message ID
+    /// * `emoji_type` - The type of emoji (1 = system, 2 = custom)
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// The reacting members, in reaction order.
+    pub async fn get_reaction_users_with_members(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_id: &str,
+        message_id: &str,
+        emoji_type: EmojiType,
+        emoji_id: &str,
+    ) -> Result<Vec<Member>> {
+        let users = self
+            .get_reaction_users_all(token, channel_id, message_id, emoji_type, emoji_id)
+            .await?;
+        let user_ids = users.into_iter().filter_map(|user| user.id).collect();
+        fetch_unique(
+            user_ids,
+            REACTION_MEMBER_CONCURRENCY,
+            |user_id: String| async move { self.get_guild_member(token, guild_id, &user_id).await },
+        )
+        .await
+    }
+
+    /// Gets how often each of a guild's custom emojis was used in reactions.
+    ///
+    /// Messages sent in `channel_ids` since `since` are fetched, and every
+    /// reaction with one of the guild's custom emojis is counted. The reacting
+    /// users are fetched for each such reaction to count unique reactors.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `channel_ids` - The channels whose messages are counted
+    /// * `since` - Only count messages sent at or after this time
+    ///
+    /// # Returns
+    ///
+    /// One entry per guild emoji, sorted by reaction count descending.
+    pub async fn get_emoji_usage_stats(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        channel_ids: &[&str],
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<EmojiUsageStat>> {
+        debug!(
+            "Getting emoji usage in guild {} across {} channels since {}",
+            guild_id,
+            channel_ids.len(),
+            since
+        );
+
+        let emojis = self.list_guild_emojis(token, guild_id).await?;
+        let channels = map_concurrent(
+            channel_ids.to_vec(),
+            EMOJI_USAGE_CONCURRENCY,
+            |channel_id| async move {
+                let messages = self
+                    .message_history_stream(token, channel_id, since)
+                    .try_collect::<Vec<_>>()
+                    .await?;
+                Ok((channel_id, messages))
+            },
+        )
+        .await?;
+
+        let targets: Vec<_> = channels
+            .iter()
+            .flat_map(|(channel_id, messages)| {
+                messages.iter().filter_map(move |message| {
+                    let reactions = message.reactions.as_ref()?;
+                    Some((*channel_id, message.id.as_deref()?, reactions))
+                })
+            })
+            .flat_map(|(channel_id, message_id, reactions)| {
+                reactions
+                    .iter()
+                    .map(move |reaction| (channel_id, message_id, reaction))
+            })
+            .filter(|(_, _, reaction)| {
+                reaction.emoji.is_custom()
+                    && emojis.iter().any(|emoji| emoji.id == reaction.emoji.id)
+            })
+            .collect();
+        let reactions = map_concurrent(
+            targets,
+            EMOJI_USAGE_CONCURRENCY,
+            |(channel_id, message_id, reaction)| async move {
+                let users = self
+                    .get_reaction_users_all(
+                        token,
+                        channel_id,
+                        message_id,
+                        reaction.emoji.emoji_type,
+                        &reaction.emoji.id,
+                    )
+                    .await?;
+                let user_ids = users.into_iter().filter_map(|user| user.id).collect();
+                Ok((reaction.clone(), user_ids))
+            },
+        )
+        .await?;
+
+        Ok(EmojiUsageStat::aggregate(&emojis, &reactions))
+    }
+
+    /// Streams the messages sent in a channel since a point in time, newest first.
+    ///
+    /// Pages are fetched on demand, walking back until a message older than
+    /// `since` is reached. The stream ends after the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `since` - Only yield messages sent at or after this time
+    ///
+    /// # Returns
+    ///
+    /// A stream of messages, newest first.
+    pub fn message_history_stream<'a>(
+        &'a self,
+        token: &'a Token,
+        channel_id: &'a str,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> impl Stream<Item = Result<Message>> + 'a {
+        paginate_messages_since(since, MESSAGE_PAGE_SIZE, move |before| {
+            self.get_messages_before(token, channel_id, before)
+        })
+    }
+
+    /// Exports the messages sent in a channel during a time range.
+    ///
+    /// Messages are read with [`BotApi::message_history_stream`], so every
+    /// message since `since` is fetched even if it was sent after `until`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The channel ID
+    /// * `since` - Start of the range (inclusive)
+    /// * `until` - End of the range (inclusive)
+    /// * `format` - How to format the export
+    ///
+    /// # Returns
+    ///
+    /// The formatted messages, oldest first.
+    pub async fn export_channel_messages(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        since: chrono::DateTime<chrono::Utc>,
+        until: chrono::DateTime<chrono::Utc>,
+        format: ExportFormat,
+    ) -> Result<String> {
+        if until < since {
+            return Err(BotError::invalid_params(format!(
+                "export range ends ({until}) before it starts ({since})"
+            )));
+        }
+        debug!(
+            "Exporting messages in channel {} from {} to {} as {:?}",
+            channel_id, since, until, format
+        );
+
+        let mut messages: Vec<Message> = self
+            .message_history_stream(token, channel_id, since)
+            .try_filter(|message| std::future::ready(message.timestamp.is_some_and(|t| t <= until)))
+            .try_collect()
+            .await?;
+        messages.reverse();
+        format_messages(&messages, format)
+    }
+
+    /// Gets a page of the newest messages in a channel sent before a message.
+    async fn get_messages_before(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        before: Option<String>,
+    ) -> Result<Vec<Message>> {
+        let mut params = HashMap::new();
+        params.insert("limit", MESSAGE_PAGE_SIZE.to_string());
+        if let Some(before) = before {
+            params.insert("before", before);
+        }
+
+        let path = format!("/channels/{channel_id}/messages");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    // Schedule APIs
+
+    /// Gets the list of schedules for a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `since` - Optional timestamp to get schedules after this time
+    ///
+    /// # Returns
+    ///
+    /// List of schedules.
+    pub async fn get_schedules(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<Schedule>> {
+        debug!("Getting schedules for channel {}", channel_id);
+
+        let body = if let Some(since) = since {
+            json!({ "since": since })
+        } else {
+            json!({})
+        };
+
+        let path = format!("/channels/{channel_id}/schedules");
+        let response = self
+            .http
+            .get(
+                token,
+                &path,
+                if since.is_some() { Some(&body) } else { None },
+            )
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Gets the schedules that haven't started yet in every schedule channel of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// Upcoming schedules with their channels, soonest first.
+    pub async fn get_all_upcoming_schedules(
+        &self,
+        token: &Token,
+        guild_id: &str,
+    ) -> Result<Vec<UpcomingSchedule>> {
+        debug!("Getting upcoming schedules in guild {}", guild_id);
+
+        let channels = self.get_schedule_channels(token, guild_id).await?;
+        let schedules = map_concurrent(
+            channels,
+            SCHEDULE_CHANNEL_CONCURRENCY,
+            |channel| async move {
+                let channel_id = channel.id.clone().unwrap_or_default();
+                let schedules = self.get_schedules(token, &channel_id, None).await?;
+                Ok((channel, schedules))
+            },
+        )
+        .await?;
+        Ok(UpcomingSchedule::collect(schedules, chrono::Utc::now()))
+    }
+
+    /// Gets a specific schedule by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID
+    ///
+    /// # Returns
+    ///
+    /// The schedule details.
+    pub async fn get_schedule(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+    ) -> Result<Schedule> {
+        debug!("Getting schedule {} in channel {}", schedule_id, channel_id);
+
+        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a new schedule in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `name` - Name of the schedule
+    /// * `start_timestamp` - Start time as Unix timestamp string
+    /// * `end_timestamp` - End time as Unix timestamp string
+    /// * `jump_channel_id` - Channel ID to jump to when event starts
+    /// * `remind_type` - Type of reminder to set
+    ///
+    /// # Returns
+    ///
+    /// The created schedule.
+    pub async fn create_schedule(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        name: &str,
+        start_timestamp: &str,
+        end_timestamp: &str,
+        jump_channel_id: &str,
+        remind_type: RemindType,
+    ) -> Result<Schedule> {
+        debug!("Creating schedule '{}' in channel {}", name, channel_id);
+
+        let body = json!({
+            "schedule": {
+                "name": name,
+                "start_timestamp": start_timestamp,
+                "end_timestamp": end_timestamp,
+                "jump_channel_id": jump_channel_id,
+                "reminder_id": u8::from(remind_type)
+            }
+        });
+
+        let path = format!("/channels/{channel_id}/schedules");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a schedule from a builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `params` - The schedule to create
+    ///
+    /// # Returns
+    ///
+    /// The created schedule.
+    pub async fn create_schedule_with(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        params: &ScheduleBuilder,
+    ) -> Result<Schedule> {
+        debug!(
+            "Creating schedule '{}' in channel {}",
+            params.name(),
+            channel_id
+        );
+
+        let body = json!({ "schedule": params });
+        let path = format!("/channels/{channel_id}/schedules");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates a schedule with a cover image.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `builder` - The schedule to create
+    /// * `image` - The cover image (at most 10 MB)
+    ///
+    /// # Returns
+    ///
+    /// The created schedule.
+    pub async fn create_schedule_with_image(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        builder: ScheduleBuilder,
+        image: ScheduleImage,
+    ) -> Result<Schedule> {
+        debug!(
+            "Creating schedule '{}' with a {} byte cover image in channel {}",
+            builder.name(),
+            image.data.len(),
+            channel_id
+        );
+
+        let body = schedule_with_image_body(&builder, &image)?;
+        let path = format!("/channels/{channel_id}/schedules");
+        let response = self
+            .http
+            .post(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Creates many schedules in a channel.
+    ///
+    /// Every schedule is validated before any request is made; if one is
+    /// invalid nothing is created. Up to 10 schedules are then created at a
+    /// time, and a failed schedule does not stop the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedules` - The schedules to create
+    ///
+    /// # Returns
+    ///
+    /// One result per schedule, in input order, or an invalid parameters
+    /// error listing every invalid schedule.
+    pub async fn bulk_create_schedules(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedules: Vec<ScheduleBuilder>,
+    ) -> Result<Vec<Result<Schedule>>> {
+        validate_schedules(&schedules).map_err(|errors| {
+            let reasons: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            BotError::invalid_params(reasons.join("; "))
+        })?;
+        debug!(
+            "Creating {} schedules in channel {}",
+            schedules.len(),
+            channel_id
+        );
+        Ok(
+            create_each(&schedules, SCHEDULE_BULK_CONCURRENCY, |schedule| {
+                self.create_schedule_with(token, channel_id, schedule)
+            })
+            .await,
+        )
+    }
+
+    /// Creates many schedules in a channel and sorts them by outcome.
+    ///
+    /// Like [`BotApi::bulk_create_schedules`], but validation problems are
+    /// returned as a list and results are grouped into a report.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedules` - The schedules to create
+    ///
+    /// # Returns
+    ///
+    /// The created and failed schedules, or every validation error.
+    pub async fn bulk_create_schedules_report(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedules: Vec<ScheduleBuilder>,
+    ) -> std::result::Result<ScheduleBulkReport, Vec<ScheduleBuildError>> {
+        validate_schedules(&schedules)?;
+        let results = create_each(&schedules, SCHEDULE_BULK_CONCURRENCY, |schedule| {
+            self.create_schedule_with(token, channel_id, schedule)
+        })
+        .await;
+        Ok(ScheduleBulkReport::new(schedules, results))
+    }
+
+    /// Creates a schedule and reminds users about it by direct message.
+    ///
+    /// After the schedule is created, a background task sleeps until
+    /// `minutes_before` minutes before the start and then opens a direct
+    /// message session with each recipient and sends the reminder. If that
+    /// time has already passed the reminder is sent right away; if the event
+    /// has already started none is sent. Failed deliveries are logged.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_params` - The schedule to create
+    /// * `reminder_config` - Who to remind and when
+    ///
+    /// # Returns
+    ///
+    /// The created schedule with a handle to cancel the reminder.
+    pub async fn create_schedule_with_reminder(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_params: ScheduleBuilder,
+        reminder_config: DmReminderConfig,
+    ) -> Result<ScheduleWithDmReminder> {
+        let start_timestamp: i64 = schedule_params.start_timestamp().parse().map_err(|_| {
+            BotError::invalid_params(format!(
+                "Invalid schedule start timestamp: {}",
+                schedule_params.start_timestamp()
+            ))
+        })?;
+        let schedule = self
+            .create_schedule_with(token, channel_id, &schedule_params)
+            .await?;
+
+        let delay = reminder_delay(
+            start_timestamp,
+            reminder_config.minutes_before,
+            chrono::Utc::now(),
+        );
+        let recipients = if delay.is_some() {
+            reminder_config.recipients.clone()
+        } else {
+            Vec::new()
+        };
+        let api = self.clone();
+        let token = token.clone();
+        let source_guild_id = reminder_config.source_guild_id.clone();
+        let content = reminder_config.content_for(&schedule);
+        let reminder = spawn_reminder(delay.unwrap_or_default(), recipients, move |user_id| {
+            let (api, token) = (api.clone(), token.clone());
+            let (source_guild_id, content) = (source_guild_id.clone(), content.clone());
+            async move {
+                let dms_guild_id = api
+                    .open_dm_session(&token, &source_guild_id, &user_id)
+                    .await?;
+                api.post_dms_with_params(
+                    &token,
+                    &dms_guild_id,
+                    DirectMessageParams::new_text(&content),
+                )
+                .await?;
+                Ok(())
+            }
+        });
+
+        Ok(ScheduleWithDmReminder::new(
+            schedule,
+            &reminder_config,
+            reminder.abort_handle(),
+        ))
+    }
+
+    /// Updates an existing schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID to update
+    /// * `name` - New name of the schedule
+    /// * `start_timestamp` - New start time as Unix timestamp string
+    /// * `end_timestamp` - New end time as Unix timestamp string
+    /// * `jump_channel_id` - New channel ID to jump to when event starts
+    /// * `remind_type` - New type of reminder to set
+    ///
+    /// # Returns
+    ///
+    /// The updated schedule.
+    pub async fn update_schedule(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+        name: &str,
+        start_timestamp: &str,
+        end_timestamp: &str,
+        jump_channel_id: &str,
+        remind_type: RemindType,
+    ) -> Result<Schedule> {
+        debug!(
+            "Updating schedule {} in channel {}",
+            schedule_id, channel_id
+        );
+
+        let body = json!({
+            "schedule": {
+                "name": name,
+                "start_timestamp": start_timestamp,
+                "end_timestamp": end_timestamp,
+                "jump_channel_id": jump_channel_id,
+                "reminder_id": u8::from(remind_type)
+            }
+        });
+
+        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
+        let response = self
+            .http
+            .patch(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Deletes a schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID to delete
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn delete_schedule(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+    ) -> Result<Value> {
+        debug!(
+            "Deleting schedule {} in channel {}",
+            schedule_id, channel_id
+        );
+
+        let path = format!("/channels/{channel_id}/schedules/{schedule_id}");
+        let response = self.http.delete(token, &path, None::<&()>).await?;
+        Ok(response)
+    }
+
+    /// Gets the members subscribed to a schedule event.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID
+    ///
+    /// # Returns
+    ///
+    /// The event's subscriptions.
+    pub async fn get_event_subscribers(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+    ) -> Result<Vec<EventSubscription>> {
+        debug!(
+            "Getting subscribers of schedule {} in channel {}",
+            schedule_id, channel_id
+        );
+
+        let path = format!("/channels/{channel_id}/schedules/{schedule_id}/subscribers");
+        let response = self.http.get(token, &path, None::<&()>).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Subscribes the bot to a schedule event.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID
+    ///
+    /// # Returns
+    ///
+    /// The created subscription.
+    pub async fn subscribe_to_event(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+    ) -> Result<EventSubscription> {
+        debug!(
+            "Subscribing to schedule {} in channel {}",
+            schedule_id, channel_id
+        );
+
+        let path = event_subscription_path(channel_id, schedule_id);
+        let body = event_subscription_body(true);
+        let response = self
+            .http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Unsubscribes the bot from a schedule event.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The schedule channel ID
+    /// * `schedule_id` - The schedule ID
+    pub async fn unsubscribe_from_event(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        schedule_id: &str,
+    ) -> Result<()> {
+        debug!(
+            "Unsubscribing from schedule {} in channel {}",
+            schedule_id, channel_id
+        );
+
+        let path = event_subscription_path(channel_id, schedule_id);
+        let body = event_subscription_body(false);
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    // Interaction APIs
+
+    /// Acknowledges an interaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `interaction_id` - The interaction ID
+    /// * `code` - Result code, 0 for success
+    ///
+    /// # Returns
+    ///
+    /// Success indication.
+    pub async fn acknowledge_interaction(
+        &self,
+        token: &Token,
+        interaction_id: &str,
+        code: u8,
+    ) -> Result<()> {
+        debug!("Acknowledging interaction {}", interaction_id);
+        let body = json!({ "code": code });
+        let path = format!("/interactions/{interaction_id}");
+        self.http
+            .put(token, &path, None::<&()>, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Gets interactions in a guild that have not been acknowledged yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `cursor` - Cursor from a previous page, or `None` to start
+    ///
+    /// # Returns
+    ///
+    /// A page of interactions.
+    pub async fn get_pending_interactions(
+        &self,
+        token: &Token,
+        guild_id: &str,
+        cursor: Option<&str>,
+    ) -> Result<InteractionPage> {
+        debug!("Getting pending interactions in guild {}", guild_id);
+        let mut params = HashMap::new();
+        params.insert("acknowledged", "false".to_string());
+        if let Some(cursor) = cursor {
+            params.insert("cursor", cursor.to_string());
+        }
+        let path = format!("/guilds/{guild_id}/interactions");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(InteractionPage::new(self.clone(), &response))
+    }
+
+    /// Polls a guild for new interactions without a gateway connection.
+    ///
+    /// Each round follows the cursor through every waiting page, acknowledges
+    /// each interaction before yielding it, then sleeps for `poll_interval`.
+    /// An interaction whose acknowledgement fails is still yielded, followed
+    /// by the acknowledgement error. Fetch errors are yielded and polling
+    /// carries on after the next sleep; the stream never ends on its own.
+    /// Use it instead of the gateway client, not alongside it, or
+    /// interactions are handled twice.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild ID
+    /// * `poll_interval` - Pause between polling rounds
+    ///
+    /// # Returns
+    ///
+    /// A stream of acknowledged interactions.
+    pub fn poll_interactions<'a>(
+        &'a self,
+        token: &'a Token,
+        guild_id: &'a str,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<Interaction>> + 'a {
+        poll_interaction_pages(
+            poll_interval,
+            move |cursor| async move {
+                self.get_pending_interactions(token, guild_id, cursor.as_deref())
+                    .await
+            },
+            move |interaction_id| async move {
+                self.acknowledge_interaction(token, &interaction_id, 0)
+                    .await
+            },
+        )
+    }
+
+    // Forum APIs
+
+    /// Gets a page of threads in a forum channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `page` - Page number, starting from 1
+    ///
+    /// # Returns
+    ///
+    /// The requested page of threads.
+    pub async fn get_threads(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        page: u32,
+    ) -> Result<ThreadPage> {
+        debug!("Getting threads page {} in channel {}", page, channel_id);
+
+        let mut params = HashMap::new();
+        params.insert("page", page.to_string());
+
+        let path = format!("/channels/{channel_id}/threads");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(ThreadPage::new(self.clone(), &response))
+    }
+
+    /// Finds the first thread in a forum channel with exactly the given title.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `title` - The title to look for
+    ///
+    /// # Returns
+    ///
+    /// The matching thread, if any.
+    pub async fn find_thread_by_title(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        title: &str,
+    ) -> Result<Option<Thread>> {
+        self.find_thread_by_title_with(token, channel_id, title, ThreadSearchOptions::default())
+            .await
+    }
+
+    /// Finds the first thread in a forum channel whose title matches `title`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `title` - The title to look for
+    /// * `options` - How titles are compared
+    ///
+    /// # Returns
+    ///
+    /// The matching thread, if any.
+    pub async fn find_thread_by_title_with(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        title: &str,
+        options: ThreadSearchOptions,
+    ) -> Result<Option<Thread>> {
+        debug!("Finding thread {:?} in channel {}", title, channel_id);
+        let fetch = |page| self.get_threads(token, channel_id, page);
+        let mut found = search_threads(fetch, title, options, 1).await?;
+        Ok(found.pop())
+    }
+
+    /// Finds threads in a forum channel whose titles contain `substring`,
+    /// ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `substring` - Text the title must contain
+    /// * `max_results` - Maximum number of threads to return
+    ///
+    /// # Returns
+    ///
+    /// Matching threads in listing order.
+    pub async fn find_threads_by_title_contains(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        substring: &str,
+        max_results: usize,
+    ) -> Result<Vec<Thread>> {
+        debug!(
+            "Finding threads containing {:?} in channel {}",
+            substring, channel_id
+        );
+        let options = ThreadSearchOptions {
+            exact_match: false,
+            case_sensitive: false,
+        };
+        let fetch = |page| self.get_threads(token, channel_id, page);
+        search_threads(fetch, substring, options, max_results).await
+    }
+
+    /// Creates a post (reply) under a forum thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID to reply to
+    /// * `content` - The post content
+    ///
+    /// # Returns
+    ///
+    /// The created post.
+    pub async fn create_thread_post(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        thread_id: &str,
+        content: ForumContent,
+    ) -> Result<ForumPost> {
+        debug!(
+            "Creating post in thread {} of channel {}",
+            thread_id, channel_id
+        );
+
+        put_thread_post(channel_id, thread_id, &content, |path, body| async move {
+            self.http.put(token, &path, None::<&()>, Some(&body)).await
+        })
+        .await
+    }
+
+    /// Gets a page of posts in a forum thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID
+    /// * `page` - Page number, starting from 1
+    /// * `page_size` - Number of posts per page
+    ///
+    /// # Returns
+    ///
+    /// The requested page of posts.
+    pub async fn get_thread_posts(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        thread_id: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<PostPage> {
+        debug!("Getting posts page {} in thread {}", page, thread_id);
+
+        let mut params = HashMap::new();
+        params.insert("page", page.to_string());
+        params.insert("page_size", page_size.to_string());
+
+        let path = format!("/channels/{channel_id}/threads/{thread_id}/posts");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(PostPage::new(&response))
+    }
+
+    /// Gets the users who have posted or replied in a forum thread.
+    ///
+    /// Every post in the thread is fetched, then every reply to each post with
+    /// [`BotApi::all_replies_stream`]. Posts and replies both count towards a
+    /// participant's post count.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID
+    ///
+    /// # Returns
+    ///
+    /// The participants, most active first.
+    pub async fn get_thread_participants(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        thread_id: &str,
+    ) -> Result<Vec<ThreadParticipant>> {
+        debug!(
+            "Getting participants of thread {} in channel {}",
+            thread_id, channel_id
+        );
+
+        let posts = collect_posts(THREAD_POST_PAGE_SIZE, |page| {
+            self.get_thread_posts(token, channel_id, thread_id, page, THREAD_POST_PAGE_SIZE)
+        })
+        .await?;
+        let post_ids = posts
+            .iter()
+            .filter_map(|post| post.post_id.as_deref())
+            .collect();
+        let replies = map_concurrent(post_ids, THREAD_REPLY_CONCURRENCY, |post_id| {
+            self.all_replies_stream(token, channel_id, thread_id, post_id)
+                .try_collect::<Vec<_>>()
+        })
+        .await?;
+
+        Ok(ThreadParticipant::from_posts(&posts, &replies.concat()))
+    }
+
+    /// Gets a page of replies to a forum post.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID
+    /// * `post_id` - The post ID
+    /// * `page` - Page number, starting from 1
+    /// * `page_size` - Number of replies per page
+    ///
+    /// # Returns
+    ///
+    /// The requested page of replies.
+    pub async fn get_thread_replies(
+        &self,
+        token: &Token,
+        channel_id: &str,
+        thread_id: &str,
+        post_id: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<ReplyPage> {
+        debug!(
+            "Getting replies page {} for post {} in thread {}",
+            page, post_id, thread_id
+        );
+
+        let mut params = HashMap::new();
+        params.insert("page", page.to_string());
+        params.insert("page_size", page_size.to_string());
+
+        let path = format!("/channels/{channel_id}/threads/{thread_id}/posts/{post_id}/replies");
+        let response = self.http.get(token, &path, Some(&params)).await?;
+        Ok(ReplyPage::new(&response))
+    }
+
+    /// Streams every reply to a forum post, fetching pages on demand.
+    ///
+    /// The stream ends once all replies have been yielded, or after the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID
+    /// * `post_id` - The post ID
+    ///
+    /// # Returns
+    ///
+    /// A stream of replies in API order.
+    pub fn all_replies_stream<'a>(
+        &'a self,
+        token: &'a Token,
+        channel_id: &'a str,
+        thread_id: &'a str,
+        post_id: &'a str,
+    ) -> impl Stream<Item = Result<ForumReply>> + 'a {
+        const PAGE_SIZE: u32 = 20;
+        paginate_replies(PAGE_SIZE, move |page| {
+            self.get_thread_replies(token, channel_id, thread_id, post_id, page, PAGE_SIZE)
+        })
+    }
+
+    /// Gets the HTTP client reference.
+    pub fn http(&self) -> &HttpClient {
+        &self.http
+    }
+
+    /// Closes the API client and cleans up resources.
+    pub async fn close(&self) {
+        self.http.close().await;
+    }
+}
+
+/// Turns a not-found error into `Ok(None)`, passing other errors through.
+fn not_found_as_none<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(BotError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks that a fetched member belongs to the expected user.
+fn ensure_member_of(member: Member, user_id: &str) -> Result<Member> {
+    match member.user.as_ref().map(|u| u.id.as_str()) {
+        Some(id) if id != user_id => Err(BotError::InvalidData(format!(
+            "Expected member {user_id}, got {id}"
+        ))),
+        _ => Ok(member),
+    }
+}
+
+/// How long the voice region list is cached.
+const VOICE_REGION_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Returns an error unless `region_id` is one of `regions`.
+fn ensure_known_region(regions: &[VoiceRegion], region_id: &str) -> Result<()> {
+    if regions.iter().any(|r| r.id == region_id) {
+        Ok(())
+    } else {
+        Err(BotError::invalid_params(format!(
+            "unknown voice region {region_id:?}"
+        )))
+    }
+}
+
+/// Page size used when listing all of the bot's guilds.
+const GUILD_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of concurrent guild detail requests.
+const GUILD_INFO_CONCURRENCY: usize = 10;
+
+/// Collects every page of guilds, requesting each page after the last guild seen.
+async fn collect_guild_pages<F, Fut>(page_size: u32, mut fetch: F) -> Result<Vec<Guild>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Guild>>>,
+{
+    let mut guilds = Vec::new();
+    let mut after = None;
+    loop {
+        let page = fetch(after.clone()).await?;
+        let full = page.len() >= page_size as usize;
+        after = page.last().and_then(|g| g.id.clone());
+        guilds.extend(page);
+        if !full || after.is_none() {
+            return Ok(guilds);
+        }
+    }
+}
+
+/// Maximum number of channels whose permissions are fetched at once.
+const PERMISSION_SNAPSHOT_CONCURRENCY: usize = 5;
+
+/// Runs `f` over `items` with at most `limit` futures in flight, keeping input order.
+async fn map_concurrent<T, U, F, Fut>(items: Vec<T>, limit: usize, f: F) -> Result<Vec<U>>
+where
+    F: FnMut(T) -> Fut,
+    Fut: std::future::Future<Output = Result<U>>,
+{
+    futures_util::stream::iter(items)
+        .map(f)
+        .buffered(limit)
+        .try_collect()
+        .await
+}
+
+/// Maximum number of concurrent membership checks in `get_mutual_guilds`.
+const MUTUAL_GUILD_CONCURRENCY: usize = 10;
+
+/// Keeps the items for which `predicate` resolves to true, with at most
+/// `limit` checks in flight, preserving input order.
+async fn filter_concurrent<T, F, Fut>(items: Vec<T>, limit: usize, predicate: F) -> Result<Vec<T>>
+where
+    F: FnMut(&T) -> Fut,
+    Fut: std::future::Future<Output = Result<bool>>,
+{
+    let keep = map_concurrent(items.iter().collect(), limit, predicate).await?;
+    Ok(items
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(item, keep)| keep.then_some(item))
+        .collect())
+}
+
+/// Adds the roles in `desired` missing from `current` and removes the roles in
+/// `current` missing from `desired`, concurrently.
+async fn sync_roles<'a, A, AFut, R, RFut>(
+    current: &'a [String],
+    desired: &[&'a str],
+    add: A,
+    remove: R,
+) -> RoleSyncResult
+where
+    A: Fn(&'a str) -> AFut,
+    AFut: Future<Output = Result<()>>,
+    R: Fn(&'a str) -> RFut,
+    RFut: Future<Output = Result<()>>,
+{
+    let to_add: Vec<&str> = desired
+        .iter()
+        .copied()
+        .filter(|role| !current.iter().any(|r| r == role))
+        .collect();
+    let to_remove: Vec<&str> = current
+        .iter()
+        .map(String::as_str)
+        .filter(|role| !desired.contains(role))
+        .collect();
+
+    let (added, removed) = futures_util::join!(
+        futures_util::future::join_all(to_add.iter().map(|&role| add(role))),
+        futures_util::future::join_all(to_remove.iter().map(|&role| remove(role)))
+    );
+
+    let mut result = RoleSyncResult::default();
+    for (role, outcome) in to_add.into_iter().zip(added) {
+        match outcome {
+            Ok(()) => result.added.push(role.to_string()),
+            Err(e) => result.errors.push((role.to_string(), e)),
+        }
+    }
+    for (role, outcome) in to_remove.into_iter().zip(removed) {
+        match outcome {
+            Ok(()) => result.removed.push(role.to_string()),
+            Err(e) => result.errors.push((role.to_string(), e)),
+        }
+    }
+    result
+}
+
+/// Keeps the members who joined after `since`, dropping those without a join time.
+fn members_joined_after(members: Vec<Member>, since: chrono::DateTime<chrono::Utc>) -> Vec<Member> {
+    members
+        .into_iter()
+        .filter(|member| member.joined_at.is_some_and(|joined_at| joined_at > since))
+        .collect()
+}
+
+/// Number of members requested per page when listing all guild members.
+const MEMBER_PAGE_SIZE: u32 = 400;
+
+/// Turns a member page fetcher into a stream of members.
+///
+/// Pages are requested with the ID of the last member seen, starting from
+/// `"0"`, until a page comes back short.
+fn paginate_members<F, Fut>(page_size: u32, fetch: F) -> impl Stream<Item = Result<Member>>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Vec<Member>>>,
+{
+    let state = (
+        fetch,
+        Some("0".to_string()),
+        std::collections::VecDeque::new(),
+    );
+    futures_util::stream::unfold(
+        state,
+        move |(mut fetch, mut after, mut buffer)| async move {
+            loop {
+                if let Some(member) = buffer.pop_front() {
+                    return Some((Ok(member), (fetch, after, buffer)));
+                }
+                let cursor = after.take()?;
+                match fetch(cursor).await {
+                    Ok(page) => {
+                        if page.len() as u32 >= page_size {
+                            after = page
+                                .last()
+                                .and_then(|m: &Member| m.user.as_ref())
+                                .map(|user| user.id.clone());
+                        }
+                        buffer.extend(page);
+                    }
+                    Err(e) => return Some((Err(e), (fetch, None, buffer))),
+                }
+            }
+        },
+    )
+}
+
+/// Number of online members requested per page.
+const ONLINE_MEMBER_PAGE_SIZE: u32 = 100;
+
+/// Streams online members page by page.
+///
+/// The first page is requested without a cursor, and each following page
+/// with the previous page's `next_cursor` until one comes back without it.
+fn paginate_online_members<F, Fut>(fetch: F) -> impl Stream<Item = Result<Member>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<OnlineMembersPage>>,
+{
+    let state = (fetch, Some(None), std::collections::VecDeque::new());
+    futures_util::stream::unfold(state, move |(mut fetch, mut next, mut buffer)| async move {
+        loop {
+            if let Some(member) = buffer.pop_front() {
+                return Some((Ok(member), (fetch, next, buffer)));
+            }
+            let cursor = next.take()?;
+            match fetch(cursor.clone()).await {
+                Ok(page) => {
+                    // Like `get_role_members`, stop on an empty page or a
+                    // cursor that is empty or doesn't move.
+                    next = page
+                        .next_cursor
+                        .filter(|c| {
+                            !page.members.is_empty() && !c.is_empty() && Some(c) != cursor.as_ref()
+                        })
+                        .map(Some);
+                    buffer.extend(page.members);
+                }
+                Err(e) => return Some((Err(e), (fetch, None, buffer))),
+            }
+        }
+    })
+}
+
+/// Writes members as CSV with a header row of field names.
+fn members_to_csv(members: &[Member], fields: &[MemberField]) -> Result<String> {
+    let csv_error = |e: csv::Error| BotError::internal(format!("failed to write CSV: {e}"));
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(fields.iter().map(MemberField::name))
+        .map_err(csv_error)?;
+    for member in members {
+        writer
+            .write_record(fields.iter().map(|field| field.value(member)))
+            .map_err(csv_error)?;
+    }
+    let data = writer
+        .into_inner()
+        .map_err(|e| BotError::internal(format!("failed to write CSV: {e}")))?;
+    String::from_utf8(data).map_err(|e| BotError::internal(format!("invalid CSV output: {e}")))
+}
+
+/// How often `wait_for_audit_result` checks a message's audit status.
+const AUDIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Calls `fetch` every `interval` until `done` accepts the result.
+///
+/// Fails with [`BotError::Timeout`] if that takes longer than `timeout`.
+async fn poll_until<T, F, Fut, D>(
+    mut fetch: F,
+    done: D,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    D: Fn(&T) -> bool,
+{
+    let poll = async {
+        loop {
+            let value = fetch().await?;
+            if done(&value) {
+                return Ok(value);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    };
+    tokio::time::timeout(timeout, poll)
+        .await
+        .map_err(|_| BotError::Timeout)?
+}
+
+/// Maximum number of forum channels whose threads are listed at once.
+const FORUM_SUMMARY_CONCURRENCY: usize = 5;
+
+/// Pause between channels in a bulk permission update.
+const PERMISSION_BATCH_DELAY: Duration = Duration::from_millis(500);
+
+/// Runs `f` for every entry, one channel at a time with `delay` between
+/// channels and the entries of each channel concurrently.
+async fn apply_permission_batches<F, Fut>(
+    batches: Vec<ChannelPermissionBatch>,
+    delay: Duration,
+    f: F,
+) -> Vec<(String, Vec<Result<()>>)>
+where
+    F: Fn(String, PermissionEntry) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut results = Vec::with_capacity(batches.len());
+    for (i, batch) in batches.into_iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(delay).await;
+        }
+        let channel_id = batch.channel_id;
+        let entry_results = futures_util::future::join_all(
+            batch
+                .entries
+                .into_iter()
+                .map(|entry| f(channel_id.clone(), entry)),
+        )
+        .await;
+        results.push((channel_id, entry_results));
+    }
+    results
+}
+
+/// Spawns a task that waits `delay` and then calls `send` for each recipient
+/// in turn, logging failures.
+fn spawn_reminder<F, Fut>(
+    delay: Duration,
+    recipients: Vec<String>,
+    send: F,
+) -> tokio::task::JoinHandle<()>
+where
+    F: Fn(String) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        for user_id in recipients {
+            if let Err(e) = send(user_id.clone()).await {
+                warn!("Failed to send schedule reminder to {}: {}", user_id, e);
+            }
+        }
+    })
+}
+
+/// Flattens member lists, keeping the first occurrence of each user.
+fn dedup_members(lists: Vec<Vec<Member>>) -> Vec<Member> {
+    let mut seen = std::collections::HashSet::new();
+    lists
+        .into_iter()
+        .flatten()
+        .filter(|member| match member.user_id() {
+            Some(id) => seen.insert(id.clone()),
+            None => true,
+        })
+        .collect()
+}
+
+/// Keeps the channels of one type.
+fn channels_of_type(channels: Vec<Channel>, channel_type: ChannelType) -> Vec<Channel> {
+    channels
+        .into_iter()
+        .filter(|channel| channel.channel_type == Some(channel_type))
+        .collect()
+}
+
+/// Keeps the channels marked NSFW.
+fn nsfw_channels(channels: Vec<Channel>) -> Vec<Channel> {
+    channels
+        .into_iter()
+        .filter(|channel| channel.is_nsfw == Some(true))
+        .collect()
+}
+
+/// Counts channels per type, skipping channels without a type.
+fn channel_type_counts(channels: &[Channel]) -> HashMap<ChannelType, usize> {
+    let mut counts = HashMap::new();
+    for channel_type in channels.iter().filter_map(|channel| channel.channel_type) {
+        *counts.entry(channel_type).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Number of users requested per page when listing all reaction users.
+const REACTION_USERS_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of concurrent member lookups for reacting users.
+const REACTION_MEMBER_CONCURRENCY: usize = 5;
+
+/// Collects reaction users from every page, passing each page's cookie to
+/// the next request.
+async fn collect_reaction_users<F, Fut>(mut fetch: F) -> Result<Vec<ReactionUser>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<ReactionUsers>>,
+{
+    let mut users = Vec::new();
+    let mut cookie = None;
+    loop {
+        let page = fetch(cookie.take()).await?;
+        users.extend(page.users);
+        match page.cookie {
+            Some(next) if !page.is_end && !next.is_empty() => cookie = Some(next),
+            _ => break,
+        }
+    }
+    Ok(users)
+}
+
+/// Runs `f` once per distinct key, with at most `limit` calls in flight,
+/// preserving the order in which keys first appear.
+async fn fetch_unique<U, F, Fut>(keys: Vec<String>, limit: usize, f: F) -> Result<Vec<U>>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<U>>,
+{
+    let mut seen = std::collections::HashSet::new();
+    let unique = keys
+        .into_iter()
+        .filter(|key| seen.insert(key.clone()))
+        .collect();
+    map_concurrent(unique, limit, f).await
+}
+
+/// Maximum number of concurrent requests of each kind in `sync_guild_roles`.
+const ROLE_SYNC_CONCURRENCY: usize = 5;
+
+/// The role changes needed to reach a desired set of roles.
+#[derive(Debug, PartialEq)]
+struct RoleSyncPlan {
+    create: Vec<RoleSpec>,
+    update: Vec<(String, RoleSpec)>,
+    delete: Vec<String>,
+}
+
+impl RoleSyncPlan {
+    /// Matches `desired` against `current` by name.
+    fn new(current: &[GuildRole], desired: Vec<RoleSpec>) -> Result<Self> {
+        let mut names = std::collections::HashSet::new();
+        if let Some(duplicate) = desired
+            .iter()
+            .find(|spec| !names.insert(spec.name.as_str()))
+        {
+            return Err(BotError::invalid_params(format!(
+                "duplicate role name {:?}",
+                duplicate.name
+            )));
+        }
+
+        let find = |name: &str| {
+            current
+                .iter()
+                .find(|role| role.id.is_some() && role.name.as_deref() == Some(name))
+        };
+        let mut plan = Self {
+            create: Vec::new(),
+            update: Vec::new(),
+            delete: current
+                .iter()
+                .filter(|role| !role.is_system())
+                .filter(|role| {
+                    !role
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| names.contains(name))
+                })
+                .filter_map(|role| role.id.clone())
+                .collect(),
+        };
+        for spec in desired {
+            match find(&spec.name) {
+                Some(role) if spec.differs_from(role) => {
+                    plan.update
+                        .push((role.id.clone().unwrap_or_default(), spec));
+                }
+                Some(_) => {}
+                None => plan.create.push(spec),
+            }
+        }
+        Ok(plan)
+    }
+}
+
+/// Turns an interaction page fetcher into an endless stream of interactions.
+///
+/// Pages are fetched back to back while `has_more` is set and with
+/// `interval` between rounds otherwise. Each interaction is passed to `ack`
+/// before it is yielded; if that fails the error is yielded right after the
+/// interaction, so no interaction is lost once the cursor has moved past it.
+fn poll_interaction_pages<F, Fut, A, AFut>(
+    interval: Duration,
+    fetch: F,
+    ack: A,
+) -> impl Stream<Item = Result<Interaction>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<InteractionPage>>,
+    A: FnMut(String) -> AFut,
+    AFut: Future<Output = Result<()>>,
+{
+    let state = (
+        fetch,
+        ack,
+        None::<String>,
+        std::collections::VecDeque::<Result<Interaction>>::new(),
+        false,
+    );
+    futures_util::stream::unfold(
+        state,
+        move |(mut fetch, mut ack, mut cursor, mut buffer, mut wait)| async move {
+            loop {
+                if let Some(item) = buffer.pop_front() {
+                    if let Ok(interaction) = &item
+                        && let Some(id) = interaction.id.clone()
+                        && let Err(e) = ack(id).await
+                    {
+                        buffer.push_front(Err(e));
+                    }
+                    return Some((item, (fetch, ack, cursor, buffer, wait)));
+                }
+                if wait {
+                    tokio::time::sleep(interval).await;
+                }
+                match fetch(cursor.clone()).await {
+                    Ok(page) => {
+                        if page.cursor.is_some() {
+                            cursor = page.cursor;
+                        }
+                        wait = !page.has_more;
+                        buffer.extend(page.interactions.into_iter().map(Ok));
+                    }
+                    Err(e) => return Some((Err(e), (fetch, ack, cursor, buffer, true))),
+                }
+            }
+        },
+    )
+}
+
+/// Number of successful polls in a row after which the backoff is reset.
+const BACKOFF_RESET_SUCCESSES: u32 = 3;
+
+/// Largest multiple of the base interval the backoff grows to.
+const MAX_BACKOFF_FACTOR: u32 = 32;
+
+/// Delay between polls that doubles on errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PollBackoff {
+    base: Duration,
+    delay: Duration,
+    successes: u32,
+}
+
+impl PollBackoff {
+    fn new(base: Duration) -> Self {
+        Self {
+            base,
+            delay: base,
+            successes: 0,
+        }
+    }
+
+    fn on_success(&mut self) {
+        self.successes += 1;
+        if self.successes >= BACKOFF_RESET_SUCCESSES {
+            self.delay = self.base;
+        }
+    }
+
+    fn on_error(&mut self) {
+        self.successes = 0;
+        self.delay = (self.delay * 2).min(self.base * MAX_BACKOFF_FACTOR);
+    }
+}
+
+/// Turns an audit log fetcher into an endless stream of new entries.
+///
+/// The first fetch happens immediately; later ones wait for the backoff delay.
+fn poll_audit_log<F, Fut>(
+    interval: Duration,
+    tracker: AuditLogStream,
+    fetch: F,
+) -> impl Stream<Item = Result<AuditLogEntry>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Vec<AuditLogEntry>>>,
+{
+    let state = (
+        fetch,
+        tracker,
+        PollBackoff::new(interval),
+        std::collections::VecDeque::<AuditLogEntry>::new(),
+        false,
+    );
+    futures_util::stream::unfold(
+        state,
+        move |(mut fetch, mut tracker, mut backoff, mut buffer, mut wait)| async move {
+            loop {
+                if let Some(entry) = buffer.pop_front() {
+                    return Some((Ok(entry), (fetch, tracker, backoff, buffer, wait)));
+                }
+                if wait {
+                    tokio::time::sleep(backoff.delay).await;
+                }
+                wait = true;
+                match fetch().await {
+                    Ok(entries) => {
+                        backoff.on_success();
+                        buffer.extend(tracker.accept(entries));
+                    }
+                    Err(e) => {
+                        backoff.on_error();
+                        return Some((Err(e), (fetch, tracker, backoff, buffer, wait)));
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// How long to wait for a voice region to answer a latency probe.
+const REGION_PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Probes every item at once, timing each probe and failing those that take
+/// longer than `timeout`.
+async fn measure_latencies<T, F, Fut>(
+    items: Vec<T>,
+    timeout: Duration,
+    probe: F,
+) -> Vec<(T, Result<Duration>)>
+where
+    F: Fn(&T) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let timings = futures_util::future::join_all(items.iter().map(|item| {
+        let started = tokio::time::Instant::now();
+        let probe = probe(item);
+        async move {
+            match tokio::time::timeout(timeout, probe).await {
+                Ok(Ok(())) => Ok(started.elapsed()),
+                Ok(Err(e)) => Err(e),
+                Err(_) => Err(BotError::Timeout),
+            }
+        }
+    }))
+    .await;
+    items.into_iter().zip(timings).collect()
+}
+
+/// Keeps the reachable voice regions, fastest first.
+fn rank_latencies(
+    results: Vec<(VoiceRegion, Result<Duration>)>,
+) -> Result<Vec<(VoiceRegion, Duration)>> {
+    let attempted = results.len();
+    let mut ranked: Vec<_> = results
+        .into_iter()
+        .filter_map(|(region, latency)| match latency {
+            Ok(latency) => Some((region, latency)),
+            Err(e) => {
+                warn!("Voice region {} is unreachable: {}", region.id, e);
+                None
+            }
+        })
+        .collect();
+    if ranked.is_empty() && attempted > 0 {
+        return Err(BotError::Timeout);
+    }
+    ranked.sort_by_key(|(_, latency)| *latency);
+    Ok(ranked)
+}
+
+/// Maximum number of schedules created at once in a bulk import.
+const SCHEDULE_BULK_CONCURRENCY: usize = 10;
+
+/// Runs `f` for every item with at most `limit` calls in flight, keeping
+/// every result in input order.
+async fn create_each<'a, T, U, F, Fut>(items: &'a [T], limit: usize, f: F) -> Vec<Result<U>>
+where
+    F: FnMut(&'a T) -> Fut,
+    Fut: Future<Output = Result<U>>,
+{
+    futures_util::stream::iter(items)
+        .map(f)
+        .buffered(limit)
+        .collect()
+        .await
+}
+
+/// Path of the bot's own subscription to a schedule event.
+fn event_subscription_path(channel_id: &str, schedule_id: &str) -> String {
+    format!("/channels/{channel_id}/schedules/{schedule_id}/subscribers/@me")
+}
+
+/// Builds the request body for a schedule with a cover image, sent as a
+/// base64 data URL.
+fn schedule_with_image_body(builder: &ScheduleBuilder, image: &ScheduleImage) -> Result<Value> {
+    image.validate()?;
+    let mut schedule = serde_json::to_value(builder)?;
+    schedule["cover_image"] = json!(format!(
+        "data:{};base64,{}",
+        image.content_type,
+        base64::engine::general_purpose::STANDARD.encode(&image.data)
+    ));
+    Ok(json!({ "schedule": schedule }))
+}
+
+/// Request body for subscribing to or unsubscribing from a schedule event.
+fn event_subscription_body(subscribed: bool) -> Value {
+    json!({ "subscribed": subscribed })
+}
+
+/// Reads Open Graph values from `og-*` response headers.
+fn embed_from_headers(url: &str, headers: &reqwest::header::HeaderMap) -> MessageEmbed {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let mut embed = MessageEmbed::new(url);
+    embed.title = header("og-title");
+    embed.description = header("og-description");
+    embed.image_url = header("og-image");
+    embed.site_name = header("og-site-name");
+    embed
+}
+
+/// Maximum number of webhooks deleted at once.
+const WEBHOOK_DELETE_CONCURRENCY: usize = 5;
+
+/// Keeps the webhooks that were older than `older_than` at `now`.
+fn webhooks_older_than(
+    webhooks: Vec<ChannelWebhook>,
+    older_than: Duration,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<ChannelWebhook> {
+    webhooks
+        .into_iter()
+        .filter(|webhook| webhook.age_at(now) > older_than)
+        .collect()
+}
+
+/// Deletes every webhook with `delete`, carrying on past failures.
+async fn delete_webhooks<F, Fut>(
+    webhooks: Vec<ChannelWebhook>,
+    mut delete: F,
+) -> WebhookDeleteReport
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let results = create_each(&webhooks, WEBHOOK_DELETE_CONCURRENCY, |webhook| {
+        delete(webhook.id.clone())
+    })
+    .await;
+    WebhookDeleteReport::new(&webhooks, results)
+}
+
+/// Maximum number of channels a message can be broadcast to.
+const MAX_BROADCAST_CHANNELS: usize = 50;
+
+/// Checks that a broadcast has between 1 and 50 channels.
+fn validate_broadcast_targets(channel_ids: &[&str]) -> Result<()> {
+    if channel_ids.is_empty() {
+        return Err(BotError::invalid_params("No channels to broadcast to"));
+    }
+    if channel_ids.len() > MAX_BROADCAST_CHANNELS {
+        return Err(BotError::invalid_params(format!(
+            "Cannot broadcast to more than {MAX_BROADCAST_CHANNELS} channels, got {}",
+            channel_ids.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Runs `send` for every channel with at most `max_concurrent` in flight,
+/// pairing each channel ID with its result.
+async fn broadcast_to<'a, U, F, Fut>(
+    channel_ids: &[&'a str],
+    max_concurrent: usize,
+    mut send: F,
+) -> Vec<(String, Result<U>)>
+where
+    F: FnMut(&'a str) -> Fut,
+    Fut: Future<Output = Result<U>>,
+{
+    let results = create_each(channel_ids, max_concurrent.max(1), |channel_id| {
+        send(channel_id)
+    })
+    .await;
+    channel_ids
+        .iter()
+        .map(|channel_id| channel_id.to_string())
+        .zip(results)
+        .collect()
+}
+
+/// Maximum number of users a direct message can be sent to at once.
+const MAX_DM_RECIPIENTS: usize = 20;
+
+/// Checks that a bulk direct message has between 1 and 20 recipients.
+fn validate_dm_targets(user_ids: &[&str]) -> Result<()> {
+    if user_ids.is_empty() {
+        return Err(BotError::invalid_params(
+            "No users to send a direct message to",
+        ));
+    }
+    if user_ids.len() > MAX_DM_RECIPIENTS {
+        return Err(BotError::invalid_params(format!(
+            "Cannot send a direct message to more than {MAX_DM_RECIPIENTS} users, got {}",
+            user_ids.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Opens a DM session with every user and sends in it, with at most
+/// `max_concurrent` users in flight, pairing each user ID with its result.
+async fn dm_each<'a, O, OFut, S, SFut>(
+    user_ids: &[&'a str],
+    max_concurrent: usize,
+    open_session: O,
+    send: S,
+) -> Vec<(String, Result<MessageResponse>)>
+where
+    O: Fn(&'a str) -> OFut,
+    OFut: Future<Output = Result<String>>,
+    S: Fn(String) -> SFut,
+    SFut: Future<Output = Result<MessageResponse>>,
+{
+    let (open_session, send) = (&open_session, &send);
+    broadcast_to(user_ids, max_concurrent, |user_id| async move {
+        let dms_guild_id = open_session(user_id).await?;
+        send(dms_guild_id).await
+    })
+    .await
+}
+
+/// Name of the category archived channels are moved into.
+const ARCHIVE_CATEGORY_NAME: &str = "Archive";
+
+/// Prefix added to the names of archived channels.
+const ARCHIVED_NAME_PREFIX: &str = "[ARCHIVED] ";
+
+/// A single change made when archiving a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ArchiveStep {
+    /// Rename the channel
+    Rename(String),
+    /// Create the archive category
+    CreateCategory,
+    /// Move the channel into the archive category
+    MoveToCategory,
+    /// Make the channel visible to admins only
+    MakeAdminOnly,
+}
+
+/// Plans the steps to archive a channel, in the order they are applied.
+///
+/// Channels that already carry the archive prefix are not renamed again.
+fn archive_steps(channel: &Channel, category_exists: bool) -> Vec<ArchiveStep> {
+    let name = channel.name.as_deref().unwrap_or_default();
+    let mut steps = Vec::new();
+    if !name.starts_with(ARCHIVED_NAME_PREFIX) {
+        steps.push(ArchiveStep::Rename(format!("{ARCHIVED_NAME_PREFIX}{name}")));
+    }
+    if !category_exists {
+        steps.push(ArchiveStep::CreateCategory);
+    }
+    steps.push(ArchiveStep::MoveToCategory);
+    steps.push(ArchiveStep::MakeAdminOnly);
+    steps
+}
+
+/// Audit log actions that appear in a member's timeline.
+const MEMBER_TIMELINE_ACTIONS: [AuditLogAction; 4] = [
+    AuditLogAction::MemberAdd,
+    AuditLogAction::MemberRemove,
+    AuditLogAction::MemberUpdate,
+    AuditLogAction::MemberBan,
+];
+
+/// Number of messages requested per page when walking back through a channel.
+const MESSAGE_PAGE_SIZE: u32 = 100;
+
+/// Number of entries requested per page when walking back through an audit log.
+const AUDIT_LOG_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of concurrent requests in `get_emoji_usage_stats`.
+const EMOJI_USAGE_CONCURRENCY: usize = 5;
+
+/// Streams messages sent at or after `since`, newest first, requesting each
+/// page before the oldest message seen so far.
+///
+/// Messages without a timestamp are skipped. The stream ends after a page
+/// reaches back past `since`, comes back short, or after the first error.
+fn paginate_messages_since<F, Fut>(
+    since: chrono::DateTime<chrono::Utc>,
+    page_size: u32,
+    fetch: F,
+) -> impl Stream<Item = Result<Message>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Vec<Message>>>,
+{
+    let state = (fetch, Some(None), std::collections::VecDeque::new());
+    futures_util::stream::unfold(state, move |(mut fetch, mut next, mut buffer)| async move {
+        loop {
+            if let Some(message) = buffer.pop_front() {
+                return Some((Ok(message), (fetch, next, buffer)));
+            }
+            let before = next.take()?;
+            match fetch(before).await {
+                Ok(page) => {
+                    let full = page.len() >= page_size as usize;
+                    let reached_since = page
+                        .iter()
+                        .any(|message: &Message| message.timestamp.is_some_and(|t| t < since));
+                    if full && !reached_since {
+                        next = page.last().and_then(|message| message.id.clone()).map(Some);
+                    }
+                    buffer.extend(
+                        page.into_iter()
+                            .filter(|message| message.timestamp.is_some_and(|t| t >= since)),
+                    );
+                }
+                Err(e) => return Some((Err(e), (fetch, None, buffer))),
+            }
+        }
+    })
+}
+
+/// Collects the audit log entries created at or after `since`, newest first,
+/// requesting each page before the oldest entry seen so far.
+///
+/// Entries without a creation time are skipped. Paging stops after a page
+/// reaches back past `since`, comes back short, or its oldest entry has no ID.
+async fn collect_audit_log_since<F, Fut>(
+    since: chrono::DateTime<chrono::Utc>,
+    page_size: u32,
+    mut fetch: F,
+) -> Result<Vec<AuditLogEntry>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Vec<AuditLogEntry>>>,
+{
+    let mut entries = Vec::new();
+    let mut before = None;
+    loop {
+        let page = fetch(before).await?;
+        let full = page.len() >= page_size as usize;
+        let reached_since = page
+            .iter()
+            .any(|entry| entry.created_at.is_some_and(|t| t < since));
+        before = page.last().and_then(|entry| entry.id.clone());
+        entries.extend(
+            page.into_iter()
+                .filter(|entry| entry.created_at.is_some_and(|t| t >= since)),
+        );
+        if !full || reached_since || before.is_none() {
+            return Ok(entries);
+        }
+    }
+}
+
+/// Pages back through an audit log until it has yielded `limit` events about
+/// a member or runs out, and returns every entry fetched.
+async fn collect_member_audit_log<F, Fut>(
+    guild_id: &str,
+    user_id: &str,
+    limit: u32,
+    page_size: u32,
+    mut fetch: F,
+) -> Result<Vec<AuditLogEntry>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Vec<AuditLogEntry>>>,
+{
+    let mut entries = Vec::new();
+    let mut found = 0;
+    let mut before = None;
+    loop {
+        let page = fetch(before).await?;
+        let full = page.len() >= page_size as usize;
+        before = page.last().and_then(|entry| entry.id.clone());
+        found += MemberEvent::timeline(guild_id, user_id, &page).len();
+        entries.extend(page);
+        if found >= limit as usize || !full || before.is_none() {
+            return Ok(entries);
+        }
+    }
+}
+
+/// Formats messages for `export_channel_messages`.
+fn format_messages(messages: &[Message], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Csv => messages_to_csv(messages),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(messages)?),
+        ExportFormat::Markdown => Ok(messages_to_markdown(messages)),
+    }
+}
+
+/// Writes messages as CSV, one row per message under a header row.
+fn messages_to_csv(messages: &[Message]) -> Result<String> {
+    let csv_error = |e: csv::Error| BotError::internal(format!("failed to write CSV: {e}"));
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(["id", "timestamp", "author_id", "username", "content"])
+        .map_err(csv_error)?;
+    for message in messages {
+        let author = message.author.as_ref();
+        writer
+            .write_record([
+                message.id.clone().unwrap_or_default(),
+                message
+                    .timestamp
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_default(),
+                author.and_then(|a| a.id.clone()).unwrap_or_default(),
+                author.and_then(|a| a.username.clone()).unwrap_or_default(),
+                message.content.clone().unwrap_or_default(),
+            ])
+            .map_err(csv_error)?;
+    }
+    let data = writer
+        .into_inner()
+        .map_err(|e| BotError::internal(format!("failed to write CSV: {e}")))?;
+    String::from_utf8(data).map_err(|e| BotError::internal(format!("invalid CSV output: {e}")))
+}
+
+/// Writes messages as a Markdown log with one line per message.
+fn messages_to_markdown(messages: &[Message]) -> String {
+    let mut log = String::from("# Message history\n\n");
+    for message in messages {
+        let timestamp = message
+            .timestamp
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "unknown time".to_string());
+        let username = message
+            .author
+            .as_ref()
+            .and_then(|a| a.username.as_deref().or(a.id.as_deref()))
+            .unwrap_or("unknown user");
+        let content = message.content.as_deref().unwrap_or_default();
+        log.push_str(&format!("- `{timestamp}` **{username}**: {content}\n"));
+    }
+    log
+}
+
+/// Default name of the channel created by `create_mod_log_channel`.
+pub(crate) const MOD_LOG_CHANNEL_NAME: &str = "mod-log";
+
+/// Header pinned in a newly created mod log channel.
+const MOD_LOG_HEADER: &str = "This channel records moderation actions taken in this guild. \
+     Only admins can view or post here.";
+
+/// Builds the parameters for an embed-only message.
+fn embed_params(embed: Embed, reply_to: Option<&str>) -> MessageParams {
+    MessageParams {
+        embed: Some(embed),
+        msg_id: reply_to.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+/// Number of boost events considered by [`BotApi::get_current_boosters`].
+///
+/// A user whose last boost event is older than this many events is not
+/// counted as a booster, so very large guilds may be undercounted.
+pub const BOOST_HISTORY_LIMIT: u32 = 1000;
+
+/// Number of posts requested per page in `get_thread_participants`.
+const THREAD_POST_PAGE_SIZE: u32 = 20;
+
+/// Maximum number of posts whose replies are fetched at once.
+const THREAD_REPLY_CONCURRENCY: usize = 5;
+
+/// Maximum number of schedule channels queried at once.
+const SCHEDULE_CHANNEL_CONCURRENCY: usize = 5;
+
+/// Maximum number of messages pinned in one bulk request.
+const MAX_BULK_PINS: usize = 50;
+
+/// Maximum number of pin requests in flight at once.
+const PIN_CONCURRENCY: usize = 5;
+
+/// Returns an error unless at most 50 messages are being pinned.
+fn validate_pin_targets(count: usize) -> Result<()> {
+    if count > MAX_BULK_PINS {
+        return Err(BotError::invalid_params(format!(
+            "Cannot pin more than {MAX_BULK_PINS} messages at once, got {count}"
+        )));
+    }
+    Ok(())
+}
+
+/// Counts the successful results.
+fn count_succeeded<T>(results: &[Result<T>]) -> u32 {
+    results.iter().filter(|result| result.is_ok()).count() as u32
+}
+
+/// Longest time the typing indicator is shown before sending a message.
+const MAX_TYPING_DURATION: Duration = Duration::from_secs(10);
+
+/// Runs `trigger`, waits `typing_duration` (capped at 10 seconds) and then
+/// runs `send`. A zero duration skips `trigger` and the wait.
+async fn send_after_typing<T, TFut, S, SFut, U>(
+    typing_duration: Duration,
+    trigger: T,
+    send: S,
+) -> Result<U>
+where
+    T: FnOnce() -> TFut,
+    TFut: Future<Output = Result<()>>,
+    S: FnOnce() -> SFut,
+    SFut: Future<Output = Result<U>>,
+{
+    if !typing_duration.is_zero() {
+        if let Err(e) = trigger().await {
+            warn!("Failed to trigger typing: {}", e);
+        }
+        tokio::time::sleep(typing_duration.min(MAX_TYPING_DURATION)).await;
+    }
+    send().await
+}
+
+/// Fetches each message with `fetch` and re-sends it with `send`, one at a
+/// time, crediting the original author.
+async fn copy_each<'a, F, FFut, S, SFut>(
+    message_ids: &[&'a str],
+    mut fetch: F,
+    mut send: S,
+) -> Vec<Result<MessageResponse>>
+where
+    F: FnMut(&'a str) -> FFut,
+    FFut: Future<Output = Result<Message>>,
+    S: FnMut(MessageParams) -> SFut,
+    SFut: Future<Output = Result<MessageResponse>>,
+{
+    let mut results = Vec::with_capacity(message_ids.len());
+    for message_id in message_ids {
+        let result = match fetch(message_id).await {
+            Ok(message) => match MessageParams::copy_from(&message) {
+                Ok(params) => send(params).await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        results.push(result);
+    }
+    results
+}
+
+/// Parses a message's revisions, ordered oldest first.
+fn parse_revisions(response: Value) -> Result<Vec<MessageRevision>> {
+    let mut revisions: Vec<MessageRevision> = serde_json::from_value(response)?;
+    revisions.sort_by_key(|revision| revision.revision);
+    Ok(revisions)
+}
+
+/// Builds the request body for starting a group thread.
+fn group_thread_body(name: &str, source_msg_id: &str) -> Result<Value> {
+    if name.trim().is_empty() {
+        return Err(BotError::invalid_params("thread name is empty"));
+    }
+    Ok(json!({ "name": name, "source_msg_id": source_msg_id }))
+}
+
+/// Sends `content` as a post in a thread with `put`, which is given the
+/// request path and body, and fills in the post's channel and thread when the
+/// response leaves them out.
+async fn put_thread_post<F, Fut>(
+    channel_id: &str,
+    thread_id: &str,
+    content: &ForumContent,
+    put: F,
+) -> Result<ForumPost>
+where
+    F: FnOnce(String, Value) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let path = format!("/channels/{channel_id}/threads/{thread_id}/posts");
+    let response = put(path, content.to_value()).await?;
+
+    let mut post = ForumPost::new(&response);
+    post.channel_id
+        .get_or_insert_with(|| channel_id.to_string());
+    post.thread_id.get_or_insert_with(|| thread_id.to_string());
+    Ok(post)
+}
+
+/// Maximum number of concurrent mute requests.
+const MUTE_BATCH_SIZE: usize = 5;
+
+/// Runs `f` over `items` in concurrent batches, sleeping `delay` between batches.
+async fn run_in_batches<'a, F, Fut>(
+    items: &'a [String],
+    batch_size: usize,
+    delay: std::time::Duration,
+    f: F,
+) -> Vec<Result<()>>
+where
+    F: Fn(&'a str) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut results = Vec::with_capacity(items.len());
+    for (i, batch) in items.chunks(batch_size).enumerate() {
+        if i > 0 {
+            tokio::time::sleep(delay).await;
+        }
+        let batch_results =
+            futures_util::future::join_all(batch.iter().map(|item| f(item.as_str()))).await;
+        results.extend(batch_results);
+    }
+    results
+}
+
+/// Builds a channel update body that changes only the parent category.
+fn channel_parent_body(current: &Channel, parent_id: Option<&str>) -> Value {
+    let mut body = json!({ "parent_id": parent_id });
+    if let Some(name) = &current.name {
+        body["name"] = json!(name);
+    }
+    if let Some(position) = current.position {
+        body["position"] = json!(position);
+    }
+    if let Some(private_type) = current.private_type {
+        body["private_type"] = json!(u32::from(private_type));
+    }
+    if let Some(speak_permission) = current.speak_permission {
+        body["speak_permission"] = json!(u32::from(speak_permission));
+    }
+    body
+}
+
+/// Creates something and runs `setup` on it, undoing the creation via
+/// `cleanup` if setup fails.
+///
+/// Cleanup failures are logged; the setup error is always the one returned.
+async fn create_with_cleanup<T, U, R, S, SFut, D, DFut>(
+    create: impl Future<Output = Result<T>>,
+    setup: S,
+    cleanup: D,
+) -> Result<(T, U)>
+where
+    T: Clone,
+    S: FnOnce(T) -> SFut,
+    SFut: Future<Output = Result<U>>,
+    D: FnOnce(T) -> DFut,
+    DFut: Future<Output = Result<R>>,
+{
+    let created = create.await?;
+    match setup(created.clone()).await {
+        Ok(set_up) => Ok((created, set_up)),
+        Err(e) => {
+            if let Err(cleanup_error) = cleanup(created).await {
+                warn!("Failed to clean up after setup error: {}", cleanup_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Gets the ID of a channel that was just created.
+fn created_channel_id(channel: &Channel) -> Result<String> {
+    channel
+        .id
+        .clone()
+        .ok_or_else(|| BotError::invalid_data("created channel has no ID"))
+}
+
+/// Builds the request body for uploading raw file bytes.
+fn file_bytes_body(file_type: u32, data: &[u8], filename: &str) -> Value {
+    json!({
+        "file_type": file_type,
+        "file_data": base64::engine::general_purpose::STANDARD.encode(data),
+        "file_name": filename,
+        "srv_send_msg": false
+    })
+}
+
+/// Suffix appended to a cloned channel's name when no new name is given.
+const CHANNEL_COPY_SUFFIX: &str = " (copy)";
+
+/// The settings copied from a channel by `clone_channel`.
+#[derive(Debug, PartialEq)]
+struct ChannelClone<'a> {
+    name: String,
+    channel_type: ChannelType,
+    sub_type: ChannelSubType,
+    position: Option<u32>,
+    parent_id: Option<&'a str>,
+    private_type: Option<u32>,
+    speak_permission: Option<u32>,
+}
+
+impl<'a> ChannelClone<'a> {
+    fn from_source(source: &'a Channel, new_name: Option<&str>, same_guild: bool) -> Result<Self> {
+        let channel_type = source
+            .channel_type
+            .ok_or_else(|| BotError::invalid_data("source channel has no type"))?;
+        let name = match new_name {
+            Some(name) => name.to_string(),
+            None => format!(
+                "{}{CHANNEL_COPY_SUFFIX}",
+                source.name.as_deref().unwrap_or_default()
+            ),
+        };
+        Ok(Self {
+            name,
+            channel_type,
+            sub_type: source.sub_type.unwrap_or(ChannelSubType::Talk),
+            position: source.position.and_then(|p| u32::try_from(p).ok()),
+            parent_id: source.parent_id.as_deref().filter(|_| same_guild),
+            private_type: source.private_type.map(u32::from),
+            speak_permission: source.speak_permission.map(u32::from),
+        })
+    }
+}
+
+/// Builds the request body for creating a guild template.
+fn guild_template_body(name: &str, description: Option<&str>) -> Result<Value> {
+    guild_template_update_body(Some(name), description)
+}
+
+/// Builds the request body for updating a guild template, leaving out
+/// fields that are `None`.
+fn guild_template_update_body(name: Option<&str>, description: Option<&str>) -> Result<Value> {
+    let mut body = json!({});
+    if let Some(name) = name {
+        let length = name.chars().count();
+        if length == 0 || length > GuildTemplate::MAX_NAME_LENGTH {
+            return Err(BotError::invalid_params(format!(
+                "template name must be 1-{} characters, got {length}",
+                GuildTemplate::MAX_NAME_LENGTH
+            )));
+        }
+        body["name"] = json!(name);
+    }
+    if let Some(description) = description {
+        body["description"] = json!(description);
+    }
+    Ok(body)
+}
+
+/// Builds the request body for syncing a template with its guild's state.
+fn guild_template_sync_body(
+    guild: &Guild,
+    channels: &[Channel],
+    roles: &[GuildRole],
+) -> Result<Value> {
+    Ok(json!({
+        "source_guild": {
+            "name": guild.name,
+            "description": guild.description,
+            "channels": serde_json::to_value(channels)?,
+            "roles": serde_json::to_value(roles)?,
+        }
+    }))
+}
+
+/// Builds the request body for creating a guild from a template.
+fn guild_from_template_body(template_code: &str, name: &str, icon: Option<&[u8]>) -> Value {
+    let mut body = json!({ "template_code": template_code, "name": name });
+    if let Some(icon) = icon {
+        body["icon"] = json!(base64::engine::general_purpose::STANDARD.encode(icon));
+    }
+    body
+}
+
+/// Builds the path for the global commands of the token's application.
+fn global_commands_path(token: &Token) -> String {
+    format!("/applications/{}/commands", token.app_id())
+}
+
+/// Builds the path for a single guild template.
+fn guild_template_path(guild_id: &str, template_code: &str) -> String {
+    format!("/guilds/{guild_id}/templates/{template_code}")
+}
+
+/// Returns an error unless `name` is a valid custom emoji name.
+fn validate_emoji_name(name: &str) -> Result<()> {
+    if GuildEmoji::is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(BotError::invalid_params(format!(
+            "emoji name must be 2-32 letters, digits or underscores, got {name:?}"
+        )))
+    }
+}
+
+/// Returns an error unless `code` is a valid vanity invite code.
+fn validate_vanity_code(code: &str) -> Result<()> {
+    if VanityUrl::is_valid_code(code) {
+        Ok(())
+    } else {
+        Err(BotError::invalid_params(format!(
+            "vanity code must be 2-30 letters, digits or hyphens, got {code:?}"
+        )))
+    }
+}
+
+/// Builds the request body for uploading a custom emoji.
+fn guild_emoji_body(name: &str, image_data: &[u8]) -> Result<Value> {
+    validate_emoji_name(name)?;
+    Ok(json!({
+        "name": name,
+        "image": base64::engine::general_purpose::STANDARD.encode(image_data),
+    }))
+}
+
+/// Unions role and user channel permissions.
+///
+/// The API only reports granted bits, so there are no denies to subtract.
+fn effective_permissions(
+    role_permissions: &[Permissions],
+    user_permissions: Permissions,
+) -> Permissions {
+    role_permissions
+        .iter()
+        .fold(user_permissions, |acc, &permissions| acc | permissions)
+}
+
+/// Returns true if the roles include the owner or administrator system role,
+/// which hold every permission in every channel.
+fn grants_all_permissions(role_ids: &[String]) -> bool {
+    role_ids
+        .iter()
+        .filter_map(|role_id| GuildAdminLevel::from_role_id(role_id))
+        .any(|level| level <= GuildAdminLevel::Administrator)
+}
+
+/// Builds the path for a role's permissions in a channel.
+fn channel_role_permissions_path(channel_id: &str, role_id: &str) -> String {
+    format!("/channels/{channel_id}/roles/{role_id}/permissions")
+}
+
+/// Returns an error unless the channel is a voice channel.
+fn ensure_voice_channel(channel: &Channel) -> Result<()> {
+    if channel.is_voice() {
+        Ok(())
+    } else {
+        Err(BotError::invalid_channel(format!(
+            "{} is not a voice channel",
+            channel.id.as_deref().unwrap_or("channel")
+        )))
+    }
+}
+
+/// Parses voice channel members from either a bare list or a `members` object.
+fn parse_voice_members(response: Value) -> Result<Vec<VoiceChannelMember>> {
+    let members = match response {
+        Value::Object(mut obj) => obj.remove("members").unwrap_or(Value::Array(vec![])),
+        other => other,
+    };
+    Ok(serde_json::from_value(members)?)
+}
+
+/// Keeps only the member join entries for the given user.
+fn filter_join_entries(entries: Vec<AuditLogEntry>, user_id: &str) -> Vec<AuditLogEntry> {
+    entries
+        .into_iter()
+        .filter(|e| {
+            e.action_type == AuditLogAction::MemberAdd && e.user_id.as_deref() == Some(user_id)
+        })
+        .collect()
+}
+
+/// Builds the request body for updating a channel topic.
+fn channel_topic_body(topic: &str) -> Result<Value> {
+    const MAX_TOPIC_LEN: usize = 200;
+    if topic.chars().count() > MAX_TOPIC_LEN {
+        return Err(BotError::invalid_params(format!(
+            "channel topic must be at most {MAX_TOPIC_LEN} characters"
+        )));
+    }
+    Ok(json!({ "topic": topic }))
+}
+
+/// Builds the request body for changing a channel's age gate.
+fn channel_nsfw_body(nsfw: bool) -> Value {
+    json!({ "nsfw": nsfw })
+}
+
+/// Builds the request body for creating a channel access invite.
+fn channel_access_invite_body(
+    user_id: &str,
+    duration_seconds: u32,
+    reason: Option<&str>,
+) -> Result<Value> {
+    if !(300..=86400).contains(&duration_seconds) {
+        return Err(BotError::invalid_params(format!(
+            "invite duration must be between 300 and 86400 seconds, got {duration_seconds}"
+        )));
+    }
+
+    let mut body = json!({
+        "user_id": user_id,
+        "duration": duration_seconds
+    });
+    if let Some(reason) = reason {
+        body["reason"] = json!(reason);
+    }
+    Ok(body)
+}
+
+/// Resolves channel names to IDs, failing on the first unknown name.
+fn resolve_channel_ids(channels: &[Channel], names: &[&str]) -> Result<Vec<String>> {
+    names
+        .iter()
+        .map(|name| {
+            channels
+                .iter()
+                .find(|c| c.name.as_deref() == Some(*name))
+                .and_then(|c| c.id.clone())
+                .ok_or_else(|| BotError::NotFound(name.to_string()))
+        })
+        .collect()
+}
+
+impl std::fmt::Debug for BotApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BotApi").field("http", &self.http).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpClient;
+
+    #[test]
+    fn test_api_creation() {
+        let http = HttpClient::new(30, false).unwrap();
+        let api = BotApi::new(http);
+        assert!(!api.http().is_sandbox());
+    }
+
+    #[test]
+    fn test_resolve_channel_ids() {
+        let channel = |id: &str, name: &str| Channel {
+            id: Some(id.to_string()),
+            name: Some(name.to_string()),
+            ..Channel::new()
+        };
+        let channels = vec![channel("1", "general"), channel("2", "news")];
+
+        let ids = resolve_channel_ids(&channels, &["news", "general"]).unwrap();
+        assert_eq!(ids, vec!["2", "1"]);
+
+        match resolve_channel_ids(&channels, &["general", "missing"]) {
+            Err(BotError::NotFound(name)) => assert_eq!(name, "missing"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_channel_topic_body() {
+        assert_eq!(
+            channel_topic_body("Rules and news").unwrap(),
+            json!({"topic": "Rules and news"})
+        );
+        assert!(channel_topic_body(&"话".repeat(200)).is_ok());
+        assert!(matches!(
+            channel_topic_body(&"a".repeat(201)),
+            Err(BotError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_channel_access_invite_body() {
+        assert_eq!(
+            channel_access_invite_body("u1", 300, None).unwrap(),
+            json!({"user_id": "u1", "duration": 300})
+        );
+        assert_eq!(
+            channel_access_invite_body("u1", 86400, Some("event host")).unwrap(),
+            json!({"user_id": "u1", "duration": 86400, "reason": "event host"})
+        );
+        assert!(channel_access_invite_body("u1", 299, None).is_err());
+        assert!(channel_access_invite_body("u1", 86401, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_in_batches() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let items: Vec<String> = (0..12).map(|i| i.to_string()).collect();
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let results = run_in_batches(&items, MUTE_BATCH_SIZE, std::time::Duration::ZERO, |item| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if item == "7" {
+                    Err(BotError::Forbidden(item.to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(results.len(), 12);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), MUTE_BATCH_SIZE);
+        assert!(matches!(&results[7], Err(BotError::Forbidden(id)) if id == "7"));
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 11);
+    }
+
+    #[test]
+    fn test_filter_join_entries() {
+        let entries: Vec<AuditLogEntry> = serde_json::from_value(json!([
+            {"id": "1", "user_id": "u1", "action_type": 1},
+            {"id": "2", "user_id": "u1", "action_type": 2},
+            {"id": "3", "user_id": "u2", "action_type": 1},
+            {"id": "4", "user_id": "u1", "action_type": 1},
+            {"id": "5", "user_id": "u1", "action_type": 4}
+        ]))
+        .unwrap();
+
+        let ids: Vec<_> = filter_join_entries(entries, "u1")
+            .into_iter()
+            .filter_map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec!["1", "4"]);
+    }
+
+    #[test]
+    fn test_ensure_voice_channel() {
+        let mut channel: Channel = serde_json::from_value(json!({"id": "c1", "type": 2})).unwrap();
+        assert!(ensure_voice_channel(&channel).is_ok());
+
+        channel.channel_type = Some(ChannelType::Text);
+        assert!(matches!(
+            ensure_voice_channel(&channel),
+            Err(BotError::InvalidChannel(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_voice_members() {
+        let member = json!({
+            "user": {"id": "u1"},
+            "joined_at": "2024-01-01T00:00:00Z",
+            "is_self_deafened": true
+        });
+
+        let members = parse_voice_members(json!([member.clone()])).unwrap();
+        assert_eq!(members.len(), 1);
+        assert!(members[0].is_self_deafened);
+
+        let members = parse_voice_members(json!({"members": [member]})).unwrap();
+        assert_eq!(members[0].user.id.as_deref(), Some("u1"));
+        assert!(parse_voice_members(json!({})).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_channel_parent_body_preserves_fields() {
+        let current: Channel = serde_json::from_value(json!({
+            "id": "c1",
+            "name": "general",
+            "position": 3,
+            "parent_id": "old",
+            "private_type": 1,
+            "speak_permission": 2
+        }))
+        .unwrap();
+
+        let body = channel_parent_body(&current, Some("cat1"));
+        assert_eq!(
+            body,
+            json!({
+                "name": "general",
+                "position": 3,
+                "parent_id": "cat1",
+                "private_type": 1,
+                "speak_permission": 2
+            })
+        );
+
+        let body = channel_parent_body(&current, None);
+        assert_eq!(body["parent_id"], Value::Null);
+        assert_eq!(body["name"], "general");
+    }
+
+    #[tokio::test]
+    async fn test_collect_guild_pages() {
+        let guild =
+            |id: u32| -> Guild { serde_json::from_value(json!({"id": id.to_string()})).unwrap() };
+        let mut requested = Vec::new();
+
+        let guilds = collect_guild_pages(2, |after| {
+            requested.push(after.clone());
+            let page = match after.as_deref() {
+                None => vec![guild(1), guild(2)],
+                Some("2") => vec![guild(3), guild(4)],
+                Some("4") => vec![guild(5)],
+                _ => vec![],
+            };
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(guilds.len(), 5);
+        assert_eq!(
+            requested,
+            vec![None, Some("2".to_string()), Some("4".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_map_concurrent_limits_in_flight() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let results = map_concurrent((0..25).collect(), 10, |i: u32| {
+            let in_flight = &in_flight;
+            let peak = &peak;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(i * 2)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results, (0..25).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn test_guild_emoji_body() {
+        let body = guild_emoji_body("wave", b"\x89PNG").unwrap();
+        assert_eq!(body["name"], "wave");
+        assert_eq!(body["image"], "iVBORw==");
+
+        assert!(matches!(
+            guild_emoji_body("no spaces", b"x"),
+            Err(BotError::InvalidParams(_))
+        ));
+        assert!(validate_emoji_name("x").is_err());
+    }
+
+    #[test]
+    fn test_ensure_known_region() {
+        let regions: Vec<VoiceRegion> = serde_json::from_value(json!([
+            {"id": "sh", "name": "Shanghai", "optimal": true},
+            {"id": "gz", "name": "Guangzhou", "deprecated": true}
+        ]))
+        .unwrap();
+
+        assert!(ensure_known_region(&regions, "sh").is_ok());
+        assert!(ensure_known_region(&regions, "gz").is_ok());
+        assert!(matches!(
+            ensure_known_region(&regions, "bj"),
+            Err(BotError::InvalidParams(_))
+        ));
+        assert!(ensure_known_region(&[], "sh").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_everyone_channel_permissions() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|_| MockResponse::ok(json!({}))).await;
+        server
+            .api()
+            .update_everyone_channel_permissions(
+                &MockServer::token(),
+                "c1",
+                "g1",
+                Permissions::SEND_MESSAGES,
+                Permissions::LIVE,
+            )
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].path, "/channels/c1/roles/g1/permissions");
+        assert_eq!(requests[0].body, json!({"add": "4", "remove": "8"}));
+    }
+
+    #[tokio::test]
+    async fn test_compare_channel_permissions() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|req| {
+            let permissions = |value: &str| MockResponse::ok(json!({"permissions": value}));
+            match req.path.as_str() {
+                "/channels/a" | "/channels/b" => MockResponse::ok(json!({"guild_id": "g1"})),
+                "/guilds/g1/roles" => {
+                    MockResponse::ok(json!({"roles": [{"id": "r1"}, {"id": "r2"}]}))
+                }
+                "/guilds/g1/members" => MockResponse::ok(json!([
+                    {"user": {"id": "u1", "username": "a"}},
+                    {"user": {"id": "u2", "username": "b"}}
+                ])),
+                "/channels/a/roles/r2/permissions" => permissions("2"),
+                "/channels/b/roles/r2/permissions" => permissions("0"),
+                "/channels/b/members/u1/permissions" => permissions("5"),
+                _ => permissions("1"),
+            }
+        })
+        .await;
+        let diff = server
+            .api()
+            .compare_channel_permissions(&MockServer::token(), "a", "b")
+            .await
+            .unwrap();
+
+        assert_eq!(diff.channel_id, "b");
+        assert_eq!(diff.changed_roles.len(), 1);
+        assert_eq!(diff.changed_roles[0].role_id, "r2");
+        assert_eq!(diff.changed_roles[0].removed(), Permissions::MANAGE_CHANNEL);
+        assert_eq!(diff.changed_users.len(), 1);
+        let change = &diff.changed_users[0];
+        assert_eq!(
+            (change.channel_id.as_str(), change.user_id.as_str()),
+            ("b", "u1")
+        );
+        assert_eq!(change.added(), Permissions::SEND_MESSAGES);
+        assert!(change.removed().is_empty());
+    }
+
+    #[test]
+    fn test_effective_permissions() {
+        let roles = [Permissions::VIEW_CHANNEL, Permissions::SEND_MESSAGES];
+        assert_eq!(
+            effective_permissions(&roles, Permissions::LIVE),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::LIVE
+        );
+        assert_eq!(
+            effective_permissions(&[], Permissions::NONE),
+            Permissions::NONE
+        );
+
+        let role_ids =
+            |ids: &[&str]| -> Vec<String> { ids.iter().map(|id| id.to_string()).collect() };
+        assert!(grants_all_permissions(&role_ids(&["1", "2"])));
+        assert!(grants_all_permissions(&role_ids(&["4"])));
+        assert!(!grants_all_permissions(&role_ids(&["1", "5", "10"])));
+        assert!(Permissions::ALL.contains(Permissions::MANAGE_CHANNEL | Permissions::LIVE));
+        assert_eq!(Permissions::ALL.bits, 0b1111);
+    }
+
+    #[test]
+    fn test_channel_clone() {
+        use crate::models::channel::{PrivateType, SpeakPermission};
+
+        let mut source = Channel::new();
+        source.name = Some("general".to_string());
+        source.channel_type = Some(ChannelType::Text);
+        source.sub_type = Some(ChannelSubType::Post);
+        source.position = Some(3);
+        source.parent_id = Some("cat1".to_string());
+        source.private_type = Some(PrivateType::AdminOnly);
+        source.speak_permission = Some(SpeakPermission::Everyone);
+
+        let clone = ChannelClone::from_source(&source, None, true).unwrap();
+        assert_eq!(
+            clone,
+            ChannelClone {
+                name: "general (copy)".to_string(),
+                channel_type: ChannelType::Text,
+                sub_type: ChannelSubType::Post,
+                position: Some(3),
+                parent_id: Some("cat1"),
+                private_type: Some(u32::from(PrivateType::AdminOnly)),
+                speak_permission: Some(u32::from(SpeakPermission::Everyone)),
+            }
+        );
+
+        let clone = ChannelClone::from_source(&source, Some("archive"), false).unwrap();
+        assert_eq!(clone.name, "archive");
+        assert_eq!(clone.parent_id, None);
+
+        source.channel_type = None;
+        assert!(ChannelClone::from_source(&source, None, true).is_err());
+    }
+
+    #[test]
+    fn test_not_found_as_none() {
+        use crate::error::http_error_from_status;
+
+        assert_eq!(not_found_as_none(Ok(1)).unwrap(), Some(1));
+        let missing: Result<u32> = Err(http_error_from_status(404, "member not found".into()));
+        assert_eq!(not_found_as_none(missing).unwrap(), None);
+        let failed: Result<u32> = Err(http_error_from_status(500, "server error".into()));
+        assert!(matches!(
+            not_found_as_none(failed),
+            Err(BotError::Server(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_with_cleanup() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let created = || async {
+            let mut channel = Channel::new();
+            channel.id = Some("c1".to_string());
+            Ok(channel)
+        };
+        let setups = &AtomicU32::new(0);
+        let cleanups = &AtomicU32::new(0);
+        let setup_ok = |_: Channel| async {
+            setups.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        };
+        let setup_err = |_: Channel| async {
+            setups.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(BotError::Forbidden("no permission".into()))
+        };
+        let cleanup = |channel: Channel| async move {
+            assert_eq!(channel.id.as_deref(), Some("c1"));
+            cleanups.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        };
+
+        let (channel, ()) = create_with_cleanup(created(), setup_ok, cleanup)
+            .await
+            .unwrap();
+        assert_eq!(channel.id.as_deref(), Some("c1"));
+        assert_eq!(cleanups.load(Ordering::SeqCst), 0);
+
+        let result = create_with_cleanup(created(), setup_err, cleanup).await;
+        assert!(matches!(result, Err(BotError::Forbidden(_))));
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+
+        let failed_create = async { Err(BotError::invalid_params("bad name")) };
+        let result = create_with_cleanup(failed_create, setup_ok, cleanup).await;
+        assert!(matches!(result, Err(BotError::InvalidParams(_))));
+        assert_eq!(setups.load(Ordering::SeqCst), 2);
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+
+        let failed_cleanup = |_: Channel| async { Err::<(), _>(BotError::Server("down".into())) };
+        let result = create_with_cleanup(created(), setup_err, failed_cleanup).await;
+        assert!(matches!(result, Err(BotError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_filter_concurrent() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = &AtomicUsize::new(0);
+        let peak = &AtomicUsize::new(0);
+        let kept = filter_concurrent((0..25).collect(), 10, |&n: &u32| async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(n % 3 == 0)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(kept, vec![0, 3, 6, 9, 12, 15, 18, 21, 24]);
+        assert_eq!(peak.load(Ordering::SeqCst), 10);
+
+        let failed = filter_concurrent(vec![1, 2], 10, |_| async {
+            Err(BotError::Server("down".into()))
+        })
+        .await;
+        assert!(failed.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_roles() {
+        use std::sync::Mutex;
+
+        let current = vec!["1".to_string(), "5".to_string(), "10".to_string()];
+        let calls = &Mutex::new(Vec::new());
+        let add = |role: &str| {
+            calls.lock().unwrap().push(format!("+{role}"));
+            let result = if role == "bad" {
+                Err(BotError::Forbidden("no".into()))
+            } else {
+                Ok(())
+            };
+            async move { result }
+        };
+        let remove = |role: &str| {
+            calls.lock().unwrap().push(format!("-{role}"));
+            async { Ok(()) }
+        };
+
+        let result = sync_roles(&current, &["1", "10", "11", "bad"], add, remove).await;
+        assert_eq!(result.added, vec!["11"]);
+        assert_eq!(result.removed, vec!["5"]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, "bad");
+        assert!(!result.is_success());
+
+        let mut made = std::mem::take(&mut *calls.lock().unwrap());
+        made.sort();
+        assert_eq!(made, vec!["+11", "+bad", "-5"]);
+
+        let result = sync_roles(&current, &["1", "5", "10"], add, remove).await;
+        assert!(result.is_success());
+        assert!(result.added.is_empty() && result.removed.is_empty());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_guild_template_requests() {
+        assert_eq!(
+            guild_template_body("Community", Some("Starter layout")).unwrap(),
+            json!({"name": "Community", "description": "Starter layout"})
+        );
+        assert_eq!(
+            guild_template_body("Community", None).unwrap(),
+            json!({"name": "Community"})
+        );
+        assert!(guild_template_body("", None).is_err());
+        assert!(guild_template_body(&"x".repeat(101), None).is_err());
+
+        assert_eq!(
+            guild_template_path("g1", "abc123"),
+            "/guilds/g1/templates/abc123"
+        );
+
+        assert_eq!(
+            guild_template_update_body(None, Some("Updated")).unwrap(),
+            json!({"description": "Updated"})
+        );
+        assert_eq!(guild_template_update_body(None, None).unwrap(), json!({}));
+        assert!(guild_template_update_body(Some(""), None).is_err());
+
+        assert_eq!(
+            guild_from_template_body("abc123", "New Guild", Some(b"png")),
+            json!({"template_code": "abc123", "name": "New Guild", "icon": "cG5n"})
+        );
+        assert_eq!(
+            guild_from_template_body("abc123", "New Guild", None),
+            json!({"template_code": "abc123", "name": "New Guild"})
+        );
+    }
+
+    #[test]
+    fn test_guild_template_sync_body() {
+        let mut guild = Guild::new();
+        guild.name = Some("Community".to_string());
+        guild.description = Some("Friendly place".to_string());
+        let mut channel = Channel::new();
+        channel.id = Some("c1".to_string());
+        channel.name = Some("general".to_string());
+        let role = GuildRole {
+            id: Some("10".to_string()),
+            name: Some("Helper".to_string()),
+            ..GuildRole::new()
+        };
+
+        let body = guild_template_sync_body(&guild, &[channel], &[role]).unwrap();
+        let source = &body["source_guild"];
+        assert_eq!(source["name"], "Community");
+        assert_eq!(source["description"], "Friendly place");
+        assert_eq!(source["channels"][0]["id"], "c1");
+        assert_eq!(source["channels"][0]["name"], "general");
+        assert_eq!(source["roles"][0]["id"], "10");
+        assert_eq!(source["roles"][0]["name"], "Helper");
+    }
+
+    #[test]
+    fn test_file_bytes_body() {
+        assert_eq!(
+            file_bytes_body(1, b"png", "cat.png"),
+            json!({
+                "file_type": 1,
+                "file_data": "cG5n",
+                "file_name": "cat.png",
+                "srv_send_msg": false
+            })
+        );
+    }
+
+    fn test_member(id: &str, roles: &[&str]) -> Member {
+        serde_json::from_value(json!({
+            "user": {"id": id, "username": format!("user {id}")},
+            "nick": null,
+            "roles": roles,
+            "joined_at": "2024-03-01T12:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_members_to_csv() {
+        let members = vec![test_member("1", &["4", "11"]), test_member("2", &[])];
+        let csv = members_to_csv(
+            &members,
+            &[MemberField::Roles, MemberField::UserId, MemberField::Nick],
+        )
+        .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines, vec!["roles,user_id,nick", "\"4,11\",1,", ",2,"]);
+
+        let csv = members_to_csv(&members, &[MemberField::JoinedAt]).unwrap();
+        assert!(
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("2024-03-01T12:00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_members() {
+        let requested = &std::sync::Mutex::new(Vec::new());
+        let stream = paginate_members(2, |after| {
+            requested.lock().unwrap().push(after.clone());
+            let page = match after.as_str() {
+                "0" => vec![test_member("1", &[]), test_member("2", &[])],
+                "2" => vec![test_member("3", &[])],
+                _ => panic!("requested page after {after} past the end"),
+            };
+            async move { Ok(page) }
+        });
+
+        let members: Vec<Member> = stream.try_collect().await.unwrap();
+        let ids: Vec<&str> = members
+            .iter()
+            .map(|m| m.user.as_ref().unwrap().id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(*requested.lock().unwrap(), vec!["0", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_audit_passes() {
+        use crate::models::message::AuditStatus;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let polls = &AtomicU32::new(0);
+        let fetch = || async move {
+            let n = polls.fetch_add(1, Ordering::SeqCst);
+            Ok(MessageAuditStatus {
+                audit_id: "a1".to_string(),
+                message_id: (n >= 2).then(|| "m1".to_string()),
+                status: if n >= 2 {
+                    AuditStatus::Passed
+                } else {
+                    AuditStatus::Pending
+                },
+                reason: None,
+            })
+        };
+        let done = |status: &MessageAuditStatus| !status.is_pending();
+
+        let status = poll_until(
+            fetch,
+            done,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status.status, AuditStatus::Passed);
+        assert_eq!(status.message_id.as_deref(), Some("m1"));
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+
+        let never = || async { Ok(0) };
+        let result = poll_until(
+            never,
+            |&n: &u32| n > 0,
+            Duration::from_millis(1),
+            Duration::from_millis(20),
+        )
+        .await;
+        assert!(matches!(result, Err(BotError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_apply_permission_batches_partial_failure() {
+        use crate::models::permission::PermissionBatchReport;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let batches = vec![
+            ChannelPermissionBatch {
+                channel_id: "c1".to_string(),
+                entries: vec![
+                    PermissionEntry::role("r1", Permissions::VIEW_CHANNEL, Permissions::NONE),
+                    PermissionEntry::user("u1", Permissions::NONE, Permissions::SEND_MESSAGES),
+                ],
+            },
+            ChannelPermissionBatch {
+                channel_id: "c2".to_string(),
+                entries: vec![
+                    PermissionEntry::role("r1", Permissions::VIEW_CHANNEL, Permissions::NONE),
+                    PermissionEntry::user("u2", Permissions::VIEW_CHANNEL, Permissions::NONE),
+                ],
+            },
+            ChannelPermissionBatch {
+                channel_id: "c3".to_string(),
+                entries: Vec::new(),
+            },
+        ];
+        let calls = &AtomicU32::new(0);
+
+        let results =
+            apply_permission_batches(batches, Duration::ZERO, |channel_id, entry| async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                if channel_id == "c2" && entry.target == PermissionTarget::User("u2".to_string()) {
+                    Err(BotError::api(403, "no permission"))
+                } else {
+                    Ok(())
+                }
+            })
+            .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        let ids: Vec<_> = results
+            .iter()
+            .map(|(id, r)| (id.as_str(), r.len()))
+            .collect();
+        assert_eq!(ids, vec![("c1", 2), ("c2", 2), ("c3", 0)]);
+        assert!(results[1].1[0].is_ok());
+        assert!(results[1].1[1].is_err());
+
+        let report = PermissionBatchReport::from(results);
+        assert_eq!(report.success_rate(), 0.75);
+        assert_eq!(report.failed_channels(), vec!["c2"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spawn_reminder_waits_for_delay() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&sent);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        let handle = spawn_reminder(
+            Duration::from_secs(600),
+            vec!["u1".to_string(), "u2".to_string(), "u3".to_string()],
+            move |user_id| {
+                let recorded = Arc::clone(&recorded);
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    if user_id == "u2" {
+                        return Err(BotError::api(403, "blocked"));
+                    }
+                    recorded.lock().unwrap().push(user_id);
+                    Ok(())
+                }
+            },
+        );
+
+        tokio::time::sleep(Duration::from_secs(599)).await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        handle.await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(*sent.lock().unwrap(), vec!["u1", "u3"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spawn_reminder_cancel() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        let handle = spawn_reminder(Duration::from_secs(60), vec!["u1".to_string()], move |_| {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        let reminder = ScheduleWithDmReminder::new(
+            Schedule::new("Raid", "0", "0", None, RemindType::None),
+            &DmReminderConfig::new("g1", vec!["u1".to_string()], 5),
+            handle.abort_handle(),
+        );
+        reminder.cancel();
+        tokio::time::sleep(Duration::from_secs(120)).await;
+
+        assert!(handle.await.unwrap_err().is_cancelled());
+        assert!(reminder.is_finished());
+        assert_eq!(attempts.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_dedup_members() {
+        let owners = vec![test_member("1", &["4", "2"])];
+        let admins = vec![test_member("1", &["4", "2"]), test_member("2", &["2", "5"])];
+        let managers = vec![test_member("2", &["2", "5"]), test_member("3", &["5"])];
+        let ids: Vec<_> = dedup_members(vec![owners, admins, managers])
+            .iter()
+            .map(|m| m.user_id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_channels_by_type() {
+        let channel = |id: &str, channel_type: Option<ChannelType>| {
+            let mut channel = Channel::new();
+            channel.id = Some(id.to_string());
+            channel.channel_type = channel_type;
+            channel
+        };
+        let channels = vec![
+            channel("1", Some(ChannelType::Text)),
+            channel("2", Some(ChannelType::Voice)),
+            channel("3", Some(ChannelType::Text)),
+            channel("4", Some(ChannelType::Discussion)),
+            channel("5", Some(ChannelType::Unknown(99))),
+            channel("6", None),
+        ];
+
+        let counts = channel_type_counts(&channels);
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&ChannelType::Text], 2);
+        assert_eq!(counts[&ChannelType::Voice], 1);
+        assert_eq!(counts[&ChannelType::Discussion], 1);
+        assert_eq!(counts[&ChannelType::Unknown(99)], 1);
+
+        let ids: Vec<_> = channels_of_type(channels.clone(), ChannelType::Text)
+            .into_iter()
+            .filter_map(|c| c.id)
+            .collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert!(channels_of_type(channels, ChannelType::Group).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_reaction_users() {
+        let page = |ids: &[&str], cookie: Option<&str>, is_end: bool| ReactionUsers {
+            users: ids
+                .iter()
+                .map(|id| ReactionUser {
+                    id: Some(id.to_string()),
+                    username: None,
+                    avatar: None,
+                })
+                .collect(),
+            cookie: cookie.map(str::to_string),
+            is_end,
+        };
+        let cookies = std::sync::Mutex::new(Vec::new());
+
+        let users = collect_reaction_users(|cookie| {
+            cookies.lock().unwrap().push(cookie.clone());
+            let result = match cookie.as_deref() {
+                None => page(&["1", "2"], Some("p2"), false),
+                Some("p2") => page(&["3"], Some("p3"), false),
+                _ => page(&["4"], Some("p4"), true),
+            };
+            async move { Ok(result) }
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<_> = users.into_iter().filter_map(|u| u.id).collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4"]);
+        assert_eq!(
+            *cookies.lock().unwrap(),
+            vec![None, Some("p2".to_string()), Some("p3".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_unique_dedups_and_limits() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = &AtomicUsize::new(0);
+        let in_flight = &AtomicUsize::new(0);
+        let peak = &AtomicUsize::new(0);
+        let keys = ["1", "2", "1", "3", "4", "2", "5", "6", "7", "1"]
+            .map(String::from)
+            .to_vec();
+
+        let members = fetch_unique(keys, REACTION_MEMBER_CONCURRENCY, |id: String| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(test_member(&id, &[]))
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<_> = members
+            .iter()
+            .map(|m| m.user_id().unwrap().as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5", "6", "7"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 7);
+        assert_eq!(peak.load(Ordering::SeqCst), REACTION_MEMBER_CONCURRENCY);
+    }
+
+    #[test]
+    fn test_role_sync_plan() {
+        let role = |id: &str, name: &str, color: u32| GuildRole {
+            id: Some(id.to_string()),
+            name: Some(name.to_string()),
+            color: Some(color),
+            hoist: Some(false),
+            ..GuildRole::new()
+        };
+        let current = vec![
+            role("1", "Everyone", 0),
+            role("2", "Admin", 0xFF0000),
+            role("10", "Helper", 0x00FF00),
+            role("11", "Artist", 0x0000FF),
+            role("12", "Legacy", 0),
+        ];
+
+        let plan = RoleSyncPlan::new(
+            &current,
+            vec![
+                RoleSpec::new("Helper").with_color(0x00FF00),
+                RoleSpec::new("Artist").with_color(0xABCDEF),
+                RoleSpec::new("Streamer").with_hoist(true),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.create,
+            vec![RoleSpec::new("Streamer").with_hoist(true)]
+        );
+        assert_eq!(
+            plan.update,
+            vec![(
+                "11".to_string(),
+                RoleSpec::new("Artist").with_color(0xABCDEF)
+            )]
+        );
+        // System roles are kept even when not listed.
+        assert_eq!(plan.delete, vec!["12"]);
+
+        assert!(matches!(
+            RoleSyncPlan::new(&current, vec![RoleSpec::new("A"), RoleSpec::new("A")]),
+            Err(BotError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_global_commands_path() {
+        let token = Token::new("102004", "secret");
+        assert_eq!(
+            global_commands_path(&token),
+            "/applications/102004/commands"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_interaction_pages() {
+        let api = BotApi::new(HttpClient::new(30, false).unwrap());
+        let requests = std::sync::Mutex::new(Vec::new());
+        let acked = std::sync::Mutex::new(Vec::new());
+        let start = tokio::time::Instant::now();
+
+        let stream = poll_interaction_pages(
+            Duration::from_secs(10),
+            |cursor: Option<String>| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push((cursor.clone(), start.elapsed().as_secs()));
+                let data = match cursor.as_deref() {
+                    None => {
+                        json!({"interactions": [{"id": "a"}], "cursor": "c1", "has_more": true})
+                    }
+                    Some("c1") => json!({"interactions": [{"id": "b"}], "cursor": "c2"}),
+                    _ => json!({"interactions": [{"id": "c"}, {"id": "d"}], "cursor": "c3"}),
+                };
+                let page = InteractionPage::new(api.clone(), &data);
+                async move { Ok(page) }
+            },
+            |id: String| {
+                acked.lock().unwrap().push(id.clone());
+                async move {
+                    if id == "c" {
+                        Err(BotError::api(500, "ack failed"))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        );
+        let items: Vec<_> = stream.take(5).collect().await;
+
+        // The failed acknowledgement is reported after its interaction
+        let ids: Vec<_> = items
+            .iter()
+            .map(|item| item.as_ref().ok().and_then(|i| i.id.clone()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                Some("a".into()),
+                Some("b".into()),
+                Some("c".into()),
+                None,
+                Some("d".into())
+            ]
+        );
+        assert!(matches!(items[3], Err(BotError::Api { .. })));
+        assert_eq!(*acked.lock().unwrap(), vec!["a", "b", "c", "d"]);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                (None, 0),
+                (Some("c1".to_string()), 0),
+                (Some("c2".to_string()), 10)
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_measure_region_latency() {
+        let region = |id: &str| -> VoiceRegion {
+            serde_json::from_value(json!({"id": id, "name": id})).unwrap()
+        };
+        let regions = vec![
+            region("slow"),
+            region("down"),
+            region("fast"),
+            region("hung"),
+        ];
+
+        let results = measure_latencies(regions, REGION_PING_TIMEOUT, |region| {
+            let id = region.id.clone();
+            async move {
+                let delay = match id.as_str() {
+                    "fast" => 20,
+                    "slow" => 300,
+                    "down" => return Err(BotError::connection("refused")),
+                    _ => 60_000,
+                };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Ok(())
+            }
+        })
+        .await;
+        assert!(matches!(results[3].1, Err(BotError::Timeout)));
+
+        let ranked = rank_latencies(results).unwrap();
+        let ranked: Vec<_> = ranked
+            .iter()
+            .map(|(region, latency)| (region.id.as_str(), latency.as_millis()))
+            .collect();
+        assert_eq!(ranked, vec![("fast", 20), ("slow", 300)]);
+
+        assert!(matches!(
+            rank_latencies(vec![(region("a"), Err(BotError::Timeout))]),
+            Err(BotError::Timeout)
+        ));
+        assert!(rank_latencies(Vec::new()).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_each_partial_failure() {
+        use crate::models::schedule::ScheduleBulkReport;
+
+        let schedules: Vec<_> = (0..12)
+            .map(|i| ScheduleBuilder::new(format!("event {i}"), "1700000000", "1700003600"))
+            .collect();
+        let results = create_each(&schedules, SCHEDULE_BULK_CONCURRENCY, |schedule| {
+            let name = schedule.name().to_string();
+            async move {
+                if name.ends_with('3') {
+                    Err(BotError::api(500, "busy"))
+                } else {
+                    Ok(Schedule::new(
+                        name,
+                        "1700000000",
+                        "1700003600",
+                        None,
+                        RemindType::None,
+                    ))
+                }
+            }
+        })
+        .await;
+        assert_eq!(results.len(), 12);
+        assert!(results[3].is_err());
+
+        let report = ScheduleBulkReport::new(schedules, results);
+        assert_eq!(report.created.len(), 11);
+        assert_eq!(report.created[3].name, "event 4");
+        let failed: Vec<_> = report.failed.iter().map(|(s, _)| s.name()).collect();
+        assert_eq!(failed, vec!["event 3"]);
+        assert!(!report.is_success());
+    }
+
+    #[test]
+    fn test_event_subscription_request() {
+        assert_eq!(
+            event_subscription_path("c1", "s1"),
+            "/channels/c1/schedules/s1/subscribers/@me"
+        );
+        assert_eq!(event_subscription_body(true), json!({"subscribed": true}));
+        assert_eq!(event_subscription_body(false), json!({"subscribed": false}));
+    }
+
+    #[test]
+    fn test_embed_from_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let mut headers = HeaderMap::new();
+        headers.insert("og-title", HeaderValue::from_static("Release notes"));
+        headers.insert(
+            "og-image",
+            HeaderValue::from_static("https://example.com/a.png"),
+        );
+        headers.insert("og-description", HeaderValue::from_static("  "));
+        headers.insert("content-type", HeaderValue::from_static("text/html"));
+
+        let embed = embed_from_headers("https://example.com/notes", &headers);
+        assert_eq!(embed.url, "https://example.com/notes");
+        assert_eq!(embed.title.as_deref(), Some("Release notes"));
+        assert_eq!(
+            embed.image_url.as_deref(),
+            Some("https://example.com/a.png")
+        );
+        assert!(embed.description.is_none());
+        assert!(embed.site_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_with_cleanup_keeps_setup_result() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let rollbacks = &AtomicU32::new(0);
+        let announce = || async { Ok(Announce::new_message("g1", "c1", "m1")) };
+        let rollback = |announce: Announce| async move {
+            assert_eq!(announce.message_id.as_deref(), Some("m1"));
+            rollbacks.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        };
+
+        let result = create_with_cleanup(
+            announce(),
+            |_| async { Err::<(), _>(BotError::api(403, "no thread permission")) },
+            rollback,
+        )
+        .await;
+        assert!(matches!(result, Err(BotError::Api { code: 403, .. })));
+        assert_eq!(rollbacks.load(Ordering::SeqCst), 1);
+
+        let (announce, thread) = create_with_cleanup(announce(), |_| async { Ok("t1") }, rollback)
+            .await
+            .unwrap();
+        assert_eq!(announce.guild_id.as_deref(), Some("g1"));
+        assert_eq!(thread, "t1");
+        assert_eq!(rollbacks.load(Ordering::SeqCst), 1);
+    }
+
+    fn test_webhook(id: &str, created_at: Option<chrono::DateTime<chrono::Utc>>) -> ChannelWebhook {
+        let mut webhook: ChannelWebhook = serde_json::from_value(json!({ "id": id })).unwrap();
+        webhook.created_at = created_at;
+        webhook
+    }
+
+    #[test]
+    fn test_webhooks_older_than() {
+        let now = chrono::Utc::now();
+        let webhooks = vec![
+            test_webhook("old", Some(now - chrono::Duration::days(40))),
+            test_webhook("new", Some(now - chrono::Duration::days(2))),
+            test_webhook("unknown", None),
+        ];
+        let old = webhooks_older_than(webhooks, Duration::from_secs(30 * 24 * 3600), now);
+        let ids: Vec<_> = old.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(ids, vec!["old"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_delete_webhooks_in_parallel() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let in_flight = &AtomicU32::new(0);
+        let peak = &AtomicU32::new(0);
+        let webhooks = (0..12)
+            .map(|i| test_webhook(&i.to_string(), None))
+            .collect();
+        let report = delete_webhooks(webhooks, |_| async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .await;
+        assert_eq!(report.deleted, 12);
+        assert!(report.is_success());
+        assert_eq!(
+            peak.load(Ordering::SeqCst),
+            WEBHOOK_DELETE_CONCURRENCY as u32
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_webhooks_continues_after_failure() {
+        let webhooks = ["w1", "w2", "w3"]
+            .iter()
+            .map(|id| test_webhook(id, None))
+            .collect();
+        let attempted = &std::sync::Mutex::new(Vec::new());
+        let report = delete_webhooks(webhooks, |webhook_id| {
+            let failed = webhook_id == "w2";
+            attempted.lock().unwrap().push(webhook_id);
+            async move {
+                if failed {
+                    Err(BotError::api(403, "no permission"))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+        assert_eq!(*attempted.lock().unwrap(), vec!["w1", "w2", "w3"]);
+        assert_eq!(report.deleted, 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "w2");
+        assert!(!report.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_partial_failure() {
+        use crate::models::message::BroadcastReport;
+
+        assert!(validate_broadcast_targets(&[]).is_err());
+        assert!(validate_broadcast_targets(&["c1"; MAX_BROADCAST_CHANNELS]).is_ok());
+        assert!(validate_broadcast_targets(&["c1"; MAX_BROADCAST_CHANNELS + 1]).is_err());
+
+        let channel_ids = ["c1", "locked", "c3", "gone"];
+        let results = broadcast_to(&channel_ids, 2, |channel_id| async move {
+            match channel_id {
+                "locked" => Err(BotError::api(403, "no permission")),
+                "gone" => Err(BotError::NotFound("channel".to_string())),
+                _ => Ok(MessageResponse::default()),
+            }
+        })
+        .await;
+        assert_eq!(results.len(), channel_ids.len());
+
+        let report = BroadcastReport::from(results);
+        assert_eq!(report.successful_channels(), vec!["c1", "c3"]);
+        let failed: Vec<_> = report.failed_channels().iter().map(|(id, _)| *id).collect();
+        assert_eq!(failed, vec!["locked", "gone"]);
+    }
+
+    #[test]
+    fn test_poll_backoff() {
+        let base = Duration::from_secs(10);
+        let mut backoff = PollBackoff::new(base);
+        backoff.on_error();
+        backoff.on_error();
+        assert_eq!(backoff.delay, Duration::from_secs(40));
+        for _ in 0..10 {
+            backoff.on_error();
+        }
+        assert_eq!(backoff.delay, base * MAX_BACKOFF_FACTOR);
+
+        backoff.on_success();
+        backoff.on_success();
+        assert_eq!(backoff.delay, base * MAX_BACKOFF_FACTOR);
+        backoff.on_success();
+        assert_eq!(backoff.delay, base);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_audit_log_deduplicates_rounds() {
+        let entry = |id: &str| -> AuditLogEntry {
+            serde_json::from_value(json!({"id": id, "action_type": 1})).unwrap()
+        };
+        let mut rounds = vec![
+            Ok(vec![entry("2"), entry("1")]),
+            Ok(vec![entry("3"), entry("2"), entry("1")]),
+            Err(BotError::api(500, "busy")),
+            Ok(vec![entry("5"), entry("4"), entry("3")]),
+            Ok(vec![entry("5"), entry("4")]),
+        ]
+        .into_iter();
+        let stream = poll_audit_log(Duration::from_secs(5), AuditLogStream::new(), move || {
+            let round = rounds.next().unwrap_or_else(|| Ok(Vec::new()));
+            async move { round }
+        });
+        let started = tokio::time::Instant::now();
+        let items: Vec<_> = stream.take(6).collect().await;
+        let keys: Vec<_> = items
+            .iter()
+            .map(|item| match item {
+                Ok(entry) => entry.key(),
+                Err(_) => "error".to_string(),
+            })
+            .collect();
+        assert_eq!(keys, vec!["1", "2", "3", "error", "4", "5"]);
+        // Two normal waits, then one doubled wait after the error.
+        assert_eq!(started.elapsed(), Duration::from_secs(20));
+    }
+
+    #[tokio::test]
+    async fn test_restore_archived_channel() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let start = |fail_move: bool| {
+            MockServer::start(move |request| match request.method.as_str() {
+                "GET" if fail_move => {
+                    MockResponse::status(500, json!({"code": 500, "message": "down"}))
+                }
+                "GET" => MockResponse::ok(json!({
+                    "id": "c1", "name": "archived-general", "parent_id": "archive", "private_type": 0
+                })),
+                _ => MockResponse::ok(json!({"id": "c1", "name": "general", "parent_id": "cat1"})),
+            })
+        };
+        let calls = |server: &MockServer| -> Vec<String> {
+            server
+                .requests()
+                .iter()
+                .map(|r| format!("{} {}", r.method, r.path))
+                .collect()
+        };
+
+        let server = start(false).await;
+        let channel = server
+            .api()
+            .restore_archived_channel(&MockServer::token(), "c1", "general", Some("cat1"))
+            .await
+            .unwrap();
+        assert_eq!(channel.name.as_deref(), Some("general"));
+        assert_eq!(
+            calls(&server),
+            vec![
+                "PUT /channels/c1",
+                "GET /channels/c1",
+                "PUT /channels/c1",
+                "PUT /channels/c1"
+            ]
+        );
+        let requests = server.requests();
+        assert_eq!(requests[0].body, json!({"private_type": 0}));
+        assert_eq!(requests[2].body["parent_id"], "cat1");
+        assert_eq!(requests[3].body, json!({"name": "general"}));
+
+        // A failed move stops the restore before the name is changed back.
+        let server = start(true).await;
+        let result = server
+            .api()
+            .restore_archived_channel(&MockServer::token(), "c1", "general", Some("cat1"))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls(&server), vec!["PUT /channels/c1", "GET /channels/c1"]);
+    }
+
+    #[test]
+    fn test_archive_steps() {
+        let channel: Channel = serde_json::from_value(json!({
+            "id": "c1",
+            "guild_id": "g1",
+            "name": "general"
+        }))
+        .unwrap();
+        assert_eq!(
+            archive_steps(&channel, false),
+            vec![
+                ArchiveStep::Rename("[ARCHIVED] general".to_string()),
+                ArchiveStep::CreateCategory,
+                ArchiveStep::MoveToCategory,
+                ArchiveStep::MakeAdminOnly,
+            ]
+        );
+
+        let mut archived = channel.clone();
+        archived.name = Some("[ARCHIVED] general".to_string());
+        assert_eq!(
+            archive_steps(&archived, true),
+            vec![ArchiveStep::MoveToCategory, ArchiveStep::MakeAdminOnly]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_messages_since_stops_at_older_messages() {
+        let since = chrono::Utc::now();
+        let message = |id: u32, minutes: i64| -> Message {
+            let mut message = Message::new();
+            message.id = Some(id.to_string());
+            message.timestamp = Some(since + chrono::Duration::minutes(minutes));
+            message
+        };
+        let mut requested = Vec::new();
+
+        let messages: Vec<Message> = paginate_messages_since(since, 2, |before| {
+            requested.push(before.clone());
+            let page = match before.as_deref() {
+                None => vec![message(4, 3), message(3, 2)],
+                Some("3") => vec![message(2, 1), message(1, -1)],
+                _ => vec![message(0, -2)],
+            };
+            async move { Ok(page) }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        let ids: Vec<_> = messages.iter().filter_map(|m| m.id.as_deref()).collect();
+        assert_eq!(ids, vec!["4", "3", "2"]);
+        assert_eq!(requested, vec![None, Some("3".to_string())]);
+    }
+
+    #[test]
+    fn test_embed_params() {
+        let embed = Embed::default_description_only("Server status: online");
+
+        let params = embed_params(embed.clone(), None);
+        assert_eq!(params.embed.as_ref(), Some(&embed));
+        assert_eq!(params.msg_id, None);
+        assert_eq!(params.content, None);
+
+        let params = embed_params(embed.clone(), Some("m1"));
+        assert_eq!(params.embed, Some(embed));
+        assert_eq!(params.msg_id.as_deref(), Some("m1"));
+    }
+
+    #[tokio::test]
+    async fn test_paginate_online_members() {
+        let requested = &std::sync::Mutex::new(Vec::new());
+        let stream = paginate_online_members(|cursor| {
+            requested.lock().unwrap().push(cursor.clone());
+            let page = match cursor.as_deref() {
+                None => OnlineMembersPage {
+                    members: vec![test_member("1", &[]), test_member("2", &[])],
+                    next_cursor: Some("c2".to_string()),
+                    total: 3,
+                },
+                Some("c2") => OnlineMembersPage {
+                    members: vec![test_member("3", &[])],
+                    next_cursor: None,
+                    total: 3,
+                },
+                Some(other) => panic!("requested page with cursor {other} past the end"),
+            };
+            async move { Ok(page) }
+        });
+
+        let members: Vec<Member> = stream.try_collect().await.unwrap();
+        let ids: Vec<&str> = members
+            .iter()
+            .map(|m| m.user.as_ref().unwrap().id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec![None, Some("c2".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_online_members_stops_on_stale_cursor() {
+        for last_cursor in ["", "c2"] {
+            let requested = &std::sync::Mutex::new(Vec::new());
+            let stream = paginate_online_members(|cursor| {
+                requested.lock().unwrap().push(cursor.clone());
+                let page = match cursor.as_deref() {
+                    None => OnlineMembersPage {
+                        members: vec![test_member("1", &[])],
+                        next_cursor: Some("c2".to_string()),
+                        total: 2,
+                    },
+                    Some("c2") => OnlineMembersPage {
+                        members: vec![test_member("2", &[])],
+                        next_cursor: Some(last_cursor.to_string()),
+                        total: 2,
+                    },
+                    Some(other) => panic!("requested page with cursor {other:?} past the end"),
+                };
+                async move { Ok(page) }
+            });
+
+            let members: Vec<Member> = stream.try_collect().await.unwrap();
+            assert_eq!(members.len(), 2);
+            assert_eq!(
+                *requested.lock().unwrap(),
+                vec![None, Some("c2".to_string())]
+            );
+        }
+    }
+
+    #[test]
+    fn test_schedule_with_image_body() {
+        let builder = ScheduleBuilder::new("Launch", "1700000000", "1700003600");
+        let image = ScheduleImage::new(b"cover".to_vec(), "image/png");
+
+        let body = schedule_with_image_body(&builder, &image).unwrap();
+        assert_eq!(body["schedule"]["name"], "Launch");
+        assert_eq!(
+            body["schedule"]["cover_image"],
+            "data:image/png;base64,Y292ZXI="
+        );
+
+        let too_large = ScheduleImage::new(vec![0; ScheduleImage::MAX_SIZE + 1], "image/png");
+        assert!(schedule_with_image_body(&builder, &too_large).is_err());
+        let at_limit = ScheduleImage::new(vec![0; ScheduleImage::MAX_SIZE], "image/png");
+        assert!(schedule_with_image_body(&builder, &at_limit).is_ok());
+        let empty = ScheduleImage::new(Vec::new(), "image/png");
+        assert!(schedule_with_image_body(&builder, &empty).is_err());
+    }
+
+    #[test]
+    fn test_format_messages() {
+        let message = |id: &str, user: &str, content: &str, minute: u32| {
+            serde_json::from_value::<Message>(json!({
+                "id": id,
+                "content": content,
+                "author": {"id": format!("u-{user}"), "username": user},
+                "timestamp": format!("2024-03-01T12:{minute:02}:00Z"),
+                "mentions": [],
+                "attachments": [],
+            }))
+            .unwrap()
+        };
+        let messages = [
+            message("1", "alice", "hello", 0),
+            message("2", "bob", "hi, alice", 1),
+            message("3", "alice", "see you", 2),
+        ];
+
+        let csv = format_messages(&messages, ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "id,timestamp,author_id,username,content");
+        assert_eq!(
+            lines[2],
+            "2,2024-03-01T12:01:00+00:00,u-bob,bob,\"hi, alice\""
+        );
+
+        let json = format_messages(&messages, ExportFormat::Json).unwrap();
+        assert!(json.starts_with("[\n"));
+        let parsed: Vec<Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2]["content"], "see you");
+
+        let markdown = format_messages(&messages, ExportFormat::Markdown).unwrap();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "# Message history");
+        assert_eq!(lines[2], "- `2024-03-01 12:00:00 UTC` **alice**: hello");
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_dm_each_partial_failure() {
+        use crate::models::message::DmBroadcastResult;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        assert!(validate_dm_targets(&[]).is_err());
+        assert!(validate_dm_targets(&["u1"; MAX_DM_RECIPIENTS]).is_ok());
+        assert!(validate_dm_targets(&["u1"; MAX_DM_RECIPIENTS + 1]).is_err());
+
+        let sends = &AtomicU32::new(0);
+        let user_ids = ["u1", "blocked", "u3"];
+        let results = dm_each(
+            &user_ids,
+            2,
+            |user_id| async move {
+                match user_id {
+                    "blocked" => Err(BotError::api(403, "user does not accept DMs")),
+                    _ => Ok(format!("dm-{user_id}")),
+                }
+            },
+            |dms_guild_id| async move {
+                sends.fetch_add(1, Ordering::SeqCst);
+                Ok(MessageResponse::new(dms_guild_id))
+            },
+        )
+        .await;
+        assert_eq!(results.len(), user_ids.len());
+        assert_eq!(sends.load(Ordering::SeqCst), 2);
+
+        let report = DmBroadcastResult::from(results);
+        assert_eq!(report.sent(), vec!["u1", "u3"]);
+        let failed: Vec<_> = report.failed().iter().map(|(id, _)| *id).collect();
+        assert_eq!(failed, vec!["blocked"]);
+        assert_eq!(
+            report.results()[2].1.as_ref().unwrap().id.as_deref(),
+            Some("dm-u3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pin_messages_in_parallel() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        assert!(validate_pin_targets(0).is_ok());
+        assert!(validate_pin_targets(MAX_BULK_PINS).is_ok());
+        assert!(validate_pin_targets(MAX_BULK_PINS + 1).is_err());
+
+        let in_flight = &AtomicU32::new(0);
+        let peak = &AtomicU32::new(0);
+        let message_ids = ["m1", "m2", "gone", "m4", "m5", "m6", "locked"];
+        let results = create_each(&message_ids, PIN_CONCURRENCY, |message_id| async move {
+            let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(running, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            match *message_id {
+                "gone" => Err(BotError::NotFound("message".to_string())),
+                "locked" => Err(BotError::api(403, "no permission")),
+                _ => Ok(()),
+            }
+        })
+        .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), PIN_CONCURRENCY as u32);
+        assert_eq!(results.len(), message_ids.len());
+        assert!(results[2].is_err());
+        assert!(results[6].is_err());
+        assert_eq!(count_succeeded(&results), 5);
+    }
+
+    #[tokio::test]
+    async fn test_send_after_typing() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let triggers = &AtomicU32::new(0);
+        let trigger = || async {
+            triggers.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        };
+        let sent = send_after_typing(Duration::ZERO, trigger, || async { Ok("sent") }).await;
+        assert_eq!(sent.unwrap(), "sent");
+        assert_eq!(triggers.load(Ordering::SeqCst), 0);
+
+        let sent =
+            send_after_typing(Duration::from_millis(1), trigger, || async { Ok("sent") }).await;
+        assert_eq!(sent.unwrap(), "sent");
+        assert_eq!(triggers.load(Ordering::SeqCst), 1);
+
+        // A failed typing call still sends the message.
+        let sent = send_after_typing(
+            Duration::from_millis(1),
+            || async { Err(BotError::api(403, "no permission")) },
+            || async { Ok("sent") },
+        )
+        .await;
+        assert_eq!(sent.unwrap(), "sent");
+    }
+
+    #[tokio::test]
+    async fn test_copy_messages() {
+        use crate::models::message::{CopyReport, MessageUser};
+        use std::sync::Mutex;
+
+        let sent = &Mutex::new(Vec::new());
+        let fetch = |message_id: &str| {
+            let message_id = message_id.to_string();
+            async move {
+                if message_id == "gone" {
+                    return Err(BotError::NotFound("message".to_string()));
+                }
+                let mut message = Message::new();
+                message.id = Some(message_id.clone());
+                message.content = Some(format!("text of {message_id}"));
+                message.author = Some(MessageUser {
+                    id: Some("u1".to_string()),
+                    username: Some("alice".to_string()),
+                    bot: None,
+                    avatar: None,
+                });
+                Ok(message)
+            }
+        };
+        let send = |params: MessageParams| async move {
+            let mut sent = sent.lock().unwrap();
+            sent.push(params.content.unwrap());
+            Ok(MessageResponse::new(format!("copy{}", sent.len())))
+        };
+
+        let message_ids = ["m1", "gone", "m3"];
+        let results = copy_each(&message_ids, fetch, send).await;
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                "> [Original by alice]\ntext of m1",
+                "> [Original by alice]\ntext of m3",
+            ]
+        );
+
+        let report = CopyReport::new(&message_ids, results);
+        assert_eq!(
+            report.copies,
+            vec![
+                ("m1".to_string(), "copy1".to_string()),
+                ("m3".to_string(), "copy2".to_string()),
+            ]
+        );
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "gone");
+        assert!(!report.is_success());
+    }
+
+    #[test]
+    fn test_nsfw_channels() {
+        assert_eq!(channel_nsfw_body(true), json!({"nsfw": true}));
+        assert_eq!(channel_nsfw_body(false), json!({"nsfw": false}));
+
+        let channels: Vec<Channel> = serde_json::from_value(json!([
+            {"id": "c1", "nsfw": true},
+            {"id": "c2", "nsfw": false},
+            {"id": "c3"},
+            {"id": "c4", "nsfw": true}
+        ]))
+        .unwrap();
+        let ids: Vec<_> = nsfw_channels(channels)
+            .into_iter()
+            .filter_map(|channel| channel.id)
+            .collect();
+        assert_eq!(ids, vec!["c1", "c4"]);
+    }
+
+    #[test]
+    fn test_parse_revisions() {
+        let revisions = parse_revisions(json!([
+            {
+                "revision": 2,
+                "content": "see you at 4",
+                "edited_at": "2024-05-01T10:10:00Z",
+                "editor_id": "u1"
+            },
+            {
+                "revision": 0,
+                "content": "see you at 2",
+                "edited_at": "2024-05-01T10:00:00Z",
+                "editor_id": "u1"
+            },
+            {
+                "revision": 1,
+                "content": "see you at 3",
+                "edited_at": "2024-05-01T10:05:00Z",
+                "editor_id": null
+            }
+        ]))
+        .unwrap();
+
+        let contents: Vec<_> = revisions
+            .iter()
+            .map(|r| r.content.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            contents,
+            vec!["see you at 2", "see you at 3", "see you at 4"]
+        );
+        assert!(revisions[0].is_original());
+        assert!(!revisions[2].is_original());
+        assert!(revisions[1].editor_id.is_none());
+        assert!(
+            revisions
+                .windows(2)
+                .all(|w| w[0].edited_at < w[1].edited_at)
+        );
+    }
+
+    #[test]
+    fn test_group_thread_body() {
+        assert_eq!(
+            group_thread_body("Weekend plans", "m1").unwrap(),
+            json!({"name": "Weekend plans", "source_msg_id": "m1"})
+        );
+        assert!(matches!(
+            group_thread_body("  ", "m1"),
+            Err(BotError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_members_joined_after() {
+        let since = chrono::DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let member = |nick: &str, joined_at: Option<&str>| {
+            let mut member = Member::new();
+            member.nick = Some(nick.to_string());
+            member.joined_at = joined_at.map(|joined_at| {
+                chrono::DateTime::parse_from_rfc3339(joined_at)
+                    .unwrap()
+                    .with_timezone(&chrono::Utc)
+            });
+            member
+        };
+
+        let members = vec![
+            member("veteran", Some("2023-01-01T00:00:00Z")),
+            member("newcomer", Some("2024-06-15T08:00:00Z")),
+            member("unknown", None),
+            member("exactly", Some("2024-06-01T00:00:00Z")),
+            member("latest", Some("2024-07-01T00:00:00Z")),
+        ];
+        let nicks: Vec<_> = members_joined_after(members, since)
+            .into_iter()
+            .filter_map(|member| member.nick)
+            .collect();
+        assert_eq!(nicks, vec!["newcomer", "latest"]);
+    }
+
+    #[tokio::test]
+    async fn test_put_thread_post() {
+        let content = ForumContent::text("Thanks, that fixed it");
+        let post = put_thread_post("c1", "t1", &content, |path, body| async move {
+            assert_eq!(path, "/channels/c1/threads/t1/posts");
+            assert_eq!(
+                body,
+                json!({"format": 1, "content": "Thanks, that fixed it"})
+            );
+            Ok(json!({
+                "post_id": "p1",
+                "author_id": "u1",
+                "format": 1,
+                "content": "Thanks, that fixed it"
+            }))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(post.post_id.as_deref(), Some("p1"));
+        assert_eq!(post.channel_id.as_deref(), Some("c1"));
+        assert_eq!(post.thread_id.as_deref(), Some("t1"));
+        assert_eq!(post.content, content);
+
+        let failed = put_thread_post("c1", "t1", &content, |_, _| async {
+            Err(BotError::api(403, "no permission"))
+        })
+        .await;
+        assert!(matches!(failed, Err(BotError::Api { code: 403, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_interactive_message_handle_requests() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|request| match request.method.as_str() {
+            "POST" => MockResponse::ok(json!({"id": "m9", "timestamp": "1700000000"})),
+            _ => MockResponse::ok(json!({})),
+        })
+        .await;
+        let api = server.api();
+        let token = MockServer::token();
+
+        let handle = api
+            .post_interactive_message(&token, "c1", MessageParams::new_text("Working..."))
+            .await
+            .unwrap();
+        assert_eq!(handle.message_id, "m9");
+        assert_eq!(handle.channel_id, "c1");
+
+        handle.edit(MessageParams::new_text("Done")).await.unwrap();
+        handle.delete(true).await.unwrap();
+
+        let requests: Vec<_> = server
+            .requests()
+            .into_iter()
+            .map(|r| (r.method, r.path, r.query, r.body))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    "POST".to_string(),
+                    "/channels/c1/messages".to_string(),
+                    None,
+                    json!({"content": "Working..."}),
+                ),
+                (
+                    "PATCH".to_string(),
+                    "/channels/c1/messages/m9".to_string(),
+                    None,
+                    json!({"content": "Done"}),
+                ),
+                (
+                    "DELETE".to_string(),
+                    "/channels/c1/messages/m9".to_string(),
+                    Some("hidetip=true".to_string()),
+                    Value::Null,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ensure_member_of() {
+        let member: Member = serde_json::from_value(json!({
+            "user": {"id": "u1", "username": "alice"},
+            "nick": "Alice",
+            "roles": ["1"],
+            "joined_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        let member = ensure_member_of(member, "u1").unwrap();
+        assert_eq!(member.user.unwrap().id, "u1");
+
+        let other: Member = serde_json::from_value(json!({
+            "user": {"id": "u2", "username": "bob"}
+        }))
+        .unwrap();
+        assert!(ensure_member_of(other, "u1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_guild_member_checks() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|request| {
+            if request.path.ends_with("/members/u1") {
+                MockResponse::ok(json!({"user": {"id": "u1", "username": "alice"}}))
+            } else {
+                MockResponse::status(404, json!({"code": 404, "message": "member not found"}))
+            }
+        })
+        .await;
+        let api = server.api();
+        let token = MockServer::token();
+
+        assert!(api.is_guild_member(&token, "g1", "u1").await.unwrap());
+        assert!(!api.is_guild_member(&token, "g1", "u2").await.unwrap());
+
+        let member = api.assert_guild_member(&token, "g1", "u1").await.unwrap();
+        assert_eq!(member.user.unwrap().id, "u1");
+        assert!(matches!(
+            api.assert_guild_member(&token, "g1", "u2").await,
+            Err(BotError::NotFound(_))
+        ));
+
+        let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/guilds/g1/members/u1",
+                "/guilds/g1/members/u2",
+                "/guilds/g1/members/u1",
+                "/guilds/g1/members/u2",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forum_channel_summaries() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/guilds/g1/channels" => MockResponse::ok(json!([
+                {"id": "f1", "name": "ideas", "type": 15, "sub_type": 0},
+                {"name": "no id", "type": 15},
+                {"id": "f2", "name": "guides", "type": 15, "sub_type": 2},
+                {"id": "d1", "name": "legacy", "type": 10007},
+                {"id": "t1", "name": "general", "type": 0},
+            ])),
+            "/channels/f1/threads" if request.query.as_deref() == Some("page=1") => {
+                MockResponse::ok(json!({
+                    "threads": [
+                        {"thread_info": {"thread_id": "a", "date_time": "2024-01-01T00:00:00+08:00"}},
+                        {"thread_info": {"thread_id": "b", "date_time": "2024-03-01T00:00:00+08:00"}},
+                    ],
+                    "is_finish": 0
+                }))
+            }
+            "/channels/f1/threads" => MockResponse::ok(json!({
+                "threads": [{"thread_info": {"thread_id": "c", "date_time": "2024-02-01T00:00:00+08:00"}}],
+                "is_finish": 1
+            })),
+            "/channels/f2/threads" => MockResponse::ok(json!({"threads": [], "is_finish": 1})),
+            _ => MockResponse::status(404, json!({"code": 404, "message": "not found"})),
+        })
+        .await;
+        let api = server.api();
+        let token = MockServer::token();
+
+        let open = api.get_open_forum_channels(&token, "g1").await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id.as_deref(), Some("f1"));
+
+        let summaries = api.get_forum_channel_summaries(&token, "g1").await.unwrap();
+        let summaries: Vec<_> = summaries
+            .iter()
+            .map(|s| {
+                (
+                    s.channel.id.clone().unwrap(),
+                    s.thread_count,
+                    s.latest_thread_at.map(|t| t.to_rfc3339()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                (
+                    "f1".to_string(),
+                    3,
+                    Some("2024-02-29T16:00:00+00:00".to_string())
+                ),
+                ("f2".to_string(), 0, None),
+            ]
+        );
+        assert!(
+            server
+                .requests()
+                .iter()
+                .all(|r| r.path != "/channels//threads")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_after_typing_delay_triggers_typing() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|request| {
+            if request.path.ends_with("/typing") {
+                MockResponse::status(403, json!({"code": 403, "message": "no permission"}))
+            } else {
+                MockResponse::ok(json!({"id": "m1"}))
+            }
+        })
+        .await;
+
+        let sent = server
+            .api()
+            .send_after_typing_delay(
+                &MockServer::token(),
+                "c1",
+                MessageParams::new_text("hi"),
+                6000,
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent.id.as_deref(), Some("m1"));
+
+        let requests: Vec<_> = server
+            .requests()
+            .into_iter()
+            .map(|r| (r.method, r.path))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                ("POST".to_string(), "/channels/c1/typing".to_string()),
+                ("POST".to_string(), "/channels/c1/messages".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_message_quota_from_message_routes() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/channels/c1/messages" => MockResponse::ok(json!({"id": "m1"}))
+                .with_header("x-ratelimit-limit", "20")
+                .with_header("x-ratelimit-remaining", "1")
+                .with_header("x-ratelimit-reset", "4102444800"),
+            "/channels/c2/messages" => {
+                MockResponse::status(429, json!({"code": 429, "message": "too many"}))
+                    .with_header("retry-after", "30")
+            }
+            _ => MockResponse::status(404, json!({"code": 404, "message": "not found"})),
+        })
+        .await;
+        let api = server.api();
+        let token = MockServer::token();
+
+        assert!(api.get_message_quota("c1").is_err());
+
+        let params = MessageParams::new_text("hi");
+        api.post_message_with_params(&token, "c1", params.clone())
+            .await
+            .unwrap();
+        let quota = api.get_message_quota("c1").unwrap();
+        assert_eq!((quota.remaining, quota.limit), (1, 20));
+        assert!(!quota.is_exhausted());
+
+        assert!(
+            api.post_message_with_params(&token, "c2", params)
+                .await
+                .is_err()
+        );
+        let quota = api.get_message_quota("c2").unwrap();
+        assert!(quota.is_exhausted());
+        assert!(!api.get_message_quota("c1").unwrap().is_exhausted());
+        assert!(quota.time_until_reset() > std::time::Duration::from_secs(25));
+    }
+
+    #[tokio::test]
+    async fn test_member_timeline_includes_moderator_kick() {
+        use crate::http::mock::{MockResponse, MockServer};
+        use crate::models::MemberEventType;
+
+        let server = MockServer::start(|request| {
+            let action = request
+                .query
+                .as_deref()
+                .and_then(|q| q.split('&').find_map(|p| p.strip_prefix("action_type=")));
+            MockResponse::ok(match action {
+                Some("1") => json!([
+                    {"id": "e1", "user_id": "u1", "action_type": 1, "created_at": "2024-05-01T00:00:00Z"},
+                    {"id": "e2", "user_id": "u2", "action_type": 1, "created_at": "2024-05-02T00:00:00Z"}
+                ]),
+                Some("2") => json!([{
+                    "id": "e3", "user_id": "mod", "target_id": "u1", "action_type": 2,
+                    "reason": "spam", "created_at": "2024-05-03T00:00:00Z"
+                }]),
+                _ => json!([]),
+            })
+        })
+        .await;
+        let api = server.api();
+
+        let timeline = api
+            .get_member_timeline(&MockServer::token(), "g1", "u1", 10)
+            .await
+            .unwrap();
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[0].event_type, MemberEventType::Joined);
+        assert_eq!(timeline[0].actor_id, None);
+        assert_eq!(
+            timeline[1].event_type,
+            MemberEventType::Kicked {
+                reason: Some("spam".to_string())
+            }
+        );
+        assert_eq!(timeline[1].actor_id.as_deref(), Some("mod"));
+        assert!(
+            server.requests().iter().all(|r| !r
+                .query
+                .as_deref()
+                .unwrap_or_default()
+                .contains("user_id"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_forum_audit_log() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|_| {
+            MockResponse::ok(json!([{
+                "action": "thread_locked",
+                "actor_id": "bot1",
+                "target_id": "t1",
+                "timestamp": "2024-05-01T00:00:00Z"
+            }]))
+        })
+        .await;
+        let entries = server
+            .api()
+            .get_forum_audit_log(&MockServer::token(), "f1", 20)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].was_automated(&["bot1"]));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/channels/f1/audit-logs");
+        assert_eq!(requests[0].query.as_deref(), Some("limit=20"));
+    }
+
+    #[tokio::test]
+    async fn test_member_timeline_pages_back() {
+        use crate::http::mock::{MockResponse, MockServer};
+        use crate::models::MemberEventType;
+
+        let others: Vec<_> = (0..AUDIT_LOG_PAGE_SIZE)
+            .map(|i| json!({"id": format!("e{i}"), "user_id": "u2", "action_type": 1, "created_at": "2024-05-02T00:00:00Z"}))
+            .collect();
+        let server = MockServer::start(move |request| {
+            let query = request.query.as_deref().unwrap_or_default();
+            if !query.contains("action_type=1") {
+                MockResponse::ok(json!([]))
+            } else if query.contains("before=e99") {
+                MockResponse::ok(json!([
+                    {"id": "f1", "user_id": "u1", "action_type": 1, "created_at": "2024-05-01T00:00:00Z"}
+                ]))
+            } else {
+                MockResponse::ok(json!(others))
+            }
+        })
+        .await;
+
+        let timeline = server
+            .api()
+            .get_member_timeline(&MockServer::token(), "g1", "u1", 10)
+            .await
+            .unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].event_type, MemberEventType::Joined);
+        assert_eq!(server.requests().len(), MEMBER_TIMELINE_ACTIONS.len() + 1);
+    }
+
+    #[tokio::test]
+    async fn test_daily_stats_pages_back_to_start() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let now = chrono::Utc::now();
+        let at = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+        let first_page: Vec<_> = (0..AUDIT_LOG_PAGE_SIZE)
+            .map(|i| json!({"id": format!("e{i}"), "user_id": format!("u{}", i % 3), "action_type": 1, "created_at": at(0)}))
+            .collect();
+        let second_page = json!([
+            {"id": "f1", "user_id": "u9", "action_type": 1, "created_at": at(1)},
+            {"id": "f2", "user_id": "u8", "action_type": 1, "created_at": at(5)},
+        ]);
+        let server = MockServer::start(move |request| {
+            if request
+                .query
+                .as_deref()
+                .unwrap_or_default()
+                .contains("before=")
+            {
+                MockResponse::ok(second_page.clone())
+            } else {
+                MockResponse::ok(json!(first_page))
+            }
+        })
+        .await;
+        let api = server.api();
+
+        let stats = api
+            .get_guild_daily_stats(&MockServer::token(), "g1", 2)
+            .await
+            .unwrap();
+        let active: Vec<_> = stats.iter().map(|day| day.unique_active_users).collect();
+        assert_eq!(active, vec![1, 3]);
+        assert_eq!(stats[1].messages_sent, None);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].query.as_deref().unwrap().contains("before=e99"));
+    }
+
+    #[tokio::test]
+    async fn test_create_mod_log_channel() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let start = |pin_status: u16| {
+            MockServer::start(move |request| {
+                match (request.method.as_str(), request.path.as_str()) {
+                    ("POST", "/guilds/g1/channels") => {
+                        MockResponse::ok(json!({"id": "c1", "name": "mod-log", "type": 0}))
+                    }
+                    ("POST", "/channels/c1/messages") => MockResponse::ok(json!({"id": "m1"})),
+                    ("PUT", "/channels/c1/pins/m1") => {
+                        MockResponse::status(pin_status, json!({"code": pin_status, "message": ""}))
+                    }
+                    ("DELETE", "/channels/c1") => MockResponse::ok(json!({"id": "c1"})),
+                    _ => MockResponse::status(404, json!({"code": 404, "message": "not found"})),
+                }
+            })
+        };
+        let calls = |server: &MockServer| -> Vec<String> {
+            server
+                .requests()
+                .iter()
+                .map(|r| format!("{} {}", r.method, r.path))
+                .collect()
+        };
+
+        let server = start(200).await;
+        let channel = server
+            .api()
+            .create_mod_log_channel(&MockServer::token(), "g1", None)
+            .await
+            .unwrap();
+        assert_eq!(channel.id.as_deref(), Some("c1"));
+        assert_eq!(
+            calls(&server),
+            vec![
+                "POST /guilds/g1/channels",
+                "POST /channels/c1/messages",
+                "PUT /channels/c1/pins/m1"
+            ]
+        );
+        let requests = server.requests();
+        assert_eq!(requests[0].body["name"], "mod-log");
+        assert_eq!(requests[0].body["private_type"], 1);
+        assert_eq!(requests[0].body["speak_permission"], 2);
+        assert_eq!(requests[1].body["content"], MOD_LOG_HEADER);
+
+        let server = start(403).await;
+        let result = server
+            .api()
+            .create_mod_log_channel(&MockServer::token(), "g1", Some("audit"))
+            .await;
+        assert!(matches!(result, Err(BotError::Forbidden(_))));
+        assert_eq!(calls(&server).last().unwrap(), "DELETE /channels/c1");
+        assert_eq!(server.requests()[0].body["name"], "audit");
     }
 }
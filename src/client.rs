@@ -3,23 +3,34 @@
 //! This module provides the main `Client` struct that serves as the entry point
 //! for bot applications, handling connections, events, and API interactions.
 
-use crate::api::BotApi;
-use crate::audio::PublicAudio;
+use crate::api::{BotApi, MOD_LOG_CHANNEL_NAME};
+use crate::audio::{PublicAudio, PublicAudioType};
+use crate::config::{ConfigMap, GuildConfig, GuildConfigStore};
 use crate::error::{BotError, Result};
-use crate::forum::OpenThread;
-use crate::gateway::Gateway;
+use crate::forum::{ForumContent, ForumPost, OpenThread, ThreadParticipant};
+use crate::gateway::{Gateway, GatewaySubscription};
 use crate::http::HttpClient;
 use crate::intents::Intents;
 use crate::manage::{C2CManageEvent, GroupManageEvent};
-use crate::models::channel::{ChannelSubType, ChannelType};
+use crate::models::channel::{ChannelSubType, ChannelType, VoiceChannelMember};
 use crate::models::gateway::GatewayEvent;
-use crate::models::guild::{GuildRole, GuildRoles, Member as GuildMember};
+use crate::models::guild::{GuildChangeEvent, GuildRole, GuildRoles, Member as GuildMember};
 use crate::models::*;
 use crate::token::Token;
+use futures_util::Stream;
+use std::any::{Any, TypeId};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info};
 
+/// Minutes without activity before a thread started by
+/// [`Context::announce_with_thread`] is archived.
+const ANNOUNCEMENT_THREAD_AUTO_ARCHIVE: u32 = 24 * 60;
+
+/// Maximum number of messages [`Context::broadcast`] and [`Context::dm_many`]
+/// send at once.
+const BROADCAST_CONCURRENCY: usize = 5;
+
 /// Event handler trait for processing gateway events.
 #[async_trait::async_trait]
 pub trait EventHandler: Send + Sync {
@@ -35,6 +46,18 @@ pub trait EventHandler: Send + Sync {
     /// Called when a group message is created.
     async fn group_message_create(&self, _ctx: Context, _message: GroupMessage) {}
 
+    /// Called when a message is edited.
+    async fn message_update(&self, _ctx: Context, _message: Message) {}
+
+    /// Called when a direct message is edited.
+    async fn direct_message_update(&self, _ctx: Context, _message: DirectMessage) {}
+
+    /// Called when a group message is edited.
+    async fn group_message_update(&self, _ctx: Context, _message: GroupMessage) {}
+
+    /// Called when a discussion thread is started in a group.
+    async fn group_thread_create(&self, _ctx: Context, _thread: GroupThread) {}
+
     /// Called when a C2C message is created.
     async fn c2c_message_create(&self, _ctx: Context, _message: C2CMessage) {}
 
@@ -74,6 +97,9 @@ pub trait EventHandler: Send + Sync {
     /// Called when a message audit is rejected.
     async fn message_audit_reject(&self, _ctx: Context, _audit: MessageAudit) {}
 
+    /// Called when a member's presence changes.
+    async fn presence_update(&self, _ctx: Context, _presence: UserPresence) {}
+
     /// Called when a friend is added.
     async fn friend_add(&self, _ctx: Context, _event: C2CManageEvent) {}
 
@@ -125,6 +151,29 @@ pub trait EventHandler: Send + Sync {
     /// Called when an open forum reply is deleted.
     async fn open_forum_reply_delete(&self, _ctx: Context, _thread: OpenThread) {}
 
+    /// Called when a post is created under a forum thread.
+    async fn forum_post_create(&self, _ctx: Context, _post: ForumPost) {}
+
+    /// Called when a member joins a voice channel.
+    async fn voice_channel_join(
+        &self,
+        _ctx: Context,
+        _member: VoiceChannelMember,
+        _channel_id: String,
+    ) {
+    }
+
+    /// Called when a member leaves a voice channel.
+    ///
+    /// The member's `joined_at` is `None`, since the event doesn't say when they joined.
+    async fn voice_channel_leave(
+        &self,
+        _ctx: Context,
+        _member: VoiceChannelMember,
+        _channel_id: String,
+    ) {
+    }
+
     /// Called for any unhandled events.
     async fn unknown_event(&self, _ctx: Context, _event: GatewayEvent) {}
 
@@ -143,6 +192,10 @@ pub struct Context {
     pub token: Token,
     /// Bot information
     pub bot_info: Option<BotInfo>,
+    /// Registered per-guild configuration stores
+    guild_configs: Arc<ConfigMap>,
+    /// Broadcasts dispatch events from the gateway
+    events: broadcast::Sender<GatewayEvent>,
 }
 
 impl Context {
@@ -152,6 +205,8 @@ impl Context {
             api,
             token,
             bot_info: None,
+            guild_configs: Arc::new(ConfigMap::new()),
+            events: crate::gateway::event_channel(),
         }
     }
 
@@ -161,6 +216,56 @@ impl Context {
         self
     }
 
+    /// Sets the registered per-guild configuration stores.
+    pub(crate) fn with_guild_configs(mut self, guild_configs: Arc<ConfigMap>) -> Self {
+        self.guild_configs = guild_configs;
+        self
+    }
+
+    /// Sets the sender the gateway broadcasts dispatch events on.
+    pub(crate) fn with_events(mut self, events: broadcast::Sender<GatewayEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Subscribes to dispatch events received from now on.
+    ///
+    /// Each subscription gets its own copy of every event. Contexts created
+    /// with [`Context::new`] are not attached to a gateway and never receive
+    /// events.
+    pub fn subscribe_events(&self) -> GatewaySubscription {
+        GatewaySubscription::new(self.events.subscribe())
+    }
+
+    /// Watches a guild's member, channel and role changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild to watch
+    ///
+    /// # Returns
+    ///
+    /// A stream of changes to the guild, see [`GatewaySubscription::guild_changes`].
+    pub fn watch_guild_changes(
+        &self,
+        guild_id: &str,
+    ) -> impl Stream<Item = GuildChangeEvent> + use<> {
+        self.subscribe_events().guild_changes(guild_id)
+    }
+
+    /// Gets the per-guild configuration store registered for `T`.
+    ///
+    /// # Returns
+    ///
+    /// The store registered with `Client::with_guild_config`, or `None` if no
+    /// store of this type was registered.
+    pub fn config<T: GuildConfig + 'static>(&self) -> Option<Arc<GuildConfigStore<T>>> {
+        self.guild_configs
+            .get(&TypeId::of::<GuildConfigStore<T>>())
+            .cloned()
+            .and_then(|store| store.downcast::<GuildConfigStore<T>>().ok())
+    }
+
     /// Sends a message to a channel.
     ///
     /// # Arguments
@@ -178,6 +283,56 @@ impl Context {
             .await
     }
 
+    /// Sends a text message to several channels, up to 5 at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_ids` - The channels to send to, at most 50
+    /// * `content` - Message content
+    ///
+    /// # Returns
+    ///
+    /// The result for each channel.
+    pub async fn broadcast(&self, channel_ids: &[&str], content: &str) -> Result<BroadcastReport> {
+        let params = crate::models::message::MessageParams::new_text(content);
+        let results = self
+            .api
+            .broadcast_message(&self.token, channel_ids, params, BROADCAST_CONCURRENCY)
+            .await?;
+        Ok(BroadcastReport::from(results))
+    }
+
+    /// Sends a text direct message to several users, up to 5 at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild the users are members of
+    /// * `user_ids` - The users to message, at most 20
+    /// * `content` - Message content
+    ///
+    /// # Returns
+    ///
+    /// The result for each user.
+    pub async fn dm_many(
+        &self,
+        guild_id: &str,
+        user_ids: &[&str],
+        content: &str,
+    ) -> Result<DmBroadcastResult> {
+        let params = DirectMessageParams::new_text(content);
+        let results = self
+            .api
+            .send_dm_to_many(
+                &self.token,
+                guild_id,
+                user_ids,
+                params,
+                BROADCAST_CONCURRENCY,
+            )
+            .await?;
+        Ok(DmBroadcastResult::from(results))
+    }
+
     /// Sends a message with embed to a channel.
     ///
     /// # Arguments
@@ -237,6 +392,91 @@ impl Context {
             .await
     }
 
+    /// Sends a message containing only an embed.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID to send the message to
+    /// * `embed` - Embed to send
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn post_embed(&self, channel_id: &str, embed: Embed) -> Result<MessageResponse> {
+        self.api
+            .post_embed(&self.token, channel_id, embed, None)
+            .await
+    }
+
+    /// Replies to a message with a message containing only an embed.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID to send the reply to
+    /// * `embed` - Embed to send
+    /// * `msg_id` - The message ID to reply to
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn post_embed_reply(
+        &self,
+        channel_id: &str,
+        embed: Embed,
+        msg_id: &str,
+    ) -> Result<MessageResponse> {
+        self.api
+            .post_embed(&self.token, channel_id, embed, Some(msg_id))
+            .await
+    }
+
+    /// Replies to a message after showing the typing indicator for a while.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID to send the reply to
+    /// * `content` - Message content
+    /// * `msg_id` - The message ID to reply to
+    /// * `typing_duration` - How long to show the indicator, at most 10 seconds
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn reply_with_typing(
+        &self,
+        channel_id: &str,
+        content: &str,
+        msg_id: &str,
+        typing_duration: std::time::Duration,
+    ) -> Result<MessageResponse> {
+        let params = MessageParams::new_text(content).with_reply(msg_id);
+        self.api
+            .send_with_typing(&self.token, channel_id, params, typing_duration)
+            .await
+    }
+
+    /// Forwards an existing message to another channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_channel_id` - The channel containing the source message
+    /// * `message_id` - The source message ID
+    /// * `to_channel_id` - The destination channel ID
+    ///
+    /// # Returns
+    ///
+    /// The sent message response.
+    pub async fn forward_message(
+        &self,
+        from_channel_id: &str,
+        message_id: &str,
+        to_channel_id: &str,
+    ) -> Result<MessageResponse> {
+        self.api
+            .forward_message(&self.token, from_channel_id, message_id, to_channel_id)
+            .await
+    }
+
     /// Sends a group message.
     ///
     /// # Arguments
@@ -288,6 +528,34 @@ impl Context {
         self.api.get_guild(&self.token, guild_id).await
     }
 
+    /// Gets member activity statistics for a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// Guild member statistics.
+    pub async fn get_guild_stats(&self, guild_id: &str) -> Result<GuildStats> {
+        self.api.get_guild_stats(&self.token, guild_id).await
+    }
+
+    /// Gets the members currently in a voice channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The voice channel ID
+    ///
+    /// # Returns
+    ///
+    /// The members in the channel.
+    pub async fn get_voice_members(&self, channel_id: &str) -> Result<Vec<VoiceChannelMember>> {
+        self.api
+            .get_voice_channel_members(&self.token, channel_id)
+            .await
+    }
+
     /// Gets channel information.
     ///
     /// # Arguments
@@ -301,6 +569,99 @@ impl Context {
         self.api.get_channel(&self.token, channel_id).await
     }
 
+    /// Gets the topic (description) of a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// The channel topic, or `None` if the channel has none.
+    pub async fn get_channel_topic(&self, channel_id: &str) -> Result<Option<String>> {
+        self.api.get_channel_topic(&self.token, channel_id).await
+    }
+
+    /// Sets the topic (description) of a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    /// * `topic` - The new topic, at most 200 characters
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn set_channel_topic(&self, channel_id: &str, topic: &str) -> Result<Channel> {
+        self.api
+            .set_channel_topic(&self.token, channel_id, topic)
+            .await
+    }
+
+    /// Gets the age-gated channels in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of channels marked NSFW.
+    pub async fn get_nsfw_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api.get_nsfw_channels(&self.token, guild_id).await
+    }
+
+    /// Marks a channel as age-gated or removes the age gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    /// * `nsfw` - Whether the channel should be age-gated
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn set_channel_nsfw(&self, channel_id: &str, nsfw: bool) -> Result<Channel> {
+        self.api
+            .set_channel_nsfw(&self.token, channel_id, nsfw)
+            .await
+    }
+
+    /// Moves a channel into a category.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    /// * `parent_id` - The category channel ID
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn move_channel_to_category(
+        &self,
+        channel_id: &str,
+        parent_id: &str,
+    ) -> Result<Channel> {
+        self.api
+            .set_channel_parent(&self.token, channel_id, Some(parent_id))
+            .await
+    }
+
+    /// Removes a channel from its category.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    ///
+    /// # Returns
+    ///
+    /// The updated channel.
+    pub async fn remove_channel_from_category(&self, channel_id: &str) -> Result<Channel> {
+        self.api
+            .set_channel_parent(&self.token, channel_id, None)
+            .await
+    }
+
     /// Gets message information.
     ///
     /// # Arguments
@@ -422,6 +783,61 @@ impl Context {
         self.api.get_channels(&self.token, guild_id).await
     }
 
+    /// Gets the channels of one type in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `channel_type` - The channel type to keep
+    ///
+    /// # Returns
+    ///
+    /// List of matching channels.
+    pub async fn get_channels_by_type(
+        &self,
+        guild_id: &str,
+        channel_type: ChannelType,
+    ) -> Result<Vec<Channel>> {
+        self.api
+            .get_channels_by_type(&self.token, guild_id, channel_type)
+            .await
+    }
+
+    /// Gets the text channels in a guild.
+    pub async fn get_text_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api.get_text_channels(&self.token, guild_id).await
+    }
+
+    /// Gets the voice channels in a guild.
+    pub async fn get_voice_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api.get_voice_channels(&self.token, guild_id).await
+    }
+
+    /// Gets the forum channels in a guild.
+    pub async fn get_forum_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api.get_forum_channels(&self.token, guild_id).await
+    }
+
+    /// Gets the announcement channels in a guild.
+    pub async fn get_announcement_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api
+            .get_announcement_channels(&self.token, guild_id)
+            .await
+    }
+
+    /// Gets the category channels in a guild.
+    pub async fn get_category_channels(&self, guild_id: &str) -> Result<Vec<Channel>> {
+        self.api.get_category_channels(&self.token, guild_id).await
+    }
+
+    /// Counts the channels of each type in a guild.
+    pub async fn count_channels_by_type(
+        &self,
+        guild_id: &str,
+    ) -> Result<std::collections::HashMap<ChannelType, usize>> {
+        self.api.count_channels_by_type(&self.token, guild_id).await
+    }
+
     /// Creates a new channel in a guild.
     ///
     /// # Arguments
@@ -790,23 +1206,43 @@ impl Context {
         Ok(())
     }
 
-    /// Unpins a message.
+    /// Pins several messages in a channel at once.
     ///
     /// # Arguments
     ///
     /// * `channel_id` - The channel ID
-    /// * `message_id` - The message ID to unpin
+    /// * `message_ids` - The messages to pin, at most 50
     ///
     /// # Returns
     ///
-    /// Result indicating success or failure.
-    pub async fn unpin_message(&self, channel_id: &str, message_id: &str) -> Result<()> {
+    /// One result per message, in the order given.
+    pub async fn pin_messages(
+        &self,
+        channel_id: &str,
+        message_ids: &[&str],
+    ) -> Result<Vec<Result<()>>> {
         self.api
-            .delete_pin(&self.token, channel_id, message_id)
+            .pin_messages(&self.token, channel_id, message_ids)
             .await
     }
 
-    /// Gets pinned messages in a channel.
+    /// Unpins a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    /// * `message_id` - The message ID to unpin
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn unpin_message(&self, channel_id: &str, message_id: &str) -> Result<()> {
+        self.api
+            .delete_pin(&self.token, channel_id, message_id)
+            .await
+    }
+
+    /// Gets pinned messages in a channel.
     ///
     /// # Arguments
     ///
@@ -975,6 +1411,423 @@ impl Context {
             .post_c2c_file(&self.token, openid, file_type, url, srv_send_msg)
             .await
     }
+
+    /// Replies to a forum thread by creating a post under it.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID to reply to
+    /// * `content` - The post content
+    ///
+    /// # Returns
+    ///
+    /// The created post.
+    pub async fn reply_to_thread(
+        &self,
+        channel_id: &str,
+        thread_id: &str,
+        content: ForumContent,
+    ) -> Result<ForumPost> {
+        self.api
+            .create_thread_post(&self.token, channel_id, thread_id, content)
+            .await
+    }
+
+    /// Gets the users who have posted or replied in a forum thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The forum channel ID
+    /// * `thread_id` - The thread ID
+    ///
+    /// # Returns
+    ///
+    /// The participants, most active first.
+    pub async fn get_thread_participants(
+        &self,
+        channel_id: &str,
+        thread_id: &str,
+    ) -> Result<Vec<ThreadParticipant>> {
+        self.api
+            .get_thread_participants(&self.token, channel_id, thread_id)
+            .await
+    }
+
+    /// Gets the custom emojis of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// List of custom emojis.
+    pub async fn list_guild_emojis(&self, guild_id: &str) -> Result<Vec<GuildEmoji>> {
+        self.api.list_guild_emojis(&self.token, guild_id).await
+    }
+
+    /// Uploads a custom emoji to a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `name` - The emoji name
+    /// * `image_data` - The raw image bytes
+    ///
+    /// # Returns
+    ///
+    /// The created emoji.
+    pub async fn create_guild_emoji(
+        &self,
+        guild_id: &str,
+        name: &str,
+        image_data: &[u8],
+    ) -> Result<GuildEmoji> {
+        self.api
+            .create_guild_emoji(&self.token, guild_id, name, image_data)
+            .await
+    }
+
+    /// Renames a custom emoji.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `emoji_id` - The emoji ID
+    /// * `name` - The new emoji name
+    ///
+    /// # Returns
+    ///
+    /// The updated emoji.
+    pub async fn update_guild_emoji(
+        &self,
+        guild_id: &str,
+        emoji_id: &str,
+        name: &str,
+    ) -> Result<GuildEmoji> {
+        self.api
+            .update_guild_emoji(&self.token, guild_id, emoji_id, name)
+            .await
+    }
+
+    /// Deletes a custom emoji.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `emoji_id` - The emoji ID
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn delete_guild_emoji(&self, guild_id: &str, emoji_id: &str) -> Result<()> {
+        self.api
+            .delete_guild_emoji(&self.token, guild_id, emoji_id)
+            .await
+    }
+
+    /// Sets the voice region of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `region_id` - The region ID
+    ///
+    /// # Returns
+    ///
+    /// The updated guild.
+    pub async fn set_guild_voice_region(&self, guild_id: &str, region_id: &str) -> Result<Guild> {
+        self.api
+            .update_guild_voice_region(&self.token, guild_id, region_id)
+            .await
+    }
+
+    /// Updates the `@everyone` role's permissions in a channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel ID
+    /// * `guild_id` - The guild the channel belongs to; also the `@everyone` role ID
+    /// * `add` - Permissions to grant
+    /// * `deny` - Permissions to revoke
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn set_everyone_channel_permissions(
+        &self,
+        channel_id: &str,
+        guild_id: &str,
+        add: Permissions,
+        deny: Permissions,
+    ) -> Result<()> {
+        self.api
+            .update_everyone_channel_permissions(&self.token, channel_id, guild_id, add, deny)
+            .await
+    }
+
+    /// Checks whether a user is a member of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    ///
+    /// `true` if the user is a member of the guild.
+    pub async fn is_guild_member(&self, guild_id: &str, user_id: &str) -> Result<bool> {
+        self.api
+            .is_guild_member(&self.token, guild_id, user_id)
+            .await
+    }
+
+    /// Gets the guilds shared by the bot and a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    ///
+    /// The guilds the user is a member of.
+    pub async fn get_mutual_guilds(&self, user_id: &str) -> Result<Vec<Guild>> {
+        self.api.get_mutual_guilds(&self.token, user_id).await
+    }
+
+    /// Sets a member's roles to exactly `desired_roles`.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    /// * `desired_roles` - The role IDs the member should end up with
+    ///
+    /// # Returns
+    ///
+    /// The roles added and removed, and any that failed.
+    pub async fn sync_member_roles(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        desired_roles: &[&str],
+    ) -> Result<RoleSyncResult> {
+        self.api
+            .sync_member_roles(&self.token, guild_id, user_id, desired_roles)
+            .await
+    }
+
+    /// Replaces the bot's global application commands.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The complete set of global commands
+    ///
+    /// # Returns
+    ///
+    /// The registered commands.
+    pub async fn sync_commands(
+        &self,
+        commands: Vec<ApplicationCommand>,
+    ) -> Result<Vec<ApplicationCommand>> {
+        self.api
+            .bulk_overwrite_global_commands(&self.token, commands)
+            .await
+    }
+
+    /// Announces a message and starts a thread from it that archives after a
+    /// day without activity.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The channel containing the message
+    /// * `message_id` - The message to announce
+    /// * `thread_name` - Name of the thread
+    ///
+    /// # Returns
+    ///
+    /// The announcement and its thread.
+    pub async fn announce_with_thread(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        thread_name: &str,
+    ) -> Result<(Announce, ChannelThread)> {
+        self.api
+            .create_announcement_thread(
+                &self.token,
+                channel_id,
+                message_id,
+                thread_name,
+                ANNOUNCEMENT_THREAD_AUTO_ARCHIVE,
+            )
+            .await
+    }
+
+    /// Gets per-day activity counts for a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `days` - Number of days to cover, ending today (UTC)
+    ///
+    /// # Returns
+    ///
+    /// One item per day, oldest first.
+    pub async fn get_guild_daily_stats(
+        &self,
+        guild_id: &str,
+        days: u32,
+    ) -> Result<Vec<GuildDailyStats>> {
+        self.api
+            .get_guild_daily_stats(&self.token, guild_id, days)
+            .await
+    }
+
+    /// Gets the guild's `"mod-log"` channel, creating it if it doesn't exist.
+    ///
+    /// A new channel is set up by [`BotApi::create_mod_log_channel`].
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// The existing or newly created channel.
+    pub async fn ensure_mod_log_channel(&self, guild_id: &str) -> Result<Channel> {
+        let channels = self.api.get_channels(&self.token, guild_id).await?;
+        if let Some(channel) = find_text_channel(channels, MOD_LOG_CHANNEL_NAME) {
+            return Ok(channel);
+        }
+        self.api
+            .create_mod_log_channel(&self.token, guild_id, None)
+            .await
+    }
+
+    /// Sets a guild's vanity invite code.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `code` - The new code (2-30 letters, digits or hyphens)
+    ///
+    /// # Returns
+    ///
+    /// The updated vanity URL.
+    pub async fn set_vanity_url(&self, guild_id: &str, code: &str) -> Result<VanityUrl> {
+        self.api
+            .update_guild_vanity_url(&self.token, guild_id, code)
+            .await
+    }
+
+    /// Removes a guild's vanity invite code.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    pub async fn remove_vanity_url(&self, guild_id: &str) -> Result<()> {
+        self.api
+            .delete_guild_vanity_url(&self.token, guild_id)
+            .await
+    }
+
+    /// Sets the search keywords a guild is discovered by.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `keywords` - The new keywords
+    ///
+    /// # Returns
+    ///
+    /// The updated discovery metadata.
+    pub async fn update_guild_keywords(
+        &self,
+        guild_id: &str,
+        keywords: Vec<String>,
+    ) -> Result<GuildDiscoveryMetadata> {
+        self.api
+            .update_guild_discovery_metadata(&self.token, guild_id, Some(keywords), None)
+            .await
+    }
+
+    /// Gets the users currently boosting a guild.
+    ///
+    /// Only the most recent [`BOOST_HISTORY_LIMIT`](crate::api::BOOST_HISTORY_LIMIT)
+    /// boost events are considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    ///
+    /// # Returns
+    ///
+    /// The IDs of boosting users.
+    pub async fn get_current_boosters(&self, guild_id: &str) -> Result<Vec<String>> {
+        self.api.get_current_boosters(&self.token, guild_id).await
+    }
+
+    /// Gets the moderation history of a forum channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel_id` - The forum channel ID
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    ///
+    /// Forum audit log entries, newest first.
+    pub async fn get_forum_audit_log(
+        &self,
+        channel_id: &str,
+        limit: u32,
+    ) -> Result<Vec<ForumAuditEntry>> {
+        self.api
+            .get_forum_audit_log(&self.token, channel_id, limit)
+            .await
+    }
+
+    /// Gets every member of a guild who joined after a specific time.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `since` - Only members who joined after this time are kept
+    ///
+    /// # Returns
+    ///
+    /// The members who joined after `since`.
+    pub async fn get_new_members(
+        &self,
+        guild_id: &str,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GuildMember>> {
+        self.api
+            .get_members_joined_after(&self.token, guild_id, since, u32::MAX)
+            .await
+    }
+
+    /// Gets a member's recent activity in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild ID
+    /// * `user_id` - The user ID
+    ///
+    /// # Returns
+    ///
+    /// The member's activity summary.
+    pub async fn get_member_activity(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+    ) -> Result<MemberActivity> {
+        self.api
+            .get_member_activity(&self.token, guild_id, user_id)
+            .await
+    }
 }
 
 /// Main client for the QQ Guild Bot API.
@@ -993,6 +1846,8 @@ pub struct Client<H: EventHandler> {
     is_sandbox: bool,
     /// Request timeout in seconds
     timeout: u64,
+    /// Registered per-guild configuration stores
+    guild_configs: ConfigMap,
 }
 
 impl<H: EventHandler + 'static> Client<H> {
@@ -1043,6 +1898,7 @@ impl<H: EventHandler + 'static> Client<H> {
             handler: Arc::new(handler),
             is_sandbox,
             timeout,
+            guild_configs: ConfigMap::new(),
         })
     }
 
@@ -1077,9 +1933,34 @@ impl<H: EventHandler + 'static> Client<H> {
             handler: Arc::new(handler),
             is_sandbox,
             timeout,
+            guild_configs: ConfigMap::new(),
         })
     }
 
+    /// Registers a per-guild configuration store.
+    ///
+    /// The store is shared with event handlers, which can access it through
+    /// `Context::config::<T>()`. Registering a second store for the same `T`
+    /// replaces the first.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The configuration store
+    ///
+    /// # Returns
+    ///
+    /// The client with the store registered.
+    pub fn with_guild_config<T: GuildConfig + 'static>(
+        mut self,
+        store: GuildConfigStore<T>,
+    ) -> Self {
+        self.guild_configs.insert(
+            TypeId::of::<GuildConfigStore<T>>(),
+            Arc::new(store) as Arc<dyn Any + Send + Sync>,
+        );
+        self
+    }
+
     /// Starts the bot and connects to the gateway.
     ///
     /// This method will block until the bot is stopped or an error occurs.
@@ -1122,9 +2003,6 @@ impl<H: EventHandler + 'static> Client<H> {
         let gateway_info = self.api.get_gateway(&self.token).await?;
         info!("Gateway URL: {}", gateway_info.url);
 
-        // Create context
-        let ctx = Context::new(self.api.clone(), self.token.clone()).with_bot_info(bot_info);
-
         // Set up event channel
         let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
 
@@ -1136,6 +2014,12 @@ impl<H: EventHandler + 'static> Client<H> {
             None, // TODO: Implement sharding
         );
 
+        // Create context
+        let ctx = Context::new(self.api.clone(), self.token.clone())
+            .with_bot_info(bot_info)
+            .with_guild_configs(Arc::new(self.guild_configs.clone()))
+            .with_events(gateway.event_broadcaster());
+
         // Start gateway connection in a separate task with auto-reconnect
         let gateway_task = {
             let mut gateway_clone = gateway;
@@ -1147,7 +2031,8 @@ impl<H: EventHandler + 'static> Client<H> {
             }
         };
 
-        tokio::spawn(gateway_task);
+        // Stop the gateway if this future is dropped, e.g. by `run_with_shutdown`
+        let _gateway = AbortOnDrop(tokio::spawn(gateway_task));
 
         // Main event processing loop - continue running even if gateway disconnects
         info!("Bot client started, waiting for events...");
@@ -1245,6 +2130,70 @@ impl<H: EventHandler + 'static> Client<H> {
                     }
                 }
             }
+            Some("MESSAGE_UPDATE") => {
+                if let Some(data) = event.data {
+                    match serde_json::from_value::<Message>(data.clone()) {
+                        Ok(message) => {
+                            self.handler.message_update(ctx, message).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to parse MESSAGE_UPDATE event: {}", e);
+                            debug!(
+                                "Raw event data: {}",
+                                serde_json::to_string_pretty(&data).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
+            Some("DIRECT_MESSAGE_UPDATE") => {
+                if let Some(data) = event.data {
+                    match serde_json::from_value::<DirectMessage>(data.clone()) {
+                        Ok(message) => {
+                            self.handler.direct_message_update(ctx, message).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to parse DIRECT_MESSAGE_UPDATE event: {}", e);
+                            debug!(
+                                "Raw event data: {}",
+                                serde_json::to_string_pretty(&data).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
+            Some("GROUP_MESSAGE_UPDATE") => {
+                if let Some(data) = event.data {
+                    match serde_json::from_value::<GroupMessage>(data.clone()) {
+                        Ok(message) => {
+                            self.handler.group_message_update(ctx, message).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to parse GROUP_MESSAGE_UPDATE event: {}", e);
+                            debug!(
+                                "Raw event data: {}",
+                                serde_json::to_string_pretty(&data).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
+            Some("GROUP_THREAD_CREATE") => {
+                if let Some(data) = event.data {
+                    match serde_json::from_value::<GroupThread>(data.clone()) {
+                        Ok(thread) => {
+                            self.handler.group_thread_create(ctx, thread).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to parse GROUP_THREAD_CREATE event: {}", e);
+                            debug!(
+                                "Raw event data: {}",
+                                serde_json::to_string_pretty(&data).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
             Some("C2C_MESSAGE_CREATE") => {
                 if let Some(data) = event.data {
                     let message = C2CMessage::from_data(
@@ -1447,6 +2396,22 @@ impl<H: EventHandler + 'static> Client<H> {
                     }
                 }
             }
+            Some("PRESENCE_UPDATE") => {
+                if let Some(data) = event.data {
+                    match serde_json::from_value::<UserPresence>(data.clone()) {
+                        Ok(presence) => {
+                            self.handler.presence_update(ctx, presence).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to parse PRESENCE_UPDATE event: {}", e);
+                            debug!(
+                                "Raw event data: {}",
+                                serde_json::to_string_pretty(&data).unwrap_or_default()
+                            );
+                        }
+                    }
+                }
+            }
             Some("friend_add") => {
                 if let Some(data) = event.data {
                     let event_id = data
@@ -1582,6 +2547,11 @@ impl<H: EventHandler + 'static> Client<H> {
             Some("audio_or_live_channel_member_enter") => {
                 if let Some(data) = event.data {
                     let audio = PublicAudio::new(ctx.api.as_ref().clone(), data);
+                    if let Some((member, channel_id)) = voice_member_from_audio(&audio, true) {
+                        self.handler
+                            .voice_channel_join(ctx.clone(), member, channel_id)
+                            .await;
+                    }
                     self.handler
                         .audio_or_live_channel_member_enter(ctx, audio)
                         .await;
@@ -1590,6 +2560,11 @@ impl<H: EventHandler + 'static> Client<H> {
             Some("audio_or_live_channel_member_exit") => {
                 if let Some(data) = event.data {
                     let audio = PublicAudio::new(ctx.api.as_ref().clone(), data);
+                    if let Some((member, channel_id)) = voice_member_from_audio(&audio, false) {
+                        self.handler
+                            .voice_channel_leave(ctx.clone(), member, channel_id)
+                            .await;
+                    }
                     self.handler
                         .audio_or_live_channel_member_exit(ctx, audio)
                         .await;
@@ -1637,6 +2612,12 @@ impl<H: EventHandler + 'static> Client<H> {
                     self.handler.open_forum_reply_delete(ctx, thread).await;
                 }
             }
+            Some("FORUM_POST_CREATE") => {
+                if let Some(data) = event.data {
+                    let post = ForumPost::new(&data);
+                    self.handler.forum_post_create(ctx, post).await;
+                }
+            }
             _ => {
                 debug!("Unknown event type: {:?}", event.event_type);
                 self.handler.unknown_event(ctx, event).await;
@@ -1666,6 +2647,39 @@ impl<H: EventHandler + 'static> Client<H> {
         self.is_sandbox
     }
 
+    /// Starts the client and runs until SIGINT (Ctrl+C) or SIGTERM is received.
+    ///
+    /// On a signal the event loop is stopped and [`Client::shutdown`] is called.
+    /// SIGTERM is only handled on Unix platforms.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` after a signal, or the result of [`Client::start`] if it
+    /// finishes first.
+    pub async fn run_until_signal(&mut self) -> Result<()> {
+        self.run_with_shutdown(shutdown_signal()).await
+    }
+
+    /// Starts the client and runs until `shutdown` resolves.
+    ///
+    /// When `shutdown` resolves the event loop is stopped and
+    /// [`Client::shutdown`] is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `shutdown` - Future that resolves when the client should stop
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` after shutdown, or the result of [`Client::start`] if it
+    /// finishes first.
+    pub async fn run_with_shutdown<F>(&mut self, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()>,
+    {
+        run_with_shutdown(self, shutdown).await
+    }
+
     /// Shuts down the client and cleans up resources.
     pub async fn shutdown(&self) {
         info!("Shutting down bot client");
@@ -1673,6 +2687,122 @@ impl<H: EventHandler + 'static> Client<H> {
     }
 }
 
+/// Starting and stopping a client, split out so the shutdown flow can be
+/// tested without a gateway connection.
+#[async_trait::async_trait(?Send)]
+trait Lifecycle {
+    /// Runs the client until it stops on its own.
+    async fn start(&mut self) -> Result<()>;
+
+    /// Cleans up after the client was stopped.
+    async fn shutdown(&self);
+}
+
+#[async_trait::async_trait(?Send)]
+impl<H: EventHandler + 'static> Lifecycle for Client<H> {
+    async fn start(&mut self) -> Result<()> {
+        Client::start(self).await
+    }
+
+    async fn shutdown(&self) {
+        Client::shutdown(self).await
+    }
+}
+
+/// Starts `client` and runs it until `shutdown` resolves, then shuts it down.
+async fn run_with_shutdown<C: Lifecycle>(
+    client: &mut C,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    match run_until(client.start(), shutdown).await {
+        Some(result) => result,
+        None => {
+            info!("Shutdown requested, stopping bot client");
+            client.shutdown().await;
+            Ok(())
+        }
+    }
+}
+
+/// Aborts a spawned task when dropped.
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Runs `run` until it completes or `shutdown` resolves.
+///
+/// Returns `None` if `shutdown` resolved first, dropping `run`.
+async fn run_until<T>(
+    run: impl Future<Output = T>,
+    shutdown: impl Future<Output = ()>,
+) -> Option<T> {
+    tokio::select! {
+        output = run => Some(output),
+        () = shutdown => None,
+    }
+}
+
+/// Resolves when the process receives SIGINT or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => info!("Received Ctrl+C"),
+        () = terminate => info!("Received SIGTERM"),
+    }
+}
+
+/// Finds the text channel with exactly the given name.
+fn find_text_channel(channels: Vec<Channel>, name: &str) -> Option<Channel> {
+    channels.into_iter().find(|channel| {
+        channel.channel_type == Some(ChannelType::Text) && channel.name.as_deref() == Some(name)
+    })
+}
+
+/// Extracts the member and channel from a voice channel enter or exit event.
+///
+/// `joined` is true for enter events, which stamp the member's `joined_at`.
+/// Returns `None` for live channels and events without a channel ID.
+fn voice_member_from_audio(
+    audio: &PublicAudio,
+    joined: bool,
+) -> Option<(VoiceChannelMember, String)> {
+    if audio.channel_type != Some(PublicAudioType::Voice) {
+        return None;
+    }
+    let channel_id = audio.channel_id.clone()?;
+    let joined_at = joined.then(chrono::Utc::now);
+    Some((
+        VoiceChannelMember::from_event_user(audio.user_id.clone(), joined_at),
+        channel_id,
+    ))
+}
+
 impl<H: EventHandler> std::fmt::Debug for Client<H> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Client")
@@ -1682,3 +2812,311 @@ impl<H: EventHandler> std::fmt::Debug for Client<H> {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        updates: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn message_update(&self, _ctx: Context, message: Message) {
+            self.updates
+                .lock()
+                .unwrap()
+                .push(format!("guild:{}", message.content.unwrap_or_default()));
+        }
+
+        async fn direct_message_update(&self, _ctx: Context, message: DirectMessage) {
+            self.updates
+                .lock()
+                .unwrap()
+                .push(format!("direct:{}", message.content.unwrap_or_default()));
+        }
+
+        async fn group_message_update(&self, _ctx: Context, message: GroupMessage) {
+            self.updates
+                .lock()
+                .unwrap()
+                .push(format!("group:{}", message.content.unwrap_or_default()));
+        }
+
+        async fn group_thread_create(&self, _ctx: Context, thread: GroupThread) {
+            self.updates.lock().unwrap().push(format!(
+                "thread:{}:{}",
+                thread.group_openid, thread.thread_id
+            ));
+        }
+
+        async fn voice_channel_join(
+            &self,
+            _ctx: Context,
+            member: VoiceChannelMember,
+            channel_id: String,
+        ) {
+            self.updates.lock().unwrap().push(format!(
+                "join:{}:{channel_id}:{}",
+                member.user.id.unwrap_or_default(),
+                member.joined_at.is_some()
+            ));
+        }
+
+        async fn voice_channel_leave(
+            &self,
+            _ctx: Context,
+            member: VoiceChannelMember,
+            channel_id: String,
+        ) {
+            self.updates.lock().unwrap().push(format!(
+                "leave:{}:{channel_id}:{}",
+                member.user.id.unwrap_or_default(),
+                member.joined_at.is_some()
+            ));
+        }
+    }
+
+    fn dispatch(event_type: &str, data: serde_json::Value) -> GatewayEvent {
+        GatewayEvent {
+            event_type: Some(event_type.to_string()),
+            data: Some(data),
+            sequence: Some(1),
+            opcode: crate::models::gateway::opcodes::DISPATCH,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_message_update_dispatch() {
+        let token = Token::new("test_app_id", "test_secret");
+        let client = Client::new(
+            token.clone(),
+            Intents::default(),
+            RecordingHandler::default(),
+            false,
+        )
+        .unwrap();
+        let ctx = Context::new(client.api.clone(), token);
+
+        let data = serde_json::json!({
+            "id": "m1",
+            "content": "fixed typo",
+            "mentions": [],
+            "attachments": [],
+            "edited_at": "2024-01-01T00:05:00Z"
+        });
+        for event_type in [
+            "MESSAGE_UPDATE",
+            "DIRECT_MESSAGE_UPDATE",
+            "GROUP_MESSAGE_UPDATE",
+        ] {
+            client
+                .handle_event(ctx.clone(), dispatch(event_type, data.clone()))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            *client.handler.updates.lock().unwrap(),
+            vec!["guild:fixed typo", "direct:fixed typo", "group:fixed typo"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_thread_create_dispatch() {
+        let token = Token::new("test_app_id", "test_secret");
+        let client = Client::new(
+            token.clone(),
+            Intents::default(),
+            RecordingHandler::default(),
+            false,
+        )
+        .unwrap();
+        let ctx = Context::new(client.api.clone(), token);
+
+        let data = serde_json::json!({
+            "thread_id": "t1",
+            "group_openid": "g1",
+            "creator_openid": "u1",
+            "created_at": "2024-01-01T00:00:00Z"
+        });
+        client
+            .handle_event(ctx.clone(), dispatch("GROUP_THREAD_CREATE", data.clone()))
+            .await
+            .unwrap();
+        // Malformed threads are logged and dropped.
+        client
+            .handle_event(
+                ctx,
+                dispatch(
+                    "GROUP_THREAD_CREATE",
+                    serde_json::json!({"thread_id": "t2"}),
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *client.handler.updates.lock().unwrap(),
+            vec!["thread:g1:t1"]
+        );
+
+        let token = Token::new("test_app_id", "test_secret");
+        let filtered = Client::new(
+            token.clone(),
+            Intents::default(),
+            crate::filter::FilteredHandler::new(
+                RecordingHandler::default(),
+                crate::filter::FilterChain::new(),
+            ),
+            false,
+        )
+        .unwrap();
+        filtered
+            .handle_event(
+                Context::new(filtered.api.clone(), token),
+                dispatch("GROUP_THREAD_CREATE", data),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            *filtered.handler.inner().updates.lock().unwrap(),
+            vec!["thread:g1:t1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voice_channel_dispatch() {
+        let token = Token::new("test_app_id", "test_secret");
+        let client = Client::new(
+            token.clone(),
+            Intents::default(),
+            RecordingHandler::default(),
+            false,
+        )
+        .unwrap();
+        let ctx = Context::new(client.api.clone(), token);
+
+        let data = serde_json::json!({
+            "guild_id": "g1",
+            "channel_id": "c1",
+            "channel_type": 2,
+            "user_id": "u1"
+        });
+        for event_type in [
+            "audio_or_live_channel_member_enter",
+            "audio_or_live_channel_member_exit",
+        ] {
+            client
+                .handle_event(ctx.clone(), dispatch(event_type, data.clone()))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            *client.handler.updates.lock().unwrap(),
+            vec!["join:u1:c1:true", "leave:u1:c1:false"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_context_watch_guild_changes() {
+        use futures_util::StreamExt;
+
+        let token = Token::new("test_app_id", "test_secret");
+        let events = crate::gateway::event_channel();
+        let ctx = Context::new(
+            Arc::new(BotApi::new(HttpClient::new(30, false).unwrap())),
+            token,
+        )
+        .with_events(events.clone());
+
+        let mut changes = Box::pin(ctx.watch_guild_changes("g1"));
+        for guild_id in ["g2", "g1"] {
+            events
+                .send(dispatch(
+                    "GUILD_ROLE_DELETE",
+                    serde_json::json!({"guild_id": guild_id, "role_id": "r1"}),
+                ))
+                .unwrap();
+        }
+
+        let change = changes.next().await.unwrap();
+        assert_eq!(change.guild_id, "g1");
+        assert_eq!(
+            change.kind,
+            crate::models::guild::GuildChangeKind::RoleDeleted("r1".to_string())
+        );
+    }
+
+    /// Stands in for a client, recording whether it was shut down.
+    struct FakeLifecycle {
+        finishes: bool,
+        shut_down: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl Lifecycle for FakeLifecycle {
+        async fn start(&mut self) -> Result<()> {
+            if self.finishes {
+                Err(BotError::connection("gateway closed"))
+            } else {
+                // Like the event loop, never finishes on its own
+                std::future::pending().await
+            }
+        }
+
+        async fn shutdown(&self) {
+            self.shut_down
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_with_shutdown() {
+        let fake = |finishes| FakeLifecycle {
+            finishes,
+            shut_down: std::sync::atomic::AtomicBool::new(false),
+        };
+
+        // Stands in for the signal
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let mut client = fake(false);
+        tx.send(()).unwrap();
+        run_with_shutdown(&mut client, async {
+            rx.await.ok();
+        })
+        .await
+        .unwrap();
+        assert!(client.shut_down.load(std::sync::atomic::Ordering::SeqCst));
+
+        // A client that stops on its own returns its result without a shutdown
+        let mut client = fake(true);
+        let result = run_with_shutdown(&mut client, std::future::pending()).await;
+        assert!(matches!(result, Err(BotError::Connection(_))));
+        assert!(!client.shut_down.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_find_text_channel() {
+        let channel = |id: &str, name: &str, channel_type: ChannelType| {
+            let mut channel = Channel::new();
+            channel.id = Some(id.to_string());
+            channel.name = Some(name.to_string());
+            channel.channel_type = Some(channel_type);
+            channel
+        };
+        let channels = vec![
+            channel("1", "mod-log", ChannelType::Group),
+            channel("2", "general", ChannelType::Text),
+            channel("3", "mod-log", ChannelType::Text),
+        ];
+
+        let found = find_text_channel(channels.clone(), MOD_LOG_CHANNEL_NAME);
+        assert_eq!(found.and_then(|c| c.id).as_deref(), Some("3"));
+        assert!(find_text_channel(channels[..2].to_vec(), MOD_LOG_CHANNEL_NAME).is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! Per-guild configuration storage.
+//!
+//! This module provides the `GuildConfig` trait and a thread-safe
+//! `GuildConfigStore` for keeping settings such as command prefixes, languages
+//! or admin roles per guild. Stores registered with `Client::with_guild_config`
+//! are available to event handlers through `Context::config`.
+
+use dashmap::DashMap;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Type-erased map of registered configuration stores, keyed by store type.
+pub(crate) type ConfigMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+/// Settings that belong to a single guild.
+pub trait GuildConfig: Send + Sync {
+    /// Returns the ID of the guild these settings belong to.
+    fn guild_id(&self) -> &str;
+
+    /// Sets the ID of the guild these settings belong to.
+    ///
+    /// Called by [`GuildConfigStore::get_or_default`] on a new default
+    /// configuration before it is stored. Does nothing unless overridden;
+    /// the default configuration is stored under the requested guild ID
+    /// either way.
+    fn set_guild_id(&mut self, _guild_id: &str) {}
+}
+
+/// Thread-safe store of per-guild configurations.
+///
+/// # Examples
+///
+/// ```rust
+/// use botrs::config::{GuildConfig, GuildConfigStore};
+///
+/// #[derive(Default)]
+/// struct Settings {
+///     guild_id: String,
+///     prefix: String,
+/// }
+///
+/// impl GuildConfig for Settings {
+///     fn guild_id(&self) -> &str {
+///         &self.guild_id
+///     }
+/// }
+///
+/// let store = GuildConfigStore::new();
+/// store.set(Settings {
+///     guild_id: "123".to_string(),
+///     prefix: "!".to_string(),
+/// });
+/// assert_eq!(store.get("123").unwrap().prefix, "!");
+/// ```
+pub struct GuildConfigStore<T: GuildConfig> {
+    configs: DashMap<String, Arc<T>>,
+}
+
+impl<T: GuildConfig> GuildConfigStore<T> {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self {
+            configs: DashMap::new(),
+        }
+    }
+
+    /// Gets the configuration for a guild.
+    pub fn get(&self, guild_id: &str) -> Option<Arc<T>> {
+        self.configs
+            .get(guild_id)
+            .map(|config| config.value().clone())
+    }
+
+    /// Inserts or replaces the configuration for the guild it belongs to.
+    pub fn set(&self, config: T) {
+        let guild_id = config.guild_id().to_string();
+        self.configs.insert(guild_id, Arc::new(config));
+    }
+
+    /// Removes and returns the configuration for a guild.
+    pub fn remove(&self, guild_id: &str) -> Option<Arc<T>> {
+        self.configs.remove(guild_id).map(|(_, config)| config)
+    }
+
+    /// Returns the number of guilds with a stored configuration.
+    pub fn len(&self) -> usize {
+        self.configs.len()
+    }
+
+    /// Returns true if no configurations are stored.
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+}
+
+impl<T: GuildConfig + Default> GuildConfigStore<T> {
+    /// Gets the configuration for a guild, storing `T::default()` under
+    /// `guild_id` if it has none.
+    pub fn get_or_default(&self, guild_id: &str) -> Arc<T> {
+        if let Some(config) = self.get(guild_id) {
+            return config;
+        }
+        self.configs
+            .entry(guild_id.to_string())
+            .or_insert_with(|| {
+                let mut config = T::default();
+                config.set_guild_id(guild_id);
+                Arc::new(config)
+            })
+            .clone()
+    }
+}
+
+impl<T: GuildConfig> Default for GuildConfigStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: GuildConfig> std::fmt::Debug for GuildConfigStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuildConfigStore")
+            .field("guilds", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::BotApi;
+    use crate::client::Context;
+    use crate::http::HttpClient;
+    use crate::token::Token;
+    use std::time::Duration;
+
+    #[derive(Debug, Default)]
+    struct Settings {
+        guild_id: String,
+        prefix: String,
+    }
+
+    impl GuildConfig for Settings {
+        fn guild_id(&self) -> &str {
+            &self.guild_id
+        }
+
+        fn set_guild_id(&mut self, guild_id: &str) {
+            self.guild_id = guild_id.to_string();
+        }
+    }
+
+    fn settings(guild_id: &str, prefix: &str) -> Settings {
+        Settings {
+            guild_id: guild_id.to_string(),
+            prefix: prefix.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_store_operations() {
+        let store = GuildConfigStore::new();
+        assert!(store.get("1").is_none());
+
+        store.set(settings("1", "!"));
+        assert_eq!(store.get("1").unwrap().prefix, "!");
+
+        store.set(settings("1", "/"));
+        assert_eq!(store.get("1").unwrap().prefix, "/");
+        assert_eq!(store.len(), 1);
+
+        assert!(store.remove("1").is_some());
+        assert!(store.is_empty());
+
+        let config = store.get_or_default("2");
+        assert_eq!(config.prefix, "");
+        assert_eq!(config.guild_id(), "2");
+        assert_eq!(store.get("2").unwrap().guild_id(), "2");
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn test_context_config_lookup() {
+        let store = GuildConfigStore::new();
+        store.set(settings("1", "!"));
+
+        let mut configs = ConfigMap::new();
+        configs.insert(
+            TypeId::of::<GuildConfigStore<Settings>>(),
+            Arc::new(store) as Arc<dyn Any + Send + Sync>,
+        );
+
+        let api = Arc::new(BotApi::new(HttpClient::new(30, false).unwrap()));
+        let ctx =
+            Context::new(api, Token::new("app", "secret")).with_guild_configs(Arc::new(configs));
+
+        let store = ctx.config::<Settings>().unwrap();
+        assert_eq!(store.get("1").unwrap().prefix, "!");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_access() {
+        let store = Arc::new(GuildConfigStore::<Settings>::new());
+
+        let tasks: Vec<_> = (0..16)
+            .map(|i| {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    for j in 0..200 {
+                        let guild_id = format!("{}", (i + j) % 8);
+                        store.set(settings(&guild_id, "!"));
+                        let _ = store.get(&guild_id);
+                        let _ = store.get_or_default(&format!("default-{}", j % 4));
+                        if j % 10 == 0 {
+                            store.remove(&guild_id);
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            for task in tasks {
+                task.await.unwrap();
+            }
+        })
+        .await
+        .expect("concurrent store access deadlocked");
+
+        assert!(store.get("default-0").is_some());
+    }
+}
@@ -74,6 +74,14 @@ pub enum BotError {
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
+    /// Operation not supported by the target channel
+    #[error("Invalid channel: {0}")]
+    InvalidChannel(String),
+
+    /// Invalid request parameter errors
+    #[error("Invalid parameters: {0}")]
+    InvalidParams(String),
+
     /// Network timeout errors
     #[error("Network timeout")]
     Timeout,
@@ -97,6 +105,10 @@ pub enum BotError {
     /// Not implemented errors
     #[error("Not implemented: {0}")]
     NotImplemented(String),
+
+    /// Webhook request signature errors
+    #[error("Invalid webhook signature: {0}")]
+    WebhookSignature(#[from] crate::webhook::WebhookSignatureError),
 }
 
 impl BotError {
@@ -128,6 +140,16 @@ impl BotError {
         Self::InvalidData(message.into())
     }
 
+    /// Creates a new invalid parameters error.
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::InvalidParams(message.into())
+    }
+
+    /// Creates a new invalid channel error.
+    pub fn invalid_channel(message: impl Into<String>) -> Self {
+        Self::InvalidChannel(message.into())
+    }
+
     /// Creates a new gateway error.
     pub fn gateway(message: impl Into<String>) -> Self {
         Self::Gateway(message.into())
@@ -0,0 +1,783 @@
+//! Message content filtering for QQ Bot
+//!
+//! This module provides the `MessageFilter` trait along with a set of built-in
+//! filters that can be composed into a `FilterChain`. A chain can be attached to
+//! any event handler through `FilteredHandler`, which checks incoming guild
+//! messages and applies the resulting moderation action before dispatching.
+
+use crate::audio::PublicAudio;
+use crate::client::{Context, EventHandler};
+use crate::error::BotError;
+use crate::forum::{ForumPost, OpenThread};
+use crate::manage::{C2CManageEvent, GroupManageEvent};
+use crate::models::channel::VoiceChannelMember;
+use crate::models::gateway::{GatewayEvent, Ready};
+use crate::models::*;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Action to take on a message after filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Let the message through
+    Allow,
+    /// Let the message through but log a warning
+    Warn,
+    /// Recall the message
+    Delete,
+    /// Recall the message and mute the author for the given duration
+    Mute(Duration),
+    /// Recall the message and kick the author from the guild
+    Kick,
+}
+
+impl FilterAction {
+    /// Get the severity rank of this action, higher being stricter
+    fn severity(&self) -> u8 {
+        match self {
+            Self::Allow => 0,
+            Self::Warn => 1,
+            Self::Delete => 2,
+            Self::Mute(_) => 3,
+            Self::Kick => 4,
+        }
+    }
+
+    /// Check whether this action is stricter than another one
+    ///
+    /// Longer mutes are considered stricter than shorter ones.
+    pub fn is_stricter_than(&self, other: &FilterAction) -> bool {
+        match (self, other) {
+            (Self::Mute(a), Self::Mute(b)) => a > b,
+            _ => self.severity() > other.severity(),
+        }
+    }
+}
+
+/// Result of checking a message against a filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterResult {
+    /// The action to take
+    pub action: FilterAction,
+    /// Why the action was chosen
+    pub reason: Option<String>,
+}
+
+impl FilterResult {
+    /// Create a result that allows the message
+    pub fn allow() -> Self {
+        Self {
+            action: FilterAction::Allow,
+            reason: None,
+        }
+    }
+
+    /// Create a result with the given action and reason
+    pub fn new(action: FilterAction, reason: impl Into<String>) -> Self {
+        Self {
+            action,
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// Check whether the message is allowed through unchanged
+    pub fn is_allowed(&self) -> bool {
+        self.action == FilterAction::Allow
+    }
+}
+
+/// A content filter that inspects messages
+pub trait MessageFilter: Send + Sync {
+    /// Check a message and decide what to do with it
+    fn check(&self, message: &Message) -> FilterResult;
+}
+
+/// Filter rejecting messages that contain any of the given words
+///
+/// Matching is case-insensitive.
+#[derive(Debug, Clone)]
+pub struct WordlistFilter {
+    words: Vec<String>,
+    action: FilterAction,
+}
+
+impl WordlistFilter {
+    /// Create a new WordlistFilter instance
+    pub fn new(words: &[&str]) -> Self {
+        Self {
+            words: words.iter().map(|w| w.to_lowercase()).collect(),
+            action: FilterAction::Delete,
+        }
+    }
+
+    /// Set the action taken on a match (defaults to `Delete`)
+    pub fn with_action(mut self, action: FilterAction) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+impl MessageFilter for WordlistFilter {
+    fn check(&self, message: &Message) -> FilterResult {
+        let content = message
+            .content
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase();
+        match self.words.iter().find(|w| content.contains(w.as_str())) {
+            Some(word) => FilterResult::new(self.action, format!("contains banned word '{word}'")),
+            None => FilterResult::allow(),
+        }
+    }
+}
+
+/// Filter rejecting messages that mention too many users
+#[derive(Debug, Clone)]
+pub struct MentionSpamFilter {
+    max_mentions: usize,
+    action: FilterAction,
+}
+
+impl MentionSpamFilter {
+    /// Create a new MentionSpamFilter instance
+    pub fn new(max_mentions: usize) -> Self {
+        Self {
+            max_mentions,
+            action: FilterAction::Mute(Duration::from_secs(600)),
+        }
+    }
+
+    /// Set the action taken on a match (defaults to a 10 minute mute)
+    pub fn with_action(mut self, action: FilterAction) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+impl MessageFilter for MentionSpamFilter {
+    fn check(&self, message: &Message) -> FilterResult {
+        let count = message.mentions.len();
+        if count > self.max_mentions {
+            FilterResult::new(
+                self.action,
+                format!("{count} mentions exceeds limit of {}", self.max_mentions),
+            )
+        } else {
+            FilterResult::allow()
+        }
+    }
+}
+
+/// Filter rejecting links to domains outside an allow list
+///
+/// Subdomains of an allowed domain are also allowed.
+#[derive(Debug, Clone)]
+pub struct UrlFilter {
+    allowed_domains: Vec<String>,
+    action: FilterAction,
+}
+
+impl UrlFilter {
+    /// Create a new UrlFilter instance
+    pub fn new(allowed_domains: &[&str]) -> Self {
+        Self {
+            allowed_domains: allowed_domains.iter().map(|d| d.to_lowercase()).collect(),
+            action: FilterAction::Delete,
+        }
+    }
+
+    /// Set the action taken on a match (defaults to `Delete`)
+    pub fn with_action(mut self, action: FilterAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    fn is_allowed_host(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        self.allowed_domains
+            .iter()
+            .any(|d| host == *d || host.ends_with(&format!(".{d}")))
+    }
+}
+
+impl MessageFilter for UrlFilter {
+    fn check(&self, message: &Message) -> FilterResult {
+        let content = message.content.as_deref().unwrap_or_default();
+        for word in content.split_whitespace() {
+            let Some(start) = word.find("http://").or_else(|| word.find("https://")) else {
+                continue;
+            };
+            let host = url::Url::parse(&word[start..])
+                .ok()
+                .and_then(|u| u.host_str().map(String::from));
+            match host {
+                Some(host) if self.is_allowed_host(&host) => {}
+                Some(host) => {
+                    return FilterResult::new(
+                        self.action,
+                        format!("link to disallowed domain '{host}'"),
+                    );
+                }
+                None => return FilterResult::new(self.action, "malformed link"),
+            }
+        }
+        FilterResult::allow()
+    }
+}
+
+/// Filter rejecting messages longer than a maximum number of characters
+#[derive(Debug, Clone)]
+pub struct LengthFilter {
+    max_len: usize,
+    action: FilterAction,
+}
+
+impl LengthFilter {
+    /// Create a new LengthFilter instance
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            action: FilterAction::Delete,
+        }
+    }
+
+    /// Set the action taken on a match (defaults to `Delete`)
+    pub fn with_action(mut self, action: FilterAction) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+impl MessageFilter for LengthFilter {
+    fn check(&self, message: &Message) -> FilterResult {
+        let len = message
+            .content
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .count();
+        if len > self.max_len {
+            FilterResult::new(
+                self.action,
+                format!("message length {len} exceeds limit of {}", self.max_len),
+            )
+        } else {
+            FilterResult::allow()
+        }
+    }
+}
+
+/// A composition of filters returning the strictest action among them
+#[derive(Default)]
+pub struct FilterChain {
+    filters: Vec<Box<dyn MessageFilter>>,
+}
+
+impl FilterChain {
+    /// Create an empty FilterChain instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a filter to the chain
+    pub fn with_filter(mut self, filter: impl MessageFilter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Add a filter to an existing chain
+    pub fn push(&mut self, filter: impl MessageFilter + 'static) {
+        self.filters.push(Box::new(filter));
+    }
+
+    /// Get the number of filters in the chain
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Check whether the chain has no filters
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+}
+
+impl MessageFilter for FilterChain {
+    fn check(&self, message: &Message) -> FilterResult {
+        self.filters.iter().map(|f| f.check(message)).fold(
+            FilterResult::allow(),
+            |strictest, result| {
+                if result.action.is_stricter_than(&strictest.action) {
+                    result
+                } else {
+                    strictest
+                }
+            },
+        )
+    }
+}
+
+impl std::fmt::Debug for FilterChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilterChain")
+            .field("filters", &self.filters.len())
+            .finish()
+    }
+}
+
+/// Event handler adapter applying a `FilterChain` to guild messages
+///
+/// New and edited messages passing the chain (or only producing a warning) are
+/// forwarded to the inner handler's `message_create` or `message_update`.
+/// Other actions recall the message and moderate the author instead. All
+/// remaining events are forwarded unchanged.
+pub struct FilteredHandler<H: EventHandler> {
+    inner: H,
+    chain: FilterChain,
+}
+
+impl<H: EventHandler> FilteredHandler<H> {
+    /// Create a new FilteredHandler instance
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The handler receiving messages that pass the chain
+    /// * `chain` - The filters to apply
+    pub fn new(inner: H, chain: FilterChain) -> Self {
+        Self { inner, chain }
+    }
+
+    /// Get the inner handler
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    /// Get the filter chain
+    pub fn chain(&self) -> &FilterChain {
+        &self.chain
+    }
+
+    /// Runs the chain on a message, enforcing any moderation action.
+    ///
+    /// Returns true if the message should be passed to the inner handler.
+    async fn screen(&self, ctx: &Context, message: &Message) -> bool {
+        let result = self.chain.check(message);
+        match result.action {
+            FilterAction::Allow => true,
+            FilterAction::Warn => {
+                warn!(
+                    "Message {:?} flagged by filter: {}",
+                    message.id,
+                    result.reason.as_deref().unwrap_or_default()
+                );
+                true
+            }
+            action => {
+                debug!(
+                    "Message {:?} blocked by filter ({:?}): {}",
+                    message.id,
+                    action,
+                    result.reason.as_deref().unwrap_or_default()
+                );
+                self.enforce(ctx, message, action).await;
+                false
+            }
+        }
+    }
+
+    async fn enforce(&self, ctx: &Context, message: &Message, action: FilterAction) {
+        let (Some(channel_id), Some(message_id)) = (&message.channel_id, &message.id) else {
+            return;
+        };
+        if let Err(e) = ctx.recall_message(channel_id, message_id, true).await {
+            self.inner.error(e).await;
+        }
+
+        let guild_id = message.guild_id.as_deref();
+        let user_id = message.author.as_ref().and_then(|a| a.id.as_deref());
+        let (Some(guild_id), Some(user_id)) = (guild_id, user_id) else {
+            return;
+        };
+        let result = match action {
+            FilterAction::Mute(duration) => {
+                let seconds = duration.as_secs().to_string();
+                ctx.mute_member(guild_id, user_id, None, Some(&seconds))
+                    .await
+            }
+            FilterAction::Kick => ctx.kick_member(guild_id, user_id, None, None).await,
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            self.inner.error(e).await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<H: EventHandler> EventHandler for FilteredHandler<H> {
+    async fn message_create(&self, ctx: Context, message: Message) {
+        if self.screen(&ctx, &message).await {
+            self.inner.message_create(ctx, message).await;
+        }
+    }
+
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        self.inner.ready(ctx, ready).await;
+    }
+
+    async fn direct_message_create(&self, ctx: Context, message: DirectMessage) {
+        self.inner.direct_message_create(ctx, message).await;
+    }
+
+    async fn group_message_create(&self, ctx: Context, message: GroupMessage) {
+        self.inner.group_message_create(ctx, message).await;
+    }
+
+    async fn group_thread_create(&self, ctx: Context, thread: GroupThread) {
+        self.inner.group_thread_create(ctx, thread).await;
+    }
+
+    async fn c2c_message_create(&self, ctx: Context, message: C2CMessage) {
+        self.inner.c2c_message_create(ctx, message).await;
+    }
+
+    async fn message_delete(&self, ctx: Context, message: Message) {
+        self.inner.message_delete(ctx, message).await;
+    }
+
+    async fn guild_create(&self, ctx: Context, guild: Guild) {
+        self.inner.guild_create(ctx, guild).await;
+    }
+
+    async fn guild_update(&self, ctx: Context, guild: Guild) {
+        self.inner.guild_update(ctx, guild).await;
+    }
+
+    async fn guild_delete(&self, ctx: Context, guild: Guild) {
+        self.inner.guild_delete(ctx, guild).await;
+    }
+
+    async fn channel_create(&self, ctx: Context, channel: Channel) {
+        self.inner.channel_create(ctx, channel).await;
+    }
+
+    async fn channel_update(&self, ctx: Context, channel: Channel) {
+        self.inner.channel_update(ctx, channel).await;
+    }
+
+    async fn channel_delete(&self, ctx: Context, channel: Channel) {
+        self.inner.channel_delete(ctx, channel).await;
+    }
+
+    async fn guild_member_add(&self, ctx: Context, member: Member) {
+        self.inner.guild_member_add(ctx, member).await;
+    }
+
+    async fn guild_member_update(&self, ctx: Context, member: Member) {
+        self.inner.guild_member_update(ctx, member).await;
+    }
+
+    async fn guild_member_remove(&self, ctx: Context, member: Member) {
+        self.inner.guild_member_remove(ctx, member).await;
+    }
+
+    async fn message_audit_pass(&self, ctx: Context, audit: MessageAudit) {
+        self.inner.message_audit_pass(ctx, audit).await;
+    }
+
+    async fn message_audit_reject(&self, ctx: Context, audit: MessageAudit) {
+        self.inner.message_audit_reject(ctx, audit).await;
+    }
+
+    async fn presence_update(&self, ctx: Context, presence: UserPresence) {
+        self.inner.presence_update(ctx, presence).await;
+    }
+
+    async fn friend_add(&self, ctx: Context, event: C2CManageEvent) {
+        self.inner.friend_add(ctx, event).await;
+    }
+
+    async fn friend_del(&self, ctx: Context, event: C2CManageEvent) {
+        self.inner.friend_del(ctx, event).await;
+    }
+
+    async fn c2c_msg_reject(&self, ctx: Context, event: C2CManageEvent) {
+        self.inner.c2c_msg_reject(ctx, event).await;
+    }
+
+    async fn c2c_msg_receive(&self, ctx: Context, event: C2CManageEvent) {
+        self.inner.c2c_msg_receive(ctx, event).await;
+    }
+
+    async fn group_add_robot(&self, ctx: Context, event: GroupManageEvent) {
+        self.inner.group_add_robot(ctx, event).await;
+    }
+
+    async fn group_del_robot(&self, ctx: Context, event: GroupManageEvent) {
+        self.inner.group_del_robot(ctx, event).await;
+    }
+
+    async fn group_msg_reject(&self, ctx: Context, event: GroupManageEvent) {
+        self.inner.group_msg_reject(ctx, event).await;
+    }
+
+    async fn group_msg_receive(&self, ctx: Context, event: GroupManageEvent) {
+        self.inner.group_msg_receive(ctx, event).await;
+    }
+
+    async fn audio_or_live_channel_member_enter(&self, ctx: Context, audio: PublicAudio) {
+        self.inner
+            .audio_or_live_channel_member_enter(ctx, audio)
+            .await;
+    }
+
+    async fn audio_or_live_channel_member_exit(&self, ctx: Context, audio: PublicAudio) {
+        self.inner
+            .audio_or_live_channel_member_exit(ctx, audio)
+            .await;
+    }
+
+    async fn open_forum_thread_create(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_thread_create(ctx, thread).await;
+    }
+
+    async fn open_forum_thread_update(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_thread_update(ctx, thread).await;
+    }
+
+    async fn open_forum_thread_delete(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_thread_delete(ctx, thread).await;
+    }
+
+    async fn open_forum_post_create(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_post_create(ctx, thread).await;
+    }
+
+    async fn open_forum_post_delete(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_post_delete(ctx, thread).await;
+    }
+
+    async fn open_forum_reply_create(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_reply_create(ctx, thread).await;
+    }
+
+    async fn open_forum_reply_delete(&self, ctx: Context, thread: OpenThread) {
+        self.inner.open_forum_reply_delete(ctx, thread).await;
+    }
+
+    async fn forum_post_create(&self, ctx: Context, post: ForumPost) {
+        self.inner.forum_post_create(ctx, post).await;
+    }
+
+    async fn message_update(&self, ctx: Context, message: Message) {
+        if self.screen(&ctx, &message).await {
+            self.inner.message_update(ctx, message).await;
+        }
+    }
+
+    async fn direct_message_update(&self, ctx: Context, message: DirectMessage) {
+        self.inner.direct_message_update(ctx, message).await;
+    }
+
+    async fn group_message_update(&self, ctx: Context, message: GroupMessage) {
+        self.inner.group_message_update(ctx, message).await;
+    }
+
+    async fn voice_channel_join(
+        &self,
+        ctx: Context,
+        member: VoiceChannelMember,
+        channel_id: String,
+    ) {
+        self.inner.voice_channel_join(ctx, member, channel_id).await;
+    }
+
+    async fn voice_channel_leave(
+        &self,
+        ctx: Context,
+        member: VoiceChannelMember,
+        channel_id: String,
+    ) {
+        self.inner
+            .voice_channel_leave(ctx, member, channel_id)
+            .await;
+    }
+
+    async fn unknown_event(&self, ctx: Context, event: GatewayEvent) {
+        self.inner.unknown_event(ctx, event).await;
+    }
+
+    async fn error(&self, error: BotError) {
+        self.inner.error(error).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> Message {
+        Message {
+            content: Some(content.to_string()),
+            ..Message::new()
+        }
+    }
+
+    #[test]
+    fn test_wordlist_filter() {
+        let filter = WordlistFilter::new(&["spam", "Scam"]);
+        assert!(filter.check(&message("hello world")).is_allowed());
+
+        let result = filter.check(&message("this is a SCAM"));
+        assert_eq!(result.action, FilterAction::Delete);
+        assert!(result.reason.unwrap().contains("scam"));
+    }
+
+    #[test]
+    fn test_mention_spam_filter() {
+        let filter = MentionSpamFilter::new(2);
+        let mut msg = message("hi all");
+        msg.mentions = vec![MessageUser::from_data(serde_json::json!({"id": "1"})); 2];
+        assert!(filter.check(&msg).is_allowed());
+
+        msg.mentions
+            .push(MessageUser::from_data(serde_json::json!({"id": "3"})));
+        assert!(matches!(filter.check(&msg).action, FilterAction::Mute(_)));
+    }
+
+    #[test]
+    fn test_url_filter() {
+        let filter = UrlFilter::new(&["qq.com"]);
+        assert!(filter.check(&message("no links here")).is_allowed());
+        assert!(
+            filter
+                .check(&message("see https://bot.q.qq.com/wiki"))
+                .is_allowed()
+        );
+
+        let result = filter.check(&message("click https://evil.example/x"));
+        assert_eq!(result.action, FilterAction::Delete);
+        assert!(result.reason.unwrap().contains("evil.example"));
+
+        // A suffix match without a dot boundary must not be allowed
+        assert!(!filter.check(&message("http://notqq.com")).is_allowed());
+    }
+
+    #[test]
+    fn test_length_filter() {
+        let filter = LengthFilter::new(5).with_action(FilterAction::Warn);
+        assert!(filter.check(&message("你好世界")).is_allowed());
+        assert_eq!(
+            filter.check(&message("too long")).action,
+            FilterAction::Warn
+        );
+    }
+
+    #[test]
+    fn test_chain_returns_strictest_action() {
+        let chain = FilterChain::new()
+            .with_filter(LengthFilter::new(3).with_action(FilterAction::Warn))
+            .with_filter(WordlistFilter::new(&["spam"]))
+            .with_filter(WordlistFilter::new(&["kickme"]).with_action(FilterAction::Kick));
+        assert_eq!(chain.len(), 3);
+
+        assert!(chain.check(&message("ok")).is_allowed());
+        assert_eq!(chain.check(&message("hello")).action, FilterAction::Warn);
+        assert_eq!(chain.check(&message("spam!")).action, FilterAction::Delete);
+        assert_eq!(
+            chain.check(&message("spam kickme")).action,
+            FilterAction::Kick
+        );
+    }
+
+    #[test]
+    fn test_longer_mute_is_stricter() {
+        let short = FilterAction::Mute(Duration::from_secs(60));
+        let long = FilterAction::Mute(Duration::from_secs(600));
+        assert!(long.is_stricter_than(&short));
+        assert!(!short.is_stricter_than(&long));
+        assert!(FilterAction::Kick.is_stricter_than(&long));
+        assert!(short.is_stricter_than(&FilterAction::Delete));
+    }
+
+    #[test]
+    fn test_empty_chain_allows() {
+        let chain = FilterChain::new();
+        assert!(chain.is_empty());
+        assert!(chain.check(&message("anything")).is_allowed());
+    }
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        received: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn message_create(&self, _ctx: Context, message: Message) {
+            self.received
+                .lock()
+                .unwrap()
+                .push(format!("create:{}", message.content.unwrap_or_default()));
+        }
+
+        async fn message_update(&self, _ctx: Context, message: Message) {
+            self.received
+                .lock()
+                .unwrap()
+                .push(format!("update:{}", message.content.unwrap_or_default()));
+        }
+
+        async fn presence_update(&self, _ctx: Context, presence: UserPresence) {
+            self.received
+                .lock()
+                .unwrap()
+                .push(format!("presence:{}", presence.user_id));
+        }
+    }
+
+    fn test_context() -> Context {
+        let http = crate::http::HttpClient::new(30, false).unwrap();
+        Context::new(
+            std::sync::Arc::new(crate::api::BotApi::new(http)),
+            crate::token::Token::new("test_app_id", "test_secret"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_filtered_handler_screens_edits() {
+        let handler = FilteredHandler::new(
+            RecordingHandler::default(),
+            FilterChain::new().with_filter(WordlistFilter::new(&["scam"])),
+        );
+        let ctx = test_context();
+
+        handler.message_create(ctx.clone(), message("hello")).await;
+        handler.message_create(ctx.clone(), message("a scam")).await;
+        handler
+            .message_update(ctx.clone(), message("hello again"))
+            .await;
+        handler
+            .message_update(ctx, message("edited into a scam"))
+            .await;
+
+        assert_eq!(
+            *handler.inner().received.lock().unwrap(),
+            vec!["create:hello", "update:hello again"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_handler_forwards_presence() {
+        let handler = FilteredHandler::new(RecordingHandler::default(), FilterChain::new());
+        let presence: UserPresence =
+            serde_json::from_value(serde_json::json!({"user_id": "u1", "status": "online"}))
+                .unwrap();
+
+        handler.presence_update(test_context(), presence).await;
+
+        assert_eq!(
+            *handler.inner().received.lock().unwrap(),
+            vec!["presence:u1"]
+        );
+    }
+}
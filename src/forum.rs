@@ -4,8 +4,13 @@
 //! posts, replies, and open forum events.
 
 use crate::api::BotApi;
+use crate::error::Result;
+use crate::models::Timestamp;
+use crate::models::channel::Channel;
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::VecDeque;
 
 /// Forum content format enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +26,19 @@ pub enum Format {
     Json = 4,
 }
 
+impl Format {
+    /// Converts a numeric format code into a `Format`.
+    pub fn from_code(code: u64) -> Option<Self> {
+        match code {
+            1 => Some(Self::PlainText),
+            2 => Some(Self::Html),
+            3 => Some(Self::Markdown),
+            4 => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
 /// Text element structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Text {
@@ -290,6 +308,21 @@ impl Title {
 
         Self { paragraphs }
     }
+
+    /// Returns the title's text, with paragraphs separated by newlines.
+    pub fn plain_text(&self) -> String {
+        self.paragraphs
+            .iter()
+            .map(|paragraph| {
+                paragraph
+                    .elems
+                    .iter()
+                    .filter_map(|elem| elem.text.as_ref()?.text.as_deref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 /// Content structure
@@ -420,6 +453,99 @@ impl std::fmt::Display for Thread {
     }
 }
 
+/// A forum channel with a summary of its threads
+#[derive(Debug, Clone)]
+pub struct ForumChannelSummary {
+    /// The forum channel
+    pub channel: Channel,
+    /// Number of threads in the channel
+    pub thread_count: u32,
+    /// When the newest thread was created
+    pub latest_thread_at: Option<Timestamp>,
+}
+
+impl ForumChannelSummary {
+    /// Summarize a forum channel's threads
+    pub fn new(channel: Channel, threads: &[Thread]) -> Self {
+        let latest_thread_at = threads
+            .iter()
+            .filter_map(|thread| thread.thread_info.date_time.as_deref())
+            .filter_map(|date_time| chrono::DateTime::parse_from_rfc3339(date_time).ok())
+            .map(|date_time| date_time.with_timezone(&chrono::Utc))
+            .max();
+
+        Self {
+            channel,
+            thread_count: threads.len() as u32,
+            latest_thread_at,
+        }
+    }
+}
+
+/// A page of threads in a forum channel
+#[derive(Debug, Clone)]
+pub struct ThreadPage {
+    /// Threads on this page
+    pub threads: Vec<Thread>,
+    /// Whether this is the last page
+    pub is_finish: bool,
+}
+
+impl ThreadPage {
+    /// Create a new ThreadPage instance
+    pub fn new(api: BotApi, data: &Value) -> Self {
+        let threads = data
+            .get("threads")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .map(|thread| Thread::new(api.clone(), None, thread))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let is_finish = data
+            .get("is_finish")
+            .and_then(|v| v.as_u64())
+            .is_none_or(|v| v == 1);
+
+        Self { threads, is_finish }
+    }
+}
+
+/// How thread titles are compared when searching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadSearchOptions {
+    /// Require the whole title to match, rather than contain the query
+    pub exact_match: bool,
+    /// Compare letter case
+    pub case_sensitive: bool,
+}
+
+impl ThreadSearchOptions {
+    /// Returns true if `title` matches `query` under these options.
+    pub fn matches(&self, title: &str, query: &str) -> bool {
+        let (title, query) = if self.case_sensitive {
+            (title.to_string(), query.to_string())
+        } else {
+            (title.to_lowercase(), query.to_lowercase())
+        };
+        if self.exact_match {
+            title == query
+        } else {
+            title.contains(&query)
+        }
+    }
+}
+
+impl Default for ThreadSearchOptions {
+    fn default() -> Self {
+        Self {
+            exact_match: true,
+            case_sensitive: true,
+        }
+    }
+}
+
 /// Open forum thread structure
 #[derive(Debug, Clone, Serialize)]
 pub struct OpenThread {
@@ -478,6 +604,421 @@ impl std::fmt::Display for OpenThread {
     }
 }
 
+/// Content body of a forum post or reply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForumContent {
+    /// Content format
+    pub format: Format,
+    /// Raw content in the given format
+    pub content: String,
+}
+
+impl ForumContent {
+    /// Create a new ForumContent instance
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The content format
+    /// * `content` - The raw content
+    pub fn new(format: Format, content: impl Into<String>) -> Self {
+        Self {
+            format,
+            content: content.into(),
+        }
+    }
+
+    /// Create plain text content
+    pub fn text(content: impl Into<String>) -> Self {
+        Self::new(Format::PlainText, content)
+    }
+
+    /// Create markdown content
+    pub fn markdown(content: impl Into<String>) -> Self {
+        Self::new(Format::Markdown, content)
+    }
+
+    /// Create HTML content
+    pub fn html(content: impl Into<String>) -> Self {
+        Self::new(Format::Html, content)
+    }
+
+    /// Create a ForumContent instance from API data
+    ///
+    /// Missing or unknown formats fall back to plain text.
+    pub fn from_data(data: &Value) -> Self {
+        let format = data
+            .get("format")
+            .and_then(|v| v.as_u64())
+            .and_then(Format::from_code)
+            .unwrap_or(Format::PlainText);
+        let content = match data.get("content") {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+
+        Self { format, content }
+    }
+
+    /// Convert to the JSON body expected by the API
+    pub fn to_value(&self) -> Value {
+        serde_json::json!({
+            "format": self.format as u8,
+            "content": self.content,
+        })
+    }
+}
+
+/// Forum post (a reply under a thread)
+#[derive(Debug, Clone)]
+pub struct ForumPost {
+    /// Post ID
+    pub post_id: Option<String>,
+    /// ID of the thread the post belongs to
+    pub thread_id: Option<String>,
+    /// Channel ID
+    pub channel_id: Option<String>,
+    /// Guild ID
+    pub guild_id: Option<String>,
+    /// Author ID
+    pub author_id: Option<String>,
+    /// Post content
+    pub content: ForumContent,
+    /// Creation date and time
+    pub created_at: Option<String>,
+}
+
+impl ForumPost {
+    /// Create a new ForumPost instance
+    ///
+    /// Accepts both the flat API response shape and the gateway event shape
+    /// where post details are nested under `post_info`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Post data from the API or gateway
+    pub fn new(data: &Value) -> Self {
+        let info = data.get("post_info").unwrap_or(data);
+        let get_str =
+            |value: &Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        Self {
+            post_id: get_str(info, "post_id"),
+            thread_id: get_str(info, "thread_id"),
+            channel_id: get_str(data, "channel_id"),
+            guild_id: get_str(data, "guild_id"),
+            author_id: get_str(data, "author_id"),
+            content: ForumContent::from_data(info),
+            created_at: get_str(info, "date_time").or_else(|| get_str(info, "created_at")),
+        }
+    }
+}
+
+impl std::fmt::Display for ForumPost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ForumPost {{ post_id: {:?}, thread_id: {:?}, channel_id: {:?}, author_id: {:?} }}",
+            self.post_id, self.thread_id, self.channel_id, self.author_id
+        )
+    }
+}
+
+/// Reply to a forum post
+#[derive(Debug, Clone)]
+pub struct ForumReply {
+    /// Reply ID
+    pub reply_id: Option<String>,
+    /// ID of the post being replied to
+    pub post_id: Option<String>,
+    /// ID of the thread the post belongs to
+    pub thread_id: Option<String>,
+    /// Reply content
+    pub content: ForumContent,
+    /// Author ID
+    pub author_id: Option<String>,
+    /// Creation date and time
+    pub created_at: Option<String>,
+}
+
+impl ForumReply {
+    /// Create a new ForumReply instance
+    ///
+    /// Accepts both the flat API response shape and the gateway event shape
+    /// where reply details are nested under `reply_info`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Reply data from the API or gateway
+    pub fn new(data: &Value) -> Self {
+        let info = data.get("reply_info").unwrap_or(data);
+        let get_str =
+            |value: &Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        Self {
+            reply_id: get_str(info, "reply_id"),
+            post_id: get_str(info, "post_id"),
+            thread_id: get_str(info, "thread_id"),
+            content: ForumContent::from_data(info),
+            author_id: get_str(data, "author_id"),
+            created_at: get_str(info, "date_time").or_else(|| get_str(info, "created_at")),
+        }
+    }
+
+    /// Check whether the reply was written by the given user
+    pub fn is_from_author(&self, author_id: &str) -> bool {
+        self.author_id.as_deref() == Some(author_id)
+    }
+}
+
+/// A page of replies to a forum post
+#[derive(Debug, Clone)]
+pub struct ReplyPage {
+    /// Replies on this page
+    pub replies: Vec<ForumReply>,
+    /// Total number of replies across all pages
+    pub total: u32,
+}
+
+impl ReplyPage {
+    /// Create a new ReplyPage instance
+    pub fn new(data: &Value) -> Self {
+        let replies: Vec<ForumReply> = data
+            .get("replies")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().map(ForumReply::new).collect())
+            .unwrap_or_default();
+        let total = data
+            .get("total")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .unwrap_or(replies.len() as u32);
+
+        Self { replies, total }
+    }
+}
+
+/// A page of posts in a forum thread
+#[derive(Debug, Clone)]
+pub struct PostPage {
+    /// Posts on this page
+    pub posts: Vec<ForumPost>,
+    /// Total number of posts across all pages
+    pub total: u32,
+}
+
+impl PostPage {
+    /// Create a new PostPage instance
+    pub fn new(data: &Value) -> Self {
+        let posts: Vec<ForumPost> = data
+            .get("posts")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().map(ForumPost::new).collect())
+            .unwrap_or_default();
+        let total = data
+            .get("total")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .unwrap_or(posts.len() as u32);
+
+        Self { posts, total }
+    }
+}
+
+/// A user who has posted or replied in a forum thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadParticipant {
+    /// The user's ID
+    pub user_id: String,
+    /// Number of posts and replies the user wrote
+    pub post_count: u32,
+    /// When the user last posted or replied
+    pub last_posted_at: Timestamp,
+}
+
+impl ThreadParticipant {
+    /// Count each author's posts and replies in a thread
+    ///
+    /// Entries without an author or a parseable creation time are skipped.
+    /// Participants are sorted by post count, most active first, with ties
+    /// kept in order of first appearance.
+    pub fn from_posts(posts: &[ForumPost], replies: &[ForumReply]) -> Vec<Self> {
+        let entries = posts
+            .iter()
+            .map(|post| (&post.author_id, &post.created_at))
+            .chain(
+                replies
+                    .iter()
+                    .map(|reply| (&reply.author_id, &reply.created_at)),
+            );
+
+        let mut participants: Vec<Self> = Vec::new();
+        for (author_id, created_at) in entries {
+            let (Some(author_id), Some(posted_at)) = (
+                author_id,
+                created_at
+                    .as_deref()
+                    .and_then(|date_time| chrono::DateTime::parse_from_rfc3339(date_time).ok())
+                    .map(|date_time| date_time.with_timezone(&chrono::Utc)),
+            ) else {
+                continue;
+            };
+            match participants.iter_mut().find(|p| p.user_id == *author_id) {
+                Some(participant) => {
+                    participant.post_count += 1;
+                    participant.last_posted_at = participant.last_posted_at.max(posted_at);
+                }
+                None => participants.push(Self {
+                    user_id: author_id.clone(),
+                    post_count: 1,
+                    last_posted_at: posted_at,
+                }),
+            }
+        }
+        participants.sort_by_key(|p| std::cmp::Reverse(p.post_count));
+        participants
+    }
+
+    /// Check whether this participant started the thread
+    pub fn is_original_poster(&self, thread: &Thread) -> bool {
+        thread.author_id.as_deref() == Some(self.user_id.as_str())
+    }
+}
+
+/// Collect every post in a thread, fetching pages on demand
+///
+/// Pages are requested starting from 1 until a page comes back empty or short,
+/// or every post reported by `total` has been collected.
+pub(crate) async fn collect_posts<F, Fut>(page_size: u32, mut fetch: F) -> Result<Vec<ForumPost>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<PostPage>>,
+{
+    let mut posts = Vec::new();
+    let mut page = 1;
+    loop {
+        let PostPage {
+            posts: page_posts,
+            total,
+        } = fetch(page).await?;
+        let count = page_posts.len() as u32;
+        posts.extend(page_posts);
+        if count == 0 || count < page_size || posts.len() as u32 >= total {
+            return Ok(posts);
+        }
+        page += 1;
+    }
+}
+
+/// Turn a page fetcher into a stream of replies
+///
+/// Pages are requested starting from 1 until a page comes back empty or short,
+/// or every reply reported by `total` has been yielded. The stream ends after
+/// yielding the first error.
+pub(crate) fn paginate_replies<F, Fut>(
+    page_size: u32,
+    fetch: F,
+) -> impl Stream<Item = Result<ForumReply>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<ReplyPage>>,
+{
+    struct State<F> {
+        fetch: F,
+        page: u32,
+        seen: u32,
+        buffer: VecDeque<ForumReply>,
+        done: bool,
+    }
+
+    let state = State {
+        fetch,
+        page: 1,
+        seen: 0,
+        buffer: VecDeque::new(),
+        done: false,
+    };
+
+    futures_util::stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(reply) = state.buffer.pop_front() {
+                return Some((Ok(reply), state));
+            }
+            if state.done {
+                return None;
+            }
+            match (state.fetch)(state.page).await {
+                Ok(page) => {
+                    let count = page.replies.len() as u32;
+                    state.page += 1;
+                    state.seen += count;
+                    state.done = count == 0 || count < page_size || state.seen >= page.total;
+                    state.buffer.extend(page.replies);
+                }
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+/// Collect threads whose titles match `query`, fetching pages on demand
+///
+/// Pages are requested starting from 1 until the last page has been seen or
+/// `max_results` threads have matched.
+pub(crate) async fn search_threads<F, Fut>(
+    fetch: F,
+    query: &str,
+    options: ThreadSearchOptions,
+    max_results: usize,
+) -> Result<Vec<Thread>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<ThreadPage>>,
+{
+    collect_threads(
+        fetch,
+        |thread| options.matches(&thread.thread_info.title.plain_text(), query),
+        max_results,
+    )
+    .await
+}
+
+/// Collect threads accepted by `keep`, fetching pages on demand
+///
+/// Pages are requested starting from 1 until the last page has been seen or
+/// `max_results` threads have been kept.
+pub(crate) async fn collect_threads<F, Fut, K>(
+    mut fetch: F,
+    mut keep: K,
+    max_results: usize,
+) -> Result<Vec<Thread>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<ThreadPage>>,
+    K: FnMut(&Thread) -> bool,
+{
+    let mut kept = Vec::new();
+    let mut page = 1;
+    while kept.len() < max_results {
+        let ThreadPage { threads, is_finish } = fetch(page).await?;
+        let done = is_finish || threads.is_empty();
+        kept.extend(
+            threads
+                .into_iter()
+                .filter(&mut keep)
+                .take(max_results - kept.len()),
+        );
+        if done {
+            break;
+        }
+        page += 1;
+    }
+    Ok(kept)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -498,4 +1039,271 @@ mod tests {
         let text = Text::new(&data);
         assert_eq!(text.text, Some("Hello, world!".to_string()));
     }
+
+    #[test]
+    fn test_forum_content_body() {
+        let content = ForumContent::text("Hello, forum!");
+        assert_eq!(
+            content.to_value(),
+            serde_json::json!({
+                "format": 1,
+                "content": "Hello, forum!"
+            })
+        );
+        assert_eq!(ForumContent::from_data(&content.to_value()), content);
+    }
+
+    #[test]
+    fn test_forum_post_from_event() {
+        let data = serde_json::json!({
+            "guild_id": "g1",
+            "channel_id": "c1",
+            "author_id": "u1",
+            "post_info": {
+                "thread_id": "t1",
+                "post_id": "p1",
+                "format": 3,
+                "content": "**hi**",
+                "date_time": "2024-01-01T00:00:00+08:00"
+            }
+        });
+        let post = ForumPost::new(&data);
+        assert_eq!(post.post_id.as_deref(), Some("p1"));
+        assert_eq!(post.thread_id.as_deref(), Some("t1"));
+        assert_eq!(post.channel_id.as_deref(), Some("c1"));
+        assert_eq!(post.content, ForumContent::markdown("**hi**"));
+        assert!(post.created_at.is_some());
+    }
+
+    #[test]
+    fn test_reply_page() {
+        let data = serde_json::json!({
+            "total": 3,
+            "replies": [
+                {"reply_id": "r1", "post_id": "p1", "thread_id": "t1", "author_id": "u1", "format": 1, "content": "first"},
+                {"reply_id": "r2", "post_id": "p1", "thread_id": "t1", "author_id": "u2", "format": 1, "content": "second"}
+            ]
+        });
+        let page = ReplyPage::new(&data);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.replies.len(), 2);
+        assert_eq!(page.replies[1].content, ForumContent::text("second"));
+        assert!(page.replies[0].is_from_author("u1"));
+        assert!(!page.replies[0].is_from_author("u2"));
+    }
+
+    #[tokio::test]
+    async fn test_paginate_replies_terminates() {
+        use futures_util::StreamExt;
+
+        let reply =
+            |id: u32| serde_json::json!({"reply_id": id.to_string(), "format": 1, "content": "hi"});
+        let requested = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = requested.clone();
+        let stream = paginate_replies(2, move |page| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let data = match page {
+                1 => serde_json::json!({"total": 3, "replies": [reply(1), reply(2)]}),
+                2 => serde_json::json!({"total": 3, "replies": [reply(3)]}),
+                _ => panic!("requested page {page} past the end"),
+            };
+            async move { Ok(ReplyPage::new(&data)) }
+        });
+
+        let ids: Vec<String> = stream.map(|r| r.unwrap().reply_id.unwrap()).collect().await;
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(requested.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_replies_stops_on_error() {
+        use futures_util::StreamExt;
+
+        let stream = paginate_replies(20, |_| async {
+            Err(crate::error::BotError::invalid_data("boom"))
+        });
+        let results: Vec<_> = stream.collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    fn thread_pages(page: u32) -> Result<ThreadPage> {
+        let api = BotApi::new(crate::http::HttpClient::new(30, false).unwrap());
+        let thread = |id: &str, title: &str| {
+            let title = serde_json::json!({
+                "paragraphs": [{"elems": [{"type": 1, "text": {"text": title}}]}]
+            });
+            serde_json::json!({
+                "channel_id": "c1",
+                "thread_info": {"thread_id": id, "title": title.to_string(), "content": "{}"}
+            })
+        };
+        let data = match page {
+            1 => serde_json::json!({
+                "threads": [thread("t1", "Release notes"), thread("t2", "Bug reports")],
+                "is_finish": 0
+            }),
+            2 => serde_json::json!({
+                "threads": [thread("t3", "release schedule"), thread("t4", "Release notes")],
+                "is_finish": 1
+            }),
+            _ => panic!("requested page {page} past the end"),
+        };
+        Ok(ThreadPage::new(api, &data))
+    }
+
+    fn thread_ids(threads: &[Thread]) -> Vec<&str> {
+        threads
+            .iter()
+            .filter_map(|t| t.thread_info.thread_id.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_thread_search_options() {
+        let exact = ThreadSearchOptions::default();
+        assert!(exact.matches("Release notes", "Release notes"));
+        assert!(!exact.matches("Release notes", "release notes"));
+        assert!(!exact.matches("Release notes", "Release"));
+
+        let contains = ThreadSearchOptions {
+            exact_match: false,
+            case_sensitive: false,
+        };
+        assert!(contains.matches("Release notes", "NOTES"));
+        assert!(!contains.matches("Release notes", "bug"));
+    }
+
+    #[tokio::test]
+    async fn test_search_threads_across_pages() {
+        let fetch = |page| async move { thread_pages(page) };
+
+        let found = search_threads(fetch, "Release notes", ThreadSearchOptions::default(), 1)
+            .await
+            .unwrap();
+        assert_eq!(thread_ids(&found), vec!["t1"]);
+        assert_eq!(found[0].thread_info.title.plain_text(), "Release notes");
+
+        let contains = ThreadSearchOptions {
+            exact_match: false,
+            case_sensitive: false,
+        };
+        let found = search_threads(fetch, "release", contains, usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(thread_ids(&found), vec!["t1", "t3", "t4"]);
+
+        let found = search_threads(fetch, "release", contains, 2).await.unwrap();
+        assert_eq!(thread_ids(&found), vec!["t1", "t3"]);
+
+        let found = search_threads(fetch, "missing", ThreadSearchOptions::default(), 1)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_forum_channel_summary() {
+        let api = BotApi::new(crate::http::HttpClient::new(30, false).unwrap());
+        let page = ThreadPage::new(
+            api,
+            &serde_json::json!({
+                "threads": [
+                    {"thread_info": {"thread_id": "t1", "date_time": "2024-05-01T08:00:00+08:00"}},
+                    {"thread_info": {"thread_id": "t2", "date_time": "2024-05-03T08:00:00+08:00"}},
+                    {"thread_info": {"thread_id": "t3"}}
+                ],
+                "is_finish": 1
+            }),
+        );
+        let mut channel = Channel::new();
+        channel.id = Some("c1".to_string());
+
+        let summary = ForumChannelSummary::new(channel.clone(), &page.threads);
+        assert_eq!(summary.thread_count, 3);
+        assert_eq!(
+            summary.latest_thread_at,
+            Some("2024-05-03T00:00:00Z".parse().unwrap())
+        );
+
+        let empty = ForumChannelSummary::new(channel, &[]);
+        assert_eq!(empty.thread_count, 0);
+        assert!(empty.latest_thread_at.is_none());
+    }
+
+    #[test]
+    fn test_thread_participants() {
+        let post = |author: &str, date_time: &str| {
+            ForumPost::new(&serde_json::json!({
+                "author_id": author,
+                "post_info": {"post_id": "p", "date_time": date_time},
+            }))
+        };
+        let posts = [
+            post("alice", "2024-05-01T08:00:00+08:00"),
+            post("bob", "2024-05-01T09:00:00+08:00"),
+            post("alice", "2024-05-02T08:00:00+08:00"),
+            post("carol", "2024-05-01T10:00:00+08:00"),
+            post("alice", "2024-05-01T12:00:00+08:00"),
+        ];
+        let replies = [ForumReply::new(&serde_json::json!({
+            "author_id": "bob",
+            "reply_info": {"date_time": "2024-05-03T08:00:00+08:00"},
+        }))];
+
+        let participants = ThreadParticipant::from_posts(&posts[..], &[]);
+        let counts: Vec<(&str, u32)> = participants
+            .iter()
+            .map(|p| (p.user_id.as_str(), p.post_count))
+            .collect();
+        assert_eq!(counts, vec![("alice", 3), ("bob", 1), ("carol", 1)]);
+        assert_eq!(
+            participants[0].last_posted_at,
+            "2024-05-02T00:00:00Z".parse::<Timestamp>().unwrap()
+        );
+
+        let participants = ThreadParticipant::from_posts(&posts, &replies);
+        assert_eq!(participants[1].user_id, "bob");
+        assert_eq!(participants[1].post_count, 2);
+        assert_eq!(
+            participants[1].last_posted_at,
+            "2024-05-03T00:00:00Z".parse::<Timestamp>().unwrap()
+        );
+
+        let thread = Thread::new(
+            BotApi::new(crate::http::HttpClient::new(30, false).unwrap()),
+            None,
+            &serde_json::json!({"author_id": "bob"}),
+        );
+        assert!(participants[1].is_original_poster(&thread));
+        assert!(!participants[0].is_original_poster(&thread));
+    }
+
+    #[tokio::test]
+    async fn test_collect_posts_stops_at_total() {
+        let requested = std::sync::Mutex::new(Vec::new());
+        let posts = collect_posts(2, |page| {
+            requested.lock().unwrap().push(page);
+            let ids: &[&str] = match page {
+                1 => &["p1", "p2"],
+                2 => &["p3", "p4"],
+                _ => panic!("requested page {page} past the total"),
+            };
+            let posts = ids
+                .iter()
+                .map(|id| serde_json::json!({"post_info": {"post_id": id}}))
+                .collect::<Vec<_>>();
+            async move {
+                Ok(PostPage::new(
+                    &serde_json::json!({"posts": posts, "total": 4}),
+                ))
+            }
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<_> = posts.iter().filter_map(|p| p.post_id.as_deref()).collect();
+        assert_eq!(ids, vec!["p1", "p2", "p3", "p4"]);
+        assert_eq!(*requested.lock().unwrap(), vec![1, 2]);
+    }
 }
@@ -6,13 +6,15 @@
 use crate::error::{BotError, Result};
 use crate::intents::Intents;
 use crate::models::gateway::*;
+use crate::models::guild::GuildChangeEvent;
+use crate::state_machine::{GatewayState, GatewayStateEvent, GatewayStateMachine};
 use crate::token::Token;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, Stream, StreamExt};
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc};
 use tokio::time::sleep;
 
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
@@ -31,22 +33,14 @@ pub struct Gateway {
     intents: Intents,
     /// Shard information [shard_id, shard_count]
     shard: Option<[u32; 2]>,
-    /// Session ID for resuming
-    session_id: Option<String>,
-    /// Last sequence number received
-    last_seq: Arc<AtomicU64>,
+    /// Connection state, shared with the heartbeat task for the sequence number
+    state: Arc<std::sync::Mutex<GatewayStateMachine>>,
     /// Heartbeat interval in milliseconds
     heartbeat_interval: Option<u64>,
-    /// Whether the connection is ready
-    is_ready: Arc<AtomicBool>,
-    /// Whether we can reconnect
-    can_reconnect: Arc<AtomicBool>,
     /// Atomic heartbeat interval for sharing between tasks
     heartbeat_interval_ms: Arc<AtomicU64>,
     /// Heartbeat task handle for cleanup
     heartbeat_handle: Option<tokio::task::JoinHandle<()>>,
-    /// Connection alive status
-    connection_alive: Arc<AtomicBool>,
     /// Connection start time for duration tracking
     connection_start_time: Option<Instant>,
     /// Total heartbeats sent counter
@@ -55,6 +49,10 @@ pub struct Gateway {
     last_heartbeat_ack: Arc<AtomicU64>,
     /// Heartbeat sent time for ACK tracking
     last_heartbeat_sent: Arc<AtomicU64>,
+    /// Write half of the active WebSocket connection
+    write: Option<Arc<Mutex<futures_util::stream::SplitSink<WsStream, Message>>>>,
+    /// Broadcasts dispatch events to subscriptions
+    subscribers: broadcast::Sender<GatewayEvent>,
 }
 
 impl Gateway {
@@ -88,18 +86,16 @@ impl Gateway {
             token,
             intents,
             shard,
-            session_id: None,
+            state: Arc::new(std::sync::Mutex::new(GatewayStateMachine::new())),
             heartbeat_interval: None,
-            last_seq: Arc::new(AtomicU64::new(0)),
-            is_ready: Arc::new(AtomicBool::new(false)),
-            can_reconnect: Arc::new(AtomicBool::new(true)),
             heartbeat_interval_ms: Arc::new(AtomicU64::new(30000)),
             heartbeat_handle: None,
-            connection_alive: Arc::new(AtomicBool::new(false)),
             connection_start_time: None,
             heartbeat_count: Arc::new(AtomicU64::new(0)),
             last_heartbeat_ack: Arc::new(AtomicU64::new(0)),
             last_heartbeat_sent: Arc::new(AtomicU64::new(0)),
+            write: None,
+            subscribers: event_channel(),
         }
     }
 
@@ -124,10 +120,9 @@ impl Gateway {
             debug!("[botrs] 连接到网关: {}", self.url);
 
             // Reset states before attempting connection (like Python's session reset)
-            self.connection_alive.store(false, Ordering::Relaxed);
-            self.is_ready.store(false, Ordering::Relaxed);
             self.heartbeat_count.store(0, Ordering::Relaxed);
             self.stop_heartbeat_task();
+            self.apply(GatewayStateEvent::ConnectStarted);
 
             let start_time = std::time::Instant::now();
             match self.try_connect(&event_sender).await {
@@ -138,14 +133,16 @@ impl Gateway {
                 Err(e) => {
                     let duration = start_time.elapsed();
                     debug!("[botrs] 连接错误 (持续时间: {:?}): {}", duration, e);
-                    // Reset connection state on error
-                    self.connection_alive.store(false, Ordering::Relaxed);
-                    self.is_ready.store(false, Ordering::Relaxed);
                 }
             }
 
+            // A connection that ended without a close frame counts as a network error
+            if self.state().is_active() {
+                self.apply(GatewayStateEvent::NetworkError);
+            }
+
             // Check if we should reconnect
-            if !self.can_reconnect.load(Ordering::Relaxed) {
+            if !self.can_reconnect() {
                 debug!("[botrs] 无法重连，停止连接尝试");
                 break;
             }
@@ -182,8 +179,7 @@ impl Gateway {
         let (ws_stream, _) = connect_async(&url).await?;
         debug!("[botrs] WebSocket连接建立成功");
 
-        // Mark connection as alive and record connection start time
-        self.connection_alive.store(true, Ordering::Relaxed);
+        // Record connection start time
         self.connection_start_time = Some(Instant::now());
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -203,6 +199,7 @@ impl Gateway {
     ) -> Result<()> {
         let (write_stream, mut read) = ws_stream.split();
         let write = Arc::new(Mutex::new(write_stream));
+        self.write = Some(write.clone());
 
         // Main message handling loop
         while let Some(message) = read.next().await {
@@ -236,9 +233,9 @@ impl Gateway {
                         );
                         self.handle_close_code(frame.code.into()).await;
                     }
-                    // Mark connection as dead and stop heartbeat task
-                    self.connection_alive.store(false, Ordering::Relaxed);
+                    // Stop heartbeat task
                     self.stop_heartbeat_task();
+                    self.write = None;
                     return Ok(()); // Return to trigger reconnection
                 }
                 Ok(Message::Ping(data)) => {
@@ -266,10 +263,9 @@ impl Gateway {
                         "连接断开: {} (持续时间: {:?}, 心跳数: {})",
                         e, connection_duration, total_heartbeats
                     );
-                    // Mark connection as dead and stop heartbeat task on error
-                    self.connection_alive.store(false, Ordering::Relaxed);
-                    self.is_ready.store(false, Ordering::Relaxed);
+                    // Stop heartbeat task on error
                     self.stop_heartbeat_task();
+                    self.write = None;
                     return Err(BotError::WebSocket(Box::new(e)));
                 }
             }
@@ -287,9 +283,8 @@ impl Gateway {
             connection_duration, total_heartbeats
         );
 
-        self.connection_alive.store(false, Ordering::Relaxed);
-        self.is_ready.store(false, Ordering::Relaxed);
         self.stop_heartbeat_task();
+        self.write = None;
         Ok(())
     }
 
@@ -319,62 +314,64 @@ impl Gateway {
         }
 
         // Update sequence number if present
-        if let Some(seq) = event.sequence {
-            if seq > 0 {
-                self.last_seq.store(seq, Ordering::Relaxed);
-            }
+        if let Some(seq) = event.sequence
+            && seq > 0
+        {
+            self.machine().record_seq(seq);
         }
 
         // Handle dispatch events
-        if event.opcode == opcodes::DISPATCH {
-            if let Some(event_type) = &event.event_type {
-                match event_type.as_str() {
-                    "READY" => {
-                        match event
-                            .data
-                            .as_ref()
-                            .and_then(|d| serde_json::from_value::<Ready>(d.clone()).ok())
-                        {
-                            Some(ready) => {
-                                self.session_id = Some(ready.session_id.clone());
-                                self.is_ready.store(true, Ordering::Relaxed);
-
-                                let elapsed = self
-                                    .connection_start_time
-                                    .map(|t| t.elapsed())
-                                    .unwrap_or(Duration::ZERO);
-                                debug!(
-                                    "[botrs] 收到 READY 事件，session_id: {}，连接耗时: {:?}",
-                                    ready.session_id, elapsed
-                                );
-                                // Start heartbeat task with 30 second interval like Python
-                                self.start_heartbeat_task(write.clone());
-                                debug!("[botrs] 心跳任务已启动");
-
-                                info!("[botrs] 机器人「{}」启动成功！", ready.user.username);
-                            }
-                            None => {
-                                debug!("[botrs] READY 事件解析失败或无数据");
-                            }
+        if event.opcode == opcodes::DISPATCH
+            && let Some(event_type) = &event.event_type
+        {
+            match event_type.as_str() {
+                "READY" => {
+                    match event
+                        .data
+                        .as_ref()
+                        .and_then(|d| serde_json::from_value::<Ready>(d.clone()).ok())
+                    {
+                        Some(ready) => {
+                            self.apply(GatewayStateEvent::ReadyReceived(Box::new(ready.clone())));
+
+                            let elapsed = self
+                                .connection_start_time
+                                .map(|t| t.elapsed())
+                                .unwrap_or(Duration::ZERO);
+                            debug!(
+                                "[botrs] 收到 READY 事件，session_id: {}，连接耗时: {:?}",
+                                ready.session_id, elapsed
+                            );
+                            // Start heartbeat task with 30 second interval like Python
+                            self.start_heartbeat_task(write.clone());
+                            debug!("[botrs] 心跳任务已启动");
+
+                            info!("[botrs] 机器人「{}」启动成功！", ready.user.username);
+                        }
+                        None => {
+                            debug!("[botrs] READY 事件解析失败或无数据");
                         }
                     }
-                    "RESUMED" => {
-                        self.is_ready.store(true, Ordering::Relaxed);
+                }
+                "RESUMED" => {
+                    self.apply(GatewayStateEvent::ResumedReceived);
 
-                        debug!("[botrs] 收到 RESUMED 事件");
-                        // Start heartbeat task after RESUMED as well
-                        self.start_heartbeat_task(write.clone());
-                        debug!("[botrs] 心跳任务已重新启动");
+                    debug!("[botrs] 收到 RESUMED 事件");
+                    // Start heartbeat task after RESUMED as well
+                    self.start_heartbeat_task(write.clone());
+                    debug!("[botrs] 心跳任务已重新启动");
 
-                        info!("[botrs] 机器人重连成功! ");
-                    }
-                    _ => {}
+                    info!("[botrs] 机器人重连成功! ");
                 }
+                _ => {}
+            }
 
-                // Regular event dispatch
-                if let Err(e) = event_sender.send(event) {
-                    debug!("Failed to send event: {}", e);
-                }
+            // Regular event dispatch
+            if self.subscribers.receiver_count() > 0 {
+                let _ = self.subscribers.send(event.clone());
+            }
+            if let Err(e) = event_sender.send(event) {
+                debug!("Failed to send event: {}", e);
             }
         }
 
@@ -390,21 +387,23 @@ impl Gateway {
         match event.opcode {
             opcodes::HELLO => {
                 // Hello message with heartbeat interval
-                if let Some(data) = &event.data {
-                    if let Ok(hello) = serde_json::from_value::<Hello>(data.clone()) {
-                        debug!(
-                            "[botrs] 收到 HELLO 事件，服务器建议心跳间隔: {}ms (我们使用固定30000ms)",
-                            hello.heartbeat_interval
-                        );
-                        self.heartbeat_interval = Some(hello.heartbeat_interval);
-                        // Use 30000ms like Python
-                        self.heartbeat_interval_ms.store(30000, Ordering::Relaxed);
-
-                        // Send identify or resume like Python's on_connected
-                        debug!("[botrs] 发送身份验证信息");
-                        if let Err(e) = self.send_identify(write).await {
-                            debug!("Failed to send identify: {}", e);
-                        }
+                if let Some(data) = &event.data
+                    && let Ok(hello) = serde_json::from_value::<Hello>(data.clone())
+                {
+                    debug!(
+                        "[botrs] 收到 HELLO 事件，服务器建议心跳间隔: {}ms (我们使用固定30000ms)",
+                        hello.heartbeat_interval
+                    );
+                    self.heartbeat_interval = Some(hello.heartbeat_interval);
+                    // Use 30000ms like Python
+                    self.heartbeat_interval_ms.store(30000, Ordering::Relaxed);
+                    self.apply(GatewayStateEvent::HelloReceived);
+
+                    // Send identify or resume like Python's on_connected
+                    debug!("[botrs] 发送身份验证信息");
+                    match self.send_identify(write).await {
+                        Ok(()) => self.apply(GatewayStateEvent::IdentifySent),
+                        Err(e) => debug!("Failed to send identify: {}", e),
                     }
                 }
                 Ok(true)
@@ -415,6 +414,7 @@ impl Gateway {
                     .unwrap()
                     .as_millis() as u64;
                 self.last_heartbeat_ack.store(now, Ordering::Relaxed);
+                self.apply(GatewayStateEvent::HeartbeatAckReceived);
 
                 let last_sent = self.last_heartbeat_sent.load(Ordering::Relaxed);
                 let ack_latency = if last_sent > 0 {
@@ -431,18 +431,17 @@ impl Gateway {
             }
             opcodes::RECONNECT => {
                 info!("[botrs] 服务器请求重连 (RECONNECT)");
-                self.can_reconnect.store(true, Ordering::Relaxed);
                 Ok(true)
             }
             opcodes::INVALID_SESSION => {
                 info!("[botrs] 会话无效 (INVALID_SESSION)");
-                self.can_reconnect.store(false, Ordering::Relaxed);
+                self.apply(GatewayStateEvent::InvalidSessionReceived);
                 Ok(true)
             }
             opcodes::HEARTBEAT => {
                 // Server requesting heartbeat
                 debug!("[botrs] 服务器请求立即心跳");
-                let seq = self.last_seq.load(Ordering::Relaxed);
+                let seq = self.last_sequence();
 
                 let heartbeat_payload = serde_json::json!({
                     "op": opcodes::HEARTBEAT,
@@ -474,13 +473,13 @@ impl Gateway {
         &mut self,
         write: &Arc<Mutex<futures_util::stream::SplitSink<WsStream, Message>>>,
     ) -> Result<()> {
-        let identify = if let Some(session_id) = &self.session_id {
+        let identify = if let Some(session_id) = self.session_id() {
             // Resume existing session
             debug!("Resuming session: {}", session_id);
             let resume = Resume {
                 token: self.token.bot_token().await?,
-                session_id: session_id.clone(),
-                seq: self.last_seq.load(Ordering::Relaxed),
+                session_id,
+                seq: self.last_sequence(),
             };
 
             GatewayEvent {
@@ -519,52 +518,113 @@ impl Gateway {
 
     /// Handles close codes and determines reconnection behavior
     async fn handle_close_code(&mut self, close_code: u16) {
-        let invalid_reconnect_codes = [9001, 9005];
-        let auth_fail_codes = [4004];
-
-        if auth_fail_codes.contains(&close_code) {
-            info!("[botrs] 鉴权失败，重置token...");
-            self.session_id = None;
-            self.last_seq.store(0, Ordering::Relaxed);
-            self.is_ready.store(false, Ordering::Relaxed);
+        self.apply(GatewayStateEvent::CloseReceived(close_code));
+
+        match self.state() {
+            GatewayState::Failed => debug!("[botrs] 无法重连，创建新连接!"),
+            _ => debug!("[botrs] 连接断开，准备重连..."),
         }
+    }
 
-        if invalid_reconnect_codes.contains(&close_code)
-            || !self.can_reconnect.load(Ordering::Relaxed)
-        {
-            debug!("[botrs] 无法重连，创建新连接!");
-            self.session_id = None;
-            self.last_seq.store(0, Ordering::Relaxed);
-            self.is_ready.store(false, Ordering::Relaxed);
-            self.can_reconnect.store(false, Ordering::Relaxed);
-        } else {
-            debug!("[botrs] 连接断开，准备重连...");
-            self.is_ready.store(false, Ordering::Relaxed);
-            self.can_reconnect.store(true, Ordering::Relaxed);
+    /// Applies an event to the connection state machine.
+    ///
+    /// Events that are not valid in the current state are logged and ignored.
+    fn apply(&mut self, event: GatewayStateEvent) {
+        if let Err(e) = self.machine().transition(event) {
+            debug!("[botrs] {}", e);
         }
     }
 
+    /// Locks the connection state machine.
+    fn machine(&self) -> std::sync::MutexGuard<'_, GatewayStateMachine> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Gets the current connection state.
+    pub fn state(&self) -> GatewayState {
+        self.machine().state().clone()
+    }
+
     /// Returns true if the gateway is connected and ready.
     pub fn is_ready(&self) -> bool {
-        self.is_ready.load(Ordering::Relaxed)
+        self.machine().is_connected()
     }
 
     /// Returns true if the gateway can reconnect.
     pub fn can_reconnect(&self) -> bool {
-        self.can_reconnect.load(Ordering::Relaxed)
+        self.machine().can_reconnect()
     }
 
     /// Gets the current session ID.
-    pub fn session_id(&self) -> Option<&str> {
-        self.session_id.as_deref()
+    pub fn session_id(&self) -> Option<String> {
+        self.machine().session_id().map(str::to_string)
+    }
+
+    /// Subscribes to dispatch events received from now on.
+    ///
+    /// Each subscription gets its own copy of every event, independent of the
+    /// channel passed to [`Gateway::connect`].
+    pub fn subscribe_events(&self) -> GatewaySubscription {
+        GatewaySubscription::new(self.subscribers.subscribe())
+    }
+
+    /// Gets the sender that dispatch events are broadcast on.
+    pub(crate) fn event_broadcaster(&self) -> broadcast::Sender<GatewayEvent> {
+        self.subscribers.clone()
     }
 
     /// Gets the last sequence number.
     pub fn last_sequence(&self) -> u64 {
-        self.last_seq.load(Ordering::Relaxed)
+        self.machine().seq()
+    }
+
+    /// Sends a raw gateway payload with the given opcode.
+    ///
+    /// This bypasses the regular connection flow and is intended for debugging,
+    /// testing reconnect behaviour, and experimenting with new opcodes. It is only
+    /// available in debug builds or with the `unstable` feature enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `opcode` - The gateway opcode
+    /// * `data` - The payload data sent as `d`
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    #[cfg(any(debug_assertions, feature = "unstable"))]
+    pub async fn send_op(&self, opcode: u8, data: serde_json::Value) -> Result<()> {
+        let write = self
+            .write
+            .as_ref()
+            .ok_or_else(|| BotError::connection("Gateway is not connected"))?;
+        let payload = serde_json::to_string(&op_payload(opcode, data))?;
+
+        debug!("[botrs] 发送消息: {}", payload);
+        let mut writer = write.lock().await;
+        writer.send(Message::Text(payload)).await?;
+        Ok(())
+    }
+
+    /// Sends a heartbeat immediately with the last received sequence number.
+    ///
+    /// Only available in debug builds or with the `unstable` feature enabled.
+    #[cfg(any(debug_assertions, feature = "unstable"))]
+    pub async fn send_heartbeat_now(&self) -> Result<()> {
+        let seq = self.last_sequence();
+        self.send_op(opcodes::HEARTBEAT, serde_json::json!(seq))
+            .await
     }
 }
 
+/// Builds a gateway payload in the `{"op": opcode, "d": data}` format.
+fn op_payload(opcode: u8, data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "op": opcode,
+        "d": data
+    })
+}
+
 impl Gateway {
     /// Starts the heartbeat task with fixed 30-second interval (matching Python implementation).
     fn start_heartbeat_task(
@@ -574,8 +634,7 @@ impl Gateway {
         // Stop any existing heartbeat task
         self.stop_heartbeat_task();
 
-        let last_seq = self.last_seq.clone();
-        let connection_alive = self.connection_alive.clone();
+        let state = self.state.clone();
         let heartbeat_counter = self.heartbeat_count.clone();
         let last_heartbeat_ack = self.last_heartbeat_ack.clone();
         let last_heartbeat_sent = self.last_heartbeat_sent.clone();
@@ -593,13 +652,8 @@ impl Gateway {
                 let current_count = heartbeat_counter.fetch_add(1, Ordering::Relaxed) + 1;
                 let total_elapsed = heartbeat_start_time.elapsed();
 
-                // Check if connection is still alive (like Python's conn check)
-                if !connection_alive.load(Ordering::Relaxed) {
-                    debug!("[botrs] 心跳任务检测到连接已关闭，停止心跳");
-                    return;
-                }
-
-                let seq = last_seq.load(Ordering::Relaxed);
+                // The task is aborted when the connection closes
+                let seq = state.lock().unwrap_or_else(|e| e.into_inner()).seq();
                 let timestamp = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
                     .unwrap()
@@ -628,18 +682,9 @@ impl Gateway {
                 }
 
                 // Create heartbeat payload matching Python implementation
-                let heartbeat_payload = serde_json::json!({
-                    "op": opcodes::HEARTBEAT,
-                    "d": seq
-                });
+                let heartbeat_payload = op_payload(opcodes::HEARTBEAT, serde_json::json!(seq));
 
                 if let Ok(payload) = serde_json::to_string(&heartbeat_payload) {
-                    // Check connection state before sending (like Python's send_msg)
-                    if !connection_alive.load(Ordering::Relaxed) {
-                        debug!("[botrs] 发送前检测到连接已关闭，停止心跳");
-                        return;
-                    }
-
                     match write.try_lock() {
                         Ok(mut writer) => {
                             let send_start = Instant::now();
@@ -655,8 +700,6 @@ impl Gateway {
                                 let send_duration = send_start.elapsed();
                                 debug!("[botrs] 心跳发送失败 (耗时: {:?}): {}", send_duration, e);
                                 debug!("[botrs] ws连接已关闭, 心跳检测停止");
-                                // Mark connection as dead when heartbeat fails
-                                connection_alive.store(false, Ordering::Relaxed);
                                 return;
                             } else {
                                 let send_duration = send_start.elapsed();
@@ -700,13 +743,124 @@ impl Gateway {
     }
 }
 
+/// Number of events buffered per subscription before the oldest are dropped.
+const EVENT_SUBSCRIPTION_CAPACITY: usize = 256;
+
+/// Creates the broadcast channel dispatch events are sent to subscriptions on.
+pub(crate) fn event_channel() -> broadcast::Sender<GatewayEvent> {
+    broadcast::channel(EVENT_SUBSCRIPTION_CAPACITY).0
+}
+
+/// A stream of dispatch events from a [`Gateway`], for waiting on specific events.
+///
+/// Mostly useful in tests and interactive flows, e.g. waiting for the
+/// `MESSAGE_CREATE` that follows a prompt.
+#[derive(Debug)]
+pub struct GatewaySubscription {
+    receiver: broadcast::Receiver<GatewayEvent>,
+}
+
+/// Former name of [`GatewaySubscription`].
+#[deprecated(since = "0.2.6", note = "Use GatewaySubscription instead")]
+pub type EventSubscription = GatewaySubscription;
+
+impl GatewaySubscription {
+    /// Creates a subscription from a broadcast receiver.
+    pub fn new(receiver: broadcast::Receiver<GatewayEvent>) -> Self {
+        Self { receiver }
+    }
+
+    /// Waits for the next event.
+    ///
+    /// Events dropped because the subscription fell behind are skipped.
+    pub async fn recv(&mut self) -> Result<GatewayEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Ok(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("[botrs] 事件订阅落后，跳过 {} 个事件", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    return Err(BotError::connection("Gateway event stream closed"));
+                }
+            }
+        }
+    }
+
+    /// Waits for an event of the given type, discarding any others.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` - The event type to wait for, e.g. `"AT_MESSAGE_CREATE"`
+    /// * `timeout` - How long to wait before giving up
+    ///
+    /// # Returns
+    ///
+    /// The matching event, or `BotError::Timeout` if none arrived in time.
+    pub async fn wait_for(&mut self, event_type: &str, timeout: Duration) -> Result<GatewayEvent> {
+        self.wait_for_with_filter(|e| e.event_type.as_deref() == Some(event_type), timeout)
+            .await
+    }
+
+    /// Waits for an event matching a predicate, discarding any others.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Returns true for the event to wait for
+    /// * `timeout` - How long to wait before giving up
+    ///
+    /// # Returns
+    ///
+    /// The matching event, or `BotError::Timeout` if none arrived in time.
+    pub async fn wait_for_with_filter(
+        &mut self,
+        filter: impl Fn(&GatewayEvent) -> bool,
+        timeout: Duration,
+    ) -> Result<GatewayEvent> {
+        let wait = async {
+            loop {
+                let event = self.recv().await?;
+                if filter(&event) {
+                    return Ok(event);
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| BotError::Timeout)?
+    }
+
+    /// Turns the subscription into a stream of member, channel and role
+    /// changes in one guild.
+    ///
+    /// Other events and changes to other guilds are skipped. The stream ends
+    /// when the gateway's event stream closes.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The guild to watch
+    pub fn guild_changes(self, guild_id: &str) -> impl Stream<Item = GuildChangeEvent> + use<> {
+        debug!("Watching changes to guild {}", guild_id);
+        let guild_id = guild_id.to_string();
+        futures_util::stream::unfold(self, |mut subscription| async move {
+            let event = subscription.recv().await.ok()?;
+            Some((GuildChangeEvent::from_gateway_event(&event), subscription))
+        })
+        .filter_map(move |change| {
+            let change = change.filter(|change| change.guild_id == guild_id);
+            async move { change }
+        })
+    }
+}
+
 impl std::fmt::Debug for Gateway {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Gateway")
             .field("url", &self.url)
             .field("intents", &self.intents)
             .field("shard", &self.shard)
-            .field("session_id", &self.session_id)
+            .field("session_id", &self.session_id())
+            .field("state", &self.state())
             .field("is_ready", &self.is_ready())
             .finish()
     }
@@ -735,4 +889,132 @@ mod tests {
 
         assert_eq!(gateway.shard, Some([0, 1]));
     }
+
+    #[test]
+    fn test_op_payload_format() {
+        let payload = op_payload(opcodes::HEARTBEAT, serde_json::json!(42));
+        assert_eq!(payload, serde_json::json!({"op": 1, "d": 42}));
+
+        let payload = op_payload(opcodes::RESUME, serde_json::json!({"seq": 7}));
+        assert_eq!(
+            serde_json::to_string(&payload).unwrap(),
+            r#"{"d":{"seq":7},"op":6}"#
+        );
+    }
+
+    #[cfg(any(debug_assertions, feature = "unstable"))]
+    #[tokio::test]
+    async fn test_send_op_requires_connection() {
+        let token = Token::new("test_app_id", "test_secret");
+        let gateway = Gateway::new("wss://example.com", token, Intents::default(), None);
+
+        assert!(
+            gateway
+                .send_op(opcodes::HEARTBEAT, serde_json::json!(0))
+                .await
+                .is_err()
+        );
+        assert!(gateway.send_heartbeat_now().await.is_err());
+    }
+
+    fn dispatch(event_type: &str, seq: u64) -> GatewayEvent {
+        GatewayEvent {
+            event_type: Some(event_type.to_string()),
+            data: None,
+            sequence: Some(seq),
+            opcode: opcodes::DISPATCH,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_event() {
+        let token = Token::new("test_app_id", "test_secret");
+        let gateway = Gateway::new("wss://example.com", token, Intents::default(), None);
+        let mut subscription = gateway.subscribe_events();
+
+        for (i, event_type) in [
+            "GUILD_CREATE",
+            "CHANNEL_CREATE",
+            "AT_MESSAGE_CREATE",
+            "GUILD_UPDATE",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            gateway
+                .subscribers
+                .send(dispatch(event_type, i as u64))
+                .unwrap();
+        }
+
+        let event = subscription
+            .wait_for("AT_MESSAGE_CREATE", Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(event.sequence, Some(2));
+
+        let event = subscription
+            .wait_for_with_filter(|e| e.sequence == Some(3), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(event.event_type.as_deref(), Some("GUILD_UPDATE"));
+
+        let result = subscription
+            .wait_for("READY", Duration::from_millis(20))
+            .await;
+        assert!(matches!(result, Err(BotError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_closed_stream() {
+        let (sender, receiver) = broadcast::channel(4);
+        let mut subscription = GatewaySubscription::new(receiver);
+        sender.send(dispatch("GUILD_CREATE", 1)).unwrap();
+        drop(sender);
+
+        let result = subscription.wait_for("READY", Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(BotError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn test_guild_changes_filters_by_guild() {
+        use crate::models::guild::GuildChangeKind;
+        use serde_json::{Value, json};
+
+        let (sender, receiver) = tokio::sync::broadcast::channel(8);
+        let event = |event_type: &str, data: Value| GatewayEvent {
+            event_type: Some(event_type.to_string()),
+            data: Some(data),
+            sequence: None,
+            opcode: 0,
+        };
+        for e in [
+            event("CHANNEL_CREATE", json!({"guild_id": "g2", "id": "other"})),
+            event(
+                "AT_MESSAGE_CREATE",
+                json!({"guild_id": "g1", "content": "hi"}),
+            ),
+            event("CHANNEL_CREATE", json!({"guild_id": "g1", "id": "c1"})),
+            event(
+                "GUILD_ROLE_DELETE",
+                json!({"guild_id": "g1", "role_id": "r1"}),
+            ),
+        ] {
+            sender.send(e).unwrap();
+        }
+        drop(sender);
+
+        let changes: Vec<GuildChangeEvent> = GatewaySubscription::new(receiver)
+            .guild_changes("g1")
+            .collect()
+            .await;
+        assert_eq!(changes.len(), 2);
+        assert!(
+            matches!(changes[0].kind, GuildChangeKind::ChannelCreated(ref c) if c.id.as_deref() == Some("c1"))
+        );
+        assert_eq!(
+            changes[1].kind,
+            GuildChangeKind::RoleDeleted("r1".to_string())
+        );
+    }
 }
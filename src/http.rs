@@ -6,11 +6,43 @@
 use crate::error::{BotError, Result, http_error_from_status};
 use crate::models::api::{ApiError, RateLimit};
 use crate::token::Token;
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
 use reqwest::{Client, Method, Response, StatusCode};
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tracing::{debug, error, warn};
 
+/// Size of the chunks a request body is streamed in when tracking progress.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Progress of an upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Bytes handed to the connection so far
+    pub bytes_sent: u64,
+    /// Total size of the request body
+    pub total_bytes: u64,
+}
+
+impl UploadProgress {
+    /// Returns true once the whole body has been sent.
+    pub fn is_complete(&self) -> bool {
+        self.bytes_sent >= self.total_bytes
+    }
+
+    /// Returns the fraction of the body sent, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_sent as f64 / self.total_bytes as f64
+        }
+    }
+}
+
 /// HTTP client for the QQ Guild Bot API.
 #[derive(Clone)]
 pub struct HttpClient {
@@ -22,6 +54,9 @@ pub struct HttpClient {
     is_sandbox: bool,
     /// Request timeout
     timeout: Duration,
+    /// Last rate limit seen for each route scope, see [`rate_limit_scope`];
+    /// scopes whose window has reset are dropped as new ones are recorded
+    rate_limits: Arc<Mutex<HashMap<String, RateLimit>>>,
 }
 
 impl HttpClient {
@@ -57,6 +92,7 @@ impl HttpClient {
             base_url,
             is_sandbox,
             timeout: Duration::from_secs(timeout),
+            rate_limits: Arc::default(),
         })
     }
 
@@ -186,6 +222,130 @@ impl HttpClient {
         self.request(Method::PATCH, token, path, query, body).await
     }
 
+    /// Downloads raw bytes from an absolute URL.
+    ///
+    /// Unlike the API methods this sends no authorization header, so it is
+    /// suitable for fetching public resources such as CDN-hosted avatars.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The full URL to download
+    ///
+    /// # Returns
+    ///
+    /// The response body.
+    pub async fn download(&self, url: &str) -> Result<Bytes> {
+        debug!("Downloading: {}", url);
+        let response = self.client.get(url).send().await.map_err(BotError::Http)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(http_error_from_status(status.as_u16(), message));
+        }
+
+        response.bytes().await.map_err(BotError::Http)
+    }
+
+    /// Posts a JSON body to an absolute URL.
+    ///
+    /// Like [`HttpClient::download`] this sends no authorization header; it is
+    /// meant for endpoints that carry their own credentials, such as webhook URLs.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The full URL to post to
+    /// * `body` - The request body
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn post_to_url<B>(&self, url: &str, body: &B) -> Result<()>
+    where
+        B: Serialize + ?Sized,
+    {
+        debug!("Posting to: {}", url);
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(BotError::Http)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(http_error_from_status(status.as_u16(), message));
+        }
+        Ok(())
+    }
+
+    /// Sends a HEAD request to an absolute URL.
+    ///
+    /// Any HTTP response counts as success, since this only checks that the
+    /// host answers. Like [`HttpClient::download`] it sends no authorization
+    /// header.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The full URL to request
+    ///
+    /// # Returns
+    ///
+    /// The response headers.
+    pub async fn head_url(&self, url: &str) -> Result<reqwest::header::HeaderMap> {
+        debug!("Sending HEAD to: {}", url);
+        let response = self.client.head(url).send().await.map_err(BotError::Http)?;
+        Ok(response.headers().clone())
+    }
+
+    /// Makes a POST request with a JSON body, reporting upload progress.
+    ///
+    /// The body is streamed in chunks and `on_progress` is called with
+    /// `(bytes_sent, total_bytes)` as each chunk is handed to the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Authentication token
+    /// * `path` - API endpoint path
+    /// * `body` - The serialized JSON body
+    /// * `on_progress` - Progress callback
+    ///
+    /// # Returns
+    ///
+    /// The response body as a JSON value.
+    pub async fn post_with_progress<F>(
+        &self,
+        token: &Token,
+        path: &str,
+        body: Vec<u8>,
+        on_progress: F,
+    ) -> Result<serde_json::Value>
+    where
+        F: Fn(u64, u64) + Send + 'static,
+    {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("Making POST request with progress to: {}", url);
+
+        let auth_header = token.authorization_header().await?;
+        let content_length = body.len();
+        let stream = progress_stream(Bytes::from(body), UPLOAD_CHUNK_SIZE, on_progress);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", auth_header)
+            .header("Content-Type", "application/json")
+            .header("Content-Length", content_length)
+            .body(reqwest::Body::wrap_stream(stream))
+            .send()
+            .await
+            .map_err(BotError::Http)?;
+
+        self.handle_response(path, response).await
+    }
+
     /// Makes a generic HTTP request to the API.
     ///
     /// # Arguments
@@ -238,21 +398,23 @@ impl HttpClient {
         // Send the request
         let response = request.send().await.map_err(BotError::Http)?;
 
-        self.handle_response(response).await
+        self.handle_response(path, response).await
     }
 
     /// Handles the HTTP response and converts it to a JSON value.
     ///
     /// # Arguments
     ///
+    /// * `path` - API endpoint path the response is for
     /// * `response` - The HTTP response
     ///
     /// # Returns
     ///
     /// The response body as a JSON value or an error.
-    async fn handle_response(&self, response: Response) -> Result<serde_json::Value> {
+    async fn handle_response(&self, path: &str, response: Response) -> Result<serde_json::Value> {
         let status = response.status();
         let headers = response.headers().clone();
+        self.record_rate_limit(path, status, &headers);
 
         // Check for rate limiting
         if status == StatusCode::TOO_MANY_REQUESTS {
@@ -283,11 +445,6 @@ impl HttpClient {
             return Err(http_error_from_status(status.as_u16(), api_error.message));
         }
 
-        // Log rate limit information if available
-        if let Some(rate_limit) = self.parse_rate_limit(&headers) {
-            debug!("Rate limit info: {:?}", rate_limit);
-        }
-
         debug!("Request successful, response: {}", json);
         Ok(json)
     }
@@ -379,6 +536,55 @@ impl HttpClient {
         })
     }
 
+    /// Remembers the rate limit reported by a response.
+    ///
+    /// A 429 response always marks the route scope as exhausted until the
+    /// `retry-after` delay has passed, even without rate limit headers.
+    /// Rate limits whose window has reset are forgotten.
+    fn record_rate_limit(
+        &self,
+        path: &str,
+        status: StatusCode,
+        headers: &reqwest::header::HeaderMap,
+    ) {
+        let scope = rate_limit_scope(path);
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut rate_limits = self.rate_limits.lock().unwrap_or_else(|e| e.into_inner());
+        rate_limits.retain(|_, rate_limit| rate_limit.reset > now);
+        let rate_limit = if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = headers
+                .get("retry-after")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(60);
+            let previous = rate_limits.get(&scope);
+            RateLimit {
+                bucket: previous.and_then(|r| r.bucket.clone()),
+                limit: previous.map_or(0, |r| r.limit),
+                remaining: 0,
+                reset: now + retry_after,
+                retry_after: Some(retry_after),
+            }
+        } else if let Some(rate_limit) = self.parse_rate_limit(headers) {
+            rate_limit
+        } else {
+            return;
+        };
+        debug!("Rate limit info for {}: {:?}", scope, rate_limit);
+        rate_limits.insert(scope, rate_limit);
+    }
+
+    /// Gets the last rate limit the API reported for sending messages to a
+    /// channel.
+    pub fn message_rate_limit(&self, channel_id: &str) -> Option<RateLimit> {
+        let scope = rate_limit_scope(&format!("/channels/{channel_id}/messages"));
+        self.rate_limits
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&scope)
+            .cloned()
+    }
+
     /// Gets the base URL being used by this client.
     pub fn base_url(&self) -> &str {
         &self.base_url
@@ -401,6 +607,45 @@ impl HttpClient {
     }
 }
 
+/// The rate limit scope of a request path: its first two segments, such as
+/// `guilds/123` for `/guilds/123/members`.
+///
+/// Message routes keep their third segment, so `/channels/123/messages/456`
+/// is scoped to `channels/123/messages` apart from other channel routes.
+fn rate_limit_scope(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments: Vec<_> = path.trim_start_matches('/').split('/').collect();
+    let len = if segments.get(2) == Some(&"messages") {
+        3
+    } else {
+        2
+    };
+    segments.into_iter().take(len).collect::<Vec<_>>().join("/")
+}
+
+/// Splits `data` into chunks, calling `on_progress` as each chunk is yielded.
+fn progress_stream<F>(
+    data: Bytes,
+    chunk_size: usize,
+    on_progress: F,
+) -> impl Stream<Item = std::io::Result<Bytes>>
+where
+    F: Fn(u64, u64),
+{
+    let total = data.len() as u64;
+    let chunks: Vec<Bytes> = (0..data.len())
+        .step_by(chunk_size)
+        .map(|start| data.slice(start..data.len().min(start + chunk_size)))
+        .collect();
+
+    let mut sent = 0;
+    futures_util::stream::iter(chunks).map(move |chunk| {
+        sent += chunk.len() as u64;
+        on_progress(sent, total);
+        Ok(chunk)
+    })
+}
+
 impl std::fmt::Debug for HttpClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HttpClient")
@@ -411,6 +656,181 @@ impl std::fmt::Debug for HttpClient {
     }
 }
 
+/// A local HTTP server standing in for the API in tests.
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::HttpClient;
+    use crate::api::BotApi;
+    use crate::token::Token;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// A request received by a [`MockServer`].
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct MockRequest {
+        /// HTTP method, e.g. `"GET"`
+        pub method: String,
+        /// Request path without the query string
+        pub path: String,
+        /// Raw query string, if any
+        pub query: Option<String>,
+        /// JSON body, or `Value::Null` if there was none
+        pub body: Value,
+    }
+
+    /// A response returned by a [`MockServer`].
+    #[derive(Debug, Clone)]
+    pub(crate) struct MockResponse {
+        status: u16,
+        body: Value,
+        headers: Vec<(String, String)>,
+    }
+
+    impl MockResponse {
+        /// A 200 response with a JSON body.
+        pub fn ok(body: Value) -> Self {
+            Self::status(200, body)
+        }
+
+        /// A response with a status code and JSON body.
+        pub fn status(status: u16, body: Value) -> Self {
+            Self {
+                status,
+                body,
+                headers: Vec::new(),
+            }
+        }
+
+        /// Adds a response header.
+        pub fn with_header(mut self, name: &str, value: &str) -> Self {
+            self.headers.push((name.to_string(), value.to_string()));
+            self
+        }
+    }
+
+    type Responder = dyn Fn(&MockRequest) -> MockResponse + Send + Sync;
+
+    /// Serves every request with a responder function and records it.
+    pub(crate) struct MockServer {
+        base_url: String,
+        requests: Arc<Mutex<Vec<MockRequest>>>,
+    }
+
+    impl MockServer {
+        /// Starts a server on a free local port.
+        pub async fn start(
+            responder: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+        ) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let responder: Arc<Responder> = Arc::new(responder);
+
+            let recorded = requests.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let responder = responder.clone();
+                    let recorded = recorded.clone();
+                    tokio::spawn(async move {
+                        let _ = serve(stream, &*responder, &recorded).await;
+                    });
+                }
+            });
+            Self { base_url, requests }
+        }
+
+        /// An HTTP client sending every request to this server.
+        pub fn http(&self) -> HttpClient {
+            let mut http = HttpClient::new(5, false).unwrap();
+            http.base_url = self.base_url.clone();
+            http
+        }
+
+        /// An API client sending every request to this server.
+        pub fn api(&self) -> BotApi {
+            BotApi::new(self.http())
+        }
+
+        /// A token that doesn't need refreshing.
+        pub fn token() -> Token {
+            Token::with_access_token("test_access_token")
+        }
+
+        /// The requests received so far, in order.
+        pub fn requests(&self) -> Vec<MockRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    /// Reads one request from the connection, answers it and closes it.
+    async fn serve(
+        mut stream: TcpStream,
+        responder: &Responder,
+        recorded: &Mutex<Vec<MockRequest>>,
+    ) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break i + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default().to_string();
+        let content_length: usize = lines
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse().ok())
+            .unwrap_or(0);
+        while buf.len() < header_end + content_length {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+
+        let body = serde_json::from_slice(&buf[header_end..]).unwrap_or(Value::Null);
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(query.to_string())),
+            None => (target, None),
+        };
+        let request = MockRequest {
+            method,
+            path,
+            query,
+            body,
+        };
+        let response = responder(&request);
+        recorded.lock().unwrap().push(request);
+
+        let body = response.body.to_string();
+        let mut out = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status,
+            body.len()
+        );
+        for (name, value) in &response.headers {
+            out.push_str(&format!("{name}: {value}\r\n"));
+        }
+        out.push_str("\r\n");
+        out.push_str(&body);
+        stream.write_all(out.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -461,4 +881,72 @@ mod tests {
         assert_eq!(rate_limit.reset, 1234567890);
         assert_eq!(rate_limit.bucket, Some("global".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_progress_stream() {
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let stream = progress_stream(Bytes::from(vec![7u8; 10]), 4, move |sent, total| {
+            recorded.lock().unwrap().push((sent, total));
+        });
+
+        let chunks: Vec<Bytes> = stream.map(|chunk| chunk.unwrap()).collect().await;
+        assert_eq!(
+            chunks.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![4, 4, 2]
+        );
+        assert_eq!(*calls.lock().unwrap(), vec![(4, 10), (8, 10), (10, 10)]);
+
+        let progress = UploadProgress {
+            bytes_sent: 5,
+            total_bytes: 10,
+        };
+        assert_eq!(progress.fraction(), 0.5);
+        assert!(!progress.is_complete());
+    }
+
+    #[test]
+    fn test_rate_limit_recorded_per_message_route() {
+        let client = HttpClient::new(30, false).unwrap();
+        assert_eq!(rate_limit_scope("/guilds/g1/members?limit=1"), "guilds/g1");
+        assert_eq!(rate_limit_scope("/gateway"), "gateway");
+        assert_eq!(
+            rate_limit_scope("/channels/c1/messages/m1"),
+            "channels/c1/messages"
+        );
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "20".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "4".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "4102444800".parse().unwrap());
+        client.record_rate_limit("/channels/c1/messages", StatusCode::OK, &headers);
+        client.record_rate_limit("/channels/c2", StatusCode::OK, &headers);
+        assert_eq!(client.message_rate_limit("c1").unwrap().remaining, 4);
+        assert!(client.message_rate_limit("c2").is_none());
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("retry-after", "30".parse().unwrap());
+        client.record_rate_limit(
+            "/channels/c1/messages",
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers,
+        );
+        let rate_limit = client.message_rate_limit("c1").unwrap();
+        assert_eq!(rate_limit.limit, 20);
+        assert_eq!(rate_limit.retry_after, Some(30));
+
+        let quota = crate::models::MessageQuota::from(&rate_limit);
+        assert!(quota.is_exhausted());
+        assert!(quota.time_until_reset() > Duration::from_secs(25));
+
+        // Recording a new rate limit drops the ones whose window has reset
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "20".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "19".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1".parse().unwrap());
+        client.record_rate_limit("/channels/c3/messages", StatusCode::OK, &headers);
+        client.record_rate_limit("/channels/c4/messages", StatusCode::OK, &headers);
+        assert!(client.message_rate_limit("c3").is_none());
+        assert!(client.message_rate_limit("c1").is_some());
+    }
 }
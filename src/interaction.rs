@@ -239,6 +239,59 @@ impl Interaction {
     pub fn button_data(&self) -> Option<&str> {
         self.data.resolved.button_data.as_deref()
     }
+
+    /// Acknowledge this interaction as handled successfully
+    pub async fn acknowledge(
+        &self,
+        api: &BotApi,
+        token: &crate::token::Token,
+    ) -> Result<(), crate::error::BotError> {
+        let id = self.id.as_deref().ok_or_else(|| {
+            crate::error::BotError::InvalidData("Missing interaction ID".to_string())
+        })?;
+        api.acknowledge_interaction(token, id, 0).await
+    }
+}
+
+/// A page of interactions waiting to be acknowledged
+#[derive(Debug, Clone)]
+pub struct InteractionPage {
+    /// Interactions on this page
+    pub interactions: Vec<Interaction>,
+    /// Cursor to request the following interactions with
+    pub cursor: Option<String>,
+    /// Whether more interactions are waiting after this page
+    pub has_more: bool,
+}
+
+impl InteractionPage {
+    /// Create a new InteractionPage instance
+    pub fn new(api: BotApi, data: &Value) -> Self {
+        let interactions = data
+            .get("interactions")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .map(|interaction| Interaction::new(api.clone(), None, interaction))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let cursor = data
+            .get("cursor")
+            .and_then(|v| v.as_str())
+            .filter(|cursor| !cursor.is_empty())
+            .map(String::from);
+        let has_more = data
+            .get("has_more")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        Self {
+            interactions,
+            cursor,
+            has_more,
+        }
+    }
 }
 
 impl std::fmt::Display for Interaction {
@@ -296,4 +349,27 @@ mod tests {
             InteractionDataType::InlineKeyboardButtonClick
         );
     }
+
+    #[test]
+    fn test_interaction_page() {
+        let api = BotApi::new(crate::http::HttpClient::new(30, false).unwrap());
+        let page = InteractionPage::new(
+            api.clone(),
+            &serde_json::json!({
+                "interactions": [{"id": "i1", "type": 11}, {"id": "i2", "type": 2}],
+                "cursor": "next",
+                "has_more": true
+            }),
+        );
+        assert_eq!(page.interactions.len(), 2);
+        assert_eq!(page.interactions[0].id.as_deref(), Some("i1"));
+        assert!(page.interactions[1].is_command_interaction());
+        assert_eq!(page.cursor.as_deref(), Some("next"));
+        assert!(page.has_more);
+
+        let empty = InteractionPage::new(api, &serde_json::json!({"cursor": ""}));
+        assert!(empty.interactions.is_empty());
+        assert!(empty.cursor.is_none());
+        assert!(!empty.has_more);
+    }
 }
@@ -5,32 +5,51 @@
 pub mod api;
 pub mod audio;
 pub mod client;
+pub mod config;
 pub mod connection;
 pub mod error;
+pub mod filter;
 pub mod forum;
 pub mod gateway;
 pub mod http;
 pub mod intents;
 pub mod interaction;
 pub mod manage;
+pub mod media;
 pub mod models;
 pub mod reaction;
+pub mod state_machine;
 pub mod token;
+pub mod webhook;
 
 // Re-export main types for convenience
 pub use api::BotApi;
 pub use audio::{Audio, AudioControl, AudioStatus, PublicAudio, PublicAudioType};
 pub use client::{Client, Context, EventHandler};
+pub use config::{GuildConfig, GuildConfigStore};
 pub use connection::{ConnectionSession, ConnectionState, Session};
 pub use error::{BotError, Result};
-pub use forum::{Content, Format, OpenThread, Thread, ThreadInfo, Title};
+pub use filter::{
+    FilterAction, FilterChain, FilterResult, FilteredHandler, LengthFilter, MentionSpamFilter,
+    MessageFilter, UrlFilter, WordlistFilter,
+};
+pub use forum::{
+    Content, Format, ForumChannelSummary, ForumContent, ForumPost, ForumReply, OpenThread,
+    PostPage, ReplyPage, Thread, ThreadInfo, ThreadPage, ThreadParticipant, ThreadSearchOptions,
+    Title,
+};
 pub use intents::Intents;
-pub use interaction::{Interaction, InteractionData, InteractionDataType, InteractionType};
+pub use interaction::{
+    Interaction, InteractionData, InteractionDataType, InteractionPage, InteractionType,
+};
 pub use manage::{C2CManageEvent, GroupManageEvent, ManageEventType};
+pub use media::MediaManager;
 pub use models::gateway::Ready;
 pub use models::*;
 pub use reaction::{Reaction, ReactionTarget, ReactionTargetType, ReactionUsers};
+pub use state_machine::{GatewayState, GatewayStateEvent, GatewayStateMachine, InvalidTransition};
 pub use token::Token;
+pub use webhook::{WebhookSignatureError, verify_webhook_signature};
 
 /// The current version of the library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
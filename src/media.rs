@@ -0,0 +1,208 @@
+//! Cached media uploads for QQ Bot
+//!
+//! Rich media sent to C2C and group chats must first be uploaded, and the
+//! returned `Media` is only valid for its `ttl`. This module provides a
+//! `MediaManager` that remembers uploads and reuses them until they expire, so
+//! the same image is not uploaded again for every message.
+
+use crate::api::BotApi;
+use crate::error::Result;
+use crate::models::message::Media;
+use crate::token::Token;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Cache of uploaded media keyed by the original file URL
+///
+/// Uploads are scoped to the chat they were made for, so the cache key also
+/// includes the target user or group.
+#[derive(Debug, Default)]
+pub struct MediaManager {
+    cache: Mutex<HashMap<String, (Media, Instant)>>,
+}
+
+impl MediaManager {
+    /// Create an empty MediaManager instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get cached media for a C2C chat, uploading it if missing or expired
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The Bot API client
+    /// * `token` - Authentication token
+    /// * `openid` - The user's OpenID
+    /// * `url` - The file URL
+    /// * `file_type` - The file type (1=image, 2=video, 3=audio, 4=file)
+    pub async fn get_or_upload_c2c(
+        &self,
+        api: &BotApi,
+        token: &Token,
+        openid: &str,
+        url: &str,
+        file_type: u32,
+    ) -> Result<Media> {
+        let key = format!("c2c:{openid}:{url}");
+        self.get_or_upload_with(key, || async {
+            let response = api
+                .post_c2c_file(token, openid, file_type, url, Some(false))
+                .await?;
+            Ok(serde_json::from_value(response)?)
+        })
+        .await
+    }
+
+    /// Get cached media for a group, uploading it if missing or expired
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The Bot API client
+    /// * `token` - Authentication token
+    /// * `group_openid` - The group OpenID
+    /// * `url` - The file URL
+    /// * `file_type` - The file type (1=image, 2=video, 3=audio, 4=file)
+    pub async fn get_or_upload_group(
+        &self,
+        api: &BotApi,
+        token: &Token,
+        group_openid: &str,
+        url: &str,
+        file_type: u32,
+    ) -> Result<Media> {
+        let key = format!("group:{group_openid}:{url}");
+        self.get_or_upload_with(key, || async {
+            let response = api
+                .post_group_file(token, group_openid, file_type, url, Some(false))
+                .await?;
+            Ok(serde_json::from_value(response)?)
+        })
+        .await
+    }
+
+    /// Remove every cached upload
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Get the number of cached uploads, including expired ones
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    async fn get_or_upload_with<F, Fut>(&self, key: String, upload: F) -> Result<Media>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Media>>,
+    {
+        if let Some((media, uploaded_at)) = self.lock().get(&key)
+            && !is_expired(media, *uploaded_at)
+        {
+            debug!("Media cache hit for {}", key);
+            return Ok(media.clone());
+        }
+
+        debug!("Media cache miss for {}, uploading", key);
+        let media = upload().await?;
+        self.lock().insert(key, (media.clone(), Instant::now()));
+        Ok(media)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Media, Instant)>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Check whether uploaded media has outlived its TTL
+///
+/// A missing or zero TTL means the media does not expire.
+fn is_expired(media: &Media, uploaded_at: Instant) -> bool {
+    match media.ttl {
+        Some(ttl) if ttl > 0 => uploaded_at.elapsed() >= Duration::from_secs(ttl.into()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn media(file_info: &str, ttl: u32) -> Media {
+        Media {
+            file_info: Some(file_info.to_string()),
+            ttl: Some(ttl),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_skips_upload() {
+        let manager = MediaManager::new();
+        let uploads = AtomicU32::new(0);
+        let upload = || async {
+            uploads.fetch_add(1, Ordering::SeqCst);
+            Ok(media("info", 3600))
+        };
+
+        let first = manager
+            .get_or_upload_with("k".to_string(), upload)
+            .await
+            .unwrap();
+        let second = manager
+            .get_or_upload_with("k".to_string(), upload)
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(uploads.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_uploads() {
+        let manager = MediaManager::new();
+        let uploads = AtomicU32::new(0);
+        let upload = || async {
+            uploads.fetch_add(1, Ordering::SeqCst);
+            Ok(media("info", 3600))
+        };
+
+        manager
+            .get_or_upload_with("a".to_string(), upload)
+            .await
+            .unwrap();
+        manager
+            .get_or_upload_with("b".to_string(), upload)
+            .await
+            .unwrap();
+        assert_eq!(uploads.load(Ordering::SeqCst), 2);
+
+        manager.clear();
+        manager
+            .get_or_upload_with("a".to_string(), upload)
+            .await
+            .unwrap();
+        assert_eq!(uploads.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let now = Instant::now();
+        assert!(!is_expired(&media("info", 60), now));
+
+        // Instants before system boot cannot be represented
+        let Some(an_hour_ago) = now.checked_sub(Duration::from_secs(3600)) else {
+            return;
+        };
+        assert!(!is_expired(&media("info", 0), an_hour_ago));
+        assert!(is_expired(&media("info", 60), an_hour_ago));
+    }
+}
@@ -1,6 +1,6 @@
 //! API response models for the QQ Guild Bot API.
 
-use crate::models::Snowflake;
+use crate::models::{Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Standard API response wrapper.
@@ -197,6 +197,42 @@ impl RateLimit {
     }
 }
 
+/// How many more messages the bot can send before being rate limited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageQuota {
+    /// Requests left in the current window
+    pub remaining: u32,
+    /// Requests allowed per window
+    pub limit: u32,
+    /// When the window resets
+    pub resets_at: Timestamp,
+}
+
+impl MessageQuota {
+    /// Returns true if no requests are left in the current window.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Returns how long until the window resets, or zero if it already has.
+    pub fn time_until_reset(&self) -> std::time::Duration {
+        (self.resets_at - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+impl From<&RateLimit> for MessageQuota {
+    fn from(rate_limit: &RateLimit) -> Self {
+        Self {
+            remaining: rate_limit.remaining,
+            limit: rate_limit.limit,
+            resets_at: chrono::DateTime::from_timestamp(rate_limit.reset as i64, 0)
+                .unwrap_or_default(),
+        }
+    }
+}
+
 /// Error response from the API.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ApiError {
@@ -342,4 +378,27 @@ mod tests {
         let auth_error = ApiError::new(401, "Unauthorized");
         assert!(auth_error.is_auth_error());
     }
+
+    #[test]
+    fn test_message_quota() {
+        let rate_limit = RateLimit {
+            bucket: None,
+            limit: 20,
+            remaining: 0,
+            reset: chrono::Utc::now().timestamp() as u64 + 60,
+            retry_after: None,
+        };
+        let quota = MessageQuota::from(&rate_limit);
+        assert!(quota.is_exhausted());
+        assert_eq!(quota.limit, 20);
+        assert!(quota.time_until_reset() > std::time::Duration::from_secs(50));
+
+        let expired = MessageQuota {
+            remaining: 3,
+            limit: 20,
+            resets_at: chrono::Utc::now() - chrono::Duration::seconds(5),
+        };
+        assert!(!expired.is_exhausted());
+        assert_eq!(expired.time_until_reset(), std::time::Duration::ZERO);
+    }
 }
@@ -0,0 +1,907 @@
+//! Audit log data structures for the QQ Guild Bot API.
+//!
+//! This module contains the entries returned by the guild audit log endpoint,
+//! which records administrative actions such as member joins, bans and
+//! channel changes.
+
+use crate::models::{HasId, Snowflake, Timestamp};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Types of actions recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum AuditLogAction {
+    /// A member joined the guild
+    MemberAdd = 1,
+    /// A member left or was removed from the guild
+    MemberRemove = 2,
+    /// A member's roles or nickname changed
+    MemberUpdate = 3,
+    /// A member was banned
+    MemberBan = 4,
+    /// A member was muted
+    MemberMute = 5,
+    /// A channel was created
+    ChannelCreate = 10,
+    /// A channel was updated
+    ChannelUpdate = 11,
+    /// A channel was deleted
+    ChannelDelete = 12,
+    /// A role was created
+    RoleCreate = 20,
+    /// A role was updated
+    RoleUpdate = 21,
+    /// A role was deleted
+    RoleDelete = 22,
+    /// A message was deleted
+    MessageDelete = 30,
+    /// A message was pinned
+    MessagePin = 31,
+    /// A forum thread was created
+    ThreadCreate = 40,
+    /// A forum thread was deleted
+    ThreadDelete = 41,
+    /// A forum post was deleted
+    PostDelete = 42,
+    /// A forum reply was deleted
+    ReplyDelete = 43,
+    /// Unknown action type
+    Unknown(u8),
+}
+
+impl From<u8> for AuditLogAction {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::MemberAdd,
+            2 => Self::MemberRemove,
+            3 => Self::MemberUpdate,
+            4 => Self::MemberBan,
+            5 => Self::MemberMute,
+            10 => Self::ChannelCreate,
+            11 => Self::ChannelUpdate,
+            12 => Self::ChannelDelete,
+            20 => Self::RoleCreate,
+            21 => Self::RoleUpdate,
+            22 => Self::RoleDelete,
+            30 => Self::MessageDelete,
+            31 => Self::MessagePin,
+            40 => Self::ThreadCreate,
+            41 => Self::ThreadDelete,
+            42 => Self::PostDelete,
+            43 => Self::ReplyDelete,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<AuditLogAction> for u8 {
+    fn from(action: AuditLogAction) -> Self {
+        match action {
+            AuditLogAction::MemberAdd => 1,
+            AuditLogAction::MemberRemove => 2,
+            AuditLogAction::MemberUpdate => 3,
+            AuditLogAction::MemberBan => 4,
+            AuditLogAction::MemberMute => 5,
+            AuditLogAction::ChannelCreate => 10,
+            AuditLogAction::ChannelUpdate => 11,
+            AuditLogAction::ChannelDelete => 12,
+            AuditLogAction::RoleCreate => 20,
+            AuditLogAction::RoleUpdate => 21,
+            AuditLogAction::RoleDelete => 22,
+            AuditLogAction::MessageDelete => 30,
+            AuditLogAction::MessagePin => 31,
+            AuditLogAction::ThreadCreate => 40,
+            AuditLogAction::ThreadDelete => 41,
+            AuditLogAction::PostDelete => 42,
+            AuditLogAction::ReplyDelete => 43,
+            AuditLogAction::Unknown(value) => value,
+        }
+    }
+}
+
+/// A single field change recorded in an audit log entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogChange {
+    /// Name of the changed field
+    pub key: String,
+    /// Value before the change
+    #[serde(default)]
+    pub old_value: Option<Value>,
+    /// Value after the change
+    #[serde(default)]
+    pub new_value: Option<Value>,
+}
+
+/// Represents an entry in a guild's audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// The entry's unique ID
+    pub id: Option<Snowflake>,
+    /// The guild the action happened in
+    pub guild_id: Option<Snowflake>,
+    /// The user who performed the action
+    pub user_id: Option<Snowflake>,
+    /// The ID of the affected object, such as a channel or role
+    pub target_id: Option<Snowflake>,
+    /// The type of action
+    pub action_type: AuditLogAction,
+    /// Field changes made by the action
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+    /// Reason given for the action
+    pub reason: Option<String>,
+    /// When the action happened
+    pub created_at: Option<Timestamp>,
+}
+
+impl AuditLogEntry {
+    /// Gets the time the member joined, if this is a member join entry.
+    pub fn joined_at(&self) -> Option<Timestamp> {
+        match self.action_type {
+            AuditLogAction::MemberAdd => self.created_at,
+            _ => None,
+        }
+    }
+
+    /// Returns true if the entry records a join through an invite.
+    pub fn was_invite_based(&self) -> bool {
+        self.changes.iter().any(|c| c.key.contains("invite"))
+    }
+
+    /// Gets the change recorded for a specific field.
+    pub fn change(&self, key: &str) -> Option<&AuditLogChange> {
+        self.changes.iter().find(|c| c.key == key)
+    }
+
+    /// Gets a key identifying this entry across audit log fetches.
+    ///
+    /// This is the entry ID when there is one; otherwise it is built from the
+    /// action, user, target and time.
+    pub fn key(&self) -> String {
+        match &self.id {
+            Some(id) => id.clone(),
+            None => format!(
+                "{}:{}:{}:{}",
+                u8::from(self.action_type),
+                self.user_id.as_deref().unwrap_or_default(),
+                self.target_id.as_deref().unwrap_or_default(),
+                self.created_at
+                    .map(|created_at| created_at.to_rfc3339())
+                    .unwrap_or_default()
+            ),
+        }
+    }
+}
+
+impl HasId for AuditLogEntry {
+    fn id(&self) -> Option<&Snowflake> {
+        self.id.as_ref()
+    }
+}
+
+/// Moderation actions recorded in a forum channel's audit log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForumAuditAction {
+    /// A thread was deleted
+    ThreadDeleted,
+    /// A post was deleted
+    PostDeleted,
+    /// A thread was pinned
+    ThreadPinned,
+    /// A thread was locked
+    ThreadLocked,
+    /// A user was muted for the given duration
+    UserMuted(std::time::Duration),
+    /// Unknown action
+    Unknown(String),
+}
+
+/// Represents an entry in a forum channel's audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawForumAuditEntry", into = "RawForumAuditEntry")]
+pub struct ForumAuditEntry {
+    /// The action taken
+    pub action: ForumAuditAction,
+    /// The user who took the action
+    pub actor_id: String,
+    /// The thread, post or user the action applies to
+    pub target_id: String,
+    /// When the action happened
+    pub timestamp: Timestamp,
+    /// Reason given for the action
+    pub reason: Option<String>,
+}
+
+impl ForumAuditEntry {
+    /// Returns true if the action was taken by one of the given bots.
+    ///
+    /// A forum audit log entry only carries the actor's ID, with nothing
+    /// saying whether the actor is a bot, so the bot IDs to check against
+    /// have to be passed in, e.g. the bot's own ID from
+    /// [`BotApi::get_bot_info`](crate::BotApi::get_bot_info).
+    pub fn was_automated(&self, bot_ids: &[&str]) -> bool {
+        bot_ids.contains(&self.actor_id.as_str())
+    }
+}
+
+/// Wire format of [`ForumAuditEntry`], with mutes carrying their duration in
+/// `mute_seconds`.
+#[derive(Serialize, Deserialize)]
+struct RawForumAuditEntry {
+    action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mute_seconds: Option<u64>,
+    actor_id: String,
+    target_id: String,
+    timestamp: Timestamp,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl From<RawForumAuditEntry> for ForumAuditEntry {
+    fn from(raw: RawForumAuditEntry) -> Self {
+        let action = match raw.action.as_str() {
+            "thread_deleted" => ForumAuditAction::ThreadDeleted,
+            "post_deleted" => ForumAuditAction::PostDeleted,
+            "thread_pinned" => ForumAuditAction::ThreadPinned,
+            "thread_locked" => ForumAuditAction::ThreadLocked,
+            "user_muted" => ForumAuditAction::UserMuted(std::time::Duration::from_secs(
+                raw.mute_seconds.unwrap_or_default(),
+            )),
+            _ => ForumAuditAction::Unknown(raw.action),
+        };
+        Self {
+            action,
+            actor_id: raw.actor_id,
+            target_id: raw.target_id,
+            timestamp: raw.timestamp,
+            reason: raw.reason,
+        }
+    }
+}
+
+impl From<ForumAuditEntry> for RawForumAuditEntry {
+    fn from(entry: ForumAuditEntry) -> Self {
+        let (action, mute_seconds) = match entry.action {
+            ForumAuditAction::ThreadDeleted => ("thread_deleted".to_string(), None),
+            ForumAuditAction::PostDeleted => ("post_deleted".to_string(), None),
+            ForumAuditAction::ThreadPinned => ("thread_pinned".to_string(), None),
+            ForumAuditAction::ThreadLocked => ("thread_locked".to_string(), None),
+            ForumAuditAction::UserMuted(duration) => {
+                ("user_muted".to_string(), Some(duration.as_secs()))
+            }
+            ForumAuditAction::Unknown(action) => (action, None),
+        };
+        Self {
+            action,
+            mute_seconds,
+            actor_id: entry.actor_id,
+            target_id: entry.target_id,
+            timestamp: entry.timestamp,
+            reason: entry.reason,
+        }
+    }
+}
+
+/// Number of entry keys an [`AuditLogStream`] remembers.
+const AUDIT_LOG_SEEN_CAPACITY: usize = 1000;
+
+/// Tracks which audit log entries have been seen while polling the log.
+///
+/// Each poll returns the newest entries, so consecutive polls overlap.
+/// [`AuditLogStream::accept`] drops the entries already seen and returns
+/// the rest oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogStream {
+    since: Option<Timestamp>,
+    seen: HashSet<String>,
+    seen_order: VecDeque<String>,
+    last_seen_id: Option<Snowflake>,
+}
+
+impl AuditLogStream {
+    /// Creates a tracker that accepts every entry not yet seen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker that only accepts entries created after `since`.
+    ///
+    /// Entries without a creation time are accepted.
+    pub fn since(since: Timestamp) -> Self {
+        Self {
+            since: Some(since),
+            ..Self::default()
+        }
+    }
+
+    /// Gets the ID of the newest entry accepted so far.
+    pub fn last_seen_id(&self) -> Option<&str> {
+        self.last_seen_id.as_deref()
+    }
+
+    /// Takes one poll's entries, newest first, and returns the new ones
+    /// oldest first.
+    ///
+    /// The keys of the last 1000 entries polled are remembered, so a poll
+    /// that comes back empty doesn't make older entries look new again.
+    pub fn accept(&mut self, entries: Vec<AuditLogEntry>) -> Vec<AuditLogEntry> {
+        let keys: Vec<String> = entries.iter().rev().map(AuditLogEntry::key).collect();
+        let mut fresh: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| !self.seen.contains(&entry.key()))
+            .filter(|entry| match (self.since, entry.created_at) {
+                (Some(since), Some(created_at)) => created_at > since,
+                _ => true,
+            })
+            .collect();
+        fresh.reverse();
+        if let Some(id) = fresh.iter().rev().find_map(|entry| entry.id.clone()) {
+            self.last_seen_id = Some(id);
+        }
+        for key in keys {
+            if self.seen.insert(key.clone()) {
+                self.seen_order.push_back(key);
+            }
+        }
+        while self.seen_order.len() > AUDIT_LOG_SEEN_CAPACITY {
+            if let Some(key) = self.seen_order.pop_front() {
+                self.seen.remove(&key);
+            }
+        }
+        fresh
+    }
+}
+
+/// Something that happened to a member, as recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberEventType {
+    /// The member joined the guild
+    Joined,
+    /// The member left the guild
+    Left,
+    /// The member was removed by someone else
+    Kicked {
+        /// Reason given for the removal
+        reason: Option<String>,
+    },
+    /// The member was banned
+    Banned,
+    /// The member was given a role
+    RoleAdded(Snowflake),
+    /// A role was taken from the member
+    RoleRemoved(Snowflake),
+    /// The member's nickname changed
+    NickChanged {
+        /// The previous nickname
+        old: Option<String>,
+        /// The new nickname
+        new: Option<String>,
+    },
+}
+
+/// An entry in a member's timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberEvent {
+    /// When it happened
+    pub timestamp: Timestamp,
+    /// What happened
+    pub event_type: MemberEventType,
+    /// The guild it happened in
+    pub guild_id: Snowflake,
+    /// Who did it, if not the member themselves
+    pub actor_id: Option<Snowflake>,
+}
+
+impl MemberEvent {
+    /// Builds a member's timeline from audit log entries, oldest first.
+    ///
+    /// An entry concerns the member if they are its target, or if they
+    /// performed it and it has no target. Entries without a creation time
+    /// are skipped, and one role update can produce several events.
+    pub fn timeline(guild_id: &str, user_id: &str, entries: &[AuditLogEntry]) -> Vec<Self> {
+        let mut events: Vec<Self> = entries
+            .iter()
+            .filter(|entry| match entry.target_id.as_deref() {
+                Some(target_id) => target_id == user_id,
+                None => entry.user_id.as_deref() == Some(user_id),
+            })
+            .flat_map(|entry| Self::from_audit_entry(guild_id, user_id, entry))
+            .collect();
+        events.sort_by_key(|event| event.timestamp);
+        events
+    }
+
+    /// Creates the events recorded by one audit log entry about a member.
+    fn from_audit_entry(guild_id: &str, user_id: &str, entry: &AuditLogEntry) -> Vec<Self> {
+        let Some(timestamp) = entry.created_at else {
+            return Vec::new();
+        };
+        let actor_id = entry.user_id.clone().filter(|actor| actor != user_id);
+        let event_types = match entry.action_type {
+            AuditLogAction::MemberAdd => vec![MemberEventType::Joined],
+            AuditLogAction::MemberRemove if actor_id.is_some() => vec![MemberEventType::Kicked {
+                reason: entry.reason.clone(),
+            }],
+            AuditLogAction::MemberRemove => vec![MemberEventType::Left],
+            AuditLogAction::MemberBan => vec![MemberEventType::Banned],
+            AuditLogAction::MemberUpdate => member_update_events(entry),
+            _ => Vec::new(),
+        };
+        event_types
+            .into_iter()
+            .map(|event_type| Self {
+                timestamp,
+                event_type,
+                guild_id: guild_id.to_string(),
+                actor_id: actor_id.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Turns the role and nickname changes of a member update into events.
+fn member_update_events(entry: &AuditLogEntry) -> Vec<MemberEventType> {
+    let mut events = Vec::new();
+    if let Some(change) = entry.change("roles") {
+        let roles = |value: &Option<Value>| -> Vec<String> {
+            value
+                .as_ref()
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default()
+        };
+        let (old, new) = (roles(&change.old_value), roles(&change.new_value));
+        events.extend(
+            new.iter()
+                .filter(|role| !old.contains(role))
+                .map(|role| MemberEventType::RoleAdded(role.clone())),
+        );
+        events.extend(
+            old.iter()
+                .filter(|role| !new.contains(role))
+                .map(|role| MemberEventType::RoleRemoved(role.clone())),
+        );
+    }
+    if let Some(change) = entry.change("nick") {
+        let nick = |value: &Option<Value>| value.as_ref().and_then(Value::as_str).map(String::from);
+        events.push(MemberEventType::NickChanged {
+            old: nick(&change.old_value),
+            new: nick(&change.new_value),
+        });
+    }
+    events
+}
+
+/// The kind of activity recorded in a [`GuildActivityFeed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActivityKind {
+    /// A message was sent
+    MessageSent,
+    /// A reaction was added
+    ReactionAdded,
+    /// A member joined the guild
+    MemberJoined,
+    /// A channel was created
+    ChannelCreated,
+    /// A role was assigned to a member
+    RoleAssigned,
+}
+
+impl ActivityKind {
+    /// Maps an audit log entry to an activity kind.
+    ///
+    /// The audit log doesn't record messages or reactions, so
+    /// [`ActivityKind::MessageSent`] and [`ActivityKind::ReactionAdded`] are
+    /// never produced here.
+    pub fn from_audit_entry(entry: &AuditLogEntry) -> Option<Self> {
+        match entry.action_type {
+            AuditLogAction::MemberAdd => Some(Self::MemberJoined),
+            AuditLogAction::ChannelCreate => Some(Self::ChannelCreated),
+            AuditLogAction::MemberUpdate if entry.change("roles").is_some() => {
+                Some(Self::RoleAssigned)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A single activity in a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// What happened
+    pub kind: ActivityKind,
+    /// The user who performed the activity
+    pub user_id: Snowflake,
+    /// The object acted on, if any
+    pub target_id: Option<Snowflake>,
+    /// When it happened
+    pub timestamp: Timestamp,
+}
+
+impl ActivityEntry {
+    /// Creates an activity entry from an audit log entry.
+    ///
+    /// Returns `None` for actions that aren't tracked as activity or entries
+    /// missing a user or timestamp.
+    pub fn from_audit_entry(entry: &AuditLogEntry) -> Option<Self> {
+        Some(Self {
+            kind: ActivityKind::from_audit_entry(entry)?,
+            user_id: entry.user_id.clone()?,
+            target_id: entry.target_id.clone(),
+            timestamp: entry.created_at?,
+        })
+    }
+}
+
+/// A summary of recent activity in a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildActivityFeed {
+    /// The guild ID
+    pub guild_id: Snowflake,
+    /// Activities, in the order the audit log returned them
+    pub entries: Vec<ActivityEntry>,
+    /// When the feed was generated
+    pub generated_at: Timestamp,
+}
+
+impl GuildActivityFeed {
+    /// Builds a feed from audit log entries, keeping activities at or after `since`.
+    pub fn from_audit_log(
+        guild_id: impl Into<String>,
+        entries: &[AuditLogEntry],
+        since: Timestamp,
+    ) -> Self {
+        Self {
+            guild_id: guild_id.into(),
+            entries: entries
+                .iter()
+                .filter_map(ActivityEntry::from_audit_entry)
+                .filter(|entry| entry.timestamp >= since)
+                .collect(),
+            generated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Returns the `n` users with the most activities and their counts.
+    ///
+    /// Users with equal counts are ordered by user ID.
+    pub fn top_active_users(&self, n: usize) -> Vec<(String, u32)> {
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        for entry in &self.entries {
+            *counts.entry(&entry.user_id).or_default() += 1;
+        }
+        let mut users: Vec<(String, u32)> = counts
+            .into_iter()
+            .map(|(user_id, count)| (user_id.to_string(), count))
+            .collect();
+        users.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        users.truncate(n);
+        users
+    }
+}
+
+/// Activity counts for one day in a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildDailyStats {
+    /// The day, as `YYYY-MM-DD` in UTC
+    pub date: String,
+    /// Number of distinct users with an audit log entry that day
+    pub unique_active_users: u32,
+    /// Number of messages sent, if known
+    pub messages_sent: Option<u32>,
+    /// Number of reactions added, if known
+    pub reactions_added: Option<u32>,
+}
+
+impl GuildDailyStats {
+    /// Groups audit log entries into one set of counts per day.
+    ///
+    /// Every day from `start` for `days` days gets an item, oldest first,
+    /// including days without entries. The audit log doesn't record
+    /// messages or reactions, so those counts are `None`.
+    pub fn group_by_day(
+        entries: &[AuditLogEntry],
+        start: chrono::NaiveDate,
+        days: u32,
+    ) -> Vec<Self> {
+        start
+            .iter_days()
+            .take(days as usize)
+            .map(|day| {
+                let on_day: Vec<_> = entries
+                    .iter()
+                    .filter(|entry| entry.created_at.is_some_and(|t| t.date_naive() == day))
+                    .collect();
+                let users: HashSet<&str> = on_day
+                    .iter()
+                    .filter_map(|entry| entry.user_id.as_deref())
+                    .collect();
+                Self {
+                    date: day.format("%Y-%m-%d").to_string(),
+                    unique_active_users: users.len() as u32,
+                    messages_sent: None,
+                    reactions_added: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the share of members who were active that day, from 0.0 to 1.0.
+    pub fn engagement_rate(&self, total_members: u32) -> f32 {
+        if total_members == 0 {
+            0.0
+        } else {
+            (self.unique_active_users as f32 / total_members as f32).min(1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_action_conversion() {
+        assert_eq!(AuditLogAction::from(1), AuditLogAction::MemberAdd);
+        assert_eq!(u8::from(AuditLogAction::ThreadDelete), 41);
+        assert_eq!(AuditLogAction::from(99), AuditLogAction::Unknown(99));
+    }
+
+    #[test]
+    fn test_audit_log_entry() {
+        let entry: AuditLogEntry = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "guild_id": "g1",
+            "user_id": "u1",
+            "action_type": 1,
+            "changes": [{"key": "invite_code", "new_value": "abc"}],
+            "created_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        assert_eq!(entry.action_type, AuditLogAction::MemberAdd);
+        assert!(entry.joined_at().is_some());
+        assert!(entry.was_invite_based());
+        assert!(entry.change("invite_code").is_some());
+
+        let mut ban = entry.clone();
+        ban.action_type = AuditLogAction::MemberBan;
+        ban.changes.clear();
+        assert!(ban.joined_at().is_none());
+        assert!(!ban.was_invite_based());
+    }
+
+    #[test]
+    fn test_guild_activity_feed() {
+        let entry = |user_id: &str, action_type: u8, changes: Value, created_at: &str| {
+            serde_json::from_value::<AuditLogEntry>(serde_json::json!({
+                "user_id": user_id,
+                "target_id": "t1",
+                "action_type": action_type,
+                "changes": changes,
+                "created_at": created_at
+            }))
+            .unwrap()
+        };
+        let roles = serde_json::json!([{"key": "roles", "new_value": ["5"]}]);
+        let nick = serde_json::json!([{"key": "nick", "new_value": "Al"}]);
+        let entries = vec![
+            entry("u1", 1, serde_json::json!([]), "2024-05-02T00:00:00Z"),
+            entry("u2", 10, serde_json::json!([]), "2024-05-02T01:00:00Z"),
+            entry("u2", 3, roles.clone(), "2024-05-02T02:00:00Z"),
+            entry("u2", 10, serde_json::json!([]), "2024-05-02T03:00:00Z"),
+            entry("u3", 3, nick, "2024-05-02T04:00:00Z"),
+            entry("u3", 4, serde_json::json!([]), "2024-05-02T05:00:00Z"),
+            entry("u1", 10, serde_json::json!([]), "2024-05-02T06:00:00Z"),
+            entry("u4", 1, serde_json::json!([]), "2024-04-01T00:00:00Z"),
+        ];
+
+        let since = "2024-05-01T00:00:00Z".parse().unwrap();
+        let feed = GuildActivityFeed::from_audit_log("g1", &entries, since);
+        assert_eq!(feed.entries.len(), 5);
+        assert_eq!(feed.entries[2].kind, ActivityKind::RoleAssigned);
+
+        assert_eq!(
+            feed.top_active_users(10),
+            vec![("u2".to_string(), 3), ("u1".to_string(), 2)]
+        );
+        assert_eq!(feed.top_active_users(1), vec![("u2".to_string(), 3)]);
+        assert!(feed.top_active_users(0).is_empty());
+    }
+
+    #[test]
+    fn test_audit_log_stream_since() {
+        let entry = |id: &str, created_at: &str| {
+            serde_json::from_value::<AuditLogEntry>(serde_json::json!({
+                "id": id,
+                "action_type": 1,
+                "created_at": created_at
+            }))
+            .unwrap()
+        };
+        let since = "2024-05-02T00:00:00Z".parse().unwrap();
+        let mut stream = AuditLogStream::since(since);
+        let fresh = stream.accept(vec![
+            entry("3", "2024-05-02T02:00:00Z"),
+            entry("2", "2024-05-02T01:00:00Z"),
+            entry("1", "2024-05-01T23:00:00Z"),
+        ]);
+        let ids: Vec<_> = fresh.iter().map(AuditLogEntry::key).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        assert_eq!(stream.last_seen_id(), Some("3"));
+
+        assert!(stream.accept(Vec::new()).is_empty());
+        let fresh = stream.accept(vec![
+            entry("4", "2024-05-02T03:00:00Z"),
+            entry("3", "2024-05-02T02:00:00Z"),
+            entry("2", "2024-05-02T01:00:00Z"),
+        ]);
+        let ids: Vec<_> = fresh.iter().map(AuditLogEntry::key).collect();
+        assert_eq!(ids, vec!["4"]);
+        assert_eq!(stream.last_seen_id(), Some("4"));
+
+        let mut anonymous = entry("x", "2024-05-02T02:00:00Z");
+        anonymous.id = None;
+        anonymous.user_id = Some("u1".to_string());
+        assert_eq!(anonymous.key(), "1:u1::2024-05-02T02:00:00+00:00");
+    }
+
+    #[test]
+    fn test_member_timeline() {
+        let entry = |value: Value| serde_json::from_value::<AuditLogEntry>(value).unwrap();
+        let entries = vec![
+            entry(serde_json::json!({
+                "user_id": "mod", "target_id": "u1", "action_type": 2,
+                "reason": "spam", "created_at": "2024-05-04T00:00:00Z"
+            })),
+            entry(serde_json::json!({
+                "user_id": "u1", "action_type": 1, "created_at": "2024-05-01T00:00:00Z"
+            })),
+            entry(serde_json::json!({
+                "user_id": "mod", "target_id": "u1", "action_type": 3,
+                "changes": [
+                    {"key": "roles", "old_value": ["5", "10"], "new_value": ["10", "11"]},
+                    {"key": "nick", "old_value": null, "new_value": "Al"}
+                ],
+                "created_at": "2024-05-02T00:00:00Z"
+            })),
+            entry(serde_json::json!({
+                "user_id": "u2", "action_type": 1, "created_at": "2024-05-01T12:00:00Z"
+            })),
+            entry(serde_json::json!({
+                "user_id": "u1", "target_id": "c1", "action_type": 10,
+                "created_at": "2024-05-03T00:00:00Z"
+            })),
+        ];
+
+        let timeline = MemberEvent::timeline("g1", "u1", &entries);
+        let types: Vec<_> = timeline.iter().map(|e| e.event_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                MemberEventType::Joined,
+                MemberEventType::RoleAdded("11".to_string()),
+                MemberEventType::RoleRemoved("5".to_string()),
+                MemberEventType::NickChanged {
+                    old: None,
+                    new: Some("Al".to_string())
+                },
+                MemberEventType::Kicked {
+                    reason: Some("spam".to_string())
+                },
+            ]
+        );
+        assert!(
+            timeline
+                .windows(2)
+                .all(|w| w[0].timestamp <= w[1].timestamp)
+        );
+        assert_eq!(timeline[0].actor_id, None);
+        assert_eq!(timeline[4].actor_id.as_deref(), Some("mod"));
+        assert_eq!(timeline[4].guild_id, "g1");
+    }
+
+    #[test]
+    fn test_guild_daily_stats() {
+        let entry = |user_id: &str, created_at: &str| {
+            serde_json::from_value::<AuditLogEntry>(serde_json::json!({
+                "user_id": user_id,
+                "action_type": 1,
+                "created_at": created_at
+            }))
+            .unwrap()
+        };
+        let entries = vec![
+            entry("u1", "2024-05-01T01:00:00Z"),
+            entry("u2", "2024-05-01T02:00:00Z"),
+            entry("u1", "2024-05-01T03:00:00Z"),
+            entry("u3", "2024-05-01T23:59:59Z"),
+            entry("u1", "2024-05-02T00:00:00Z"),
+            entry("u1", "2024-05-02T10:00:00Z"),
+            entry("u2", "2024-05-03T01:00:00Z"),
+            entry("u3", "2024-05-03T02:00:00Z"),
+            entry("u4", "2024-05-03T03:00:00Z"),
+            entry("u5", "2024-04-30T23:00:00Z"),
+        ];
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let stats = GuildDailyStats::group_by_day(&entries, start, 4);
+        let counts: Vec<_> = stats
+            .iter()
+            .map(|day| (day.date.as_str(), day.unique_active_users))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("2024-05-01", 3),
+                ("2024-05-02", 1),
+                ("2024-05-03", 3),
+                ("2024-05-04", 0)
+            ]
+        );
+        assert_eq!(stats[0].messages_sent, None);
+        assert_eq!(stats[0].engagement_rate(6), 0.5);
+        assert_eq!(stats[0].engagement_rate(0), 0.0);
+    }
+
+    #[test]
+    fn test_forum_audit_log() {
+        let entries: Vec<ForumAuditEntry> = serde_json::from_value(serde_json::json!([
+            {
+                "action": "thread_deleted",
+                "actor_id": "bot1",
+                "target_id": "t1",
+                "timestamp": "2024-01-01T10:00:00Z",
+                "reason": "spam"
+            },
+            {
+                "action": "user_muted",
+                "mute_seconds": 600,
+                "actor_id": "admin",
+                "target_id": "u1",
+                "timestamp": "2024-01-01T10:05:00Z"
+            },
+            {
+                "action": "thread_locked",
+                "actor_id": "admin",
+                "target_id": "t2",
+                "timestamp": "2024-01-01T10:10:00Z",
+                "reason": null
+            },
+            {
+                "action": "thread_archived",
+                "actor_id": "bot1",
+                "target_id": "t3",
+                "timestamp": "2024-01-01T10:15:00Z"
+            }
+        ]))
+        .unwrap();
+
+        let actions: Vec<_> = entries.iter().map(|e| e.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ForumAuditAction::ThreadDeleted,
+                ForumAuditAction::UserMuted(std::time::Duration::from_secs(600)),
+                ForumAuditAction::ThreadLocked,
+                ForumAuditAction::Unknown("thread_archived".to_string()),
+            ]
+        );
+        assert_eq!(entries[0].reason.as_deref(), Some("spam"));
+        assert!(entries[1].reason.is_none());
+
+        let bots = ["bot1", "bot2"];
+        let automated: Vec<_> = entries
+            .iter()
+            .filter(|e| e.was_automated(&bots))
+            .map(|e| e.target_id.as_str())
+            .collect();
+        assert_eq!(automated, vec!["t1", "t3"]);
+
+        let round_trip: ForumAuditEntry =
+            serde_json::from_value(serde_json::to_value(&entries[1]).unwrap()).unwrap();
+        assert_eq!(round_trip, entries[1]);
+    }
+}
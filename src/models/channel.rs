@@ -2,7 +2,9 @@
 //!
 //! This module contains channel types that correspond to the Python botpy implementation.
 
-use crate::models::{HasId, HasName, Snowflake};
+use crate::models::message::MessageUser;
+use crate::models::permission::Permissions;
+use crate::models::{HasId, HasName, Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Represents a channel in a guild.
@@ -33,9 +35,18 @@ pub struct Channel {
     pub application_id: Option<Snowflake>,
     /// The permissions string
     pub permissions: Option<String>,
+    /// The channel topic (description)
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Whether the channel is age-gated
+    #[serde(default, rename = "nsfw")]
+    pub is_nsfw: Option<bool>,
 }
 
 impl Channel {
+    /// Application ID of schedule channels.
+    pub const SCHEDULE_APPLICATION_ID: &'static str = "1000050";
+
     /// Creates a new channel.
     pub fn new() -> Self {
         Self {
@@ -51,6 +62,8 @@ impl Channel {
             speak_permission: None,
             application_id: None,
             permissions: None,
+            topic: None,
+            is_nsfw: None,
         }
     }
 
@@ -99,6 +112,8 @@ impl Channel {
                 .get("permissions")
                 .and_then(|v| v.as_str())
                 .map(String::from),
+            topic: data.get("topic").and_then(|v| v.as_str()).map(String::from),
+            is_nsfw: data.get("nsfw").and_then(|v| v.as_bool()),
         }
     }
 
@@ -112,6 +127,17 @@ impl Channel {
         matches!(self.channel_type, Some(ChannelType::Text))
     }
 
+    /// Formats the channel name with its type, e.g. `"# announcements (Text)"`.
+    pub fn display_name_with_type(&self) -> String {
+        let channel_type = self.channel_type.unwrap_or(ChannelType::Unknown(0));
+        format!(
+            "{} {} ({})",
+            channel_type.display_icon(),
+            self.name.as_deref().unwrap_or(""),
+            channel_type.display_name()
+        )
+    }
+
     /// Returns true if this is a voice channel.
     pub fn is_voice(&self) -> bool {
         matches!(self.channel_type, Some(ChannelType::Voice))
@@ -132,11 +158,30 @@ impl Channel {
         matches!(self.channel_type, Some(ChannelType::Application))
     }
 
+    /// Returns true if this is a schedule channel.
+    pub fn is_schedule(&self) -> bool {
+        self.is_application()
+            && self.application_id.as_deref() == Some(Self::SCHEDULE_APPLICATION_ID)
+    }
+
     /// Returns true if this is a discussion (forum) channel.
     pub fn is_discussion(&self) -> bool {
         matches!(self.channel_type, Some(ChannelType::Discussion))
     }
 
+    /// Returns true if this is an open discussion channel.
+    ///
+    /// There is no separate discussion sub type, so this is the
+    /// [`ChannelSubType::Talk`] sub type.
+    pub fn is_open_discussion(&self) -> bool {
+        matches!(self.sub_type, Some(ChannelSubType::Talk))
+    }
+
+    /// Returns true if this is an announcement (post) channel.
+    pub fn is_announcement(&self) -> bool {
+        matches!(self.sub_type, Some(ChannelSubType::Post))
+    }
+
     /// Returns true if the channel is public.
     pub fn is_public(&self) -> bool {
         matches!(self.private_type, Some(PrivateType::Public) | None)
@@ -203,6 +248,8 @@ pub enum ChannelType {
     Voice = 2,
     /// Group channel/Category (4)
     Group = 4,
+    /// Forum channel (15)
+    Forum = 15,
     /// Live channel (10005)
     Live = 10005,
     /// Application channel (10006)
@@ -219,6 +266,7 @@ impl From<u32> for ChannelType {
             0 => Self::Text,
             2 => Self::Voice,
             4 => Self::Group,
+            15 => Self::Forum,
             10005 => Self::Live,
             10006 => Self::Application,
             10007 => Self::Discussion,
@@ -232,6 +280,34 @@ impl ChannelType {
     pub fn from_u8(value: u8) -> Option<Self> {
         Some(Self::from(value as u32))
     }
+
+    /// Gets the icon shown before channels of this type.
+    pub fn display_icon(&self) -> char {
+        match self {
+            ChannelType::Text => '#',
+            ChannelType::Voice => '🔊',
+            ChannelType::Group => '📁',
+            ChannelType::Forum => '💬',
+            ChannelType::Live => '📺',
+            ChannelType::Application => '🧩',
+            ChannelType::Discussion => '💬',
+            ChannelType::Unknown(_) => '❔',
+        }
+    }
+
+    /// Gets a human-readable name for this type.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ChannelType::Text => "Text",
+            ChannelType::Voice => "Voice",
+            ChannelType::Group => "Category",
+            ChannelType::Forum => "Forum",
+            ChannelType::Live => "Live",
+            ChannelType::Application => "Application",
+            ChannelType::Discussion => "Forum",
+            ChannelType::Unknown(_) => "Unknown",
+        }
+    }
 }
 
 impl From<ChannelType> for u32 {
@@ -240,6 +316,7 @@ impl From<ChannelType> for u32 {
             ChannelType::Text => 0,
             ChannelType::Voice => 2,
             ChannelType::Group => 4,
+            ChannelType::Forum => 15,
             ChannelType::Live => 10005,
             ChannelType::Application => 10006,
             ChannelType::Discussion => 10007,
@@ -406,6 +483,212 @@ impl From<SpeakPermission> for u32 {
     }
 }
 
+/// A temporary invite granting a user access to a channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelAccessInvite {
+    /// The invite code, used to revoke the invite
+    pub invite_code: String,
+    /// The channel the invite grants access to
+    pub channel_id: Snowflake,
+    /// The user the invite was created for
+    pub user_id: Snowflake,
+    /// When the granted access expires
+    pub expires_at: Timestamp,
+}
+
+impl ChannelAccessInvite {
+    /// Returns true if the invite has expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now())
+    }
+
+    /// Returns true if the invite has expired at the given time.
+    pub fn is_expired_at(&self, now: Timestamp) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// A shareable invite link to a channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelInvite {
+    /// The invite code
+    pub code: String,
+    /// The channel the invite leads to
+    pub channel_id: Snowflake,
+    /// The guild the channel belongs to
+    pub guild_id: Snowflake,
+    /// The user who created the invite
+    pub inviter_id: Snowflake,
+    /// Number of times the invite has been used
+    #[serde(default)]
+    pub uses: u32,
+    /// Maximum number of uses, 0 for unlimited
+    #[serde(default)]
+    pub max_uses: u32,
+    /// When the invite expires, if ever
+    pub expires_at: Option<Timestamp>,
+    /// Whether members who joined through the invite are removed when they
+    /// go offline
+    #[serde(default)]
+    pub temporary: bool,
+}
+
+impl ChannelInvite {
+    /// Base URL of invite links.
+    pub const URL_BASE: &'static str = "https://pd.qq.com/s/";
+
+    /// Gets the invite link.
+    pub fn invite_url(&self) -> String {
+        format!("{}{}", Self::URL_BASE, self.code)
+    }
+}
+
+/// Options for creating a [`ChannelInvite`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InviteOptions {
+    /// Seconds until the invite expires, 0 for never
+    pub max_age: u32,
+    /// Maximum number of uses, 0 for unlimited
+    pub max_uses: u32,
+    /// Whether members who joined through the invite are removed when they
+    /// go offline
+    pub temporary: bool,
+    /// Whether to always create a new invite instead of reusing a similar one
+    pub unique: bool,
+}
+
+impl InviteOptions {
+    /// Options for a fresh invite that can be used once within a day.
+    pub fn single_use() -> Self {
+        Self {
+            max_uses: 1,
+            unique: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for InviteOptions {
+    /// An invite with unlimited uses that expires after a day.
+    fn default() -> Self {
+        Self {
+            max_age: 86400,
+            max_uses: 0,
+            temporary: false,
+            unique: false,
+        }
+    }
+}
+
+/// A member currently in a voice channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceChannelMember {
+    /// The user in the channel
+    pub user: MessageUser,
+    /// When the user joined the channel, unset for members who just left
+    #[serde(default)]
+    pub joined_at: Option<Timestamp>,
+    /// Whether the user has muted their microphone
+    #[serde(default)]
+    pub is_self_muted: bool,
+    /// Whether the user has deafened themselves
+    #[serde(default)]
+    pub is_self_deafened: bool,
+}
+
+impl VoiceChannelMember {
+    /// Creates a voice channel member from a channel enter or exit event.
+    ///
+    /// Gateway events only carry the user ID, so the mute flags are unset.
+    /// For enter events `joined_at` is the time the event was received; for
+    /// exit events it is `None`.
+    pub fn from_event_user(user_id: Option<String>, joined_at: Option<Timestamp>) -> Self {
+        Self {
+            user: MessageUser {
+                id: user_id,
+                username: None,
+                bot: None,
+                avatar: None,
+            },
+            joined_at,
+            is_self_muted: false,
+            is_self_deafened: false,
+        }
+    }
+}
+
+/// Name of the synthetic category holding channels without a parent.
+pub const ROOT_CATEGORY_NAME: &str = "root";
+
+/// A category channel together with the channels nested under it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelCategory {
+    /// The category channel
+    pub category: Channel,
+    /// Channels whose parent is this category
+    pub children: Vec<Channel>,
+}
+
+impl ChannelCategory {
+    /// Creates a category with no children.
+    pub fn new(category: Channel) -> Self {
+        Self {
+            category,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the channels in `all_channels` whose parent is this category.
+    pub fn children<'a>(&self, all_channels: &'a [Channel]) -> Vec<&'a Channel> {
+        let Some(id) = self.category.id.as_deref() else {
+            return Vec::new();
+        };
+        all_channels
+            .iter()
+            .filter(|channel| channel.parent_id.as_deref() == Some(id))
+            .collect()
+    }
+
+    /// Returns true if this is the synthetic category for unparented channels.
+    pub fn is_root(&self) -> bool {
+        self.category.id.is_none()
+    }
+
+    /// Groups channels under their categories.
+    ///
+    /// Categories keep their listing order. Channels whose parent isn't a
+    /// category in `channels` are collected into a trailing root category,
+    /// which is only present if it has children.
+    pub fn build_tree(channels: Vec<Channel>) -> Vec<ChannelCategory> {
+        let (categories, others): (Vec<_>, Vec<_>) =
+            channels.into_iter().partition(Channel::is_group);
+        let mut tree: Vec<ChannelCategory> =
+            categories.into_iter().map(ChannelCategory::new).collect();
+        let mut root = Vec::new();
+
+        for channel in others {
+            let parent = tree.iter_mut().find(|category| {
+                category.category.id.is_some() && category.category.id == channel.parent_id
+            });
+            match parent {
+                Some(category) => category.children.push(channel),
+                None => root.push(channel),
+            }
+        }
+
+        if !root.is_empty() {
+            let mut category = Channel::new();
+            category.name = Some(ROOT_CATEGORY_NAME.to_string());
+            category.channel_type = Some(ChannelType::Group);
+            tree.push(ChannelCategory {
+                category,
+                children: root,
+            });
+        }
+        tree
+    }
+}
+
 /// Channel permissions for a user or role.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChannelPermissions {
@@ -439,6 +722,15 @@ impl ChannelPermissions {
     pub fn is_role_permission(&self) -> bool {
         self.role_id.is_some()
     }
+
+    /// Parses the permissions string, treating a missing or malformed
+    /// value as no permissions.
+    pub fn permission_set(&self) -> Permissions {
+        self.permissions
+            .as_deref()
+            .and_then(|bits| bits.parse().ok())
+            .unwrap_or_default()
+    }
 }
 
 impl Default for ChannelPermissions {
@@ -447,6 +739,22 @@ impl Default for ChannelPermissions {
     }
 }
 
+/// A thread started from a message in a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelThread {
+    /// The thread ID
+    pub id: Snowflake,
+    /// The thread name
+    pub name: String,
+    /// The channel the thread belongs to
+    pub parent_id: Snowflake,
+    /// The message the thread was started from
+    pub message_id: Option<Snowflake>,
+    /// Minutes without activity before the thread is archived
+    #[serde(default)]
+    pub auto_archive_duration: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,4 +846,166 @@ mod tests {
         assert!(!perms.is_user_permission());
         assert!(perms.is_role_permission());
     }
+
+    #[test]
+    fn test_channel_topic() {
+        let data = serde_json::json!({
+            "id": "123",
+            "name": "general",
+            "type": 0,
+            "topic": "Welcome!"
+        });
+        let channel: Channel = serde_json::from_value(data.clone()).unwrap();
+        assert_eq!(channel.topic.as_deref(), Some("Welcome!"));
+
+        let api = crate::api::BotApi::new(crate::http::HttpClient::new(30, false).unwrap());
+        let channel = Channel::from_data(api, "123".to_string(), data);
+        assert_eq!(channel.topic.as_deref(), Some("Welcome!"));
+    }
+
+    #[test]
+    fn test_channel_invite() {
+        let invite: ChannelInvite = serde_json::from_value(serde_json::json!({
+            "code": "AbC123",
+            "channel_id": "c1",
+            "guild_id": "g1",
+            "inviter_id": "u1",
+            "max_uses": 1,
+            "expires_at": null
+        }))
+        .unwrap();
+        assert_eq!(invite.invite_url(), "https://pd.qq.com/s/AbC123");
+        assert_eq!(invite.uses, 0);
+        assert!(!invite.temporary);
+
+        assert_eq!(
+            serde_json::to_value(InviteOptions::default()).unwrap(),
+            serde_json::json!({
+                "max_age": 86400,
+                "max_uses": 0,
+                "temporary": false,
+                "unique": false
+            })
+        );
+        let single = InviteOptions::single_use();
+        assert_eq!((single.max_age, single.max_uses), (86400, 1));
+        assert!(single.unique);
+    }
+
+    #[test]
+    fn test_channel_access_invite_expiry() {
+        let invite: ChannelAccessInvite = serde_json::from_value(serde_json::json!({
+            "invite_code": "abc",
+            "channel_id": "c1",
+            "user_id": "u1",
+            "expires_at": "2024-01-01T12:00:00Z"
+        }))
+        .unwrap();
+
+        let before = "2024-01-01T11:59:59Z".parse::<Timestamp>().unwrap();
+        let after = "2024-01-01T12:00:00Z".parse::<Timestamp>().unwrap();
+        assert!(!invite.is_expired_at(before));
+        assert!(invite.is_expired_at(after));
+        assert!(invite.is_expired());
+
+        let mut future = invite.clone();
+        future.expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert!(!future.is_expired());
+    }
+
+    #[test]
+    fn test_voice_channel_member_deserialize() {
+        let member: VoiceChannelMember = serde_json::from_value(serde_json::json!({
+            "user": {"id": "u1", "username": "alice"},
+            "joined_at": "2024-01-01T00:00:00Z",
+            "is_self_muted": true
+        }))
+        .unwrap();
+        assert_eq!(member.user.id.as_deref(), Some("u1"));
+        assert!(member.joined_at.is_some());
+        assert!(member.is_self_muted);
+        assert!(!member.is_self_deafened);
+    }
+
+    #[test]
+    fn test_display_name_with_type() {
+        let cases = [
+            (ChannelType::Text, "# general (Text)"),
+            (ChannelType::Voice, "🔊 general (Voice)"),
+            (ChannelType::Group, "📁 general (Category)"),
+            (ChannelType::Forum, "💬 general (Forum)"),
+            (ChannelType::Live, "📺 general (Live)"),
+            (ChannelType::Application, "🧩 general (Application)"),
+            (ChannelType::Discussion, "💬 general (Forum)"),
+            (ChannelType::Unknown(42), "❔ general (Unknown)"),
+        ];
+
+        let mut channel = Channel::new();
+        channel.name = Some("general".to_string());
+        for (channel_type, expected) in cases {
+            channel.channel_type = Some(channel_type);
+            assert_eq!(channel.display_name_with_type(), expected);
+        }
+
+        channel.channel_type = None;
+        assert_eq!(channel.display_name_with_type(), "❔ general (Unknown)");
+    }
+
+    #[test]
+    fn test_channel_tree() {
+        let channel = |id: &str, channel_type: ChannelType, parent_id: Option<&str>| {
+            let mut channel = Channel::new();
+            channel.id = Some(id.to_string());
+            channel.name = Some(id.to_string());
+            channel.channel_type = Some(channel_type);
+            channel.parent_id = parent_id.map(str::to_string);
+            channel
+        };
+        let channels = vec![
+            channel("cat1", ChannelType::Group, None),
+            channel("general", ChannelType::Text, Some("cat1")),
+            channel("cat2", ChannelType::Group, None),
+            channel("voice", ChannelType::Voice, Some("cat2")),
+            channel("lobby", ChannelType::Text, None),
+            channel("stray", ChannelType::Text, Some("missing")),
+            channel("news", ChannelType::Text, Some("cat1")),
+        ];
+
+        let category = ChannelCategory::new(channels[0].clone());
+        let children: Vec<_> = category
+            .children(&channels)
+            .iter()
+            .filter_map(|c| c.id.as_deref())
+            .collect();
+        assert_eq!(children, vec!["general", "news"]);
+
+        let tree = ChannelCategory::build_tree(channels);
+        let summary: Vec<(&str, Vec<&str>)> = tree
+            .iter()
+            .map(|category| {
+                (
+                    category.category.name.as_deref().unwrap(),
+                    category
+                        .children
+                        .iter()
+                        .filter_map(|c| c.id.as_deref())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("cat1", vec!["general", "news"]),
+                ("cat2", vec!["voice"]),
+                (ROOT_CATEGORY_NAME, vec!["lobby", "stray"]),
+            ]
+        );
+        assert!(tree[2].is_root());
+        assert!(!tree[0].is_root());
+
+        let tree = ChannelCategory::build_tree(vec![channel("cat1", ChannelType::Group, None)]);
+        assert_eq!(tree.len(), 1);
+        assert!(tree[0].children.is_empty());
+    }
 }
@@ -0,0 +1,239 @@
+//! Application command data structures for the QQ Guild Bot API.
+//!
+//! This module contains the definitions of slash commands registered by a bot
+//! in a guild, along with their options and choices.
+
+use crate::models::{HasId, HasName, Snowflake};
+use serde::{Deserialize, Serialize};
+
+/// A slash command registered by the bot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationCommand {
+    /// The command's unique ID, assigned by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Snowflake>,
+    /// The ID of the bot application that owns the command
+    pub application_id: Snowflake,
+    /// The command name
+    pub name: String,
+    /// The command description
+    pub description: String,
+    /// The command's parameters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+}
+
+impl ApplicationCommand {
+    /// Creates a command with no options.
+    pub fn new(
+        application_id: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: None,
+            application_id: application_id.into(),
+            name: name.into(),
+            description: description.into(),
+            options: Vec::new(),
+        }
+    }
+
+    /// Adds an option to the command.
+    pub fn with_option(mut self, option: CommandOption) -> Self {
+        self.options.push(option);
+        self
+    }
+}
+
+impl HasId for ApplicationCommand {
+    fn id(&self) -> Option<&Snowflake> {
+        self.id.as_ref()
+    }
+}
+
+impl HasName for ApplicationCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A parameter of an application command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandOption {
+    /// The option name
+    pub name: String,
+    /// The option description
+    pub description: String,
+    /// The type of value the option takes
+    #[serde(rename = "type")]
+    pub option_type: CommandOptionType,
+    /// Whether the option must be provided
+    #[serde(default)]
+    pub required: bool,
+    /// The values the option is limited to, if any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<CommandChoice>,
+    /// Nested options, for sub-commands and sub-command groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+}
+
+impl CommandOption {
+    /// Creates an optional option with no choices.
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        option_type: CommandOptionType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            option_type,
+            required: false,
+            choices: Vec::new(),
+            options: Vec::new(),
+        }
+    }
+
+    /// Marks the option as required.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Adds a choice to the option.
+    pub fn with_choice(mut self, choice: CommandChoice) -> Self {
+        self.choices.push(choice);
+        self
+    }
+
+    /// Adds a nested option.
+    pub fn with_option(mut self, option: CommandOption) -> Self {
+        self.options.push(option);
+        self
+    }
+}
+
+/// A predefined value for a command option.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandChoice {
+    /// The name shown to users
+    pub name: String,
+    /// The value passed to the command, a string or number
+    pub value: serde_json::Value,
+}
+
+impl CommandChoice {
+    /// Creates a choice.
+    pub fn new(name: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// The type of value a command option takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum CommandOptionType {
+    /// A sub-command
+    SubCommand = 1,
+    /// A group of sub-commands
+    SubCommandGroup = 2,
+    /// A string
+    String = 3,
+    /// An integer
+    Integer = 4,
+    /// A boolean
+    Boolean = 5,
+    /// A user
+    User = 6,
+    /// A channel
+    Channel = 7,
+    /// A role
+    Role = 8,
+    /// A floating point number
+    Number = 10,
+    /// Unknown option type
+    Unknown(u8),
+}
+
+impl From<u8> for CommandOptionType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::SubCommand,
+            2 => Self::SubCommandGroup,
+            3 => Self::String,
+            4 => Self::Integer,
+            5 => Self::Boolean,
+            6 => Self::User,
+            7 => Self::Channel,
+            8 => Self::Role,
+            10 => Self::Number,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<CommandOptionType> for u8 {
+    fn from(option_type: CommandOptionType) -> Self {
+        match option_type {
+            CommandOptionType::SubCommand => 1,
+            CommandOptionType::SubCommandGroup => 2,
+            CommandOptionType::String => 3,
+            CommandOptionType::Integer => 4,
+            CommandOptionType::Boolean => 5,
+            CommandOptionType::User => 6,
+            CommandOptionType::Channel => 7,
+            CommandOptionType::Role => 8,
+            CommandOptionType::Number => 10,
+            CommandOptionType::Unknown(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_option_type_conversion() {
+        assert_eq!(CommandOptionType::from(3), CommandOptionType::String);
+        assert_eq!(u8::from(CommandOptionType::Number), 10);
+        assert_eq!(CommandOptionType::from(42), CommandOptionType::Unknown(42));
+    }
+
+    #[test]
+    fn test_application_command_round_trip() {
+        let command = ApplicationCommand::new("app1", "config", "Change bot settings").with_option(
+            CommandOption::new("set", "Set a value", CommandOptionType::SubCommand)
+                .with_option(
+                    CommandOption::new("key", "Setting name", CommandOptionType::String)
+                        .required()
+                        .with_choice(CommandChoice::new("Language", "lang"))
+                        .with_choice(CommandChoice::new("Prefix", "prefix")),
+                )
+                .with_option(CommandOption::new(
+                    "level",
+                    "Verbosity",
+                    CommandOptionType::Integer,
+                )),
+        );
+
+        let value = serde_json::to_value(&command).unwrap();
+        assert!(value.get("id").is_none());
+        assert_eq!(value["options"][0]["type"], 1);
+        assert_eq!(value["options"][0]["options"][0]["required"], true);
+        assert_eq!(
+            value["options"][0]["options"][0]["choices"][1]["value"],
+            "prefix"
+        );
+        assert!(value["options"][0]["options"][1].get("choices").is_none());
+
+        let parsed: ApplicationCommand = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, command);
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module contains structures for handling emojis in reactions and messages.
 
-use crate::models::{HasId, Snowflake};
+use crate::models::{HasId, Snowflake, message::ReactionSummary};
 use serde::{Deserialize, Serialize};
 
 /// Types of emojis supported by the QQ Guild API.
@@ -164,6 +164,90 @@ impl std::fmt::Display for Emoji {
     }
 }
 
+/// A custom emoji uploaded to a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildEmoji {
+    /// The emoji's unique ID
+    pub id: Snowflake,
+    /// The emoji's name
+    pub name: String,
+    /// Whether the emoji must be wrapped in colons to be used
+    #[serde(default)]
+    pub require_colons: bool,
+    /// Whether the emoji is animated
+    #[serde(default)]
+    pub animated: bool,
+    /// Whether the emoji can currently be used
+    #[serde(default = "default_true")]
+    pub available: bool,
+    /// The ID of the user who uploaded the emoji
+    pub creator_id: Option<Snowflake>,
+}
+
+impl GuildEmoji {
+    /// Returns true if `name` is a valid custom emoji name.
+    ///
+    /// Names must be 2 to 32 characters of ASCII letters, digits or underscores.
+    pub fn is_valid_name(name: &str) -> bool {
+        (2..=32).contains(&name.len())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
+
+impl HasId for GuildEmoji {
+    fn id(&self) -> Option<&Snowflake> {
+        Some(&self.id)
+    }
+}
+
+/// How often a guild's custom emoji was used in reactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmojiUsageStat {
+    /// The custom emoji
+    pub emoji: GuildEmoji,
+    /// Total reactions with this emoji across all counted messages
+    pub reaction_count: u32,
+    /// Number of distinct users who reacted with this emoji
+    pub unique_reactors: u32,
+}
+
+impl EmojiUsageStat {
+    /// Aggregates message reactions into per-emoji usage, most used first.
+    ///
+    /// Each reaction is paired with the IDs of the users who reacted with it.
+    /// Every emoji in `emojis` gets an entry, and reactions with system emojis
+    /// or emojis from other guilds are ignored. Ties keep the order of `emojis`.
+    pub fn aggregate(
+        emojis: &[GuildEmoji],
+        reactions: &[(ReactionSummary, Vec<Snowflake>)],
+    ) -> Vec<Self> {
+        let mut stats: Vec<Self> = emojis
+            .iter()
+            .map(|emoji| Self {
+                emoji: emoji.clone(),
+                reaction_count: 0,
+                unique_reactors: 0,
+            })
+            .collect();
+        for stat in &mut stats {
+            let mut reactors = std::collections::HashSet::new();
+            for (reaction, users) in reactions {
+                if reaction.emoji.is_custom() && reaction.emoji.id == stat.emoji.id {
+                    stat.reaction_count += reaction.count;
+                    reactors.extend(users.iter());
+                }
+            }
+            stat.unique_reactors = reactors.len() as u32;
+        }
+        stats.sort_by_key(|stat| std::cmp::Reverse(stat.reaction_count));
+        stats
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +347,70 @@ mod tests {
         assert!(display.contains("Emoji 456"));
         assert!(display.contains("System emoji"));
     }
+
+    #[test]
+    fn test_guild_emoji_name_validation() {
+        assert!(GuildEmoji::is_valid_name("ok"));
+        assert!(GuildEmoji::is_valid_name("party_parrot_2"));
+        assert!(GuildEmoji::is_valid_name(&"a".repeat(32)));
+
+        assert!(!GuildEmoji::is_valid_name("a"));
+        assert!(!GuildEmoji::is_valid_name(&"a".repeat(33)));
+        assert!(!GuildEmoji::is_valid_name("has space"));
+        assert!(!GuildEmoji::is_valid_name("dash-name"));
+        assert!(!GuildEmoji::is_valid_name("表情"));
+    }
+
+    #[test]
+    fn test_guild_emoji_deserialize() {
+        let emoji: GuildEmoji = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "name": "wave"
+        }))
+        .unwrap();
+        assert!(emoji.available);
+        assert!(!emoji.animated);
+        assert_eq!(emoji.creator_id, None);
+    }
+
+    #[test]
+    fn test_emoji_usage_aggregate_across_channels() {
+        let guild_emoji = |id: &str| GuildEmoji {
+            id: id.to_string(),
+            name: format!("emoji_{id}"),
+            require_colons: true,
+            animated: false,
+            available: true,
+            creator_id: None,
+        };
+        let reaction = |emoji: Emoji, count: u32, users: &[&str]| {
+            (
+                ReactionSummary {
+                    emoji,
+                    count,
+                    me: false,
+                },
+                users.iter().map(|u| u.to_string()).collect::<Vec<_>>(),
+            )
+        };
+        let emojis = [guild_emoji("1"), guild_emoji("2"), guild_emoji("3")];
+        let reactions = [
+            // channel A
+            reaction(Emoji::custom("1", None, None), 2, &["u1", "u2"]),
+            reaction(Emoji::custom("2", None, None), 1, &["u1"]),
+            // channel B
+            reaction(Emoji::custom("2", None, None), 3, &["u1", "u3", "u4"]),
+            reaction(Emoji::system("1"), 9, &["u5"]),
+        ];
+
+        let stats = EmojiUsageStat::aggregate(&emojis, &reactions);
+
+        assert_eq!(stats[0].emoji.id, "2");
+        assert_eq!(stats[0].reaction_count, 4);
+        assert_eq!(stats[0].unique_reactors, 3);
+        assert_eq!(stats[1].emoji.id, "1");
+        assert_eq!(stats[1].reaction_count, 2);
+        assert_eq!(stats[2].emoji.id, "3");
+        assert_eq!(stats[2].reaction_count, 0);
+    }
 }
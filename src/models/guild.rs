@@ -2,8 +2,11 @@
 //!
 //! This module contains guild types that correspond to the Python botpy implementation.
 
+use crate::models::channel::Channel;
+use crate::models::gateway::GatewayEvent;
 use crate::models::{HasId, HasName, Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents a guild (server) in the QQ Guild system.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -26,6 +29,11 @@ pub struct Guild {
     pub description: Option<String>,
     /// When the current user joined this guild
     pub joined_at: Option<Timestamp>,
+    /// The guild's premium tier
+    pub premium_tier: Option<u8>,
+    /// The number of boosts the guild has
+    #[serde(alias = "premium_subscription_count")]
+    pub booster_count: Option<u32>,
 }
 
 impl Guild {
@@ -41,6 +49,8 @@ impl Guild {
             max_members: None,
             description: None,
             joined_at: None,
+            premium_tier: None,
+            booster_count: None,
         }
     }
 
@@ -72,6 +82,14 @@ impl Guild {
                 .and_then(|v| v.as_str())
                 .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
                 .map(|dt| dt.with_timezone(&chrono::Utc)),
+            premium_tier: data
+                .get("premium_tier")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u8),
+            booster_count: data
+                .get("premium_subscription_count")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32),
         }
     }
 
@@ -157,6 +175,42 @@ impl GuildRoles {
             role_num_limit: None,
         }
     }
+
+    /// Gets a map from role ID to the number of members with the role.
+    pub fn member_counts(&self) -> HashMap<String, u32> {
+        self.roles
+            .iter()
+            .filter_map(|role| Some((role.id.clone()?, role.member_count())))
+            .collect()
+    }
+
+    /// Gets each role's member count and share of the guild, largest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_members` - The guild's total member count; percentages are
+    ///   `0.0` when this is zero
+    pub fn breakdown(&self, total_members: u32) -> Vec<RoleMemberCountBreakdown> {
+        let mut breakdown: Vec<_> = self
+            .roles
+            .iter()
+            .filter_map(|role| {
+                let count = role.member_count();
+                Some(RoleMemberCountBreakdown {
+                    role_id: role.id.clone()?,
+                    role_name: role.name.clone().unwrap_or_default(),
+                    count,
+                    percentage: if total_members == 0 {
+                        0.0
+                    } else {
+                        count as f32 / total_members as f32 * 100.0
+                    },
+                })
+            })
+            .collect();
+        breakdown.sort_by_key(|b| std::cmp::Reverse(b.count));
+        breakdown
+    }
 }
 
 /// Represents a role in a guild.
@@ -189,16 +243,35 @@ impl GuildRole {
         }
     }
 
+    /// IDs of the roles every guild has and that cannot be deleted.
+    pub const SYSTEM_ROLE_IDS: [&'static str; 4] = ["1", "2", "4", "5"];
+
     /// Returns true if this role is hoisted (displayed separately).
     pub fn is_hoisted(&self) -> bool {
         self.hoist.unwrap_or(false)
     }
 
+    /// Returns true if this is one of the guild's built-in roles.
+    pub fn is_system(&self) -> bool {
+        self.id
+            .as_deref()
+            .is_some_and(|id| Self::SYSTEM_ROLE_IDS.contains(&id))
+    }
+
     /// Gets the role's color as a hex value.
     pub fn color_hex(&self) -> Option<String> {
         self.color.map(|c| format!("#{c:06X}"))
     }
 
+    /// Formats the role name with its color, e.g. `"Admin (#FF0000)"`.
+    pub fn display_with_color(&self) -> String {
+        let name = self.name.as_deref().unwrap_or("");
+        match self.color_hex() {
+            Some(hex) => format!("{name} ({hex})"),
+            None => name.to_string(),
+        }
+    }
+
     /// Gets the number of members with this role.
     pub fn member_count(&self) -> u32 {
         self.number.unwrap_or(0)
@@ -236,6 +309,699 @@ impl HasName for GuildRole {
     }
 }
 
+/// The number of members holding a role and their share of the guild.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoleMemberCountBreakdown {
+    /// The role ID
+    pub role_id: Snowflake,
+    /// The role name
+    pub role_name: String,
+    /// Number of members with the role
+    pub count: u32,
+    /// Percentage of the guild's members with the role, from 0 to 100
+    pub percentage: f32,
+}
+
+/// Member activity statistics for a guild.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildStats {
+    /// Number of members who joined in the last 7 days
+    #[serde(default)]
+    pub joined_count_7d: u32,
+    /// Number of members who joined in the last 30 days
+    #[serde(default)]
+    pub joined_count_30d: u32,
+    /// Number of members who left in the last 7 days
+    #[serde(default)]
+    pub deleted_count_7d: u32,
+    /// Number of members who left in the last 30 days
+    #[serde(default)]
+    pub deleted_count_30d: u32,
+    /// Total number of members
+    #[serde(default)]
+    pub member_count: u32,
+    /// Number of members currently online
+    #[serde(default)]
+    pub online_count: u32,
+}
+
+impl GuildStats {
+    /// Gets the 7-day retention rate as `1.0 - deleted / joined`.
+    ///
+    /// Returns `1.0` when nobody joined in the period, and never goes below `0.0`.
+    pub fn retention_rate_7d(&self) -> f32 {
+        if self.joined_count_7d == 0 {
+            return 1.0;
+        }
+        (1.0 - self.deleted_count_7d as f32 / self.joined_count_7d as f32).max(0.0)
+    }
+}
+
+/// A member's recent activity in a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberActivity {
+    /// The member's user ID
+    pub user_id: Snowflake,
+    /// The guild ID
+    pub guild_id: Snowflake,
+    /// When the member last sent a message
+    pub last_message_at: Option<Timestamp>,
+    /// Messages sent in the last 7 days
+    #[serde(default)]
+    pub message_count_7d: u32,
+    /// Minutes spent in voice channels in the last 7 days
+    #[serde(default)]
+    pub voice_minutes_7d: u32,
+}
+
+impl MemberActivity {
+    /// Returns true if the member sent a message within the given duration.
+    pub fn is_active_recently(&self, within: std::time::Duration) -> bool {
+        let Some(last_message_at) = self.last_message_at else {
+            return false;
+        };
+        match chrono::Duration::from_std(within) {
+            Ok(within) => chrono::Utc::now() - last_message_at <= within,
+            // Longer than chrono can represent, so any message counts
+            Err(_) => true,
+        }
+    }
+}
+
+/// A change to a guild's members, channels or roles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuildChangeEvent {
+    /// The guild that changed
+    pub guild_id: Snowflake,
+    /// What changed
+    pub kind: GuildChangeKind,
+}
+
+/// The kind of change in a [`GuildChangeEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuildChangeKind {
+    /// A member joined the guild
+    MemberJoined(Member),
+    /// A member left or was removed from the guild
+    MemberLeft(Member),
+    /// A channel was created
+    ChannelCreated(Channel),
+    /// A channel was deleted
+    ChannelDeleted(Channel),
+    /// A role was created
+    RoleCreated(GuildRole),
+    /// A role was deleted, identified by its ID
+    RoleDeleted(String),
+}
+
+impl GuildChangeEvent {
+    /// Converts a gateway dispatch into a guild change.
+    ///
+    /// Returns `None` for unrelated events, events without a `guild_id`, and
+    /// payloads that fail to parse.
+    pub fn from_gateway_event(event: &GatewayEvent) -> Option<Self> {
+        let data = event.data.as_ref()?;
+        let guild_id = data.get("guild_id")?.as_str()?.to_string();
+        fn parse<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Option<T> {
+            serde_json::from_value(value.clone()).ok()
+        }
+
+        let kind = match event.event_type.as_deref()? {
+            "GUILD_MEMBER_ADD" => GuildChangeKind::MemberJoined(parse(data)?),
+            "GUILD_MEMBER_REMOVE" => GuildChangeKind::MemberLeft(parse(data)?),
+            "CHANNEL_CREATE" => GuildChangeKind::ChannelCreated(parse(data)?),
+            "CHANNEL_DELETE" => GuildChangeKind::ChannelDeleted(parse(data)?),
+            "GUILD_ROLE_CREATE" => GuildChangeKind::RoleCreated(parse(data.get("role")?)?),
+            "GUILD_ROLE_DELETE" => {
+                GuildChangeKind::RoleDeleted(data.get("role_id")?.as_str()?.to_string())
+            }
+            _ => return None,
+        };
+        Some(Self { guild_id, kind })
+    }
+}
+
+/// A member attribute that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemberField {
+    /// The member's user ID
+    UserId,
+    /// The member's username
+    Username,
+    /// The member's nickname in the guild
+    Nick,
+    /// The member's role IDs, comma-separated
+    Roles,
+    /// When the member joined, in RFC 3339 format
+    JoinedAt,
+}
+
+impl MemberField {
+    /// Gets the column name for this field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UserId => "user_id",
+            Self::Username => "username",
+            Self::Nick => "nick",
+            Self::Roles => "roles",
+            Self::JoinedAt => "joined_at",
+        }
+    }
+
+    /// Gets this field's value for a member, or an empty string if unset.
+    pub fn value(&self, member: &Member) -> String {
+        match self {
+            Self::UserId => member
+                .user
+                .as_ref()
+                .map(|user| user.id.clone())
+                .unwrap_or_default(),
+            Self::Username => member
+                .user
+                .as_ref()
+                .map(|user| user.username.clone())
+                .unwrap_or_default(),
+            Self::Nick => member.nick.clone().unwrap_or_default(),
+            Self::Roles => member
+                .roles
+                .as_deref()
+                .map(|roles| roles.join(","))
+                .unwrap_or_default(),
+            Self::JoinedAt => member
+                .joined_at
+                .map(|joined_at| joined_at.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The outcome of syncing a member's roles to a desired set.
+#[derive(Debug, Default)]
+pub struct RoleSyncResult {
+    /// Roles that were added
+    pub added: Vec<String>,
+    /// Roles that were removed
+    pub removed: Vec<String>,
+    /// Roles whose add or remove failed, with the error
+    pub errors: Vec<(String, crate::error::BotError)>,
+}
+
+impl RoleSyncResult {
+    /// Returns true if every change was applied.
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// The desired state of a guild role, matched to existing roles by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleSpec {
+    /// The role's name
+    pub name: String,
+    /// The role's color, or `None` to leave it unchanged
+    pub color: Option<u32>,
+    /// Whether the role is displayed separately, or `None` to leave it unchanged
+    pub hoist: Option<bool>,
+}
+
+impl RoleSpec {
+    /// Creates a spec for a role with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            color: None,
+            hoist: None,
+        }
+    }
+
+    /// Sets the color.
+    pub fn with_color(mut self, color: u32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets whether the role is displayed separately.
+    pub fn with_hoist(mut self, hoist: bool) -> Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// Returns true if `role` differs from this spec.
+    pub fn differs_from(&self, role: &GuildRole) -> bool {
+        self.color.is_some_and(|color| role.color != Some(color))
+            || self.hoist.is_some_and(|hoist| role.is_hoisted() != hoist)
+    }
+}
+
+/// The outcome of syncing a guild's roles to a list of [`RoleSpec`]s.
+///
+/// Unlike [`RoleSyncResult`], which tracks one member's role assignments,
+/// this records changes to the guild's roles themselves.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GuildRoleSyncResult {
+    /// Roles that were created
+    pub created: Vec<GuildRole>,
+    /// Roles that were updated
+    pub updated: Vec<GuildRole>,
+    /// IDs of roles that were deleted
+    pub deleted: Vec<String>,
+}
+
+/// The words and patterns a guild filters out of messages.
+///
+/// The patterns are validated and compiled when the list is created or
+/// deserialized, so a list with an invalid pattern can't be built.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawGuildBannedWords", into = "RawGuildBannedWords")]
+pub struct GuildBannedWords {
+    words: Vec<String>,
+    regex_patterns: Vec<String>,
+    set: regex::RegexSet,
+}
+
+/// Wire format of [`GuildBannedWords`].
+#[derive(Serialize, Deserialize)]
+struct RawGuildBannedWords {
+    #[serde(default)]
+    words: Vec<String>,
+    #[serde(default)]
+    regex_patterns: Vec<String>,
+}
+
+impl TryFrom<RawGuildBannedWords> for GuildBannedWords {
+    type Error = crate::error::BotError;
+
+    fn try_from(raw: RawGuildBannedWords) -> crate::error::Result<Self> {
+        Self::new(raw.words, raw.regex_patterns)
+    }
+}
+
+impl From<GuildBannedWords> for RawGuildBannedWords {
+    fn from(banned: GuildBannedWords) -> Self {
+        Self {
+            words: banned.words,
+            regex_patterns: banned.regex_patterns,
+        }
+    }
+}
+
+impl Default for GuildBannedWords {
+    fn default() -> Self {
+        Self {
+            words: Vec::new(),
+            regex_patterns: Vec::new(),
+            set: regex::RegexSet::empty(),
+        }
+    }
+}
+
+impl PartialEq for GuildBannedWords {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words && self.regex_patterns == other.regex_patterns
+    }
+}
+
+impl Eq for GuildBannedWords {}
+
+impl GuildBannedWords {
+    /// Creates a banned word list, compiling its patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - Words banned anywhere in a message
+    /// * `regex_patterns` - Regular expressions banned in a message
+    ///
+    /// # Returns
+    ///
+    /// The list, or an error if any pattern is invalid.
+    pub fn new(words: Vec<String>, regex_patterns: Vec<String>) -> crate::error::Result<Self> {
+        let mut banned = Self {
+            words,
+            regex_patterns,
+            set: regex::RegexSet::empty(),
+        };
+        let alternatives: Vec<String> = banned.alternatives().map(|(_, pattern)| pattern).collect();
+        banned.set = regex::RegexSet::new(alternatives).map_err(invalid_banned_pattern)?;
+        Ok(banned)
+    }
+
+    /// Gets the words banned anywhere in a message.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Gets the regular expressions banned in a message.
+    pub fn regex_patterns(&self) -> &[String] {
+        &self.regex_patterns
+    }
+
+    /// Finds the first banned word or pattern in `text`.
+    ///
+    /// Words are checked first, as plain substrings, then patterns.
+    ///
+    /// # Returns
+    ///
+    /// The matching word or pattern, if any.
+    pub fn matches(&self, text: &str) -> Option<&str> {
+        let index = self.set.matches(text).iter().next()?;
+        self.alternatives().nth(index).map(|(source, _)| source)
+    }
+
+    /// Combines all words and patterns into one regular expression.
+    ///
+    /// Words are matched literally. With nothing banned the expression
+    /// matches no text.
+    ///
+    /// # Returns
+    ///
+    /// The combined expression, or an error if any pattern is invalid.
+    pub fn to_regex(&self) -> crate::error::Result<regex::Regex> {
+        let alternatives: Vec<String> = self
+            .alternatives()
+            .map(|(_, pattern)| format!("(?:{pattern})"))
+            .collect();
+        let pattern = if alternatives.is_empty() {
+            r"[^\s\S]".to_string()
+        } else {
+            alternatives.join("|")
+        };
+        regex::Regex::new(&pattern).map_err(invalid_banned_pattern)
+    }
+
+    /// Pairs each non-empty word and each pattern with the expression that
+    /// matches it, words first.
+    fn alternatives(&self) -> impl Iterator<Item = (&str, String)> {
+        self.words
+            .iter()
+            .filter(|word| !word.is_empty())
+            .map(|word| (word.as_str(), regex::escape(word)))
+            .chain(
+                self.regex_patterns
+                    .iter()
+                    .map(|pattern| (pattern.as_str(), pattern.clone())),
+            )
+    }
+
+    /// Returns a copy with `words` added, skipping ones already banned.
+    pub fn with_words_added(&self, words: &[&str]) -> crate::error::Result<Self> {
+        let mut updated = self.words.clone();
+        for word in words {
+            if !updated.iter().any(|existing| existing == word) {
+                updated.push(word.to_string());
+            }
+        }
+        Self::new(updated, self.regex_patterns.clone())
+    }
+
+    /// Returns a copy with `words` removed.
+    pub fn with_words_removed(&self, words: &[&str]) -> crate::error::Result<Self> {
+        let mut updated = self.words.clone();
+        updated.retain(|existing| !words.contains(&existing.as_str()));
+        Self::new(updated, self.regex_patterns.clone())
+    }
+}
+
+/// Converts a banned word pattern compile error.
+fn invalid_banned_pattern(e: regex::Error) -> crate::error::BotError {
+    crate::error::BotError::invalid_params(format!("invalid banned word pattern: {e}"))
+}
+
+/// A capability a guild unlocks by reaching a premium tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PremiumPerk {
+    /// Animated guild icon (tier 1)
+    AnimatedIcon,
+    /// Custom invite background (tier 1)
+    InviteSplash,
+    /// Higher voice channel audio quality (tier 1)
+    HigherAudioQuality,
+    /// Guild banner (tier 2)
+    Banner,
+    /// Larger file uploads (tier 2)
+    LargerUploads,
+    /// Custom invite link (tier 3)
+    VanityUrl,
+}
+
+impl PremiumPerk {
+    /// Gets the tier that unlocks this perk.
+    pub fn tier(&self) -> u8 {
+        match self {
+            Self::AnimatedIcon | Self::InviteSplash | Self::HigherAudioQuality => 1,
+            Self::Banner | Self::LargerUploads => 2,
+            Self::VanityUrl => 3,
+        }
+    }
+
+    /// Gets every perk unlocked at `tier`, including lower tiers' perks.
+    pub fn for_tier(tier: u8) -> Vec<Self> {
+        [
+            Self::AnimatedIcon,
+            Self::InviteSplash,
+            Self::HigherAudioQuality,
+            Self::Banner,
+            Self::LargerUploads,
+            Self::VanityUrl,
+        ]
+        .into_iter()
+        .filter(|perk| perk.tier() <= tier)
+        .collect()
+    }
+}
+
+/// A guild's boost count and the premium tier it has reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildPremiumStatus {
+    /// The premium tier, 0 if none
+    pub tier: u8,
+    /// The number of boosts
+    pub boost_count: u32,
+    /// The role given to boosters, if the guild has one
+    pub booster_role_id: Option<Snowflake>,
+    /// Perks unlocked at the current tier
+    pub perks: Vec<PremiumPerk>,
+}
+
+impl GuildPremiumStatus {
+    /// Highest premium tier.
+    pub const MAX_TIER: u8 = 3;
+
+    /// Boosts needed to reach tiers 1, 2 and 3.
+    pub const TIER_BOOSTS: [u32; 3] = [2, 7, 14];
+
+    /// Builds the premium status from a guild.
+    pub fn from_guild(guild: &Guild, booster_role_id: Option<Snowflake>) -> Self {
+        let tier = guild.premium_tier.unwrap_or(0).min(Self::MAX_TIER);
+        Self {
+            tier,
+            boost_count: guild.booster_count.unwrap_or(0),
+            booster_role_id,
+            perks: PremiumPerk::for_tier(tier),
+        }
+    }
+
+    /// Gets how many more boosts are needed to reach the next tier.
+    ///
+    /// Returns `None` at the highest tier.
+    pub fn next_tier_boosts_needed(&self) -> Option<u32> {
+        let required = Self::TIER_BOOSTS.get(usize::from(self.tier))?;
+        Some(required.saturating_sub(self.boost_count))
+    }
+}
+
+/// What happened in a guild boost event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostAction {
+    /// The user started boosting the guild
+    Subscribed,
+    /// The user stopped boosting the guild
+    Unsubscribed,
+    /// The user upgraded an existing boost
+    Upgraded,
+}
+
+/// A change to a user's premium subscription to a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoostEvent {
+    /// The boosting user's ID
+    pub user_id: Snowflake,
+    /// What the user did
+    pub action: BoostAction,
+    /// When it happened
+    pub timestamp: Timestamp,
+}
+
+impl BoostEvent {
+    /// Returns true if the user is still boosting after this event.
+    pub fn is_active(&self) -> bool {
+        self.action != BoostAction::Unsubscribed
+    }
+
+    /// Gets the users who are boosting once every event has been applied.
+    ///
+    /// Events are applied oldest first, so each user's latest event decides
+    /// whether they are boosting. Users are returned in the order they most
+    /// recently started boosting.
+    pub fn current_boosters(events: &[BoostEvent]) -> Vec<Snowflake> {
+        let mut events: Vec<&BoostEvent> = events.iter().collect();
+        events.sort_by_key(|event| event.timestamp);
+
+        let mut boosters: Vec<Snowflake> = Vec::new();
+        for event in events {
+            let position = boosters.iter().position(|id| *id == event.user_id);
+            match (event.is_active(), position) {
+                (true, None) => boosters.push(event.user_id.clone()),
+                (false, Some(index)) => {
+                    boosters.remove(index);
+                }
+                _ => {}
+            }
+        }
+        boosters
+    }
+}
+
+/// A reusable snapshot of a guild's channels and roles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildTemplate {
+    /// The template code
+    pub code: String,
+    /// The template name
+    pub name: String,
+    /// The template description
+    pub description: Option<String>,
+    /// Number of times the template has been used
+    #[serde(default)]
+    pub usage_count: u32,
+    /// The ID of the user who created the template
+    pub creator_id: Snowflake,
+    /// When the template was created
+    pub created_at: Timestamp,
+    /// When the template was last synced with its guild
+    pub updated_at: Timestamp,
+    /// The ID of the guild the template was made from
+    pub source_guild_id: Snowflake,
+}
+
+impl GuildTemplate {
+    /// Maximum length of a template name.
+    pub const MAX_NAME_LENGTH: usize = 100;
+}
+
+/// A page of the members currently online in a guild.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnlineMembersPage {
+    /// Online members on this page
+    #[serde(default)]
+    pub members: Vec<Member>,
+    /// Cursor to request the following page with, `None` on the last page
+    pub next_cursor: Option<String>,
+    /// Total number of members online
+    #[serde(default)]
+    pub total: u32,
+}
+
+/// A guild's custom invite code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VanityUrl {
+    /// The invite code, if the guild has one
+    pub code: Option<String>,
+    /// Number of times the invite has been used
+    #[serde(default)]
+    pub uses: u32,
+}
+
+impl VanityUrl {
+    /// Returns true if `code` is a valid vanity invite code.
+    ///
+    /// Codes must be 2 to 30 ASCII letters, digits or hyphens.
+    pub fn is_valid_code(code: &str) -> bool {
+        (2..=30).contains(&code.len())
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    }
+}
+
+/// How a guild is presented in public guild discovery.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildDiscoveryMetadata {
+    /// The guild ID
+    pub guild_id: String,
+    /// The guild's main discovery category
+    #[serde(default)]
+    pub primary_category_id: u32,
+    /// Search keywords for the guild
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Whether the guild is listed in discovery
+    #[serde(default)]
+    pub is_published: bool,
+    /// Whether the guild's emojis can be discovered by other users
+    #[serde(default)]
+    pub emoji_discoverability_enabled: bool,
+}
+
+/// Discovery fields to change; absent fields are left as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GuildDiscoveryUpdate {
+    /// New search keywords
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Vec<String>>,
+    /// New main discovery category
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_category_id: Option<u32>,
+}
+
+/// The bot's own membership details in a guild.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildBotInfo {
+    /// When the bot joined the guild
+    pub joined_at: Option<Timestamp>,
+    /// The bot's role IDs in the guild
+    #[serde(default)]
+    pub roles: Vec<Snowflake>,
+    /// The bot's nickname in the guild
+    pub nick: Option<String>,
+}
+
+impl From<Member> for GuildBotInfo {
+    fn from(member: Member) -> Self {
+        Self {
+            joined_at: member.joined_at,
+            roles: member.roles.unwrap_or_default(),
+            nick: member.nick,
+        }
+    }
+}
+
+/// A voice server region a guild can use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceRegion {
+    /// The region's unique ID
+    pub id: String,
+    /// The region's display name
+    pub name: String,
+    /// Whether this is the region closest to the bot
+    #[serde(default)]
+    pub optimal: bool,
+    /// Whether the region is deprecated and should be avoided
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Whether the region is a custom region
+    #[serde(default)]
+    pub custom: bool,
+}
+
+impl VoiceRegion {
+    /// Domain under which each region's voice servers are hosted.
+    pub const HOST: &'static str = "voice.qq.com";
+
+    /// Gets the URL used to measure latency to this region.
+    pub fn ping_url(&self) -> String {
+        format!("https://{}.{}/ping", self.id, Self::HOST)
+    }
+}
+
 /// Represents a role in a guild (legacy type alias).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Role {
@@ -371,6 +1137,64 @@ impl Member {
     pub fn has_role(&self, role_id: &str) -> bool {
         self.role_ids().iter().any(|id| id == role_id)
     }
+
+    /// Gets the number of whole days since the member joined the guild.
+    pub fn days_since_join(&self) -> Option<u64> {
+        let joined_at = self.joined_at?;
+        Some((chrono::Utc::now() - joined_at).num_days().max(0) as u64)
+    }
+
+    /// Gets the member's highest admin level in a guild.
+    ///
+    /// The guild owner is detected from `guild.owner_id` as well as the owner
+    /// system role. Other levels come from the member's roles that appear in
+    /// `roles`, the guild's role list.
+    pub fn admin_level(&self, guild: &Guild, roles: &[GuildRole]) -> Option<GuildAdminLevel> {
+        if guild.owner_id.is_some() && guild.owner_id.as_ref() == self.user_id() {
+            return Some(GuildAdminLevel::Owner);
+        }
+        roles
+            .iter()
+            .filter_map(|role| role.id.as_deref())
+            .filter(|role_id| self.has_role(role_id))
+            .filter_map(GuildAdminLevel::from_role_id)
+            .min()
+    }
+}
+
+/// A member's administrative standing in a guild, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GuildAdminLevel {
+    /// Guild owner
+    Owner,
+    /// Guild administrator
+    Administrator,
+    /// Channel manager
+    Manager,
+}
+
+impl GuildAdminLevel {
+    /// All admin levels, highest first.
+    pub const ALL: [Self; 3] = [Self::Owner, Self::Administrator, Self::Manager];
+
+    /// Gets the system role that grants this level.
+    ///
+    /// QQ guild roles carry no permission bits, so admin rights are tied to
+    /// these fixed role IDs.
+    pub fn role_id(&self) -> &'static str {
+        match self {
+            Self::Owner => "4",
+            Self::Administrator => "2",
+            Self::Manager => "5",
+        }
+    }
+
+    /// Gets the level granted by a system role, if any.
+    pub fn from_role_id(role_id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.role_id() == role_id)
+    }
 }
 
 impl Default for Member {
@@ -491,4 +1315,404 @@ mod tests {
         assert!(!member.has_role("role3"));
         assert_eq!(member.role_ids().len(), 2);
     }
+
+    #[test]
+    fn test_guild_stats() {
+        let stats: GuildStats = serde_json::from_value(serde_json::json!({
+            "joined_count_7d": 20,
+            "joined_count_30d": 80,
+            "deleted_count_7d": 5,
+            "deleted_count_30d": 12,
+            "member_count": 1000,
+            "online_count": 150
+        }))
+        .unwrap();
+        assert_eq!(stats.member_count, 1000);
+        assert!((stats.retention_rate_7d() - 0.75).abs() < f32::EPSILON);
+
+        let empty: GuildStats = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(empty.retention_rate_7d(), 1.0);
+
+        let churn = GuildStats {
+            joined_count_7d: 2,
+            deleted_count_7d: 5,
+            ..Default::default()
+        };
+        assert_eq!(churn.retention_rate_7d(), 0.0);
+    }
+
+    #[test]
+    fn test_guild_bot_info_from_member() {
+        let member: Member = serde_json::from_value(serde_json::json!({
+            "nick": "helper",
+            "roles": ["1", "2"],
+            "joined_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        let info = GuildBotInfo::from(member);
+        assert_eq!(info.nick.as_deref(), Some("helper"));
+        assert_eq!(info.roles, vec!["1", "2"]);
+        assert!(info.joined_at.is_some());
+        assert_eq!(GuildBotInfo::from(Member::new()).roles.len(), 0);
+    }
+
+    #[test]
+    fn test_role_display_with_color() {
+        let mut role = GuildRole::new();
+        role.name = Some("Admin".to_string());
+        assert_eq!(role.display_with_color(), "Admin");
+
+        role.color = Some(0xFF0000);
+        assert_eq!(role.display_with_color(), "Admin (#FF0000)");
+    }
+
+    #[test]
+    fn test_role_breakdown() {
+        let roles: GuildRoles = serde_json::from_value(serde_json::json!({
+            "roles": [
+                {"id": "1", "name": "Member", "number": 50},
+                {"id": "2", "name": "Admin", "number": 5},
+                {"id": "3", "name": "Moderator", "number": 20}
+            ]
+        }))
+        .unwrap();
+
+        let counts = roles.member_counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["3"], 20);
+
+        let breakdown = roles.breakdown(200);
+        let names: Vec<_> = breakdown.iter().map(|b| b.role_name.as_str()).collect();
+        assert_eq!(names, vec!["Member", "Moderator", "Admin"]);
+        assert_eq!(breakdown[0].percentage, 25.0);
+        assert_eq!(breakdown[1].percentage, 10.0);
+        assert_eq!(breakdown[2].percentage, 2.5);
+
+        assert!(roles.breakdown(0).iter().all(|b| b.percentage == 0.0));
+    }
+
+    #[test]
+    fn test_member_activity() {
+        let now = chrono::Utc::now();
+        let mut activity: MemberActivity = serde_json::from_value(serde_json::json!({
+            "user_id": "u1",
+            "guild_id": "g1",
+            "last_message_at": (now - chrono::Duration::minutes(30)).to_rfc3339(),
+            "message_count_7d": 42,
+            "voice_minutes_7d": 90
+        }))
+        .unwrap();
+        assert_eq!(activity.message_count_7d, 42);
+
+        let hour = std::time::Duration::from_secs(3600);
+        assert!(activity.is_active_recently(hour));
+        assert!(!activity.is_active_recently(std::time::Duration::from_secs(60)));
+
+        activity.last_message_at = Some(now - chrono::Duration::days(3));
+        assert!(!activity.is_active_recently(hour));
+
+        activity.last_message_at = None;
+        assert!(!activity.is_active_recently(hour));
+    }
+
+    #[test]
+    fn test_guild_template_deserialize() {
+        let template: GuildTemplate = serde_json::from_value(serde_json::json!({
+            "code": "abc123",
+            "name": "Community",
+            "description": null,
+            "usage_count": 4,
+            "creator_id": "u1",
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-02-01T00:00:00Z",
+            "source_guild_id": "g1"
+        }))
+        .unwrap();
+        assert_eq!(template.code, "abc123");
+        assert_eq!(template.usage_count, 4);
+        assert!(template.description.is_none());
+        assert!(template.updated_at > template.created_at);
+    }
+
+    #[test]
+    fn test_guild_change_event_mapping() {
+        let event = |event_type: &str, data: serde_json::Value| GatewayEvent {
+            event_type: Some(event_type.to_string()),
+            data: Some(data),
+            sequence: Some(1),
+            opcode: 0,
+        };
+
+        let joined = GuildChangeEvent::from_gateway_event(&event(
+            "GUILD_MEMBER_ADD",
+            serde_json::json!({"guild_id": "g1", "user": {"id": "u1", "username": "alice"}, "nick": "Al"}),
+        ))
+        .unwrap();
+        assert_eq!(joined.guild_id, "g1");
+        assert!(
+            matches!(joined.kind, GuildChangeKind::MemberJoined(ref m) if m.nick.as_deref() == Some("Al"))
+        );
+
+        let deleted = GuildChangeEvent::from_gateway_event(&event(
+            "CHANNEL_DELETE",
+            serde_json::json!({"guild_id": "g1", "id": "c1", "name": "general"}),
+        ))
+        .unwrap();
+        assert!(
+            matches!(deleted.kind, GuildChangeKind::ChannelDeleted(ref c) if c.id.as_deref() == Some("c1"))
+        );
+
+        let role = GuildChangeEvent::from_gateway_event(&event(
+            "GUILD_ROLE_CREATE",
+            serde_json::json!({"guild_id": "g1", "role": {"id": "r1", "name": "Mod"}}),
+        ))
+        .unwrap();
+        assert!(
+            matches!(role.kind, GuildChangeKind::RoleCreated(ref r) if r.id.as_deref() == Some("r1"))
+        );
+
+        let removed = GuildChangeEvent::from_gateway_event(&event(
+            "GUILD_ROLE_DELETE",
+            serde_json::json!({"guild_id": "g1", "role_id": "r1"}),
+        ))
+        .unwrap();
+        assert_eq!(removed.kind, GuildChangeKind::RoleDeleted("r1".to_string()));
+
+        let message = event(
+            "AT_MESSAGE_CREATE",
+            serde_json::json!({"guild_id": "g1", "content": "hi"}),
+        );
+        assert!(GuildChangeEvent::from_gateway_event(&message).is_none());
+        let no_guild = event("CHANNEL_CREATE", serde_json::json!({"id": "c1"}));
+        assert!(GuildChangeEvent::from_gateway_event(&no_guild).is_none());
+    }
+
+    #[test]
+    fn test_member_admin_level() {
+        let member = |id: &str, roles: &[&str]| Member {
+            user: Some(
+                serde_json::from_value(serde_json::json!({"id": id, "username": id})).unwrap(),
+            ),
+            roles: Some(roles.iter().map(|r| r.to_string()).collect()),
+            ..Member::new()
+        };
+        let role = |id: &str| GuildRole {
+            id: Some(id.to_string()),
+            ..GuildRole::new()
+        };
+        let mut guild = Guild::new();
+        guild.owner_id = Some("owner".to_string());
+        let roles = vec![role("1"), role("2"), role("5"), role("10")];
+
+        assert_eq!(
+            member("owner", &["1"]).admin_level(&guild, &roles),
+            Some(GuildAdminLevel::Owner)
+        );
+        assert_eq!(
+            member("a", &["1", "5", "2"]).admin_level(&guild, &roles),
+            Some(GuildAdminLevel::Administrator)
+        );
+        assert_eq!(
+            member("m", &["5", "10"]).admin_level(&guild, &roles),
+            Some(GuildAdminLevel::Manager)
+        );
+        assert_eq!(member("u", &["1", "10"]).admin_level(&guild, &roles), None);
+        // Role 4 is not in the guild's role list, so it is ignored.
+        assert_eq!(member("x", &["4"]).admin_level(&guild, &roles), None);
+        assert_eq!(
+            member("x", &["4"]).admin_level(&guild, &[role("4")]),
+            Some(GuildAdminLevel::Owner)
+        );
+    }
+
+    #[test]
+    fn test_guild_banned_words() {
+        let strings =
+            |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+
+        // An invalid pattern is rejected when the list is built or deserialized
+        assert!(
+            GuildBannedWords::new(strings(&["spam"]), strings(&[r"free\s+nitro", "("])).is_err()
+        );
+        assert!(
+            serde_json::from_value::<GuildBannedWords>(
+                serde_json::json!({"regex_patterns": ["("]})
+            )
+            .is_err()
+        );
+
+        let banned = GuildBannedWords::new(
+            strings(&["spam", "", "scam"]),
+            strings(&[r"free\s+nitro", "sp.m"]),
+        )
+        .unwrap();
+        assert_eq!(banned.matches("no spam please"), Some("spam"));
+        assert_eq!(banned.matches("a scam"), Some("scam"));
+        assert_eq!(banned.matches("get FREE nitro"), None);
+        assert_eq!(banned.matches("get free   nitro"), Some(r"free\s+nitro"));
+        assert_eq!(banned.matches("spum"), Some("sp.m"));
+        assert_eq!(banned.matches("hello"), None);
+        assert_eq!(GuildBannedWords::default().matches("hello"), None);
+
+        let valid: GuildBannedWords = serde_json::from_value(serde_json::json!({
+            "words": ["a.b"],
+            "regex_patterns": [r"free\s+nitro"]
+        }))
+        .unwrap();
+        assert_eq!(valid.words(), ["a.b"]);
+        assert_eq!(valid.matches("axb"), None);
+        assert_eq!(valid.matches("x a.b y"), Some("a.b"));
+        let regex = valid.to_regex().unwrap();
+        assert!(regex.is_match("x a.b y"));
+        assert!(!regex.is_match("axb"));
+        assert!(regex.is_match("free nitro"));
+        assert!(
+            !GuildBannedWords::default()
+                .to_regex()
+                .unwrap()
+                .is_match("anything")
+        );
+
+        let updated = valid.with_words_added(&["a.b", "c"]).unwrap();
+        assert_eq!(updated.words(), ["a.b", "c"]);
+        assert_eq!(updated.matches("c"), Some("c"));
+        let removed = updated.with_words_removed(&["a.b"]).unwrap();
+        assert_eq!(removed.words(), ["c"]);
+        assert_eq!(removed.matches("a.b"), None);
+        assert_eq!(
+            serde_json::to_value(&removed).unwrap(),
+            serde_json::json!({"words": ["c"], "regex_patterns": [r"free\s+nitro"]})
+        );
+    }
+
+    #[test]
+    fn test_guild_premium_status() {
+        let guild: Guild = serde_json::from_value(serde_json::json!({
+            "id": "g1",
+            "premium_tier": 1,
+            "premium_subscription_count": 3
+        }))
+        .unwrap();
+        let status = GuildPremiumStatus::from_guild(&guild, Some("r1".to_string()));
+        assert_eq!((status.tier, status.boost_count), (1, 3));
+        assert_eq!(status.next_tier_boosts_needed(), Some(4));
+        assert_eq!(status.perks.len(), 3);
+        assert!(!status.perks.contains(&PremiumPerk::Banner));
+
+        let mut tier2 = Guild::new();
+        tier2.premium_tier = Some(2);
+        tier2.booster_count = Some(9);
+        let status = GuildPremiumStatus::from_guild(&tier2, None);
+        assert_eq!(status.next_tier_boosts_needed(), Some(5));
+        assert!(status.perks.contains(&PremiumPerk::LargerUploads));
+        assert!(!status.perks.contains(&PremiumPerk::VanityUrl));
+
+        let mut max = Guild::new();
+        max.premium_tier = Some(3);
+        max.booster_count = Some(20);
+        let status = GuildPremiumStatus::from_guild(&max, None);
+        assert_eq!(status.next_tier_boosts_needed(), None);
+        assert_eq!(status.perks.len(), 6);
+
+        let none = GuildPremiumStatus::from_guild(&Guild::new(), None);
+        assert_eq!(none.tier, 0);
+        assert_eq!(none.next_tier_boosts_needed(), Some(2));
+        assert!(none.perks.is_empty());
+    }
+
+    #[test]
+    fn test_voice_region_ping_url() {
+        let region: VoiceRegion =
+            serde_json::from_value(serde_json::json!({"id": "sh", "name": "Shanghai"})).unwrap();
+        assert_eq!(region.ping_url(), "https://sh.voice.qq.com/ping");
+    }
+
+    #[test]
+    fn test_vanity_url_code_validation() {
+        assert!(VanityUrl::is_valid_code("ab"));
+        assert!(VanityUrl::is_valid_code("rust-lang-2024"));
+        assert!(VanityUrl::is_valid_code(&"a".repeat(30)));
+
+        assert!(!VanityUrl::is_valid_code("a"));
+        assert!(!VanityUrl::is_valid_code(&"a".repeat(31)));
+        assert!(!VanityUrl::is_valid_code("has space"));
+        assert!(!VanityUrl::is_valid_code("under_score"));
+        assert!(!VanityUrl::is_valid_code("emoji🎉"));
+    }
+
+    #[test]
+    fn test_current_boosters_from_history() {
+        let event = |user_id: &str, action: BoostAction, minute: u32| BoostEvent {
+            user_id: user_id.to_string(),
+            action,
+            timestamp: chrono::DateTime::parse_from_rfc3339(&format!(
+                "2024-03-01T12:{minute:02}:00Z"
+            ))
+            .unwrap()
+            .with_timezone(&chrono::Utc),
+        };
+        // Deliberately out of order: the API returns newest first.
+        let history = vec![
+            event("u3", BoostAction::Subscribed, 40),
+            event("u1", BoostAction::Upgraded, 30),
+            event("u2", BoostAction::Unsubscribed, 20),
+            event("u2", BoostAction::Subscribed, 10),
+            event("u4", BoostAction::Subscribed, 5),
+            event("u4", BoostAction::Unsubscribed, 15),
+            event("u4", BoostAction::Subscribed, 25),
+            event("u1", BoostAction::Subscribed, 0),
+        ];
+
+        assert!(history[1].is_active());
+        assert!(!history[2].is_active());
+        assert_eq!(
+            BoostEvent::current_boosters(&history),
+            vec!["u1", "u4", "u3"]
+        );
+        assert!(BoostEvent::current_boosters(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_guild_discovery_update_skips_absent_fields() {
+        let update = GuildDiscoveryUpdate {
+            keywords: Some(vec!["rust".to_string(), "bots".to_string()]),
+            primary_category_id: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({"keywords": ["rust", "bots"]})
+        );
+
+        let update = GuildDiscoveryUpdate {
+            keywords: None,
+            primary_category_id: Some(7),
+        };
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({"primary_category_id": 7})
+        );
+        assert_eq!(
+            serde_json::to_value(GuildDiscoveryUpdate::default()).unwrap(),
+            serde_json::json!({})
+        );
+
+        let metadata: GuildDiscoveryMetadata =
+            serde_json::from_value(serde_json::json!({"guild_id": "g1", "is_published": true}))
+                .unwrap();
+        assert!(metadata.is_published);
+        assert!(metadata.keywords.is_empty());
+    }
+
+    #[test]
+    fn test_member_days_since_join() {
+        let mut member = Member::new();
+        assert_eq!(member.days_since_join(), None);
+
+        member.joined_at = Some(chrono::Utc::now() - chrono::Duration::hours(80));
+        assert_eq!(member.days_since_join(), Some(3));
+
+        member.joined_at = Some(chrono::Utc::now() + chrono::Duration::hours(1));
+        assert_eq!(member.days_since_join(), Some(0));
+    }
 }
@@ -151,12 +151,24 @@ pub struct Message {
     pub mentions: Vec<MessageUser>,
     /// Attachments in this message
     pub attachments: Vec<MessageAttachment>,
+    /// Embeds in this message
+    #[serde(default)]
+    pub embeds: Vec<MessageEmbed>,
+    /// Ark template of this message
+    #[serde(default)]
+    pub ark: Option<Ark>,
+    /// Emoji reactions on this message
+    #[serde(default)]
+    pub reactions: Option<Vec<ReactionSummary>>,
     /// Global message sequence number
     pub seq: Option<u64>,
     /// Channel-specific message sequence number
     pub seq_in_channel: Option<String>,
     /// When this message was sent
     pub timestamp: Option<Timestamp>,
+    /// When this message was last edited
+    #[serde(default)]
+    pub edited_at: Option<Timestamp>,
     /// Event ID from the gateway
     pub event_id: Option<String>,
 }
@@ -174,13 +186,26 @@ impl Message {
             message_reference: None,
             mentions: Vec::new(),
             attachments: Vec::new(),
+            embeds: Vec::new(),
+            ark: None,
+            reactions: None,
             seq: None,
             seq_in_channel: None,
             timestamp: None,
+            edited_at: None,
             event_id: None,
         }
     }
 
+    /// Gets the total number of reactions across all emojis.
+    pub fn total_reaction_count(&self) -> u32 {
+        self.reactions
+            .iter()
+            .flatten()
+            .map(|reaction| reaction.count)
+            .sum()
+    }
+
     /// Creates a new message from API data.
     pub fn from_data(_api: crate::api::BotApi, event_id: String, data: serde_json::Value) -> Self {
         Self {
@@ -238,6 +263,16 @@ impl Message {
                         .collect()
                 })
                 .unwrap_or_default(),
+            embeds: data
+                .get("embeds")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default(),
+            ark: data
+                .get("ark")
+                .and_then(|v| serde_json::from_value(v.clone()).ok()),
+            reactions: data
+                .get("reactions")
+                .and_then(|v| serde_json::from_value(v.clone()).ok()),
             seq: data.get("seq").and_then(|v| v.as_u64()),
             seq_in_channel: data
                 .get("seq_in_channel")
@@ -248,6 +283,11 @@ impl Message {
                 .and_then(|v| v.as_str())
                 .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
                 .map(|dt| dt.with_timezone(&chrono::Utc)),
+            edited_at: data
+                .get("edited_at")
+                .and_then(|v| v.as_str())
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&chrono::Utc)),
             event_id: Some(event_id),
         }
     }
@@ -275,6 +315,22 @@ impl Message {
         }
     }
 
+    /// Fetches this message's edit history, oldest revision first.
+    pub async fn fetch_edit_history(
+        &self,
+        api: &crate::api::BotApi,
+        token: &crate::token::Token,
+    ) -> Result<Vec<MessageRevision>, crate::error::BotError> {
+        if let (Some(channel_id), Some(message_id)) = (&self.channel_id, &self.id) {
+            api.get_message_edit_history(token, channel_id, message_id)
+                .await
+        } else {
+            Err(crate::error::BotError::InvalidData(
+                "Missing channel_id or message_id for edit history".to_string(),
+            ))
+        }
+    }
+
     /// Returns true if this message has content.
     pub fn has_content(&self) -> bool {
         self.content.as_ref().is_some_and(|c| !c.is_empty())
@@ -294,6 +350,13 @@ impl Message {
     pub fn is_from_bot(&self) -> bool {
         self.author.as_ref().is_some_and(|a| a.bot.unwrap_or(false))
     }
+
+    /// Gets the link previews attached to this message.
+    ///
+    /// Embeds without a URL are skipped.
+    pub fn embedded_urls(&self) -> Vec<&MessageEmbed> {
+        self.embeds.iter().filter(|e| !e.url.is_empty()).collect()
+    }
 }
 
 impl Default for Message {
@@ -308,6 +371,255 @@ impl HasId for Message {
     }
 }
 
+/// Output format for exported channel messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// One CSV row per message under a header row
+    Csv,
+    /// A pretty-printed JSON array of messages
+    Json,
+    /// A human-readable Markdown log
+    Markdown,
+}
+
+/// Messages surrounding an anchor message in a channel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessagesAround {
+    /// Messages sent before the anchor, oldest first
+    pub before: Vec<Message>,
+    /// The anchor message, if it was returned by the API
+    pub target: Option<Message>,
+    /// Messages sent after the anchor, oldest first
+    pub after: Vec<Message>,
+}
+
+impl MessagesAround {
+    /// Splits a list of messages around the message with the given ID.
+    ///
+    /// Messages are ordered chronologically first. If the anchor is not present
+    /// in the list, all messages are placed in `before`.
+    pub fn split(mut messages: Vec<Message>, target_id: &str) -> Self {
+        messages.sort_by_key(|m| (m.timestamp, m.seq));
+
+        match messages
+            .iter()
+            .position(|m| m.id.as_deref() == Some(target_id))
+        {
+            Some(index) => {
+                let after = messages.split_off(index + 1);
+                let target = messages.pop();
+                Self {
+                    before: messages,
+                    target,
+                    after,
+                }
+            }
+            None => Self {
+                before: messages,
+                target: None,
+                after: Vec::new(),
+            },
+        }
+    }
+
+    /// Returns all messages, including the anchor, in chronological order.
+    pub fn context_window(&self) -> Vec<Message> {
+        self.before
+            .iter()
+            .chain(self.target.iter())
+            .chain(self.after.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// How long a person would take to type a message.
+pub struct TypingDelay;
+
+impl TypingDelay {
+    /// Longest delay [`compute`](Self::compute) returns.
+    pub const MAX: std::time::Duration = std::time::Duration::from_secs(5);
+
+    /// Average characters per word used to turn words per minute into a
+    /// typing speed.
+    pub const CHARS_PER_WORD: u32 = 5;
+
+    /// Computes the typing delay for `content` at `wpm` words per minute,
+    /// capped at [`TypingDelay::MAX`]. A speed of zero gives the maximum.
+    pub fn compute(content: &str, wpm: u32) -> std::time::Duration {
+        let chars_per_minute = u64::from(wpm) * u64::from(Self::CHARS_PER_WORD);
+        if chars_per_minute == 0 {
+            return Self::MAX;
+        }
+        let chars = content.chars().count() as u64;
+        let millis = chars.saturating_mul(60_000) / chars_per_minute;
+        std::time::Duration::from_millis(millis).min(Self::MAX)
+    }
+}
+
+/// One version of an edited message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageRevision {
+    /// Revision number, starting at 0 for the original message
+    pub revision: u32,
+    /// The message content in this revision
+    pub content: Option<String>,
+    /// When this revision was made
+    pub edited_at: Timestamp,
+    /// The user who made this revision
+    pub editor_id: Option<String>,
+}
+
+impl MessageRevision {
+    /// Returns true if this is the message as originally sent.
+    pub fn is_original(&self) -> bool {
+        self.revision == 0
+    }
+}
+
+/// A message together with the messages sent just before and after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageWithContext {
+    /// Messages sent before the message, oldest first
+    pub before: Vec<Message>,
+    /// The message itself
+    pub message: Message,
+    /// Messages sent after the message, oldest first
+    pub after: Vec<Message>,
+}
+
+impl MessageWithContext {
+    /// Builds the context from split messages, keeping at most
+    /// `context_size` messages on each side closest to the target.
+    ///
+    /// Returns `None` if the target message is missing.
+    pub fn from_around(around: MessagesAround, context_size: usize) -> Option<Self> {
+        let MessagesAround {
+            mut before,
+            target,
+            mut after,
+        } = around;
+        let message = target?;
+        before.drain(..before.len().saturating_sub(context_size));
+        after.truncate(context_size);
+        Some(Self {
+            before,
+            message,
+            after,
+        })
+    }
+
+    /// Returns all messages, including the target, in chronological order.
+    pub fn full_thread(&self) -> Vec<Message> {
+        self.before
+            .iter()
+            .chain(std::iter::once(&self.message))
+            .chain(self.after.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// A channel or user ID with the result of sending a message to it.
+type ChannelSendResult = (
+    String,
+    crate::error::Result<crate::models::api::MessageResponse>,
+);
+
+/// Per-channel results of sending one message to several channels.
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    /// Each channel ID with its send result, in the order given
+    pub results: Vec<ChannelSendResult>,
+}
+
+impl BroadcastReport {
+    /// Gets the channels the message was sent to.
+    pub fn successful_channels(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|(_, result)| result.is_ok())
+            .map(|(channel_id, _)| channel_id.as_str())
+            .collect()
+    }
+
+    /// Gets the channels the message could not be sent to, with the error.
+    pub fn failed_channels(&self) -> Vec<(&str, &crate::error::BotError)> {
+        self.results
+            .iter()
+            .filter_map(|(channel_id, result)| {
+                result.as_ref().err().map(|e| (channel_id.as_str(), e))
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<ChannelSendResult>> for BroadcastReport {
+    fn from(results: Vec<ChannelSendResult>) -> Self {
+        Self { results }
+    }
+}
+
+/// Per-user results of sending one direct message to several users.
+#[derive(Debug, Default)]
+pub struct DmBroadcastResult(BroadcastReport);
+
+impl DmBroadcastResult {
+    /// Gets each user ID with its send result, in the order given.
+    pub fn results(&self) -> &[ChannelSendResult] {
+        &self.0.results
+    }
+
+    /// Gets the users the direct message was sent to.
+    pub fn sent(&self) -> Vec<&str> {
+        self.0.successful_channels()
+    }
+
+    /// Gets the users the direct message could not be sent to, with the error.
+    pub fn failed(&self) -> Vec<(&str, &crate::error::BotError)> {
+        self.0.failed_channels()
+    }
+}
+
+impl From<Vec<ChannelSendResult>> for DmBroadcastResult {
+    fn from(results: Vec<ChannelSendResult>) -> Self {
+        Self(BroadcastReport::from(results))
+    }
+}
+
+/// Results of copying messages from one channel to another.
+#[derive(Debug, Default)]
+pub struct CopyReport {
+    /// Each copied message as `(source_id, dest_id)`, in the order given
+    pub copies: Vec<(String, String)>,
+    /// Each message that couldn't be copied, with the error
+    pub failures: Vec<(String, crate::error::BotError)>,
+}
+
+impl CopyReport {
+    /// Pairs each source message ID with its copy result.
+    pub fn new(
+        source_ids: &[&str],
+        results: Vec<crate::error::Result<crate::models::api::MessageResponse>>,
+    ) -> Self {
+        let mut report = Self::default();
+        for (source_id, result) in source_ids.iter().zip(results) {
+            match result {
+                Ok(response) => report
+                    .copies
+                    .push((source_id.to_string(), response.id.unwrap_or_default())),
+                Err(e) => report.failures.push((source_id.to_string(), e)),
+            }
+        }
+        report
+    }
+
+    /// Returns true if every message was copied.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
 /// Represents a direct message.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DirectMessage {
@@ -566,6 +878,37 @@ impl Default for GroupMessage {
     }
 }
 
+/// A discussion thread started from a group message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupThread {
+    /// The thread ID
+    pub thread_id: String,
+    /// The group the thread is in
+    pub group_openid: String,
+    /// The OpenID of the member who started the thread
+    pub creator_openid: String,
+    /// When the thread was created
+    pub created_at: Timestamp,
+}
+
+impl GroupThread {
+    /// Sends a text message to this thread.
+    pub async fn reply(
+        &self,
+        api: &crate::api::BotApi,
+        token: &crate::token::Token,
+        content: &str,
+    ) -> Result<crate::models::api::MessageResponse, crate::error::BotError> {
+        api.post_group_thread_message(
+            token,
+            &self.group_openid,
+            &self.thread_id,
+            GroupMessageParams::new_text(content),
+        )
+        .await
+    }
+}
+
 /// Represents a C2C (client-to-client) message.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct C2CMessage {
@@ -759,6 +1102,76 @@ impl Default for MessageAudit {
     }
 }
 
+/// The review state of a message held for audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum AuditStatus {
+    /// Still waiting for review
+    Pending = 0,
+    /// Approved and sent
+    Passed = 1,
+    /// Rejected and not sent
+    Rejected = 2,
+    /// Unknown status
+    Unknown(u8),
+}
+
+impl From<u8> for AuditStatus {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Pending,
+            1 => Self::Passed,
+            2 => Self::Rejected,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<AuditStatus> for u8 {
+    fn from(status: AuditStatus) -> Self {
+        match status {
+            AuditStatus::Pending => 0,
+            AuditStatus::Passed => 1,
+            AuditStatus::Rejected => 2,
+            AuditStatus::Unknown(value) => value,
+        }
+    }
+}
+
+/// The current audit state of a held message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageAuditStatus {
+    /// The audit ID
+    pub audit_id: Snowflake,
+    /// The ID of the message, once it has been sent
+    pub message_id: Option<Snowflake>,
+    /// The review state
+    pub status: AuditStatus,
+    /// Why the message was rejected, if it was
+    pub reason: Option<String>,
+}
+
+impl MessageAuditStatus {
+    /// Returns true if the message is still waiting for review.
+    pub fn is_pending(&self) -> bool {
+        self.status == AuditStatus::Pending
+    }
+}
+
+/// The reactions with one emoji on a message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionSummary {
+    /// The emoji reacted with
+    pub emoji: crate::models::emoji::Emoji,
+    /// How many users reacted with this emoji
+    #[serde(default)]
+    pub count: u32,
+    /// Whether the bot itself reacted with this emoji
+    #[serde(default)]
+    pub me: bool,
+}
+
 /// User information in a regular message.
 /// Represents a user mentioned in a message.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -789,6 +1202,66 @@ impl MessageUser {
                 .map(String::from),
         }
     }
+
+    /// Gets the full URL of the user's avatar.
+    ///
+    /// The API usually returns a complete URL, which is used as is. Otherwise
+    /// the URL is built from the user ID, the same way as [`User::avatar_url`].
+    ///
+    /// [`User::avatar_url`]: crate::models::user::User::avatar_url
+    pub fn avatar_url(&self) -> Option<String> {
+        let avatar = self.avatar.as_deref().filter(|a| !a.is_empty())?;
+        if avatar.starts_with("http://") || avatar.starts_with("https://") {
+            Some(avatar.to_string())
+        } else {
+            self.id.as_deref().map(crate::models::user::avatar_url_for)
+        }
+    }
+
+    /// Fetches this user's member information in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The bot API client
+    /// * `token` - Authentication token
+    /// * `guild_id` - The guild to look the user up in
+    ///
+    /// # Returns
+    ///
+    /// The guild member for this user, or [`BotError::NotFound`] if they
+    /// aren't in the guild.
+    ///
+    /// [`BotError::NotFound`]: crate::error::BotError::NotFound
+    pub async fn fetch_member(
+        &self,
+        api: &crate::api::BotApi,
+        token: &crate::token::Token,
+        guild_id: &str,
+    ) -> Result<crate::models::guild::Member, crate::error::BotError> {
+        let user_id = self.id.as_deref().ok_or_else(|| {
+            crate::error::BotError::InvalidData("Message user has no ID".to_string())
+        })?;
+        api.assert_guild_member(token, guild_id, user_id).await
+    }
+
+    /// Downloads the user's avatar image.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client used for the download
+    ///
+    /// # Returns
+    ///
+    /// The raw image bytes.
+    pub async fn fetch_avatar_bytes(
+        &self,
+        http: &crate::http::HttpClient,
+    ) -> Result<bytes::Bytes, crate::error::BotError> {
+        let url = self.avatar_url().ok_or_else(|| {
+            crate::error::BotError::InvalidData("Message user has no avatar".to_string())
+        })?;
+        http.download(&url).await
+    }
 }
 
 /// User information in a direct message.
@@ -1050,6 +1523,414 @@ mod tests {
         message.author.as_mut().unwrap().bot = Some(false);
         assert!(!message.is_from_bot());
     }
+
+    #[test]
+    fn test_messages_around_split() {
+        let message = |id: &str, seq: u64| Message {
+            id: Some(id.to_string()),
+            seq: Some(seq),
+            ..Message::new()
+        };
+        let messages = vec![
+            message("c", 3),
+            message("a", 1),
+            message("d", 4),
+            message("b", 2),
+        ];
+
+        let around = MessagesAround::split(messages.clone(), "b");
+        assert_eq!(around.before.len(), 1);
+        assert_eq!(around.target.as_ref().unwrap().id.as_deref(), Some("b"));
+        assert_eq!(around.after.len(), 2);
+
+        let ids: Vec<_> = around
+            .context_window()
+            .into_iter()
+            .filter_map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c", "d"]);
+
+        let missing = MessagesAround::split(messages, "z");
+        assert!(missing.target.is_none());
+        assert_eq!(missing.before.len(), 4);
+        assert!(missing.after.is_empty());
+    }
+
+    #[test]
+    fn test_forward_params() {
+        let attachment = |url: &str, content_type: &str| MessageAttachment {
+            id: None,
+            filename: None,
+            content_type: Some(content_type.to_string()),
+            size: None,
+            url: Some(url.to_string()),
+            width: None,
+            height: None,
+        };
+
+        let mut message = Message::new();
+        message.content = Some("look at this".to_string());
+        message.attachments = vec![
+            attachment("gchat.qpic.cn/a.png", "image/png"),
+            attachment("https://example.com/b.zip", "application/zip"),
+        ];
+
+        let params = MessageParams::forward_from(&message).unwrap();
+        assert_eq!(params.image.as_deref(), Some("https://gchat.qpic.cn/a.png"));
+        assert_eq!(
+            params.content.as_deref(),
+            Some("look at this\nhttps://example.com/b.zip")
+        );
+        assert!(params.msg_id.is_none());
+
+        let empty = Message::new();
+        assert!(matches!(
+            MessageParams::forward_from(&empty),
+            Err(crate::error::BotError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_interactive_handle_from_response() {
+        let api = std::sync::Arc::new(crate::api::BotApi::new(
+            crate::http::HttpClient::new(30, false).unwrap(),
+        ));
+        let token = crate::token::Token::new("app", "secret");
+
+        let response = crate::models::api::MessageResponse::new("msg1");
+        let handle =
+            InteractiveMessageHandle::from_response(api.clone(), token.clone(), "chan1", &response)
+                .unwrap();
+        assert_eq!(handle.message_id, "msg1");
+        assert_eq!(handle.channel_id, "chan1");
+
+        let mut missing = response.clone();
+        missing.id = None;
+        assert!(InteractiveMessageHandle::from_response(api, token, "chan1", &missing).is_err());
+    }
+
+    #[test]
+    fn test_message_user_avatar_url() {
+        let mut user = MessageUser::from_data(serde_json::json!({
+            "id": "u1",
+            "avatar": "https://thirdqq.qlogo.cn/0"
+        }));
+        assert_eq!(
+            user.avatar_url().as_deref(),
+            Some("https://thirdqq.qlogo.cn/0")
+        );
+
+        user.avatar = Some("abc123".to_string());
+        assert_eq!(
+            user.avatar_url().as_deref(),
+            Some("https://thirdqq.qlogo.cn/headimg_dl?dst_uin=u1&spec=640")
+        );
+
+        user.avatar = Some(String::new());
+        assert_eq!(user.avatar_url(), None);
+    }
+
+    #[test]
+    fn test_message_reactions() {
+        let data = serde_json::json!({
+            "id": "m1",
+            "reactions": [
+                {"emoji": {"id": "4", "type": 1}, "count": 3, "me": true},
+                {"emoji": {"id": "128077", "type": 2}, "count": 2}
+            ]
+        });
+
+        let message = Message::from_data(
+            crate::api::BotApi::new(crate::http::HttpClient::new(30, false).unwrap()),
+            "e1".to_string(),
+            data.clone(),
+        );
+        let reactions = message.reactions.as_ref().unwrap();
+        assert_eq!(reactions.len(), 2);
+        assert!(reactions[0].me);
+        assert!(!reactions[1].me);
+        assert_eq!(message.total_reaction_count(), 5);
+
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "mentions": [],
+            "attachments": []
+        }))
+        .unwrap();
+        assert_eq!(message.reactions, None);
+        assert_eq!(message.total_reaction_count(), 0);
+
+        let message: Message = serde_json::from_value(
+            serde_json::json!({"mentions": [], "attachments": [], "reactions": data["reactions"]}),
+        )
+        .unwrap();
+        assert_eq!(message.total_reaction_count(), 5);
+    }
+
+    #[test]
+    fn test_image_message_params() {
+        let params = MessageParams::new_image_from_url("https://example.com/a.png");
+        assert_eq!(params.image.as_deref(), Some("https://example.com/a.png"));
+        assert_eq!(params.file_image, None);
+        assert_eq!(params.content, None);
+        assert!(params.is_image_only());
+
+        let params = MessageParams::new_image_from_bytes(b"img");
+        assert_eq!(params.file_image.as_deref(), Some("aW1n"));
+        assert_eq!(params.image, None);
+        assert!(params.is_image_only());
+        assert!(params.clone().with_reply("m1").is_image_only());
+
+        let mut params = params;
+        params.content = Some("caption".to_string());
+        assert!(!params.is_image_only());
+        assert!(!MessageParams::new_text("hi").is_image_only());
+
+        let params = DirectMessageParams::new_image_from_url("https://example.com/a.png");
+        assert_eq!(params.image.as_deref(), Some("https://example.com/a.png"));
+        assert_eq!(params.file_image, None);
+        let params = DirectMessageParams::new_image_from_bytes(b"img");
+        assert_eq!(params.file_image.as_deref(), Some("aW1n"));
+        assert_eq!(params.image, None);
+        assert_eq!(params.content, None);
+
+        let params = GroupMessageParams::new_media(Media {
+            file_info: Some("info".to_string()),
+            ttl: None,
+        });
+        assert_eq!(params.msg_type, 7);
+        assert!(params.media.is_some());
+        assert_eq!(params.content, None);
+    }
+
+    #[test]
+    fn test_message_edited_at() {
+        let data = serde_json::json!({
+            "id": "m1",
+            "content": "edited",
+            "mentions": [],
+            "attachments": [],
+            "timestamp": "2024-01-01T00:00:00+08:00",
+            "edited_at": "2024-01-01T00:05:00+08:00"
+        });
+
+        let message: Message = serde_json::from_value(data.clone()).unwrap();
+        let edited_at = message.edited_at.unwrap();
+        assert!(edited_at > message.timestamp.unwrap());
+
+        let message = Message::from_data(
+            crate::api::BotApi::new(crate::http::HttpClient::new(30, false).unwrap()),
+            "e1".to_string(),
+            data,
+        );
+        assert_eq!(message.edited_at, Some(edited_at));
+        assert_eq!(Message::new().edited_at, None);
+    }
+
+    #[test]
+    fn test_message_audit_status() {
+        let status: MessageAuditStatus = serde_json::from_value(serde_json::json!({
+            "audit_id": "a1",
+            "message_id": null,
+            "status": 0,
+            "reason": null
+        }))
+        .unwrap();
+        assert!(status.is_pending());
+
+        let status: MessageAuditStatus = serde_json::from_value(serde_json::json!({
+            "audit_id": "a1",
+            "status": 2,
+            "reason": "spam"
+        }))
+        .unwrap();
+        assert_eq!(status.status, AuditStatus::Rejected);
+        assert_eq!(status.reason.as_deref(), Some("spam"));
+        assert_eq!(AuditStatus::from(9), AuditStatus::Unknown(9));
+    }
+
+    #[test]
+    fn test_message_with_context() {
+        let message = |id: &str, seq: u64| Message {
+            id: Some(id.to_string()),
+            seq: Some(seq),
+            ..Message::new()
+        };
+        let messages: Vec<_> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .zip(1..)
+            .map(|(id, seq)| message(id, seq))
+            .collect();
+        let ids = |messages: &[Message]| -> Vec<String> {
+            messages.iter().filter_map(|m| m.id.clone()).collect()
+        };
+
+        let around = MessagesAround::split(messages.clone(), "c");
+        let context = MessageWithContext::from_around(around, 1).unwrap();
+        assert_eq!(ids(&context.before), vec!["b"]);
+        assert_eq!(context.message.id.as_deref(), Some("c"));
+        assert_eq!(ids(&context.after), vec!["d"]);
+        assert_eq!(ids(&context.full_thread()), vec!["b", "c", "d"]);
+
+        let around = MessagesAround::split(messages.clone(), "b");
+        let context = MessageWithContext::from_around(around, 10).unwrap();
+        assert_eq!(
+            ids(&context.full_thread()),
+            vec!["a", "b", "c", "d", "e", "f"]
+        );
+
+        let missing = MessagesAround::split(messages, "z");
+        assert!(MessageWithContext::from_around(missing, 2).is_none());
+    }
+
+    #[test]
+    fn test_typing_delay() {
+        use std::time::Duration;
+
+        assert_eq!(TypingDelay::compute("", 60), Duration::ZERO);
+        // 60 wpm is 300 characters a minute, so 5 characters take a second.
+        assert_eq!(TypingDelay::compute("hello", 60), Duration::from_secs(1));
+        assert_eq!(
+            TypingDelay::compute("hello", 120),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            TypingDelay::compute("你好世界啊", 60),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            TypingDelay::compute(&"x".repeat(1000), 60),
+            TypingDelay::MAX
+        );
+        assert_eq!(TypingDelay::compute("hello", 0), TypingDelay::MAX);
+    }
+
+    #[test]
+    fn test_keyboard_button_row() {
+        let keyboard = KeyboardPayload::button_row(vec![
+            ButtonSpec::new("Yes", ButtonStyle::Success, "vote:yes"),
+            ButtonSpec::new("Docs", ButtonStyle::Link, "https://example.com").disabled(true),
+        ]);
+        assert_eq!(
+            keyboard.content,
+            serde_json::json!({
+                "rows": [{
+                    "buttons": [
+                        {
+                            "id": "0",
+                            "render_data": {"label": "Yes", "visited_label": "Yes", "style": 3},
+                            "action": {"type": 1, "permission": {"type": 2}, "data": "vote:yes"}
+                        },
+                        {
+                            "id": "1",
+                            "render_data": {"label": "Docs", "visited_label": "Docs", "style": 5},
+                            "action": {
+                                "type": 0,
+                                "permission": {"type": 0, "specify_user_ids": []},
+                                "data": "https://example.com"
+                            }
+                        }
+                    ]
+                }]
+            })
+        );
+
+        let typed: KeyboardContent = serde_json::from_value(keyboard.content).unwrap();
+        let buttons = typed.rows.unwrap()[0].buttons.clone().unwrap();
+        assert_eq!(buttons[1].action.as_ref().unwrap().action_type, Some(0));
+    }
+
+    #[test]
+    fn test_message_embedded_urls() {
+        let mut message = Message::new();
+        message.embeds = serde_json::from_value(serde_json::json!([
+            {
+                "title": "Docs",
+                "url": "https://example.com/docs",
+                "image": {"url": "https://example.com/cover.png"},
+                "provider": {"name": "Example"}
+            },
+            {"title": "No link", "color": 255}
+        ]))
+        .unwrap();
+        assert_eq!(message.embeds.len(), 2);
+        assert_eq!(Embed::from(message.embeds[1].clone()).color, Some(255));
+        let previews = message.embedded_urls();
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews[0].url, "https://example.com/docs");
+        assert_eq!(previews[0].title.as_deref(), Some("Docs"));
+        assert_eq!(
+            previews[0].image_url.as_deref(),
+            Some("https://example.com/cover.png")
+        );
+        assert_eq!(previews[0].site_name.as_deref(), Some("Example"));
+        assert!(previews[0].description.is_none());
+
+        let preview = MessageEmbed {
+            url: "https://example.com".to_string(),
+            title: Some("Example".to_string()),
+            description: None,
+            image_url: None,
+            site_name: None,
+            embed: None,
+        };
+        let embed = Embed::from(preview);
+        assert_eq!(embed.url.as_deref(), Some("https://example.com"));
+        assert_eq!(embed.title.as_deref(), Some("Example"));
+    }
+
+    #[test]
+    fn test_copy_params() {
+        let mut message = Message::new();
+        message.content = Some("meeting at 3".to_string());
+        message.author = Some(MessageUser {
+            id: Some("u1".to_string()),
+            username: Some("alice".to_string()),
+            bot: None,
+            avatar: None,
+        });
+        let params = MessageParams::copy_from(&message).unwrap();
+        assert_eq!(
+            params.content.as_deref(),
+            Some("> [Original by alice]\nmeeting at 3")
+        );
+
+        message.author.as_mut().unwrap().username = None;
+        message.content = None;
+        message.embeds = vec![Embed::default_description_only("notes").into()];
+        let params = MessageParams::copy_from(&message).unwrap();
+        assert_eq!(params.content.as_deref(), Some("> [Original by u1]"));
+        assert!(params.embed.is_some());
+
+        assert!(MessageParams::copy_from(&Message::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_message_user_fetch_member() {
+        use crate::http::mock::{MockResponse, MockServer};
+
+        let server = MockServer::start(|_| {
+            MockResponse::ok(serde_json::json!({
+                "user": {"id": "u1", "username": "alice"},
+                "nick": "Alice",
+                "roles": ["1"],
+                "joined_at": "2024-01-01T00:00:00Z"
+            }))
+        })
+        .await;
+        let user = MessageUser::from_data(serde_json::json!({"id": "u1"}));
+
+        let member = user
+            .fetch_member(&server.api(), &MockServer::token(), "g1")
+            .await
+            .unwrap();
+        assert_eq!(member.user.unwrap().id, "u1");
+        assert_eq!(member.nick.as_deref(), Some("Alice"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/guilds/g1/members/u1");
+    }
 }
 
 /// Ark template message structure.
@@ -1117,6 +1998,97 @@ pub struct Embed {
     pub fields: Option<Vec<EmbedField>>,
 }
 
+impl Embed {
+    /// Creates an embed with only a description.
+    pub fn default_description_only(description: &str) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Link preview data for a URL in a message.
+///
+/// On the wire this is an [`Embed`]; fields without a preview counterpart
+/// (colour, footer, fields, ...) are kept so the embed can be re-sent as is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Embed", into = "Embed")]
+pub struct MessageEmbed {
+    /// The previewed URL
+    pub url: String,
+    /// Page title
+    pub title: Option<String>,
+    /// Page description
+    pub description: Option<String>,
+    /// Preview image URL
+    pub image_url: Option<String>,
+    /// Name of the site
+    pub site_name: Option<String>,
+    /// The embed this preview was read from, whose other fields are sent
+    /// back unchanged
+    pub embed: Option<Embed>,
+}
+
+impl MessageEmbed {
+    /// Creates an empty preview for a URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            title: None,
+            description: None,
+            image_url: None,
+            site_name: None,
+            embed: None,
+        }
+    }
+}
+
+impl From<Embed> for MessageEmbed {
+    /// An embed without a URL gets an empty one.
+    fn from(embed: Embed) -> Self {
+        Self {
+            url: embed.url.clone().unwrap_or_default(),
+            title: embed.title.clone(),
+            description: embed.description.clone(),
+            image_url: embed.image.as_ref().and_then(|i| i.url.clone()),
+            site_name: embed.provider.as_ref().and_then(|p| p.name.clone()),
+            embed: Some(embed),
+        }
+    }
+}
+
+impl From<MessageEmbed> for Embed {
+    fn from(preview: MessageEmbed) -> Self {
+        let mut embed = preview.embed.unwrap_or_default();
+        embed.url = Some(preview.url).filter(|url| !url.is_empty());
+        embed.title = preview.title;
+        embed.description = preview.description;
+        match (embed.image.as_mut(), preview.image_url) {
+            (Some(image), url) => image.url = url,
+            (None, Some(url)) => {
+                embed.image = Some(EmbedImage {
+                    url: Some(url),
+                    width: None,
+                    height: None,
+                })
+            }
+            (None, None) => {}
+        }
+        match (embed.provider.as_mut(), preview.site_name) {
+            (Some(provider), name) => provider.name = name,
+            (None, Some(name)) => {
+                embed.provider = Some(EmbedProvider {
+                    name: Some(name),
+                    url: None,
+                })
+            }
+            (None, None) => {}
+        }
+        embed
+    }
+}
+
 /// Embed footer structure.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EmbedFooter {
@@ -1270,6 +2242,100 @@ pub struct KeyboardPayload {
     pub content: serde_json::Value,
 }
 
+impl KeyboardPayload {
+    /// Creates a keyboard with a single row of buttons.
+    ///
+    /// [`ButtonStyle::Link`] buttons open `custom_id` as a URL; the others
+    /// send it back in a button click interaction. Disabled buttons cannot be
+    /// clicked by anyone.
+    pub fn button_row(buttons: Vec<ButtonSpec>) -> Self {
+        let buttons: Vec<serde_json::Value> = buttons
+            .into_iter()
+            .enumerate()
+            .map(|(index, button)| button.to_json(index))
+            .collect();
+        Self {
+            content: serde_json::json!({ "rows": [{ "buttons": buttons }] }),
+        }
+    }
+}
+
+/// The look and behavior of a keyboard button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ButtonStyle {
+    /// Highlighted callback button
+    Primary = 1,
+    /// Plain callback button
+    Secondary = 2,
+    /// Callback button for confirming actions
+    Success = 3,
+    /// Callback button for destructive actions
+    Danger = 4,
+    /// Button that opens a URL
+    Link = 5,
+}
+
+/// A button in a [`KeyboardPayload::button_row`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonSpec {
+    /// Text shown on the button
+    pub label: String,
+    /// The button's style
+    pub style: ButtonStyle,
+    /// Data sent back when clicked, or the URL for link buttons
+    pub custom_id: String,
+    /// Whether the button can be clicked
+    pub disabled: bool,
+}
+
+impl ButtonSpec {
+    /// Creates an enabled button.
+    pub fn new(label: impl Into<String>, style: ButtonStyle, custom_id: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            style,
+            custom_id: custom_id.into(),
+            disabled: false,
+        }
+    }
+
+    /// Sets whether the button is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Builds the button's JSON representation.
+    fn to_json(&self, index: usize) -> serde_json::Value {
+        // Action types: 0 opens a link, 1 sends a callback.
+        let action_type = if self.style == ButtonStyle::Link {
+            0
+        } else {
+            1
+        };
+        // Permission types: 0 lets only the listed users click, 2 lets everyone click.
+        let permission = if self.disabled {
+            serde_json::json!({ "type": 0, "specify_user_ids": [] })
+        } else {
+            serde_json::json!({ "type": 2 })
+        };
+        serde_json::json!({
+            "id": index.to_string(),
+            "render_data": {
+                "label": self.label,
+                "visited_label": self.label,
+                "style": self.style as u8,
+            },
+            "action": {
+                "type": action_type,
+                "permission": permission,
+                "data": self.custom_id,
+            },
+        })
+    }
+}
+
 /// Markdown message payload.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct MarkdownPayload {
@@ -1310,6 +2376,63 @@ pub struct Reference {
     pub ignore_get_message_error: Option<bool>,
 }
 
+/// Handle to a sent channel message that can later be edited or recalled.
+///
+/// Bundles the sent message ID with the API client and token so callers do not
+/// need to track them separately.
+#[derive(Debug, Clone)]
+pub struct InteractiveMessageHandle {
+    /// The sent message's ID
+    pub message_id: Snowflake,
+    /// The channel the message was sent to
+    pub channel_id: Snowflake,
+    /// API client used for follow-up requests
+    pub api: std::sync::Arc<crate::api::BotApi>,
+    /// Authentication token used for follow-up requests
+    pub token: crate::token::Token,
+}
+
+impl InteractiveMessageHandle {
+    /// Creates a handle from the response to a sent message.
+    ///
+    /// Returns an error if the response does not contain a message ID.
+    pub fn from_response(
+        api: std::sync::Arc<crate::api::BotApi>,
+        token: crate::token::Token,
+        channel_id: impl Into<Snowflake>,
+        response: &crate::models::api::MessageResponse,
+    ) -> Result<Self, crate::error::BotError> {
+        let message_id = response.id.clone().ok_or_else(|| {
+            crate::error::BotError::invalid_data("Message response did not contain an ID")
+        })?;
+        Ok(Self {
+            message_id,
+            channel_id: channel_id.into(),
+            api,
+            token,
+        })
+    }
+
+    /// Replaces the message with new content.
+    pub async fn edit(&self, new_params: MessageParams) -> Result<(), crate::error::BotError> {
+        self.api
+            .edit_message(&self.token, &self.channel_id, &self.message_id, new_params)
+            .await
+    }
+
+    /// Recalls the message.
+    pub async fn delete(&self, hidetip: bool) -> Result<(), crate::error::BotError> {
+        self.api
+            .recall_message(
+                &self.token,
+                &self.channel_id,
+                &self.message_id,
+                Some(hidetip),
+            )
+            .await
+    }
+}
+
 /// Parameters for sending a message to a channel.
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct MessageParams {
@@ -1463,6 +2586,32 @@ impl MessageParams {
         }
     }
 
+    /// Creates a new MessageParams sending the image at a URL.
+    pub fn new_image_from_url(url: &str) -> Self {
+        Self {
+            image: Some(url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new MessageParams uploading image data, encoded to base64.
+    pub fn new_image_from_bytes(data: &[u8]) -> Self {
+        Self::default().with_file_image(data)
+    }
+
+    /// Returns true if the message carries an image and nothing else.
+    ///
+    /// Reply fields (`msg_id`, `event_id`) are ignored.
+    pub fn is_image_only(&self) -> bool {
+        (self.image.is_some() || self.file_image.is_some())
+            && self.content.is_none()
+            && self.embed.is_none()
+            && self.ark.is_none()
+            && self.message_reference.is_none()
+            && self.markdown.is_none()
+            && self.keyboard.is_none()
+    }
+
     /// Sets file image data, automatically encoding to base64.
     pub fn with_file_image(mut self, data: &[u8]) -> Self {
         self.file_image = Some(base64::engine::general_purpose::STANDARD.encode(data));
@@ -1474,6 +2623,80 @@ impl MessageParams {
         self.msg_id = Some(message_id.into());
         self
     }
+
+    /// Creates MessageParams that re-send an existing message.
+    ///
+    /// The content, first embed and ark are copied. The first image attachment is
+    /// sent as the message image, and any other attachments are referenced by URL
+    /// at the end of the content. Nothing is re-uploaded.
+    ///
+    /// Returns an error if the message has neither content nor media.
+    pub fn forward_from(message: &Message) -> Result<Self, crate::error::BotError> {
+        let attachment_url = |a: &MessageAttachment| {
+            a.url.as_ref().map(|url| {
+                if url.starts_with("http://") || url.starts_with("https://") {
+                    url.clone()
+                } else {
+                    format!("https://{url}")
+                }
+            })
+        };
+
+        let image_index = message.attachments.iter().position(|a| a.is_image());
+        let image = image_index.and_then(|i| attachment_url(&message.attachments[i]));
+        let extra_urls: Vec<String> = message
+            .attachments
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != image_index)
+            .filter_map(|(_, a)| attachment_url(a))
+            .collect();
+
+        let mut content = message.content.clone().filter(|c| !c.is_empty());
+        if !extra_urls.is_empty() {
+            let urls = extra_urls.join("\n");
+            content = Some(match content {
+                Some(text) => format!("{text}\n{urls}"),
+                None => urls,
+            });
+        }
+
+        let params = Self {
+            content,
+            embed: message.embeds.first().cloned().map(Embed::from),
+            ark: message.ark.clone(),
+            image,
+            ..Default::default()
+        };
+        if params.content.is_none()
+            && params.embed.is_none()
+            && params.ark.is_none()
+            && params.image.is_none()
+        {
+            return Err(crate::error::BotError::invalid_params("nothing to forward"));
+        }
+        Ok(params)
+    }
+
+    /// Creates MessageParams that copy an existing message, crediting its author.
+    ///
+    /// Like [`forward_from`](Self::forward_from), with a
+    /// `> [Original by {author}]` line before the content. The author is the
+    /// username, or the user ID if there is none.
+    pub fn copy_from(message: &Message) -> Result<Self, crate::error::BotError> {
+        let mut params = Self::forward_from(message)?;
+        let author = message
+            .author
+            .as_ref()
+            .and_then(|author| author.username.as_ref().or(author.id.as_ref()))
+            .map_or("unknown", String::as_str);
+        let header = format!("> [Original by {author}]");
+        params.content = Some(match params.content {
+            Some(content) => format!("{header}\n{content}"),
+            None => header,
+        });
+        Ok(params)
+    }
 }
 
 impl GroupMessageParams {
@@ -1486,6 +2709,19 @@ impl GroupMessageParams {
         }
     }
 
+    /// Creates a new media message (`msg_type = 7`).
+    ///
+    /// Group messages cannot reference an image URL or carry image data
+    /// directly; upload the image with `BotApi::post_group_file` first and
+    /// pass the returned media here.
+    pub fn new_media(media: Media) -> Self {
+        Self {
+            msg_type: 7,
+            media: Some(media),
+            ..Default::default()
+        }
+    }
+
     /// Sets the message reference for replying.
     pub fn with_reply(mut self, message_id: impl Into<String>) -> Self {
         self.msg_id = Some(message_id.into());
@@ -1519,6 +2755,19 @@ impl DirectMessageParams {
         }
     }
 
+    /// Creates a new DirectMessageParams sending the image at a URL.
+    pub fn new_image_from_url(url: &str) -> Self {
+        Self {
+            image: Some(url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new DirectMessageParams uploading image data, encoded to base64.
+    pub fn new_image_from_bytes(data: &[u8]) -> Self {
+        Self::default().with_file_image(data)
+    }
+
     /// Sets file image data, automatically encoding to base64.
     pub fn with_file_image(mut self, data: &[u8]) -> Self {
         self.file_image = Some(base64::engine::general_purpose::STANDARD.encode(data));
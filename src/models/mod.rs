@@ -5,31 +5,45 @@
 
 pub mod announce;
 pub mod api;
+pub mod audit_log;
 pub mod channel;
+pub mod command;
 pub mod emoji;
 pub mod gateway;
 pub mod guild;
 pub mod message;
 pub mod permission;
+pub mod poll;
 pub mod robot;
 pub mod schedule;
 pub mod user;
+pub mod webhook;
 
 // Re-export commonly used types
 pub use announce::*;
 pub use api::*;
+pub use audit_log::*;
 pub use channel::*;
+pub use command::*;
 pub use emoji::*;
 pub use gateway::*;
 // Guild types are already exported by the specific re-exports below
 pub use message::*;
 pub use permission::*;
+pub use poll::*;
 pub use robot::*;
 pub use schedule::*;
 pub use user::*;
+pub use webhook::*;
 
 // Re-export specific types for convenience
-pub use guild::{Guild, Member, Role};
+pub use guild::{
+    BoostAction, BoostEvent, Guild, GuildAdminLevel, GuildBannedWords, GuildBotInfo,
+    GuildChangeEvent, GuildChangeKind, GuildDiscoveryMetadata, GuildDiscoveryUpdate,
+    GuildPremiumStatus, GuildRoleSyncResult, GuildStats, GuildTemplate, Member, MemberActivity,
+    MemberField, OnlineMembersPage, PremiumPerk, Role, RoleMemberCountBreakdown, RoleSpec,
+    RoleSyncResult, VanityUrl, VoiceRegion,
+};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -3,7 +3,7 @@
 //! This module contains structures for managing API permissions and permission demands
 //! in QQ Guild bots.
 
-use crate::models::{HasId, Snowflake};
+use crate::models::{HasId, Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Represents an API permission for a bot in a guild.
@@ -187,6 +187,389 @@ impl std::fmt::Display for APIPermissionDemand {
     }
 }
 
+/// A set of channel permissions, sent to the API as a decimal bit string.
+///
+/// # Examples
+///
+/// ```rust
+/// use botrs::models::permission::Permissions;
+///
+/// let perms = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+/// assert!(perms.contains(Permissions::VIEW_CHANNEL));
+/// assert_eq!(perms.to_string(), "5");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Permissions {
+    /// The raw permission bits
+    pub bits: u64,
+}
+
+impl Permissions {
+    /// No permissions.
+    pub const NONE: Self = Self::from_bits(0);
+    /// View the channel.
+    pub const VIEW_CHANNEL: Self = Self::from_bits(1 << 0);
+    /// Manage the channel.
+    pub const MANAGE_CHANNEL: Self = Self::from_bits(1 << 1);
+    /// Send messages in the channel.
+    pub const SEND_MESSAGES: Self = Self::from_bits(1 << 2);
+    /// Start a live stream in the channel.
+    pub const LIVE: Self = Self::from_bits(1 << 3);
+    /// Every known permission.
+    pub const ALL: Self = Self::from_bits(
+        Self::VIEW_CHANNEL.bits
+            | Self::MANAGE_CHANNEL.bits
+            | Self::SEND_MESSAGES.bits
+            | Self::LIVE.bits,
+    );
+
+    /// Creates a permission set from raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self { bits }
+    }
+
+    /// Returns true if every permission in `other` is set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Returns true if no permissions are set.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+}
+
+impl std::fmt::Display for Permissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.bits)
+    }
+}
+
+impl std::str::FromStr for Permissions {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_bits(s.parse()?))
+    }
+}
+
+impl From<Permissions> for String {
+    fn from(permissions: Permissions) -> Self {
+        permissions.to_string()
+    }
+}
+
+impl TryFrom<String> for Permissions {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::ops::BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits | rhs.bits)
+    }
+}
+
+impl std::ops::BitOrAssign for Permissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.bits |= rhs.bits;
+    }
+}
+
+impl std::ops::BitAnd for Permissions {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::from_bits(self.bits & rhs.bits)
+    }
+}
+
+impl std::ops::Not for Permissions {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self::from_bits(!self.bits)
+    }
+}
+
+/// The channel permissions of every role in a guild at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPermissionSnapshot {
+    /// The guild ID
+    pub guild_id: Snowflake,
+    /// Permissions for each channel
+    pub channels: Vec<ChannelPermissionEntry>,
+    /// When the snapshot was taken
+    pub captured_at: Timestamp,
+}
+
+impl ChannelPermissionSnapshot {
+    /// Compares this snapshot with a later one.
+    ///
+    /// Roles, users and channels missing from one side are treated as
+    /// having no permissions there. The diff spans every channel, so its
+    /// `channel_id` is empty and each change carries its own channel.
+    pub fn diff(&self, other: &Self) -> PermissionDiff {
+        let empty = ChannelPermissionEntry::default();
+        let mut channel_ids: Vec<&str> = Vec::new();
+        for entry in self.channels.iter().chain(&other.channels) {
+            if !channel_ids.contains(&entry.channel_id.as_str()) {
+                channel_ids.push(&entry.channel_id);
+            }
+        }
+
+        let mut diff = PermissionDiff::default();
+        for channel_id in channel_ids {
+            let before = self.channel(channel_id).unwrap_or(&empty);
+            let after = other.channel(channel_id).unwrap_or(&empty);
+            let channel_diff = PermissionDiff::between(channel_id, before, after);
+            diff.changed_roles.extend(channel_diff.changed_roles);
+            diff.changed_users.extend(channel_diff.changed_users);
+        }
+        diff
+    }
+
+    /// Gets the entry for a channel.
+    pub fn channel(&self, channel_id: &str) -> Option<&ChannelPermissionEntry> {
+        self.channels
+            .iter()
+            .find(|entry| entry.channel_id == channel_id)
+    }
+}
+
+/// The user and role permissions of a single channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPermissionEntry {
+    /// The channel ID
+    pub channel_id: Snowflake,
+    /// Permissions granted to individual users
+    pub user_permissions: Vec<(Snowflake, Permissions)>,
+    /// Permissions granted to roles
+    pub role_permissions: Vec<(Snowflake, Permissions)>,
+}
+
+/// A change to a role's permissions in a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolePermissionChange {
+    /// The channel the change applies to
+    pub channel_id: Snowflake,
+    /// The role ID
+    pub role_id: Snowflake,
+    /// Permissions before the change
+    pub before: Permissions,
+    /// Permissions after the change
+    pub after: Permissions,
+}
+
+impl RolePermissionChange {
+    /// Returns the permissions that were granted.
+    pub fn added(&self) -> Permissions {
+        self.after & !self.before
+    }
+
+    /// Returns the permissions that were revoked.
+    pub fn removed(&self) -> Permissions {
+        self.before & !self.after
+    }
+}
+
+/// A change to a user's permissions in a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPermissionChange {
+    /// The channel the change applies to
+    pub channel_id: Snowflake,
+    /// The user ID
+    pub user_id: Snowflake,
+    /// Permissions before the change
+    pub before: Permissions,
+    /// Permissions after the change
+    pub after: Permissions,
+}
+
+impl UserPermissionChange {
+    /// Returns the permissions that were granted.
+    pub fn added(&self) -> Permissions {
+        self.after & !self.before
+    }
+
+    /// Returns the permissions that were revoked.
+    pub fn removed(&self) -> Permissions {
+        self.before & !self.after
+    }
+}
+
+/// The permission changes in one or more channels.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionDiff {
+    /// The channel ID, or empty if the diff spans several channels
+    pub channel_id: Snowflake,
+    /// Roles whose permissions changed, with the channel of each change
+    pub changed_roles: Vec<RolePermissionChange>,
+    /// Users whose permissions changed, with the channel of each change
+    pub changed_users: Vec<UserPermissionChange>,
+}
+
+impl PermissionDiff {
+    /// Compares two sets of channel permissions.
+    ///
+    /// Every change is attributed to `channel_id`.
+    pub fn between(
+        channel_id: impl Into<String>,
+        before: &ChannelPermissionEntry,
+        after: &ChannelPermissionEntry,
+    ) -> Self {
+        let channel_id = channel_id.into();
+        Self {
+            changed_roles: diff_permission_lists(&before.role_permissions, &after.role_permissions)
+                .into_iter()
+                .map(|(role_id, before, after)| RolePermissionChange {
+                    channel_id: channel_id.clone(),
+                    role_id,
+                    before,
+                    after,
+                })
+                .collect(),
+            changed_users: diff_permission_lists(&before.user_permissions, &after.user_permissions)
+                .into_iter()
+                .map(|(user_id, before, after)| UserPermissionChange {
+                    channel_id: channel_id.clone(),
+                    user_id,
+                    before,
+                    after,
+                })
+                .collect(),
+            channel_id,
+        }
+    }
+
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed_roles.is_empty() && self.changed_users.is_empty()
+    }
+}
+
+/// Who a channel permission change applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PermissionTarget {
+    /// A single user
+    User(Snowflake),
+    /// A guild role
+    Role(Snowflake),
+}
+
+/// A single permission change in a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionEntry {
+    /// The user or role to update
+    pub target: PermissionTarget,
+    /// Permissions to grant
+    pub add: Permissions,
+    /// Permissions to revoke
+    pub remove: Permissions,
+}
+
+impl PermissionEntry {
+    /// Creates an entry updating a user's permissions.
+    pub fn user(user_id: impl Into<String>, add: Permissions, remove: Permissions) -> Self {
+        Self {
+            target: PermissionTarget::User(user_id.into()),
+            add,
+            remove,
+        }
+    }
+
+    /// Creates an entry updating a role's permissions.
+    pub fn role(role_id: impl Into<String>, add: Permissions, remove: Permissions) -> Self {
+        Self {
+            target: PermissionTarget::Role(role_id.into()),
+            add,
+            remove,
+        }
+    }
+}
+
+/// The permission changes to apply to one channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPermissionBatch {
+    /// The channel ID
+    pub channel_id: Snowflake,
+    /// Changes to apply in the channel
+    pub entries: Vec<PermissionEntry>,
+}
+
+/// The outcome of a bulk channel permission update.
+#[derive(Debug)]
+pub struct PermissionBatchReport {
+    /// Per-entry results for each channel, in batch order
+    pub channels: Vec<(Snowflake, Vec<crate::error::Result<()>>)>,
+}
+
+impl PermissionBatchReport {
+    /// Creates a report from per-channel results.
+    pub fn new(channels: Vec<(Snowflake, Vec<crate::error::Result<()>>)>) -> Self {
+        Self { channels }
+    }
+
+    /// Returns the fraction of entries that succeeded.
+    ///
+    /// A report with no entries counts as fully successful.
+    pub fn success_rate(&self) -> f32 {
+        let results = self.channels.iter().flat_map(|(_, results)| results);
+        let (total, succeeded) = results.fold((0usize, 0usize), |(total, ok), result| {
+            (total + 1, ok + usize::from(result.is_ok()))
+        });
+        if total == 0 {
+            1.0
+        } else {
+            succeeded as f32 / total as f32
+        }
+    }
+
+    /// Returns the IDs of channels where at least one entry failed.
+    pub fn failed_channels(&self) -> Vec<&str> {
+        self.channels
+            .iter()
+            .filter(|(_, results)| results.iter().any(|result| result.is_err()))
+            .map(|(channel_id, _)| channel_id.as_str())
+            .collect()
+    }
+}
+
+impl From<Vec<(Snowflake, Vec<crate::error::Result<()>>)>> for PermissionBatchReport {
+    fn from(channels: Vec<(Snowflake, Vec<crate::error::Result<()>>)>) -> Self {
+        Self::new(channels)
+    }
+}
+
+/// Pairs up IDs across two permission lists, keeping those that differ.
+fn diff_permission_lists(
+    before: &[(Snowflake, Permissions)],
+    after: &[(Snowflake, Permissions)],
+) -> Vec<(Snowflake, Permissions, Permissions)> {
+    let lookup = |list: &[(Snowflake, Permissions)], id: &str| {
+        list.iter()
+            .find(|(other, _)| other == id)
+            .map_or(Permissions::NONE, |&(_, permissions)| permissions)
+    };
+    let mut changes = Vec::new();
+    for (id, _) in before.iter().chain(after) {
+        if changes.iter().any(|(seen, _, _)| seen == id) {
+            continue;
+        }
+        let (old, new) = (lookup(before, id), lookup(after, id));
+        if old != new {
+            changes.push((id.clone(), old, new));
+        }
+    }
+    changes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,4 +683,113 @@ mod tests {
         // Should be truncated to 50 characters
         assert!(display.len() < 200);
     }
+
+    #[test]
+    fn test_permissions() {
+        let perms = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+        assert!(perms.contains(Permissions::VIEW_CHANNEL));
+        assert!(!perms.contains(Permissions::MANAGE_CHANNEL));
+        assert_eq!(
+            perms & !Permissions::VIEW_CHANNEL,
+            Permissions::SEND_MESSAGES
+        );
+        assert!(Permissions::NONE.is_empty());
+
+        assert_eq!(serde_json::to_value(perms).unwrap(), serde_json::json!("5"));
+        let parsed: Permissions = serde_json::from_value(serde_json::json!("9")).unwrap();
+        assert_eq!(parsed, Permissions::VIEW_CHANNEL | Permissions::LIVE);
+        assert!("abc".parse::<Permissions>().is_err());
+    }
+
+    #[test]
+    fn test_channel_permission_snapshot_diff() {
+        let entry = |channel_id: &str, roles: &[(&str, Permissions)]| ChannelPermissionEntry {
+            channel_id: channel_id.to_string(),
+            user_permissions: Vec::new(),
+            role_permissions: roles
+                .iter()
+                .map(|&(id, permissions)| (id.to_string(), permissions))
+                .collect(),
+        };
+        let snapshot = |channels| ChannelPermissionSnapshot {
+            guild_id: "g1".to_string(),
+            channels,
+            captured_at: chrono::Utc::now(),
+        };
+        let view = Permissions::VIEW_CHANNEL;
+        let send = Permissions::SEND_MESSAGES;
+
+        let before = snapshot(vec![
+            entry("c1", &[("r1", view | send), ("r2", view)]),
+            entry("c2", &[("r1", view)]),
+        ]);
+        let mut after = snapshot(vec![
+            entry("c1", &[("r1", view), ("r2", view), ("r3", send)]),
+            entry("c2", &[("r1", view)]),
+            entry("c3", &[("r2", view)]),
+        ]);
+        after.channels[1].user_permissions = vec![("u1".to_string(), send)];
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.changed_roles.len(), 3);
+        assert_eq!(diff.changed_roles[0].channel_id, "c1");
+        assert_eq!(diff.changed_roles[0].role_id, "r1");
+        assert_eq!(diff.changed_roles[0].removed(), send);
+        assert!(diff.changed_roles[0].added().is_empty());
+        assert_eq!(diff.changed_roles[1].channel_id, "c1");
+        assert_eq!(diff.changed_roles[1].role_id, "r3");
+        assert_eq!(diff.changed_roles[1].added(), send);
+        assert_eq!(diff.changed_roles[2].channel_id, "c3");
+        assert_eq!(diff.changed_roles[2].added(), view);
+        assert!(diff.channel_id.is_empty());
+
+        assert_eq!(diff.changed_users.len(), 1);
+        assert_eq!(diff.changed_users[0].channel_id, "c2");
+        assert_eq!(diff.changed_users[0].user_id, "u1");
+        assert_eq!(diff.changed_users[0].added(), send);
+
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_permission_batch_report() {
+        let failure = || Err(crate::error::BotError::api(500, "internal error"));
+        let report = PermissionBatchReport::new(vec![
+            ("c1".to_string(), vec![Ok(()), Ok(())]),
+            ("c2".to_string(), vec![Ok(()), failure()]),
+            ("c3".to_string(), vec![failure()]),
+            ("c4".to_string(), vec![]),
+        ]);
+        assert_eq!(report.success_rate(), 0.6);
+        assert_eq!(report.failed_channels(), vec!["c2", "c3"]);
+
+        let empty = PermissionBatchReport::new(Vec::new());
+        assert_eq!(empty.success_rate(), 1.0);
+        assert!(empty.failed_channels().is_empty());
+    }
+
+    #[test]
+    fn test_permission_diff_between_channels() {
+        let view = Permissions::VIEW_CHANNEL;
+        let send = Permissions::SEND_MESSAGES;
+        let entry = |channel_id: &str, moderator: Permissions| ChannelPermissionEntry {
+            channel_id: channel_id.to_string(),
+            user_permissions: Vec::new(),
+            role_permissions: vec![
+                ("everyone".to_string(), view),
+                ("moderator".to_string(), moderator),
+            ],
+        };
+
+        let diff = PermissionDiff::between("c2", &entry("c1", view | send), &entry("c2", view));
+        assert!(!diff.is_empty());
+        assert_eq!(diff.channel_id, "c2");
+        assert_eq!(diff.changed_roles.len(), 1);
+        assert_eq!(diff.changed_roles[0].channel_id, "c2");
+        assert_eq!(diff.changed_roles[0].role_id, "moderator");
+        assert_eq!(diff.changed_roles[0].removed(), send);
+
+        let same = PermissionDiff::between("c2", &entry("c1", view), &entry("c2", view));
+        assert!(same.is_empty());
+    }
 }
@@ -0,0 +1,210 @@
+//! Poll-related data structures for the QQ Guild Bot API.
+//!
+//! This module contains the poll sent in a channel message and the vote
+//! counts returned for it.
+
+use crate::error::{BotError, Result};
+use crate::models::{Emoji, Snowflake, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Minimum number of options in a poll.
+pub const MIN_POLL_OPTIONS: usize = 2;
+
+/// Maximum number of options in a poll.
+pub const MAX_POLL_OPTIONS: usize = 10;
+
+/// Longest a poll can run, in hours.
+pub const MAX_POLL_DURATION_HOURS: u32 = 7 * 24;
+
+/// A poll attached to a channel message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Poll {
+    /// The question being asked
+    pub question: String,
+    /// The answers to choose from
+    pub options: Vec<PollOption>,
+    /// How long the poll stays open, in hours
+    pub duration_hours: u32,
+    /// Whether voters may pick more than one option
+    #[serde(default)]
+    pub allow_multiselect: bool,
+}
+
+impl Poll {
+    /// Creates a poll with no options.
+    ///
+    /// # Arguments
+    ///
+    /// * `question` - The question being asked
+    /// * `duration_hours` - How long the poll stays open
+    pub fn new(question: impl Into<String>, duration_hours: u32) -> Self {
+        Self {
+            question: question.into(),
+            options: Vec::new(),
+            duration_hours,
+            allow_multiselect: false,
+        }
+    }
+
+    /// Adds an option, numbered after the existing ones starting from 1.
+    pub fn option(self, text: impl Into<String>) -> Self {
+        self.push_option(text.into(), None)
+    }
+
+    /// Adds an option shown with an emoji.
+    pub fn option_with_emoji(self, text: impl Into<String>, emoji: Emoji) -> Self {
+        self.push_option(text.into(), Some(emoji))
+    }
+
+    /// Sets whether voters may pick more than one option.
+    pub fn multiselect(mut self, allow_multiselect: bool) -> Self {
+        self.allow_multiselect = allow_multiselect;
+        self
+    }
+
+    fn push_option(mut self, text: String, emoji: Option<Emoji>) -> Self {
+        let id = self.options.len() as u32 + 1;
+        self.options.push(PollOption { id, text, emoji });
+        self
+    }
+
+    /// Checks the question, option count and duration.
+    pub fn validate(&self) -> Result<()> {
+        if self.question.trim().is_empty() {
+            return Err(BotError::invalid_params("Poll question is empty"));
+        }
+        if !(MIN_POLL_OPTIONS..=MAX_POLL_OPTIONS).contains(&self.options.len()) {
+            return Err(BotError::invalid_params(format!(
+                "Poll must have {MIN_POLL_OPTIONS} to {MAX_POLL_OPTIONS} options, got {}",
+                self.options.len()
+            )));
+        }
+        if !(1..=MAX_POLL_DURATION_HOURS).contains(&self.duration_hours) {
+            return Err(BotError::invalid_params(format!(
+                "Poll duration must be 1 to {MAX_POLL_DURATION_HOURS} hours, got {}",
+                self.duration_hours
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One answer in a [`Poll`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollOption {
+    /// The option's number within the poll
+    pub id: u32,
+    /// The answer text
+    pub text: String,
+    /// Emoji shown next to the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<Emoji>,
+}
+
+/// Vote counts for a poll.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollResult {
+    /// The poll ID
+    pub poll_id: Snowflake,
+    /// `(option ID, vote count)` for each option
+    #[serde(default)]
+    pub votes: Vec<(u32, u32)>,
+    /// Whether the poll has closed
+    #[serde(default)]
+    pub ended: bool,
+    /// When the counts became final, once the poll has closed
+    pub finalized_at: Option<Timestamp>,
+}
+
+impl PollResult {
+    /// Gets the number of votes for an option.
+    pub fn votes_for(&self, option_id: u32) -> u32 {
+        self.votes
+            .iter()
+            .find(|(id, _)| *id == option_id)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Gets the total number of votes cast.
+    pub fn total_votes(&self) -> u32 {
+        self.votes.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Gets the IDs of the options with the most votes.
+    ///
+    /// Empty if no votes were cast.
+    pub fn leading_options(&self) -> Vec<u32> {
+        let max = self.votes.iter().map(|(_, count)| *count).max();
+        match max {
+            Some(max) if max > 0 => self
+                .votes
+                .iter()
+                .filter(|(_, count)| *count == max)
+                .map(|(id, _)| *id)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_serialization() {
+        let poll = Poll::new("Next raid night?", 24)
+            .option("Friday")
+            .option("Saturday")
+            .multiselect(true);
+        assert!(poll.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(&poll).unwrap(),
+            serde_json::json!({
+                "question": "Next raid night?",
+                "options": [
+                    {"id": 1, "text": "Friday"},
+                    {"id": 2, "text": "Saturday"}
+                ],
+                "duration_hours": 24,
+                "allow_multiselect": true
+            })
+        );
+
+        assert!(Poll::new("Only one?", 24).option("Yes").validate().is_err());
+        let poll = Poll::new("Too long?", MAX_POLL_DURATION_HOURS + 1)
+            .option("Yes")
+            .option("No");
+        assert!(poll.validate().is_err());
+        assert!(
+            Poll::new(" ", 1)
+                .option("a")
+                .option("b")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_poll_result_parsing() {
+        let result: PollResult = serde_json::from_value(serde_json::json!({
+            "poll_id": "p1",
+            "votes": [[1, 4], [2, 7], [3, 7]],
+            "ended": true,
+            "finalized_at": "2024-03-01T12:00:00Z"
+        }))
+        .unwrap();
+        assert!(result.ended);
+        assert!(result.finalized_at.is_some());
+        assert_eq!(result.votes_for(2), 7);
+        assert_eq!(result.votes_for(9), 0);
+        assert_eq!(result.total_votes(), 18);
+        assert_eq!(result.leading_options(), vec![2, 3]);
+
+        let open: PollResult =
+            serde_json::from_value(serde_json::json!({"poll_id": "p2", "finalized_at": null}))
+                .unwrap();
+        assert!(!open.ended);
+        assert!(open.leading_options().is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module contains structures for creating and managing channel schedules
 //! in QQ Guild bots.
 
-use crate::models::{HasId, HasName, Snowflake};
+use crate::models::{Channel, HasId, HasName, Member, Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Reminder types for schedule events.
@@ -256,6 +256,387 @@ impl Schedule {
     }
 }
 
+/// A schedule that hasn't started yet, with the channel it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpcomingSchedule {
+    /// The schedule
+    pub schedule: Schedule,
+    /// The schedule channel it was created in
+    pub channel: Channel,
+}
+
+impl UpcomingSchedule {
+    /// Collects the schedules starting after `now` from each channel.
+    ///
+    /// Schedules whose start time can't be parsed are skipped. The result is
+    /// sorted by start time, soonest first.
+    pub fn collect(
+        channels: Vec<(Channel, Vec<Schedule>)>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<Self> {
+        let mut upcoming: Vec<Self> = channels
+            .into_iter()
+            .flat_map(|(channel, schedules)| {
+                schedules.into_iter().map(move |schedule| Self {
+                    schedule,
+                    channel: channel.clone(),
+                })
+            })
+            .filter(|upcoming| upcoming.starts_at().is_some_and(|start| start > now))
+            .collect();
+        upcoming.sort_by_key(|upcoming| upcoming.starts_at());
+        upcoming
+    }
+
+    /// Returns how long until the schedule starts, or zero if it already has.
+    pub fn time_until_start(&self) -> std::time::Duration {
+        self.starts_at()
+            .and_then(|start| (start - chrono::Utc::now()).to_std().ok())
+            .unwrap_or_default()
+    }
+
+    fn starts_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let start = self.schedule.start_timestamp_parsed().ok()?;
+        chrono::DateTime::from_timestamp(start, 0)
+    }
+}
+
+/// Parameters for creating a schedule.
+///
+/// Serializes to the `schedule` object of the create schedule request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduleBuilder {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    start_timestamp: String,
+    end_timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jump_channel_id: Option<Snowflake>,
+    #[serde(rename = "reminder_id")]
+    remind_type: RemindType,
+}
+
+impl ScheduleBuilder {
+    /// Creates a builder for a schedule without a reminder.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the schedule event
+    /// * `start_timestamp` - Start time as Unix timestamp string
+    /// * `end_timestamp` - End time as Unix timestamp string
+    pub fn new(
+        name: impl Into<String>,
+        start_timestamp: impl Into<String>,
+        end_timestamp: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            start_timestamp: start_timestamp.into(),
+            end_timestamp: end_timestamp.into(),
+            jump_channel_id: None,
+            remind_type: RemindType::None,
+        }
+    }
+
+    /// Sets the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the channel to jump to when the event starts.
+    pub fn jump_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.jump_channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Sets the reminder type.
+    pub fn remind_type(mut self, remind_type: RemindType) -> Self {
+        self.remind_type = remind_type;
+        self
+    }
+
+    /// Gets the schedule name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the start timestamp.
+    pub fn start_timestamp(&self) -> &str {
+        &self.start_timestamp
+    }
+
+    /// Checks that the schedule can be created.
+    ///
+    /// The name must not be blank, both timestamps must be Unix timestamps
+    /// and the end must come after the start.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("schedule name is empty".to_string());
+        }
+        let parse = |field: &str, value: &str| {
+            value
+                .parse::<i64>()
+                .map_err(|_| format!("{field} {value:?} is not a Unix timestamp"))
+        };
+        let start = parse("start timestamp", &self.start_timestamp)?;
+        let end = parse("end timestamp", &self.end_timestamp)?;
+        if end <= start {
+            return Err("schedule must end after it starts".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A cover image for a schedule event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleImage {
+    /// The raw image bytes
+    pub data: Vec<u8>,
+    /// The image MIME type, e.g. `"image/png"`
+    pub content_type: String,
+}
+
+impl ScheduleImage {
+    /// Largest accepted image, in bytes.
+    pub const MAX_SIZE: usize = 10 * 1024 * 1024;
+
+    /// Creates a cover image from raw bytes.
+    pub fn new(data: Vec<u8>, content_type: impl Into<String>) -> Self {
+        Self {
+            data,
+            content_type: content_type.into(),
+        }
+    }
+
+    /// Reads a cover image from a file.
+    ///
+    /// The content type is inferred from the file extension, which must be
+    /// one of `png`, `jpg`, `jpeg`, `gif` or `webp`.
+    pub async fn from_file(path: &std::path::Path) -> crate::error::Result<Self> {
+        let content_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::content_type_for)
+            .ok_or_else(|| {
+                crate::error::BotError::invalid_params(format!(
+                    "unsupported image file type: {}",
+                    path.display()
+                ))
+            })?;
+        let data = tokio::fs::read(path).await?;
+        Ok(Self::new(data, content_type))
+    }
+
+    /// Checks the image isn't empty or larger than [`ScheduleImage::MAX_SIZE`].
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.data.is_empty() {
+            return Err(crate::error::BotError::invalid_params(
+                "cover image is empty",
+            ));
+        }
+        if self.data.len() > Self::MAX_SIZE {
+            return Err(crate::error::BotError::invalid_params(format!(
+                "cover image is {} bytes, more than the {} byte limit",
+                self.data.len(),
+                Self::MAX_SIZE
+            )));
+        }
+        Ok(())
+    }
+
+    fn content_type_for(extension: &str) -> Option<&'static str> {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" => Some("image/png"),
+            "jpg" | "jpeg" => Some("image/jpeg"),
+            "gif" => Some("image/gif"),
+            "webp" => Some("image/webp"),
+            _ => None,
+        }
+    }
+}
+
+/// Why a schedule in a bulk import was rejected before any request was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleBuildError {
+    /// Position of the schedule in the import
+    pub index: usize,
+    /// Name of the schedule
+    pub name: String,
+    /// What is wrong with it
+    pub reason: String,
+}
+
+impl std::fmt::Display for ScheduleBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "schedule #{} ({:?}): {}",
+            self.index, self.name, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ScheduleBuildError {}
+
+/// Validates every schedule, collecting all problems.
+pub fn validate_schedules(schedules: &[ScheduleBuilder]) -> Result<(), Vec<ScheduleBuildError>> {
+    let errors: Vec<_> = schedules
+        .iter()
+        .enumerate()
+        .filter_map(|(index, schedule)| {
+            schedule.validate().err().map(|reason| ScheduleBuildError {
+                index,
+                name: schedule.name.clone(),
+                reason,
+            })
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The outcome of a bulk schedule import.
+#[derive(Debug, Default)]
+pub struct ScheduleBulkReport {
+    /// Schedules that were created
+    pub created: Vec<Schedule>,
+    /// Schedules that failed, with the error
+    pub failed: Vec<(ScheduleBuilder, crate::error::BotError)>,
+}
+
+impl ScheduleBulkReport {
+    /// Pairs each schedule with its creation result.
+    pub fn new(
+        schedules: Vec<ScheduleBuilder>,
+        results: Vec<crate::error::Result<Schedule>>,
+    ) -> Self {
+        let mut report = Self::default();
+        for (builder, result) in schedules.into_iter().zip(results) {
+            match result {
+                Ok(schedule) => report.created.push(schedule),
+                Err(e) => report.failed.push((builder, e)),
+            }
+        }
+        report
+    }
+
+    /// Returns true if every schedule was created.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Who receives a direct message reminder for a schedule, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DmReminderConfig {
+    /// Guild the direct message sessions are opened from
+    pub source_guild_id: Snowflake,
+    /// Users to remind
+    pub recipients: Vec<Snowflake>,
+    /// How many minutes before the start to send the reminder
+    pub minutes_before: u32,
+    /// Reminder text, or a default naming the schedule when `None`
+    pub content: Option<String>,
+}
+
+impl DmReminderConfig {
+    /// Creates a reminder config with the default reminder text.
+    pub fn new(
+        source_guild_id: impl Into<String>,
+        recipients: Vec<Snowflake>,
+        minutes_before: u32,
+    ) -> Self {
+        Self {
+            source_guild_id: source_guild_id.into(),
+            recipients,
+            minutes_before,
+            content: None,
+        }
+    }
+
+    /// Sets the reminder text.
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Gets the reminder text for a schedule.
+    pub fn content_for(&self, schedule: &Schedule) -> String {
+        self.content.clone().unwrap_or_else(|| {
+            format!(
+                "Reminder: {} starts in {} minutes",
+                schedule.name, self.minutes_before
+            )
+        })
+    }
+}
+
+/// A created schedule with a pending direct message reminder.
+///
+/// Dropping the handle does not cancel the reminder; call
+/// [`cancel`](Self::cancel) for that.
+#[derive(Debug)]
+pub struct ScheduleWithDmReminder {
+    /// The created schedule
+    pub schedule: Schedule,
+    /// How many minutes before the start the reminder is sent
+    pub dm_reminder_minutes_before: u32,
+    /// Users who will be reminded
+    pub recipients: Vec<Snowflake>,
+    reminder: tokio::task::AbortHandle,
+}
+
+impl ScheduleWithDmReminder {
+    /// Creates a handle for a reminder task.
+    pub(crate) fn new(
+        schedule: Schedule,
+        config: &DmReminderConfig,
+        reminder: tokio::task::AbortHandle,
+    ) -> Self {
+        Self {
+            schedule,
+            dm_reminder_minutes_before: config.minutes_before,
+            recipients: config.recipients.clone(),
+            reminder,
+        }
+    }
+
+    /// Cancels the reminder if it has not been sent yet.
+    pub fn cancel(&self) {
+        self.reminder.abort();
+    }
+
+    /// Returns true once the reminder has been sent or cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.reminder.is_finished()
+    }
+}
+
+/// Returns how long to wait before reminding about an event.
+///
+/// `start_timestamp` is in Unix seconds. The wait is zero if the reminder time
+/// has already passed, and `None` if the event itself has already started.
+pub(crate) fn reminder_delay(
+    start_timestamp: i64,
+    minutes_before: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<std::time::Duration> {
+    let now = now.timestamp();
+    if start_timestamp <= now {
+        return None;
+    }
+    let remind_at = start_timestamp - i64::from(minutes_before) * 60;
+    Some(std::time::Duration::from_secs(
+        remind_at.saturating_sub(now).max(0) as u64,
+    ))
+}
+
 impl HasId for Schedule {
     fn id(&self) -> Option<&Snowflake> {
         self.id.as_ref()
@@ -282,6 +663,28 @@ impl std::fmt::Display for Schedule {
     }
 }
 
+/// A member's subscription to a schedule event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSubscription {
+    /// The subscribed user
+    pub user_id: Snowflake,
+    /// The schedule subscribed to
+    pub schedule_id: Snowflake,
+    /// The guild the schedule belongs to
+    pub guild_id: Snowflake,
+    /// When the user subscribed
+    pub subscribed_at: Timestamp,
+}
+
+impl EventSubscription {
+    /// Finds the subscribed user in a member list.
+    pub fn user<'a>(&self, members: &'a [Member]) -> Option<&'a Member> {
+        members
+            .iter()
+            .find(|member| member.user.as_ref().is_some_and(|u| u.id == self.user_id))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,4 +841,147 @@ mod tests {
         assert!(schedule.end_timestamp_parsed().is_err());
         assert_eq!(schedule.duration_seconds(), None);
     }
+
+    #[test]
+    fn test_schedule_builder_body() {
+        let builder = ScheduleBuilder::new("Raid", "1700000000", "1700003600")
+            .jump_channel("c1")
+            .remind_type(RemindType::Before5Minutes);
+        assert_eq!(
+            serde_json::to_value(&builder).unwrap(),
+            serde_json::json!({
+                "name": "Raid",
+                "start_timestamp": "1700000000",
+                "end_timestamp": "1700003600",
+                "jump_channel_id": "c1",
+                "reminder_id": 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_reminder_delay() {
+        let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(
+            reminder_delay(1_700_000_000 + 3600, 10, now),
+            Some(std::time::Duration::from_secs(3000))
+        );
+        assert_eq!(
+            reminder_delay(1_700_000_000 + 60, 10, now),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(reminder_delay(1_700_000_000, 10, now), None);
+    }
+
+    #[test]
+    fn test_validate_schedules() {
+        let valid = ScheduleBuilder::new("Raid", "1700000000", "1700003600");
+        assert!(valid.validate().is_ok());
+
+        let errors = validate_schedules(&[
+            valid.clone(),
+            ScheduleBuilder::new(" ", "1700000000", "1700003600"),
+            ScheduleBuilder::new("Late", "tomorrow", "1700003600"),
+            ScheduleBuilder::new("Backwards", "1700003600", "1700000000"),
+        ])
+        .unwrap_err();
+        let indices: Vec<_> = errors.iter().map(|e| e.index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+        assert_eq!(
+            errors[1].to_string(),
+            "schedule #2 (\"Late\"): start timestamp \"tomorrow\" is not a Unix timestamp"
+        );
+        assert!(validate_schedules(&[valid]).is_ok());
+    }
+
+    #[test]
+    fn test_event_subscription_user() {
+        let subscription: EventSubscription = serde_json::from_value(serde_json::json!({
+            "user_id": "2",
+            "schedule_id": "s1",
+            "guild_id": "g1",
+            "subscribed_at": "2024-03-01T12:00:00Z"
+        }))
+        .unwrap();
+        let member = |id: &str| -> Member {
+            serde_json::from_value(serde_json::json!({"user": {"id": id, "username": id}})).unwrap()
+        };
+        let members = vec![member("1"), member("2"), Member::new()];
+        let user = subscription.user(&members).unwrap();
+        assert_eq!(user.user.as_ref().unwrap().id, "2");
+        assert!(subscription.user(&members[..1]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_image_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cover.JPG");
+        tokio::fs::write(&path, [0xFF, 0xD8, 0xFF]).await.unwrap();
+        let image = ScheduleImage::from_file(&path).await.unwrap();
+        assert_eq!(image.content_type, "image/jpeg");
+        assert_eq!(image.data, vec![0xFF, 0xD8, 0xFF]);
+        assert!(image.validate().is_ok());
+
+        let unsupported = ScheduleImage::from_file(&dir.path().join("cover.bmp")).await;
+        assert!(matches!(
+            unsupported,
+            Err(crate::error::BotError::InvalidParams(_))
+        ));
+
+        let empty = ScheduleImage::new(Vec::new(), "image/png");
+        assert!(matches!(
+            empty.validate(),
+            Err(crate::error::BotError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn test_upcoming_schedules_sorted_across_channels() {
+        let channel = |id: &str| {
+            let mut channel = Channel::new();
+            channel.id = Some(id.to_string());
+            channel.channel_type = Some(crate::models::channel::ChannelType::Application);
+            channel.application_id = Some(Channel::SCHEDULE_APPLICATION_ID.to_string());
+            channel
+        };
+        let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let schedule = |name: &str, start: i64| {
+            Schedule::new(
+                name,
+                (1_700_000_000 + start).to_string(),
+                (1_700_000_000 + start + 3600).to_string(),
+                None,
+                RemindType::None,
+            )
+        };
+        let first = channel("c1");
+        assert!(first.is_schedule());
+
+        let upcoming = UpcomingSchedule::collect(
+            vec![
+                (
+                    first,
+                    vec![
+                        schedule("standup", 600),
+                        schedule("past", -60),
+                        schedule("retro", 7200),
+                    ],
+                ),
+                (
+                    channel("c2"),
+                    vec![schedule("launch", 1800), schedule("now", 0)],
+                ),
+            ],
+            now,
+        );
+
+        let order: Vec<(&str, &str)> = upcoming
+            .iter()
+            .map(|u| (u.schedule.name.as_str(), u.channel.id.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("standup", "c1"), ("launch", "c2"), ("retro", "c1")]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! User-related data models for the QQ Guild Bot API.
 
-use crate::models::{HasId, Snowflake, Timestamp};
+use crate::models::{Activity, HasId, Snowflake, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Represents a user in the QQ Guild system.
@@ -67,12 +67,7 @@ impl User {
     ///
     /// Returns the full URL to the user's avatar image.
     pub fn avatar_url(&self) -> Option<String> {
-        self.avatar.as_ref().map(|_hash| {
-            format!(
-                "https://thirdqq.qlogo.cn/headimg_dl?dst_uin={}&spec=640",
-                self.id
-            )
-        })
+        self.avatar.as_ref().map(|_hash| avatar_url_for(&self.id))
     }
 
     /// Gets the user's display name.
@@ -100,6 +95,11 @@ impl User {
     }
 }
 
+/// Builds the QQ CDN avatar URL for a user ID.
+pub(crate) fn avatar_url_for(user_id: &str) -> String {
+    format!("https://thirdqq.qlogo.cn/headimg_dl?dst_uin={user_id}&spec=640")
+}
+
 impl HasId for User {
     fn id(&self) -> Option<&Snowflake> {
         Some(&self.id)
@@ -291,6 +291,56 @@ impl crate::models::HasName for Role {
     }
 }
 
+/// Whether a user is online.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PresenceStatus {
+    /// Online
+    Online,
+    /// Away/idle
+    Idle,
+    /// Do not disturb
+    #[serde(rename = "dnd")]
+    DnD,
+    /// Offline or invisible
+    #[default]
+    Offline,
+}
+
+/// A user's status on each kind of client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ClientStatus {
+    /// Status on desktop
+    pub desktop: Option<PresenceStatus>,
+    /// Status on mobile
+    pub mobile: Option<PresenceStatus>,
+    /// Status on the web client
+    pub web: Option<PresenceStatus>,
+}
+
+/// A user's presence in a guild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPresence {
+    /// The user ID
+    pub user_id: Snowflake,
+    /// The user's overall status
+    #[serde(default)]
+    pub status: PresenceStatus,
+    /// What the user is doing
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    /// The user's status on each kind of client
+    #[serde(default)]
+    pub client_status: ClientStatus,
+}
+
+impl UserPresence {
+    /// Returns true unless the user is offline.
+    pub fn is_online(&self) -> bool {
+        self.status != PresenceStatus::Offline
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,4 +409,26 @@ mod tests {
 
         assert_eq!(role.hex_color(), "#FF5733");
     }
+
+    #[test]
+    fn test_user_presence_parsing() {
+        let presence: UserPresence = serde_json::from_value(serde_json::json!({
+            "user_id": "42",
+            "status": "dnd",
+            "activities": [{"name": "Raid", "type": 0}],
+            "client_status": {"desktop": "dnd", "mobile": "idle"}
+        }))
+        .unwrap();
+        assert_eq!(presence.status, PresenceStatus::DnD);
+        assert!(presence.is_online());
+        assert_eq!(presence.activities[0].name, "Raid");
+        assert_eq!(presence.client_status.desktop, Some(PresenceStatus::DnD));
+        assert_eq!(presence.client_status.mobile, Some(PresenceStatus::Idle));
+        assert_eq!(presence.client_status.web, None);
+
+        let offline: UserPresence =
+            serde_json::from_value(serde_json::json!({"user_id": "7"})).unwrap();
+        assert!(!offline.is_online());
+        assert!(offline.activities.is_empty());
+    }
 }
@@ -0,0 +1,200 @@
+//! Webhook-related data structures for the QQ Guild Bot API.
+//!
+//! This module contains the channel webhook type and the payload used to
+//! update one.
+
+use crate::error::BotError;
+use crate::http::HttpClient;
+use crate::models::message::Embed;
+use crate::models::{HasId, HasName, Snowflake, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of a webhook name.
+pub const MAX_WEBHOOK_NAME_LENGTH: usize = 80;
+
+/// Represents a webhook that posts messages into a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelWebhook {
+    /// The webhook's unique ID
+    pub id: Snowflake,
+    /// The channel the webhook posts into
+    pub channel_id: Option<Snowflake>,
+    /// The guild the channel belongs to
+    pub guild_id: Option<Snowflake>,
+    /// The webhook's display name
+    pub name: Option<String>,
+    /// URL of the webhook's avatar
+    pub avatar_url: Option<String>,
+    /// URL used to execute the webhook
+    pub url: Option<String>,
+    /// When the webhook was created
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+}
+
+impl ChannelWebhook {
+    /// Returns how long ago the webhook was created.
+    ///
+    /// Webhooks without a creation time have an age of zero.
+    pub fn age(&self) -> std::time::Duration {
+        self.age_at(chrono::Utc::now())
+    }
+
+    /// Returns how old the webhook was at `now`.
+    pub fn age_at(&self, now: Timestamp) -> std::time::Duration {
+        self.created_at
+            .and_then(|created_at| (now - created_at).to_std().ok())
+            .unwrap_or_default()
+    }
+
+    /// Posts an embed through this webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client used for the request
+    /// * `embed` - The embed to post
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure.
+    pub async fn execute_with_embed(
+        &self,
+        http: &HttpClient,
+        embed: Embed,
+    ) -> Result<(), BotError> {
+        let url = self
+            .url
+            .as_deref()
+            .ok_or_else(|| BotError::InvalidData("Webhook has no execute URL".to_string()))?;
+        let body = serde_json::json!({ "embeds": [embed] });
+        http.post_to_url(url, &body).await?;
+        Ok(())
+    }
+}
+
+impl HasId for ChannelWebhook {
+    fn id(&self) -> Option<&Snowflake> {
+        Some(&self.id)
+    }
+}
+
+impl HasName for ChannelWebhook {
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("")
+    }
+}
+
+/// Fields to change when updating a webhook; absent fields are left as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WebhookUpdate<'a> {
+    /// New display name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    /// New avatar URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<&'a str>,
+}
+
+impl WebhookUpdate<'_> {
+    /// Validates the update, checking the name length.
+    pub fn validate(&self) -> Result<(), BotError> {
+        match self.name {
+            Some(name) if name.chars().count() > MAX_WEBHOOK_NAME_LENGTH => {
+                Err(BotError::InvalidParams(format!(
+                    "webhook name must be at most {MAX_WEBHOOK_NAME_LENGTH} characters"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The outcome of deleting several webhooks.
+#[derive(Debug, Default)]
+pub struct WebhookDeleteReport {
+    /// Number of webhooks deleted
+    pub deleted: u32,
+    /// IDs of the webhooks that couldn't be deleted, with the error
+    pub failures: Vec<(Snowflake, BotError)>,
+}
+
+impl WebhookDeleteReport {
+    /// Pairs each webhook with its delete result.
+    pub fn new(webhooks: &[ChannelWebhook], results: Vec<Result<(), BotError>>) -> Self {
+        let mut report = Self::default();
+        for (webhook, result) in webhooks.iter().zip(results) {
+            match result {
+                Ok(()) => report.deleted += 1,
+                Err(e) => report.failures.push((webhook.id.clone(), e)),
+            }
+        }
+        report
+    }
+
+    /// Returns true if every webhook was deleted.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_update_skips_absent_fields() {
+        let update = WebhookUpdate {
+            name: Some("alerts"),
+            avatar_url: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({"name": "alerts"})
+        );
+
+        let update = WebhookUpdate {
+            name: None,
+            avatar_url: Some("https://example.com/a.png"),
+        };
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({"avatar_url": "https://example.com/a.png"})
+        );
+        assert_eq!(
+            serde_json::to_value(WebhookUpdate::default()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn test_webhook_update_name_length() {
+        let name = "a".repeat(MAX_WEBHOOK_NAME_LENGTH);
+        let update = WebhookUpdate {
+            name: Some(&name),
+            avatar_url: None,
+        };
+        assert!(update.validate().is_ok());
+
+        let name = "a".repeat(MAX_WEBHOOK_NAME_LENGTH + 1);
+        let update = WebhookUpdate {
+            name: Some(&name),
+            avatar_url: None,
+        };
+        assert!(matches!(update.validate(), Err(BotError::InvalidParams(_))));
+    }
+
+    #[test]
+    fn test_webhook_age() {
+        let mut webhook: ChannelWebhook =
+            serde_json::from_value(serde_json::json!({"id": "w1"})).unwrap();
+        let now = chrono::Utc::now();
+        assert_eq!(webhook.age_at(now), std::time::Duration::ZERO);
+
+        webhook.created_at = Some(now - chrono::Duration::hours(2));
+        assert_eq!(webhook.age_at(now), std::time::Duration::from_secs(7200));
+        assert!(webhook.age() >= std::time::Duration::from_secs(7200));
+
+        webhook.created_at = Some(now + chrono::Duration::hours(1));
+        assert_eq!(webhook.age_at(now), std::time::Duration::ZERO);
+    }
+}
@@ -0,0 +1,451 @@
+//! Typed state machine for the WebSocket gateway connection.
+//!
+//! The gateway moves through a fixed set of states while connecting,
+//! authenticating and resuming. Modelling them explicitly keeps the
+//! connection logic in one place and rejects events that arrive in an
+//! unexpected state instead of silently flipping flags.
+
+use crate::models::gateway::Ready;
+
+/// Close codes after which the session cannot be resumed or recreated.
+///
+/// These are the codes the gateway has always refused to reconnect after.
+const FATAL_CLOSE_CODES: [u16; 2] = [9001, 9005];
+
+/// Close code sent when authentication fails; the session must be recreated.
+const AUTH_FAILED_CLOSE_CODE: u16 = 4004;
+
+/// The state of a gateway connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GatewayState {
+    /// The WebSocket is being opened and we are waiting for HELLO
+    Connecting,
+    /// HELLO was received and a new session is being identified
+    Authenticating,
+    /// The session is established and dispatch events are flowing
+    Connected {
+        /// The current session ID
+        session_id: String,
+        /// The last received sequence number
+        seq: u64,
+    },
+    /// HELLO was received and an existing session is being resumed
+    Resuming {
+        /// The session being resumed
+        session_id: String,
+        /// The sequence number to resume from
+        seq: u64,
+    },
+    /// The connection is closed but may be reopened
+    Disconnected {
+        /// Why the connection was closed
+        reason: String,
+    },
+    /// The connection is closed and must not be reopened
+    Failed,
+}
+
+impl GatewayState {
+    /// Gets a short name for the state, used in errors and logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Connecting => "Connecting",
+            Self::Authenticating => "Authenticating",
+            Self::Connected { .. } => "Connected",
+            Self::Resuming { .. } => "Resuming",
+            Self::Disconnected { .. } => "Disconnected",
+            Self::Failed => "Failed",
+        }
+    }
+
+    /// Returns true if a WebSocket connection is currently open.
+    pub fn is_active(&self) -> bool {
+        !matches!(self, Self::Disconnected { .. } | Self::Failed)
+    }
+}
+
+impl std::fmt::Display for GatewayState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connected { session_id, seq } | Self::Resuming { session_id, seq } => {
+                write!(f, "{}(session={}, seq={})", self.name(), session_id, seq)
+            }
+            Self::Disconnected { reason } => write!(f, "Disconnected({reason})"),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// Events that drive the gateway state machine.
+#[derive(Debug, Clone)]
+pub enum GatewayStateEvent {
+    /// A new connection attempt was started
+    ConnectStarted,
+    /// The server sent HELLO
+    HelloReceived,
+    /// The identify or resume payload was sent
+    IdentifySent,
+    /// The server sent READY for a new session
+    ReadyReceived(Box<Ready>),
+    /// The server sent RESUMED for an existing session
+    ResumedReceived,
+    /// The server acknowledged a heartbeat
+    HeartbeatAckReceived,
+    /// The server rejected the session
+    InvalidSessionReceived,
+    /// The server closed the connection with the given code
+    CloseReceived(u16),
+    /// The connection was lost without a close frame
+    NetworkError,
+}
+
+impl GatewayStateEvent {
+    /// Gets a short name for the event, used in errors and logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ConnectStarted => "ConnectStarted",
+            Self::HelloReceived => "HelloReceived",
+            Self::IdentifySent => "IdentifySent",
+            Self::ReadyReceived(_) => "ReadyReceived",
+            Self::ResumedReceived => "ResumedReceived",
+            Self::HeartbeatAckReceived => "HeartbeatAckReceived",
+            Self::InvalidSessionReceived => "InvalidSessionReceived",
+            Self::CloseReceived(_) => "CloseReceived",
+            Self::NetworkError => "NetworkError",
+        }
+    }
+}
+
+/// Error returned when an event is not valid in the current state.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid gateway transition: {event} in state {from}")]
+pub struct InvalidTransition {
+    /// The state the machine was in
+    pub from: &'static str,
+    /// The event that was rejected
+    pub event: &'static str,
+}
+
+/// Tracks the state of a gateway connection and the session it carries.
+///
+/// The session ID and sequence number survive disconnects so that the next
+/// connection can resume, unless the close code requires a fresh session.
+#[derive(Debug, Clone)]
+pub struct GatewayStateMachine {
+    state: GatewayState,
+    session_id: Option<String>,
+    seq: u64,
+}
+
+impl Default for GatewayStateMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GatewayStateMachine {
+    /// Creates a new state machine in the disconnected state.
+    pub fn new() -> Self {
+        Self {
+            state: GatewayState::Disconnected {
+                reason: "not connected".to_string(),
+            },
+            session_id: None,
+            seq: 0,
+        }
+    }
+
+    /// Gets the current state.
+    pub fn state(&self) -> &GatewayState {
+        &self.state
+    }
+
+    /// Gets the session ID that will be used to resume, if any.
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Gets the last recorded sequence number.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns true if the session is established.
+    pub fn is_connected(&self) -> bool {
+        matches!(self.state, GatewayState::Connected { .. })
+    }
+
+    /// Returns true if another connection attempt may be made.
+    pub fn can_reconnect(&self) -> bool {
+        self.state != GatewayState::Failed
+    }
+
+    /// Records the sequence number of a received dispatch event.
+    pub fn record_seq(&mut self, seq: u64) {
+        self.seq = seq;
+        if let GatewayState::Connected { seq: current, .. }
+        | GatewayState::Resuming { seq: current, .. } = &mut self.state
+        {
+            *current = seq;
+        }
+    }
+
+    /// Applies an event, moving to the next state.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event that occurred
+    ///
+    /// # Returns
+    ///
+    /// An error if the event is not valid in the current state. The state is
+    /// left unchanged in that case.
+    pub fn transition(&mut self, event: GatewayStateEvent) -> Result<(), InvalidTransition> {
+        use GatewayState as S;
+        use GatewayStateEvent as E;
+
+        let next = match (&self.state, event) {
+            (S::Disconnected { .. }, E::ConnectStarted) => S::Connecting,
+            (S::Connecting, E::HelloReceived) => match &self.session_id {
+                Some(session_id) => S::Resuming {
+                    session_id: session_id.clone(),
+                    seq: self.seq,
+                },
+                None => S::Authenticating,
+            },
+            (S::Authenticating | S::Resuming { .. }, E::IdentifySent) => self.state.clone(),
+            (S::Authenticating | S::Resuming { .. }, E::ReadyReceived(ready)) => {
+                self.session_id = Some(ready.session_id.clone());
+                S::Connected {
+                    session_id: ready.session_id,
+                    seq: self.seq,
+                }
+            }
+            (S::Resuming { session_id, seq }, E::ResumedReceived) => S::Connected {
+                session_id: session_id.clone(),
+                seq: *seq,
+            },
+            (S::Connected { .. }, E::HeartbeatAckReceived) => self.state.clone(),
+            (
+                S::Authenticating | S::Resuming { .. } | S::Connected { .. },
+                E::InvalidSessionReceived,
+            ) => {
+                self.clear_session();
+                S::Failed
+            }
+            (state, E::CloseReceived(code)) if state.is_active() => {
+                if FATAL_CLOSE_CODES.contains(&code) {
+                    self.clear_session();
+                    S::Failed
+                } else {
+                    if code == AUTH_FAILED_CLOSE_CODE {
+                        self.clear_session();
+                    }
+                    S::Disconnected {
+                        reason: format!("closed with code {code}"),
+                    }
+                }
+            }
+            (state, E::NetworkError) if state.is_active() => S::Disconnected {
+                reason: "network error".to_string(),
+            },
+            (state, event) => {
+                return Err(InvalidTransition {
+                    from: state.name(),
+                    event: event.name(),
+                });
+            }
+        };
+
+        self.state = next;
+        Ok(())
+    }
+
+    fn clear_session(&mut self) {
+        self.session_id = None;
+        self.seq = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(session_id: &str) -> Ready {
+        serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "session_id": session_id,
+            "user": {"id": "bot", "username": "bot", "bot": true},
+            "shard": [0, 1]
+        }))
+        .unwrap()
+    }
+
+    fn all_events() -> Vec<GatewayStateEvent> {
+        vec![
+            GatewayStateEvent::ConnectStarted,
+            GatewayStateEvent::HelloReceived,
+            GatewayStateEvent::IdentifySent,
+            GatewayStateEvent::ReadyReceived(Box::new(ready("new"))),
+            GatewayStateEvent::ResumedReceived,
+            GatewayStateEvent::HeartbeatAckReceived,
+            GatewayStateEvent::InvalidSessionReceived,
+            GatewayStateEvent::CloseReceived(4009),
+            GatewayStateEvent::NetworkError,
+        ]
+    }
+
+    /// Builds a machine in each state, with a stored session where it applies.
+    fn all_machines() -> Vec<GatewayStateMachine> {
+        let with = |state: GatewayState, session: Option<&str>| GatewayStateMachine {
+            state,
+            session_id: session.map(str::to_string),
+            seq: 7,
+        };
+        vec![
+            with(
+                GatewayState::Disconnected {
+                    reason: "test".to_string(),
+                },
+                Some("old"),
+            ),
+            with(GatewayState::Connecting, None),
+            with(GatewayState::Authenticating, None),
+            with(
+                GatewayState::Resuming {
+                    session_id: "old".to_string(),
+                    seq: 7,
+                },
+                Some("old"),
+            ),
+            with(
+                GatewayState::Connected {
+                    session_id: "old".to_string(),
+                    seq: 7,
+                },
+                Some("old"),
+            ),
+            with(GatewayState::Failed, None),
+        ]
+    }
+
+    /// Expected next state name for each (state, event) pair, or None if invalid.
+    fn expected(state: &str, event: &str) -> Option<&'static str> {
+        match (state, event) {
+            ("Disconnected", "ConnectStarted") => Some("Connecting"),
+            ("Connecting", "HelloReceived") => Some("Authenticating"),
+            ("Authenticating", "IdentifySent") => Some("Authenticating"),
+            ("Resuming", "IdentifySent") => Some("Resuming"),
+            ("Authenticating" | "Resuming", "ReadyReceived") => Some("Connected"),
+            ("Resuming", "ResumedReceived") => Some("Connected"),
+            ("Connected", "HeartbeatAckReceived") => Some("Connected"),
+            ("Authenticating" | "Resuming" | "Connected", "InvalidSessionReceived") => {
+                Some("Failed")
+            }
+            ("Connecting" | "Authenticating" | "Resuming" | "Connected", "CloseReceived") => {
+                Some("Disconnected")
+            }
+            ("Connecting" | "Authenticating" | "Resuming" | "Connected", "NetworkError") => {
+                Some("Disconnected")
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_every_transition() {
+        for machine in all_machines() {
+            for event in all_events() {
+                let mut m = machine.clone();
+                let from = m.state().name();
+                let name = event.name();
+                let result = m.transition(event);
+
+                match expected(from, name) {
+                    Some(to) => {
+                        assert!(result.is_ok(), "{from} --{name}--> should be valid");
+                        assert_eq!(m.state().name(), to, "{from} --{name}-->");
+                    }
+                    None => {
+                        assert_eq!(result, Err(InvalidTransition { from, event: name }));
+                        assert_eq!(m.state(), machine.state(), "state must not change");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_session_lifecycle() {
+        let mut m = GatewayStateMachine::new();
+        assert!(!m.is_connected());
+
+        m.transition(GatewayStateEvent::ConnectStarted).unwrap();
+        m.transition(GatewayStateEvent::HelloReceived).unwrap();
+        assert_eq!(m.state(), &GatewayState::Authenticating);
+        m.transition(GatewayStateEvent::IdentifySent).unwrap();
+        m.record_seq(1);
+        m.transition(GatewayStateEvent::ReadyReceived(Box::new(ready("s1"))))
+            .unwrap();
+        assert!(m.is_connected());
+        m.record_seq(5);
+        assert_eq!(
+            m.state(),
+            &GatewayState::Connected {
+                session_id: "s1".to_string(),
+                seq: 5
+            }
+        );
+
+        // A normal close keeps the session so the next connection resumes.
+        m.transition(GatewayStateEvent::CloseReceived(4009))
+            .unwrap();
+        assert!(m.can_reconnect());
+        m.transition(GatewayStateEvent::ConnectStarted).unwrap();
+        m.transition(GatewayStateEvent::HelloReceived).unwrap();
+        assert_eq!(
+            m.state(),
+            &GatewayState::Resuming {
+                session_id: "s1".to_string(),
+                seq: 5
+            }
+        );
+        m.transition(GatewayStateEvent::ResumedReceived).unwrap();
+        assert_eq!(m.session_id(), Some("s1"));
+        assert!(m.is_connected());
+    }
+
+    #[test]
+    fn test_close_codes() {
+        let connected = || {
+            let mut m = GatewayStateMachine::new();
+            m.transition(GatewayStateEvent::ConnectStarted).unwrap();
+            m.transition(GatewayStateEvent::HelloReceived).unwrap();
+            m.transition(GatewayStateEvent::ReadyReceived(Box::new(ready("s1"))))
+                .unwrap();
+            m.record_seq(3);
+            m
+        };
+
+        let mut m = connected();
+        m.transition(GatewayStateEvent::CloseReceived(4004))
+            .unwrap();
+        assert!(m.can_reconnect());
+        assert_eq!(m.session_id(), None);
+        assert_eq!(m.seq(), 0);
+
+        for code in FATAL_CLOSE_CODES {
+            let mut m = connected();
+            m.transition(GatewayStateEvent::CloseReceived(code))
+                .unwrap();
+            assert_eq!(m.state(), &GatewayState::Failed);
+            assert!(!m.can_reconnect());
+            assert_eq!(m.session_id(), None);
+        }
+
+        let mut m = connected();
+        m.transition(GatewayStateEvent::InvalidSessionReceived)
+            .unwrap();
+        assert!(!m.can_reconnect());
+    }
+}
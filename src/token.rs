@@ -146,10 +146,11 @@ impl Token {
             .map_err(|_| BotError::internal("Failed to get current time"))?
             .as_secs();
 
-        if let Some(expires_at) = self.expires_at {
-            if current_time < expires_at && self.access_token.is_some() {
-                return Ok(());
-            }
+        if let Some(expires_at) = self.expires_at
+            && current_time < expires_at
+            && self.access_token.is_some()
+        {
+            return Ok(());
         }
 
         // Create HTTP client for token request
@@ -282,6 +283,19 @@ impl Token {
     }
 }
 
+#[cfg(test)]
+impl Token {
+    /// Creates a token with an access token that never expires, so requests
+    /// made with it don't try to refresh it.
+    pub(crate) fn with_access_token(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: Some(access_token.into()),
+            expires_at: Some(u64::MAX),
+            ..Self::new("test_app_id", "test_secret")
+        }
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.safe_display())
@@ -0,0 +1,183 @@
+//! Signature verification for webhook event delivery.
+//!
+//! When events are delivered over HTTP instead of the gateway, every request
+//! carries an Ed25519 signature of its timestamp and body. The signing key is
+//! derived from the bot secret, so its public key can be used to check the
+//! signature the request claims.
+
+use crate::error::{BotError, Result};
+use ring::signature::{ED25519, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+
+/// Length of the Ed25519 seed derived from the bot secret.
+const SEED_LENGTH: usize = 32;
+
+/// A webhook request whose signature doesn't match its timestamp and body.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("webhook signature mismatch")]
+pub struct WebhookSignatureError {
+    /// The signature the request was sent with
+    pub actual: String,
+}
+
+/// Verifies the signature of a webhook request.
+///
+/// The signed message is `timestamp` followed by `body`, and `signature` is
+/// the hex-encoded value of the `X-Signature-Ed25519` header. It is checked
+/// against the public key of the signing key derived from `secret`.
+///
+/// # Arguments
+///
+/// * `body` - The raw request body
+/// * `timestamp` - The `X-Signature-Timestamp` header value
+/// * `signature` - The `X-Signature-Ed25519` header value
+/// * `secret` - The bot secret
+///
+/// # Returns
+///
+/// `Ok(())` if the signature matches, or [`BotError::WebhookSignature`] if it doesn't.
+pub fn verify_webhook_signature(
+    body: &[u8],
+    timestamp: &str,
+    signature: &str,
+    secret: &str,
+) -> Result<()> {
+    let key_pair = signing_key(secret)?;
+    let public_key = UnparsedPublicKey::new(&ED25519, key_pair.public_key().as_ref());
+    let verified = decode_hex(signature.trim()).is_some_and(|signature| {
+        public_key
+            .verify(&signed_message(body, timestamp), &signature)
+            .is_ok()
+    });
+    if verified {
+        Ok(())
+    } else {
+        Err(BotError::WebhookSignature(WebhookSignatureError {
+            actual: signature.to_string(),
+        }))
+    }
+}
+
+/// Signs a webhook payload the same way the platform does.
+///
+/// The Ed25519 seed is the secret repeated until it is at least 32 bytes
+/// long, then truncated to 32 bytes.
+///
+/// # Returns
+///
+/// The hex-encoded signature of `timestamp` followed by `body`.
+pub fn sign_webhook_payload(body: &[u8], timestamp: &str, secret: &str) -> Result<String> {
+    Ok(signing_key(secret)?
+        .sign(&signed_message(body, timestamp))
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Derives the Ed25519 key pair webhook payloads are signed with.
+fn signing_key(secret: &str) -> Result<Ed25519KeyPair> {
+    if secret.is_empty() {
+        return Err(BotError::invalid_params("webhook secret is empty"));
+    }
+    let seed: Vec<u8> = secret.bytes().cycle().take(SEED_LENGTH).collect();
+    Ed25519KeyPair::from_seed_unchecked(&seed)
+        .map_err(|e| BotError::internal(format!("invalid webhook signing key: {e}")))
+}
+
+/// The bytes a webhook signature covers: `timestamp` followed by `body`.
+fn signed_message(body: &[u8], timestamp: &str) -> Vec<u8> {
+    let mut message = Vec::with_capacity(timestamp.len() + body.len());
+    message.extend_from_slice(timestamp.as_bytes());
+    message.extend_from_slice(body);
+    message
+}
+
+/// Decodes a hex string, in either case, or returns `None` if it isn't valid hex.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Callback validation example from the webhook documentation: the
+    // platform sends `plain_token` and `event_ts`, and expects the signature
+    // of `event_ts` followed by `plain_token`.
+    const DOC_SECRET: &str = "DG5g3B4j9X2KOErG";
+    const DOC_TIMESTAMP: &str = "1725442341";
+    const DOC_BODY: &[u8] = b"Arq0D5A61EgUu4OxUvOp";
+    const DOC_SIGNATURE: &str = "87befc99c42c651b3aac0278e71ada338433ae26fcb24307bdc5ad38c1adc2d01bcfcadc0842edac85e85205028a1132afe09280305f13aa6909ffc2d652c706";
+
+    #[test]
+    fn test_documented_signature() {
+        assert_eq!(
+            sign_webhook_payload(DOC_BODY, DOC_TIMESTAMP, DOC_SECRET).unwrap(),
+            DOC_SIGNATURE
+        );
+        assert!(
+            verify_webhook_signature(DOC_BODY, DOC_TIMESTAMP, DOC_SIGNATURE, DOC_SECRET).is_ok()
+        );
+        assert!(
+            verify_webhook_signature(
+                DOC_BODY,
+                DOC_TIMESTAMP,
+                &DOC_SIGNATURE.to_uppercase(),
+                DOC_SECRET
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_timestamp_and_secret() {
+        let err = verify_webhook_signature(DOC_BODY, "1725442342", DOC_SIGNATURE, DOC_SECRET)
+            .unwrap_err();
+        match err {
+            BotError::WebhookSignature(e) => {
+                assert_eq!(e.actual, DOC_SIGNATURE);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(
+            verify_webhook_signature(DOC_BODY, DOC_TIMESTAMP, DOC_SIGNATURE, "other-secret")
+                .is_err()
+        );
+        assert!(verify_webhook_signature(DOC_BODY, DOC_TIMESTAMP, "", DOC_SECRET).is_err());
+        assert!(verify_webhook_signature(DOC_BODY, DOC_TIMESTAMP, "zz", DOC_SECRET).is_err());
+        assert!(verify_webhook_signature(DOC_BODY, DOC_TIMESTAMP, DOC_SIGNATURE, "").is_err());
+    }
+
+    #[test]
+    fn test_any_body_modification_fails_verification() {
+        let body = br#"{"op":0,"s":42,"t":"AT_MESSAGE_CREATE","d":{"content":"hi"}}"#;
+        let signature = sign_webhook_payload(body, DOC_TIMESTAMP, DOC_SECRET).unwrap();
+        assert!(verify_webhook_signature(body, DOC_TIMESTAMP, &signature, DOC_SECRET).is_ok());
+
+        for index in 0..body.len() {
+            for flip in [0x01, 0x20, 0x80] {
+                let mut tampered = body.to_vec();
+                tampered[index] ^= flip;
+                assert!(
+                    verify_webhook_signature(&tampered, DOC_TIMESTAMP, &signature, DOC_SECRET)
+                        .is_err(),
+                    "flipping {flip:#04x} at byte {index} was not detected"
+                );
+            }
+        }
+        assert!(
+            verify_webhook_signature(&body[1..], DOC_TIMESTAMP, &signature, DOC_SECRET).is_err()
+        );
+        let mut extended = body.to_vec();
+        extended.push(b' ');
+        assert!(
+            verify_webhook_signature(&extended, DOC_TIMESTAMP, &signature, DOC_SECRET).is_err()
+        );
+    }
+}